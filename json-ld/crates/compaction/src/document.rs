@@ -1,8 +1,7 @@
-use alloc::boxed::Box;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
 use json_ld_core::{ExpandedDocument, FlattenedDocument, Loader, Term};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::{IntoJson, Keyword};
 use rdf_types::{vocabulary, Vocabulary};
 
@@ -42,11 +41,11 @@ pub trait Compact<I, B> {
 		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
 		loader: &'a L,
 		options: crate::Options,
-	) -> Pin<Box<dyn Future<Output = CompactDocumentResult> + 'a>>
+	) -> impl Future<Output = CompactDocumentResult> + MaybeSend + 'a
 	where
-		N: rdf_types::VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: rdf_types::VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader;
 
 	/// Compacts the input document with the given `vocabulary` to
@@ -56,11 +55,11 @@ pub trait Compact<I, B> {
 		vocabulary: &'a mut N,
 		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
 		loader: &'a L,
-	) -> Pin<Box<dyn Future<Output = CompactDocumentResult> + 'a>>
+	) -> impl Future<Output = CompactDocumentResult> + MaybeSend + 'a
 	where
-		N: rdf_types::VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: rdf_types::VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.compact_full(vocabulary, context, loader, crate::Options::default())
@@ -71,11 +70,11 @@ pub trait Compact<I, B> {
 		&'a self,
 		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
 		loader: &'a L,
-	) -> Pin<Box<dyn Future<Output = CompactDocumentResult> + 'a>>
+	) -> impl Future<Output = CompactDocumentResult> + MaybeSend + 'a
 	where
 		(): rdf_types::VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.compact_with(vocabulary::no_vocabulary_mut(), context, loader)
@@ -89,14 +88,14 @@ impl<I, B> Compact<I, B> for ExpandedDocument<I, B> {
 		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
 		loader: &'a L,
 		options: crate::Options,
-	) -> Pin<Box<dyn Future<Output = CompactDocumentResult> + 'a>>
+	) -> impl Future<Output = CompactDocumentResult> + MaybeSend + 'a
 	where
-		N: rdf_types::VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: rdf_types::VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(async move {
+		async move {
 			let mut compacted_output = self
 				.objects()
 				.compact_fragment_full(
@@ -112,7 +111,7 @@ impl<I, B> Compact<I, B> for ExpandedDocument<I, B> {
 			compacted_output.embed_context(vocabulary, context, options)?;
 
 			Ok(compacted_output)
-		})
+		}
 	}
 }
 
@@ -123,14 +122,14 @@ impl<I, B> Compact<I, B> for FlattenedDocument<I, B> {
 		context: json_ld_context_processing::ProcessedRef<'a, 'a, I, B>,
 		loader: &'a L,
 		options: crate::Options,
-	) -> Pin<Box<dyn Future<Output = CompactDocumentResult> + 'a>>
+	) -> impl Future<Output = CompactDocumentResult> + MaybeSend + 'a
 	where
-		N: rdf_types::VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: rdf_types::VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(async move {
+		async move {
 			let mut compacted_output = self
 				.compact_fragment_full(
 					vocabulary,
@@ -145,7 +144,7 @@ impl<I, B> Compact<I, B> for FlattenedDocument<I, B> {
 			compacted_output.embed_context(vocabulary, context, options)?;
 
 			Ok(compacted_output)
-		})
+		}
 	}
 }
 
@@ -16,7 +16,6 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
 use indexmap::IndexSet;
 use json_ld_context_processing::{Options as ProcessingOptions, Process};
 use json_ld_core::{
@@ -24,6 +23,7 @@ use json_ld_core::{
 	object::Any,
 	Context, Indexed, Loader, ProcessingMode, Term, Value,
 };
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::{ContainerKind, ErrorCode, Keyword};
 use json_syntax::object::Entry;
 use mown::Mown;
@@ -134,7 +134,7 @@ impl Default for Options {
 	}
 }
 
-pub trait CompactFragment<I, B> {
+pub trait CompactFragment<I, B>: MaybeSync {
 	fn compact_fragment_full<'a, N, L>(
 		&'a self,
 		vocabulary: &'a mut N,
@@ -143,11 +143,11 @@ pub trait CompactFragment<I, B> {
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader;
 
 	#[inline(always)]
@@ -156,11 +156,11 @@ pub trait CompactFragment<I, B> {
 		vocabulary: &'a mut N,
 		active_context: &'a Context<I, B>,
 		loader: &'a mut L,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.compact_fragment_full(
@@ -178,11 +178,11 @@ pub trait CompactFragment<I, B> {
 		&'a self,
 		active_context: &'a Context<I, B>,
 		loader: &'a mut L,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.compact_fragment_full(
@@ -202,7 +202,7 @@ enum TypeLangValue<'a, I> {
 }
 
 /// Type that can be compacted with an index.
-pub trait CompactIndexedFragment<I, B> {
+pub trait CompactIndexedFragment<I, B>: MaybeSync {
 	#[allow(clippy::too_many_arguments)]
 	fn compact_indexed_fragment<'a, N, L>(
 		&'a self,
@@ -213,11 +213,11 @@ pub trait CompactIndexedFragment<I, B> {
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader;
 }
 
@@ -230,11 +230,11 @@ impl<I, B, T: CompactIndexedFragment<I, B>> CompactFragment<I, B> for Indexed<T>
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.inner().compact_indexed_fragment(
@@ -249,7 +249,7 @@ impl<I, B, T: CompactIndexedFragment<I, B>> CompactFragment<I, B> for Indexed<T>
 	}
 }
 
-impl<I, B, T: Any<I, B>> CompactIndexedFragment<I, B> for T {
+impl<I, B, T: Any<I, B> + MaybeSync> CompactIndexedFragment<I, B> for T {
 	fn compact_indexed_fragment<'a, N, L>(
 		&'a self,
 		vocabulary: &'a mut N,
@@ -259,15 +259,15 @@ impl<I, B, T: Any<I, B>> CompactIndexedFragment<I, B> for T {
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		use json_ld_core::object::Ref;
-		Box::pin(async move {
+		async move {
 			match self.as_ref() {
 				Ref::Value(value) => {
 					compact_indexed_value_with(
@@ -401,7 +401,7 @@ impl<I, B, T: Any<I, B>> CompactIndexedFragment<I, B> for T {
 					}
 				}
 			}
-		})
+		}
 	}
 }
 
@@ -465,10 +465,10 @@ async fn compact_collection_with<'a, N, L, O, T>(
 	options: Options,
 ) -> CompactFragmentResult
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
-	T: 'a + CompactFragment<N::Iri, N::BlankId>,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	T: 'a + CompactFragment<N::Iri, N::BlankId> + MaybeSend + MaybeSync,
 	O: 'a + Iterator<Item = &'a T>,
 	L: Loader,
 {
@@ -515,7 +515,7 @@ where
 	Ok(result.into_iter().next().unwrap())
 }
 
-impl<T: CompactFragment<I, B>, I, B, S> CompactFragment<I, B> for IndexSet<T, S> {
+impl<T: CompactFragment<I, B>, I, B, S: MaybeSync> CompactFragment<I, B> for IndexSet<T, S> {
 	fn compact_fragment_full<'a, N, L>(
 		&'a self,
 		vocabulary: &'a mut N,
@@ -524,14 +524,14 @@ impl<T: CompactFragment<I, B>, I, B, S> CompactFragment<I, B> for IndexSet<T, S>
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(async move {
+		async move {
 			compact_collection_with(
 				vocabulary,
 				self.iter(),
@@ -542,7 +542,7 @@ impl<T: CompactFragment<I, B>, I, B, S> CompactFragment<I, B> for IndexSet<T, S>
 				options,
 			)
 			.await
-		})
+		}
 	}
 }
 
@@ -555,14 +555,14 @@ impl<T: CompactFragment<I, B>, I, B> CompactFragment<I, B> for Vec<T> {
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(compact_collection_with(
+		compact_collection_with(
 			vocabulary,
 			self.iter(),
 			active_context,
@@ -570,7 +570,7 @@ impl<T: CompactFragment<I, B>, I, B> CompactFragment<I, B> for Vec<T> {
 			active_property,
 			loader,
 			options,
-		))
+		)
 	}
 }
 
@@ -583,14 +583,14 @@ impl<T: CompactFragment<I, B> + Send + Sync, I, B> CompactFragment<I, B> for [T]
 		active_property: Option<&'a str>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = CompactFragmentResult> + 'a>>
+	) -> impl Future<Output = CompactFragmentResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I, BlankId = B>,
-		I: Clone + Hash + Eq,
-		B: Clone + Hash + Eq,
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Hash + Eq + MaybeSend + MaybeSync,
+		B: Clone + Hash + Eq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(compact_collection_with(
+		compact_collection_with(
 			vocabulary,
 			self.iter(),
 			active_context,
@@ -598,6 +598,6 @@ impl<T: CompactFragment<I, B> + Send + Sync, I, B> CompactFragment<I, B> for [T]
 			active_property,
 			loader,
 			options,
-		))
+		)
 	}
 }
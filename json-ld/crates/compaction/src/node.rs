@@ -5,6 +5,7 @@ use contextual::WithContext;
 use core::hash::Hash;
 use json_ld_context_processing::{Options as ProcessingOptions, Process, ProcessingMode};
 use json_ld_core::{Container, ContainerKind, Context, Id, Loader, Node, Term, Type};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::Keyword;
 use mown::Mown;
 use rdf_types::VocabularyMut;
@@ -27,9 +28,9 @@ pub async fn compact_indexed_node_with<N, L>(
 	options: Options,
 ) -> Result<json_syntax::Value, Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// If active context has a previous context, the active context is not propagated.
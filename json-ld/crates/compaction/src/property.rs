@@ -14,6 +14,7 @@ use json_ld_core::{
 	object::{self, List},
 	Container, ContainerKind, Context, Id, Indexed, Loader, Node, Object, Term,
 };
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::Keyword;
 use rdf_types::VocabularyMut;
 
@@ -31,9 +32,9 @@ async fn compact_property_list<N, L>(
 	options: Options,
 ) -> Result<(), Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// If expanded item is a list object:
@@ -116,9 +117,9 @@ async fn compact_property_graph<N, L>(
 	options: Options,
 ) -> Result<(), Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// If expanded item is a graph object
@@ -399,11 +400,11 @@ pub async fn compact_property<'a, N, L, O, T>(
 	options: Options,
 ) -> Result<(), Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
 	O: IntoIterator<Item = &'a Indexed<T>>,
-	T: 'a + object::Any<N::Iri, N::BlankId>,
+	T: 'a + object::Any<N::Iri, N::BlankId> + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	let mut is_empty = true;
@@ -2,6 +2,7 @@ use crate::{compact_iri, compact_key, Error, Options};
 use core::hash::Hash;
 use json_ld_context_processing::{Options as ProcessingOptions, Process};
 use json_ld_core::{object, Container, ContainerKind, Context, Id, Loader, Term, Type, Value};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::Keyword;
 use mown::Mown;
 use rdf_types::VocabularyMut;
@@ -17,9 +18,9 @@ pub async fn compact_indexed_value_with<N, L>(
 	options: Options,
 ) -> Result<json_syntax::Value, Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Hash + Eq,
-	N::BlankId: Clone + Hash + Eq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Hash + Eq + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Hash + Eq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// If the term definition for active property in active context has a local context:
@@ -1,10 +1,9 @@
 use super::{expand_iri_simple, expand_iri_with, Environment, Merged};
-use crate::{Error, Options, ProcessingStack};
+use crate::{Error, Options, ProcessingStack, Warning};
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::string::ToString;
-use async_recursion::async_recursion;
 use core::hash::Hash;
 use hashbrown::HashMap;
 use iref::{Iri, IriRef};
@@ -12,6 +11,7 @@ use json_ld_core::{
 	context::{NormalTermDefinition, TypeTermDefinition},
 	Container, Context, Id, Loader, ProcessingMode, Term, Type, ValidId,
 };
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::{
 	context::{
 		definition::{EntryValueRef, KeyOrKeyword, KeyOrKeywordRef},
@@ -90,7 +90,6 @@ pub struct DefinedTerm {
 /// Follows the `https://www.w3.org/TR/json-ld11-api/#create-term-definition` algorithm.
 /// Default value for `base_url` is `None`. Default values for `protected` and `override_protected` are `false`.
 #[allow(clippy::too_many_arguments)]
-#[async_recursion(?Send)]
 pub async fn define<'a, N, L>(
 	mut env: Environment<'a, N, L>,
 	active_context: &'a mut Context<N::Iri, N::BlankId>,
@@ -103,9 +102,9 @@ pub async fn define<'a, N, L>(
 	options: Options,
 ) -> Result<(), Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + PartialEq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	let term = term.to_owned();
@@ -176,6 +175,12 @@ where
 				}
 				EntryValueRef::Definition(d) => {
 					let key = term.as_key().unwrap();
+					if options.strict && key.is_keyword_like() {
+						return Err(Error::Strict(Warning::KeywordLikeTerm(
+							key.as_str().to_owned(),
+						)));
+					}
+
 					// Initialize `previous_definition` to any existing term definition for `term` in
 					// `active_context`, removing that term definition from active context.
 					let previous_definition = active_context.set_normal(key.clone(), None);
@@ -253,6 +258,12 @@ where
 						// If the value associated with the @reverse entry is a string having
 						// the form of a keyword, return; processors SHOULD generate a warning.
 						if reverse_value.is_keyword_like() {
+							if options.strict {
+								return Err(Error::Strict(Warning::KeywordLikeValue(
+									reverse_value.as_str().to_owned(),
+								)));
+							}
+
 							return Ok(());
 						}
 
@@ -332,6 +343,12 @@ where
 									// processors SHOULD generate a warning.
 									if id_value.is_keyword_like() && !id_value.is_keyword() {
 										debug_assert!(Keyword::try_from(id_value.as_str()).is_err());
+										if options.strict {
+											return Err(Error::Strict(Warning::KeywordLikeValue(
+												id_value.as_str().to_owned(),
+											)));
+										}
+
 										return Ok(());
 									}
 
@@ -686,6 +703,14 @@ where
 							// Otherwise, an invalid language mapping error has been detected and
 							// processing is aborted.
 							// Set the `language` mapping of definition to `language`.
+							if let Nullable::Some(language) = &language_value {
+								if options.strict && !language.is_well_formed() {
+									return Err(Error::Strict(Warning::MalformedLanguageTag(
+										language.as_str().to_owned(),
+									)));
+								}
+							}
+
 							definition.language =
 								Some(language_value.map(LenientLangTag::to_owned));
 						}
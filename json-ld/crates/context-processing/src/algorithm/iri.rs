@@ -2,6 +2,7 @@ use alloc::boxed::Box;
 use alloc::string::String;
 use alloc::string::ToString;
 use core::hash::Hash;
+use json_ld_core::{MaybeSend, MaybeSync};
 
 use super::{DefinedTerms, Environment, Merged};
 use crate::{Error, Options, ProcessingStack, Warning};
@@ -40,9 +41,9 @@ pub async fn expand_iri_with<'a, N, L>(
 	options: Options,
 ) -> ExpandIriResult<N::Iri, N::BlankId>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + PartialEq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	match value {
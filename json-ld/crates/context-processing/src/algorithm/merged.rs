@@ -55,6 +55,12 @@ impl<'a> Merged<'a> {
 			.or_else(|| self.imported().and_then(|i| i.direction))
 	}
 
+	pub fn propagate(&self) -> Option<bool> {
+		self.base
+			.propagate
+			.or_else(|| self.imported().and_then(|i| i.propagate))
+	}
+
 	pub fn protected(&self) -> Option<bool> {
 		self.base
 			.protected
@@ -1,13 +1,14 @@
 use alloc::borrow::ToOwned;
 use core::hash::Hash;
 
-use crate::{Error, Options, Process, Processed, ProcessingResult, ProcessingStack};
+use crate::{
+	Error, Options, Process, Processed, ProcessingResult, ProcessingStack, RemoteContextTrace,
+};
 use alloc::boxed::Box;
-use async_recursion::async_recursion;
 use core::future::Future;
-use core::pin::Pin;
 use iref::IriRef;
 use json_ld_core::{Context, Environment, ExtractContext, Loader, ProcessingMode, Term};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::{self as syntax, Nullable};
 use rdf_types::{vocabulary::IriVocabularyMut, VocabularyMut};
 
@@ -28,21 +29,21 @@ impl Process for syntax::context::Context {
 		loader: &'a L,
 		base_url: Option<N::Iri>,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + 'a>>
+	) -> impl Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
 	where
-		N: VocabularyMut,
-		N::Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + PartialEq,
+		N: VocabularyMut + MaybeSend,
+		N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
-		Box::pin(process_context(
+		process_context(
 			Environment { vocabulary, loader },
 			active_context,
 			self,
 			ProcessingStack::default(),
 			base_url,
 			options,
-		))
+		)
 	}
 }
 
@@ -66,7 +67,6 @@ fn resolve_iri<I>(
 //
 // The recommended default value for `remote_contexts` is the empty set,
 // `false` for `override_protected`, and `true` for `propagate`.
-#[async_recursion(?Send)]
 async fn process_context<'l: 'a, 'a, N, L>(
 	mut env: Environment<'a, N, L>,
 	active_context: &'a Context<N::Iri, N::BlankId>,
@@ -76,9 +76,9 @@ async fn process_context<'l: 'a, 'a, N, L>(
 	mut options: Options,
 ) -> ProcessingResult<'l, N::Iri, N::BlankId>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + PartialEq,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// 1) Initialize result to the result of cloning active context.
@@ -161,6 +161,27 @@ where
 				// If the document has no top-level map with an @context entry, an invalid remote
 				// context has been detected and processing is aborted.
 				// Set loaded context to the value of that entry.
+				let cyclic = remote_contexts.cycle(&context_iri);
+				if cyclic && options.forbid_context_cycles {
+					return Err(Error::CyclicContext(RemoteContextTrace::new(
+						&*env.vocabulary,
+						&remote_contexts,
+						&context_iri,
+					)));
+				}
+
+				if !cyclic
+					&& options
+						.max_remote_contexts
+						.is_some_and(|max| remote_contexts.len() >= max)
+				{
+					return Err(Error::ContextOverflow(RemoteContextTrace::new(
+						&*env.vocabulary,
+						&remote_contexts,
+						&context_iri,
+					)));
+				}
+
 				if remote_contexts.push(context_iri.clone()) {
 					let loaded_context = env
 						.loader
@@ -178,6 +199,9 @@ where
 						override_protected: false,
 						propagate: true,
 						vocab: options.vocab,
+						strict: options.strict,
+						max_remote_contexts: options.max_remote_contexts,
+						forbid_context_cycles: options.forbid_context_cycles,
 					};
 
 					let r = Box::pin(process_context(
@@ -187,7 +211,7 @@ where
 						},
 						&result,
 						&loaded_context,
-						remote_contexts.clone(),
+						remote_contexts.enter(),
 						Some(context_iri),
 						new_options,
 					))
@@ -352,6 +376,16 @@ where
 					}
 				}
 
+				// 5.11) If context has a @propagate entry and processing mode is
+				// json-ld-1.0, an invalid context entry error has been detected and
+				// processing is aborted. Its value has been validated when parsing
+				// the context, and is applied in step 2.
+				if context.propagate().is_some()
+					&& options.processing_mode == ProcessingMode::JsonLd1_0
+				{
+					return Err(Error::InvalidContextEntry);
+				}
+
 				// 5.12) Create a map `defined` to keep track of whether or not a term
 				// has already been defined or is currently being defined during recursion.
 				let mut defined = DefinedTerms::new();
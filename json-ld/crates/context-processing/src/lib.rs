@@ -7,28 +7,43 @@ extern crate alloc;
 extern crate thiserror_nostd_notrait as thiserror;
 
 use algorithm::{Action, RejectVocab};
-use alloc::boxed::Box;
 use alloc::string::String;
 use core::future::Future;
-use core::pin::Pin;
 use core::{fmt, hash::Hash};
 pub use json_ld_core::{warning, Context, ProcessingMode};
 use json_ld_core::{ExtractContextError, LoadError, Loader};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::ErrorCode;
 use rdf_types::VocabularyMut;
 
 pub mod algorithm;
 mod processed;
 mod stack;
+mod validation;
 
 pub use processed::*;
-pub use stack::ProcessingStack;
+pub use stack::{ContextChain, ProcessingStack, Referrers, RemoteContextTrace};
+pub use validation::{validate_context, ContextIssue, ContextIssueKind};
 
 /// Warnings that can be raised during context processing.
+#[derive(Debug)]
 pub enum Warning {
 	KeywordLikeTerm(String),
 	KeywordLikeValue(String),
 	MalformedIri(String),
+	MalformedLanguageTag(String),
+}
+
+impl Warning {
+	/// Returns the error code reported when this warning is promoted to an
+	/// error (see [`Options::strict`]).
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Self::KeywordLikeTerm(_) => ErrorCode::InvalidTermDefinition,
+			Self::KeywordLikeValue(_) | Self::MalformedIri(_) => ErrorCode::InvalidIriMapping,
+			Self::MalformedLanguageTag(_) => ErrorCode::InvalidLanguageMapping,
+		}
+	}
 }
 
 impl fmt::Display for Warning {
@@ -37,6 +52,28 @@ impl fmt::Display for Warning {
 			Self::KeywordLikeTerm(s) => write!(f, "keyword-like term `{s}`"),
 			Self::KeywordLikeValue(s) => write!(f, "keyword-like value `{s}`"),
 			Self::MalformedIri(s) => write!(f, "malformed IRI `{s}`"),
+			Self::MalformedLanguageTag(s) => write!(f, "malformed language tag `{s}`"),
+		}
+	}
+}
+
+impl<N> json_ld_core::warning::Structured<N> for Warning {
+	fn warning_code(&self) -> json_ld_core::warning::Code {
+		use json_ld_core::warning::Code;
+		match self {
+			Self::KeywordLikeTerm(_) => Code::KeywordLikeTerm,
+			Self::KeywordLikeValue(_) => Code::KeywordLikeValue,
+			Self::MalformedIri(_) => Code::MalformedIri,
+			Self::MalformedLanguageTag(_) => Code::MalformedLanguageTag,
+		}
+	}
+
+	fn term_with(&self, _vocabulary: &N) -> Option<String> {
+		match self {
+			Self::KeywordLikeTerm(s)
+			| Self::KeywordLikeValue(s)
+			| Self::MalformedIri(s)
+			| Self::MalformedLanguageTag(s) => Some(s.clone()),
 		}
 	}
 }
@@ -119,6 +156,15 @@ pub enum Error {
 
 	#[error("Use of forbidden `@vocab`")]
 	ForbiddenVocab,
+
+	#[error("Context overflow: {0}")]
+	ContextOverflow(RemoteContextTrace),
+
+	#[error("Cyclic remote context: {0}")]
+	CyclicContext(RemoteContextTrace),
+
+	#[error("{0}")]
+	Strict(Warning),
 }
 
 impl From<RejectVocab> for Error {
@@ -152,6 +198,9 @@ impl Error {
 			Self::ContextLoadingFailed(_) => ErrorCode::LoadingRemoteContextFailed,
 			Self::ContextExtractionFailed(_) => ErrorCode::LoadingRemoteContextFailed,
 			Self::ForbiddenVocab => ErrorCode::InvalidVocabMapping,
+			Self::ContextOverflow(_) => ErrorCode::ContextOverflow,
+			Self::CyclicContext(_) => ErrorCode::ContextOverflow,
+			Self::Strict(w) => w.code(),
 		}
 	}
 }
@@ -168,11 +217,11 @@ pub trait Process {
 		loader: &'a L,
 		base_url: Option<N::Iri>,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + 'a>>
+	) -> impl Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
 	where
-		N: VocabularyMut,
-		N::Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + PartialEq,
+		N: VocabularyMut + MaybeSend,
+		N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 		L: Loader;
 
 	/// Process the local context with specific options.
@@ -184,11 +233,11 @@ pub trait Process {
 		loader: &'a L,
 		base_url: Option<N::Iri>,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + 'a>>
+	) -> impl Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
 	where
-		N: VocabularyMut,
-		N::Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + PartialEq,
+		N: VocabularyMut + MaybeSend,
+		N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.process_full(vocabulary, active_context, loader, base_url, options)
@@ -203,11 +252,11 @@ pub trait Process {
 		vocabulary: &'a mut N,
 		loader: &'a L,
 		base_url: Option<N::Iri>,
-	) -> Pin<Box<dyn Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + 'a>>
+	) -> impl Future<Output = Result<Processed<'a, N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
 	where
-		N: VocabularyMut,
-		N::Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + PartialEq,
+		N: VocabularyMut + MaybeSend,
+		N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		let active_context = Context::default();
@@ -236,6 +285,26 @@ pub struct Options {
 
 	/// Forbid the use of `@vocab` to expand terms.
 	pub vocab: Action,
+
+	/// Promote warnings to errors.
+	///
+	/// When set, any [`Warning`] fails the processing with
+	/// [`Error::Strict`] instead of being ignored.
+	pub strict: bool,
+
+	/// Maximum length of a chain of remote contexts, each one loaded by the
+	/// previous one.
+	///
+	/// Processing fails with [`Error::ContextOverflow`] when the limit is
+	/// exceeded. Default is `None`, meaning no limit.
+	pub max_remote_contexts: Option<usize>,
+
+	/// Forbid cyclic remote contexts.
+	///
+	/// By default, a remote context loaded again while it is being processed
+	/// (directly or through the contexts it loads) is ignored. If this is
+	/// set, processing fails with [`Error::CyclicContext`] instead.
+	pub forbid_context_cycles: bool,
 }
 
 impl Options {
@@ -271,6 +340,9 @@ impl Default for Options {
 			override_protected: false,
 			propagate: true,
 			vocab: Action::Keep,
+			strict: false,
+			max_remote_contexts: None,
+			forbid_context_cycles: false,
 		}
 	}
 }
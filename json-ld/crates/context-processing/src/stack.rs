@@ -1,4 +1,9 @@
+use alloc::string::{String, ToString};
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use contextual::WithContext;
+use core::fmt;
+use rdf_types::vocabulary::IriVocabulary;
 
 /// Single frame of the context processing stack.
 struct StackNode<I> {
@@ -7,12 +12,28 @@ struct StackNode<I> {
 
 	/// URL of the last loaded context.
 	url: I,
+
+	/// Frame of the remote context that loaded this one, if any.
+	referrer: Option<Arc<StackNode<I>>>,
+
+	/// Number of frames, including this one.
+	len: usize,
 }
 
 impl<I> StackNode<I> {
 	/// Create a new stack frame registering the load of the given context URL.
-	fn new(previous: Option<Arc<StackNode<I>>>, url: I) -> StackNode<I> {
-		StackNode { previous, url }
+	fn new(
+		previous: Option<Arc<StackNode<I>>>,
+		url: I,
+		referrer: Option<Arc<StackNode<I>>>,
+	) -> StackNode<I> {
+		let len = previous.as_ref().map(|p| p.len).unwrap_or(0) + 1;
+		StackNode {
+			previous,
+			url,
+			referrer,
+			len,
+		}
 	}
 
 	/// Checks if this frame or any parent holds the given URL.
@@ -33,16 +54,23 @@ impl<I> StackNode<I> {
 
 /// Context processing stack.
 ///
-/// Contains the list of the loaded contexts to detect loops.
+/// Contains the list of the loaded contexts to detect loops, and for each
+/// of them the remote context that loaded it.
 #[derive(Clone)]
 pub struct ProcessingStack<I> {
 	head: Option<Arc<StackNode<I>>>,
+
+	/// Frame of the remote context being processed, if any.
+	current: Option<Arc<StackNode<I>>>,
 }
 
 impl<I> ProcessingStack<I> {
 	/// Creates a new empty processing stack.
 	pub fn new() -> Self {
-		Self { head: None }
+		Self {
+			head: None,
+			current: None,
+		}
 	}
 
 	/// Checks if the stack is empty.
@@ -50,6 +78,39 @@ impl<I> ProcessingStack<I> {
 		self.head.is_none()
 	}
 
+	/// Returns the number of URLs in the stack.
+	pub fn len(&self) -> usize {
+		self.head.as_ref().map(|head| head.len).unwrap_or(0)
+	}
+
+	/// Returns an iterator over the URLs of the stack, from the last loaded
+	/// context to the first.
+	pub fn iter(&self) -> Iter<I> {
+		Iter {
+			node: self.head.as_deref(),
+		}
+	}
+
+	/// Returns an iterator over the referrer chain of the remote context
+	/// being processed: its URL, then the URL of the context that loaded it,
+	/// and so on up to the first loaded context.
+	///
+	/// The iterator is empty if no remote context is being processed.
+	pub fn referrers(&self) -> Referrers<I> {
+		Referrers {
+			node: self.current.as_deref(),
+		}
+	}
+
+	/// Returns the stack used to process the last pushed context, whose
+	/// referrer chain starts with this context.
+	pub fn enter(&self) -> Self {
+		Self {
+			head: self.head.clone(),
+			current: self.head.clone(),
+		}
+	}
+
 	/// Checks if the given URL is already in the stack.
 	///
 	/// This is used for loop detection.
@@ -76,7 +137,7 @@ impl<I> ProcessingStack<I> {
 		} else {
 			let mut head = None;
 			core::mem::swap(&mut head, &mut self.head);
-			self.head = Some(Arc::new(StackNode::new(head, url)));
+			self.head = Some(Arc::new(StackNode::new(head, url, self.current.clone())));
 			true
 		}
 	}
@@ -87,3 +148,161 @@ impl<I> Default for ProcessingStack<I> {
 		Self::new()
 	}
 }
+
+/// Iterator over the URLs of a [`ProcessingStack`].
+pub struct Iter<'a, I> {
+	node: Option<&'a StackNode<I>>,
+}
+
+impl<'a, I> Iterator for Iter<'a, I> {
+	type Item = &'a I;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.node.map(|node| {
+			self.node = node.previous.as_deref();
+			&node.url
+		})
+	}
+}
+
+/// Iterator over the referrer chain of a [`ProcessingStack`].
+pub struct Referrers<'a, I> {
+	node: Option<&'a StackNode<I>>,
+}
+
+impl<'a, I> Iterator for Referrers<'a, I> {
+	type Item = &'a I;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.node.map(|node| {
+			self.node = node.referrer.as_deref();
+			&node.url
+		})
+	}
+}
+
+/// Chain of remote contexts, from the first loaded context to the one that
+/// could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextChain(pub Vec<String>);
+
+impl ContextChain {
+	/// Creates the chain of the contexts in `stack`, followed by `url`.
+	pub(crate) fn new<N: IriVocabulary>(
+		vocabulary: &N,
+		stack: &ProcessingStack<N::Iri>,
+		url: &N::Iri,
+	) -> Self {
+		Self::from_rev_iter(vocabulary, stack.iter(), url)
+	}
+
+	/// Creates the referrer chain of the context being processed in
+	/// `stack`, followed by `url`.
+	pub(crate) fn referrers<N: IriVocabulary>(
+		vocabulary: &N,
+		stack: &ProcessingStack<N::Iri>,
+		url: &N::Iri,
+	) -> Self {
+		Self::from_rev_iter(vocabulary, stack.referrers(), url)
+	}
+
+	fn from_rev_iter<'a, N: IriVocabulary>(
+		vocabulary: &N,
+		rev_iris: impl Iterator<Item = &'a N::Iri>,
+		url: &N::Iri,
+	) -> Self
+	where
+		N::Iri: 'a,
+	{
+		let mut iris: Vec<String> = rev_iris
+			.map(|iri| iri.with(vocabulary).as_str().to_string())
+			.collect();
+		iris.reverse();
+		iris.push(url.with(vocabulary).as_str().to_string());
+		Self(iris)
+	}
+
+	/// Returns the IRIs of the chain.
+	pub fn iris(&self) -> &[String] {
+		&self.0
+	}
+
+	/// Returns the cycle ending the chain, if any.
+	///
+	/// The cycle starts and ends with the same IRI.
+	pub fn cycle(&self) -> Option<&[String]> {
+		let (last, rest) = self.0.split_last()?;
+		let start = rest.iter().position(|iri| iri == last)?;
+		Some(&self.0[start..])
+	}
+}
+
+impl fmt::Display for ContextChain {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for (i, iri) in self.0.iter().enumerate() {
+			if i > 0 {
+				f.write_str(" -> ")?;
+			}
+
+			write!(f, "<{iri}>")?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Remote contexts involved in a [context overflow](crate::Error::ContextOverflow)
+/// or a [cyclic context](crate::Error::CyclicContext) error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteContextTrace {
+	/// Remote contexts loaded so far, in loading order, followed by the one
+	/// that could not be loaded.
+	///
+	/// This includes the contexts loaded before the failing one by the same
+	/// referrers, even if they are not part of the referrer chain.
+	pub visited: ContextChain,
+
+	/// Referrer chain of the context that could not be loaded, starting with
+	/// the first loaded context, each context being loaded by the previous
+	/// one.
+	pub referrers: ContextChain,
+}
+
+impl RemoteContextTrace {
+	pub(crate) fn new<N: IriVocabulary>(
+		vocabulary: &N,
+		stack: &ProcessingStack<N::Iri>,
+		url: &N::Iri,
+	) -> Self {
+		Self {
+			visited: ContextChain::new(vocabulary, stack, url),
+			referrers: ContextChain::referrers(vocabulary, stack, url),
+		}
+	}
+
+	/// Returns the IRI of the context that could not be loaded.
+	pub fn iri(&self) -> &str {
+		self.referrers
+			.0
+			.last()
+			.map(String::as_str)
+			.unwrap_or_default()
+	}
+
+	/// Returns the cycle of referrers ending the referrer chain, if any.
+	pub fn cycle(&self) -> Option<&[String]> {
+		self.referrers.cycle()
+	}
+}
+
+impl fmt::Display for RemoteContextTrace {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.referrers)?;
+
+		if self.visited != self.referrers {
+			write!(f, " (visited: {})", self.visited)?;
+		}
+
+		Ok(())
+	}
+}
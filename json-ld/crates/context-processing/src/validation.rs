@@ -0,0 +1,283 @@
+//! Standalone context validation.
+use crate::{Error, Options, Process, Warning};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::Hash;
+use json_ld_core::{Context, Loader, MaybeSend, MaybeSync};
+use json_ld_syntax::context::InvalidContext;
+use json_ld_syntax::{ErrorCode, Object, TryFromJson, Value};
+use rdf_types::VocabularyMut;
+
+/// Keys of a context definition that do not define a term.
+const CONTEXT_KEYWORDS: [&str; 8] = [
+	"@base",
+	"@direction",
+	"@import",
+	"@language",
+	"@propagate",
+	"@protected",
+	"@version",
+	"@vocab",
+];
+
+/// Problem found by [`validate_context`].
+#[derive(Debug)]
+pub struct ContextIssue {
+	/// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) to the faulty
+	/// context entry or term definition in the validated context.
+	pub pointer: String,
+
+	/// Problem.
+	pub kind: ContextIssueKind,
+}
+
+/// Kind of [`ContextIssue`].
+#[derive(Debug)]
+pub enum ContextIssueKind {
+	/// The context is not syntactically valid.
+	Syntax(InvalidContext),
+
+	/// The context processing algorithm fails.
+	Error(Error),
+
+	/// The context processing algorithm emits a warning, that would fail
+	/// the processing in [strict](Options::strict) mode.
+	Warning(Warning),
+}
+
+impl ContextIssue {
+	/// Checks if this issue is an error, and not a warning.
+	pub fn is_error(&self) -> bool {
+		!matches!(self.kind, ContextIssueKind::Warning(_))
+	}
+
+	/// Returns the error code of this issue.
+	pub fn code(&self) -> ErrorCode {
+		match &self.kind {
+			ContextIssueKind::Syntax(e) => e.code(),
+			ContextIssueKind::Error(e) => e.code(),
+			ContextIssueKind::Warning(w) => w.code(),
+		}
+	}
+}
+
+impl fmt::Display for ContextIssue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match &self.kind {
+			ContextIssueKind::Syntax(e) => write!(f, "{}: {e}", self.pointer),
+			ContextIssueKind::Error(e) => write!(f, "{}: {e}", self.pointer),
+			ContextIssueKind::Warning(w) => write!(f, "{}: warning: {w}", self.pointer),
+		}
+	}
+}
+
+/// Validates the given `@context` value.
+///
+/// The context is processed on top of an empty active context with the
+/// given `base_url`, purely for validation. Unlike the context processing
+/// algorithm, which stops at the first error, every context entry and term
+/// definition is checked, so that all the problems of the context are
+/// reported at once, with their location. Warnings are reported too, as
+/// they would be by the processing in [strict](Options::strict) mode.
+///
+/// Remote contexts are loaded with `loader`. Returns an empty list if the
+/// context is valid.
+pub async fn validate_context<N, L>(
+	vocabulary: &mut N,
+	loader: &L,
+	context: &Value,
+	base_url: Option<N::Iri>,
+	options: Options,
+) -> Vec<ContextIssue>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	let mut validator = Validator {
+		vocabulary,
+		loader,
+		base_url: base_url.clone(),
+		options,
+		issues: Vec::new(),
+	};
+
+	let mut active_context = Context::new(base_url);
+
+	let entries: Vec<(String, &Value)> = match context {
+		Value::Array(items) => items
+			.iter()
+			.enumerate()
+			.map(|(i, item)| (alloc::format!("/{i}"), item))
+			.collect(),
+		context => alloc::vec![(String::new(), context)],
+	};
+
+	for (pointer, entry) in entries {
+		if let Some(result) = validator.check_entry(&active_context, entry, pointer).await {
+			active_context = result
+		}
+	}
+
+	validator.issues
+}
+
+struct Validator<'a, N: VocabularyMut, L> {
+	vocabulary: &'a mut N,
+	loader: &'a L,
+	base_url: Option<N::Iri>,
+	options: Options,
+	issues: Vec<ContextIssue>,
+}
+
+impl<'a, N, L> Validator<'a, N, L>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + PartialEq + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	/// Validates a single context entry, returning the resulting active
+	/// context if it can be processed.
+	///
+	/// If the entry is a context definition with issues, its context-wide
+	/// entries and each of its term definitions are checked separately to
+	/// locate them.
+	async fn check_entry(
+		&mut self,
+		active_context: &Context<N::Iri, N::BlankId>,
+		entry: &Value,
+		pointer: String,
+	) -> Option<Context<N::Iri, N::BlankId>> {
+		let mut issues = Vec::new();
+		let result = self
+			.check(active_context, entry.clone(), &pointer, &mut issues)
+			.await;
+
+		if issues.is_empty() {
+			return result;
+		}
+
+		if let Value::Object(object) = entry {
+			let (keywords, terms): (Vec<_>, Vec<_>) = object
+				.iter()
+				.partition(|e| CONTEXT_KEYWORDS.contains(&e.key.as_str()));
+			let keywords: Object = keywords.into_iter().cloned().collect();
+
+			let mut located = Vec::new();
+			self.check(
+				active_context,
+				Value::Object(keywords.clone()),
+				&pointer,
+				&mut located,
+			)
+			.await;
+
+			if located.is_empty() {
+				for term in terms {
+					let mut definition = keywords.clone();
+					definition.push(term.key.clone(), term.value.clone());
+
+					let mut term_pointer = pointer.clone();
+					term_pointer.push('/');
+					for c in term.key.as_str().chars() {
+						match c {
+							'~' => term_pointer.push_str("~0"),
+							'/' => term_pointer.push_str("~1"),
+							c => term_pointer.push(c),
+						}
+					}
+
+					self.check(
+						active_context,
+						Value::Object(definition),
+						&term_pointer,
+						&mut located,
+					)
+					.await;
+				}
+			}
+
+			if !located.is_empty() {
+				issues = located
+			}
+		}
+
+		self.issues.extend(issues);
+		result
+	}
+
+	/// Processes the given context on top of `active_context`, pushing the
+	/// problems found into `issues`.
+	///
+	/// If the strict mode is disabled, the context is first processed in
+	/// strict mode to detect warnings.
+	async fn check(
+		&mut self,
+		active_context: &Context<N::Iri, N::BlankId>,
+		context: Value,
+		pointer: &str,
+		issues: &mut Vec<ContextIssue>,
+	) -> Option<Context<N::Iri, N::BlankId>> {
+		let context = match json_ld_syntax::context::Context::try_from_json(context) {
+			Ok(context) => context,
+			Err(e) => {
+				issues.push(ContextIssue {
+					pointer: pointer.into(),
+					kind: ContextIssueKind::Syntax(e),
+				});
+				return None;
+			}
+		};
+
+		let strict_options = Options {
+			strict: true,
+			..self.options
+		};
+
+		let result = context
+			.process_with(
+				&mut *self.vocabulary,
+				active_context,
+				self.loader,
+				self.base_url.clone(),
+				strict_options,
+			)
+			.await
+			.map(|processed| processed.into_processed());
+
+		let result = match result {
+			Err(Error::Strict(warning)) if !self.options.strict => {
+				issues.push(ContextIssue {
+					pointer: pointer.into(),
+					kind: ContextIssueKind::Warning(warning),
+				});
+
+				context
+					.process_with(
+						&mut *self.vocabulary,
+						active_context,
+						self.loader,
+						self.base_url.clone(),
+						self.options,
+					)
+					.await
+					.map(|processed| processed.into_processed())
+			}
+			result => result,
+		};
+
+		match result {
+			Ok(context) => Some(context),
+			Err(e) => {
+				issues.push(ContextIssue {
+					pointer: pointer.into(),
+					kind: ContextIssueKind::Error(e),
+				});
+				None
+			}
+		}
+	}
+}
@@ -0,0 +1,28 @@
+//! Allocation failure.
+
+/// Error raised by the fallible construction methods (`try_insert`,
+/// `try_extend`, etc.) when memory cannot be allocated.
+///
+/// Infallible methods abort on allocation failure, which may not be
+/// acceptable on heap-constrained targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("memory allocation failed")]
+pub struct AllocError;
+
+impl From<alloc::collections::TryReserveError> for AllocError {
+	fn from(_: alloc::collections::TryReserveError) -> Self {
+		Self
+	}
+}
+
+impl From<indexmap::TryReserveError> for AllocError {
+	fn from(_: indexmap::TryReserveError) -> Self {
+		Self
+	}
+}
+
+impl From<smallvec::CollectionAllocErr> for AllocError {
+	fn from(_: smallvec::CollectionAllocErr) -> Self {
+		Self
+	}
+}
@@ -0,0 +1,237 @@
+use super::{Context, Key, NormalTermDefinition};
+use crate::{Container, Direction, Id, LenientLangTagBuf, Term, Type, ValidId};
+use alloc::vec::Vec;
+use iref::IriBuf;
+use json_ld_syntax::Keyword;
+use rdf_types::{vocabulary::IriVocabulary, BlankIdBuf, Vocabulary};
+
+/// Context builder.
+///
+/// Constructs a processed [`Context`] in code, without going through the
+/// context processing algorithm. The syntactic `@context` matching the built
+/// context can be obtained with [`Self::into_syntax`] or
+/// [`Self::into_json`].
+///
+/// ```
+/// use json_ld_core::context::{ContextBuilder, TermDefinitionBuilder};
+/// use json_ld_core::{Container, Context, Type};
+/// use iref::IriBuf;
+///
+/// let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+///
+/// let context: Context = ContextBuilder::new()
+///   .vocab(iri("https://schema.org/"))
+///   .term("name", iri("https://schema.org/name"))
+///   .define(
+///     "knows",
+///     TermDefinitionBuilder::new(iri("https://schema.org/knows"))
+///       .typ(Type::Id)
+///       .container(Container::Set)
+///       .protected(true),
+///   )
+///   .build(&());
+///
+/// assert_eq!(context.len(), 2);
+/// ```
+pub struct ContextBuilder<T = IriBuf, B = BlankIdBuf> {
+	context: Context<T, B>,
+	simple_terms: Vec<Key>,
+}
+
+impl<T, B> Default for ContextBuilder<T, B> {
+	fn default() -> Self {
+		Self {
+			context: Context::default(),
+			simple_terms: Vec::new(),
+		}
+	}
+}
+
+impl<T, B> ContextBuilder<T, B> {
+	/// Creates a new empty context builder.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the base IRI (`@base`).
+	pub fn base(mut self, iri: T) -> Self {
+		self.context.set_base_iri(Some(iri));
+		self
+	}
+
+	/// Sets the vocabulary mapping (`@vocab`).
+	pub fn vocab(mut self, iri: T) -> Self {
+		self.context.set_vocabulary(Some(Term::Id(Id::iri(iri))));
+		self
+	}
+
+	/// Sets the default language (`@language`).
+	pub fn language(mut self, language: LenientLangTagBuf) -> Self {
+		self.context.set_default_language(Some(language));
+		self
+	}
+
+	/// Sets the default base direction (`@direction`).
+	pub fn direction(mut self, direction: Direction) -> Self {
+		self.context.set_default_base_direction(Some(direction));
+		self
+	}
+
+	/// Defines `key` as a simple term mapped to the given IRI.
+	///
+	/// As with simple term definitions in a `@context`, the term is usable as
+	/// a prefix if it contains neither `:` nor `/` and the IRI ends with a
+	/// gen-delim character.
+	pub fn term(mut self, key: impl Into<Key>, iri: T) -> Self {
+		let key = key.into();
+		self.simple_terms.push(key.clone());
+		self.context
+			.set_normal(key, Some(TermDefinitionBuilder::new(iri).definition));
+		self
+	}
+
+	/// Defines `key` as an alias of the given keyword.
+	pub fn alias(self, key: impl Into<Key>, keyword: Keyword) -> Self {
+		self.define(key, TermDefinitionBuilder::keyword(keyword))
+	}
+
+	/// Defines `key` with the given expanded term definition.
+	///
+	/// Replaces any previous definition of `key`.
+	pub fn define(mut self, key: impl Into<Key>, definition: TermDefinitionBuilder<T, B>) -> Self {
+		let key = key.into();
+		self.simple_terms.retain(|k| *k != key);
+		self.context.set_normal(key, Some(definition.definition));
+		self
+	}
+
+	/// Builds the processed context.
+	pub fn build(mut self, vocabulary: &impl IriVocabulary<Iri = T>) -> Context<T, B> {
+		for key in self.simple_terms {
+			if key.as_str().contains([':', '/']) {
+				continue;
+			}
+
+			if let Some(mut definition) = self.context.set_normal(key.clone(), None) {
+				if let Some(Term::Id(Id::Valid(ValidId::Iri(iri)))) = &definition.value {
+					definition.prefix = vocabulary
+						.iri(iri)
+						.and_then(|iri| iri.as_str().chars().last())
+						.map(is_gen_delim)
+						.unwrap_or(false);
+				}
+
+				self.context.set_normal(key, Some(definition));
+			}
+		}
+
+		self.context
+	}
+
+	/// Builds the syntactic `@context` matching the processed context.
+	pub fn into_syntax(
+		self,
+		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
+	) -> json_ld_syntax::context::Context {
+		self.build(vocabulary).into_syntax(vocabulary)
+	}
+
+	/// Builds the JSON `@context` value matching the processed context.
+	pub fn into_json(
+		self,
+		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
+	) -> json_syntax::Value {
+		self.build(vocabulary).into_json(vocabulary)
+	}
+}
+
+pub(super) fn is_gen_delim(c: char) -> bool {
+	matches!(c, ':' | '/' | '?' | '#' | '[' | ']' | '@')
+}
+
+/// Expanded term definition builder, used with [`ContextBuilder::define`].
+pub struct TermDefinitionBuilder<T = IriBuf, B = BlankIdBuf> {
+	definition: NormalTermDefinition<T, B>,
+}
+
+impl<T, B> TermDefinitionBuilder<T, B> {
+	/// Creates a term definition mapped to the given IRI (`@id`).
+	pub fn new(iri: T) -> Self {
+		Self {
+			definition: NormalTermDefinition {
+				value: Some(Term::Id(Id::iri(iri))),
+				..Default::default()
+			},
+		}
+	}
+
+	/// Creates a term definition mapped to the given keyword.
+	pub fn keyword(keyword: Keyword) -> Self {
+		Self {
+			definition: NormalTermDefinition {
+				value: Some(Term::Keyword(keyword)),
+				..Default::default()
+			},
+		}
+	}
+
+	/// Creates a reverse property definition (`@reverse`) mapped to the
+	/// given IRI.
+	pub fn reverse(iri: T) -> Self {
+		Self {
+			definition: NormalTermDefinition {
+				value: Some(Term::Id(Id::iri(iri))),
+				reverse_property: true,
+				..Default::default()
+			},
+		}
+	}
+
+	/// Sets the container mapping (`@container`).
+	pub fn container(mut self, container: Container) -> Self {
+		self.definition.container = container;
+		self
+	}
+
+	/// Sets the type mapping (`@type`), used to coerce the values of the
+	/// term.
+	pub fn typ(mut self, typ: Type<T>) -> Self {
+		self.definition.typ = Some(typ);
+		self
+	}
+
+	/// Sets the language mapping (`@language`).
+	///
+	/// `None` means that the values of the term have no language, even if a
+	/// default language is set.
+	pub fn language(mut self, language: Option<LenientLangTagBuf>) -> Self {
+		self.definition.language = Some(language.into());
+		self
+	}
+
+	/// Sets the direction mapping (`@direction`).
+	///
+	/// `None` means that the values of the term have no direction, even if
+	/// a default base direction is set.
+	pub fn direction(mut self, direction: Option<Direction>) -> Self {
+		self.definition.direction = Some(direction.into());
+		self
+	}
+
+	/// Sets the prefix flag (`@prefix`).
+	pub fn prefix(mut self, prefix: bool) -> Self {
+		self.definition.prefix = prefix;
+		self
+	}
+
+	/// Sets the protected flag (`@protected`).
+	pub fn protected(mut self, protected: bool) -> Self {
+		self.definition.protected = protected;
+		self
+	}
+
+	/// Returns the built term definition.
+	pub fn build(self) -> NormalTermDefinition<T, B> {
+		self.definition
+	}
+}
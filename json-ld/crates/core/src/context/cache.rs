@@ -0,0 +1,444 @@
+//! Processed contexts cache.
+use super::Context;
+use crate::ProcessingMode;
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use iref::IriBuf;
+use rdf_types::BlankIdBuf;
+
+/// Parameters of the processing of a context on top of an empty active
+/// context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContextCacheKey<T> {
+	/// Context, as found in the `@context` entry of a document.
+	pub context: json_syntax::Value,
+
+	/// Base IRI of the active context.
+	pub base_iri: Option<T>,
+
+	/// Original base URL of the active context.
+	pub original_base_url: Option<T>,
+
+	/// Base URL of the context.
+	pub base_url: Option<T>,
+
+	/// Processing mode.
+	pub processing_mode: ProcessingMode,
+
+	/// Whether or not warnings are promoted to errors.
+	pub strict: bool,
+
+	/// Whether or not protected terms can be overridden.
+	pub override_protected: bool,
+
+	/// Namespace of the loader used to load the remote contexts, see
+	/// [`ContextCache::with_namespace`].
+	pub namespace: String,
+}
+
+impl<T> ContextCacheKey<T> {
+	/// Creates the key identifying the processing of `context` on top of
+	/// `active_context` with the given parameters, remote contexts being
+	/// loaded with the loader of the given `namespace`.
+	///
+	/// Returns `None` if `active_context` is not empty (it defines terms, a
+	/// vocabulary mapping, a default language or base direction), since the
+	/// result of the processing would then depend on it.
+	#[allow(clippy::too_many_arguments)]
+	pub fn new<B>(
+		active_context: &Context<T, B>,
+		context: json_syntax::Value,
+		base_url: Option<T>,
+		processing_mode: ProcessingMode,
+		strict: bool,
+		override_protected: bool,
+		namespace: &str,
+	) -> Option<Self>
+	where
+		T: Clone,
+	{
+		let is_empty = active_context.is_empty()
+			&& active_context.vocabulary().is_none()
+			&& active_context.default_language().is_none()
+			&& active_context.default_base_direction().is_none()
+			&& active_context.previous_context().is_none();
+
+		if is_empty {
+			Some(Self {
+				context,
+				base_iri: active_context.base_iri().cloned(),
+				original_base_url: active_context.original_base_url().cloned(),
+				base_url,
+				processing_mode,
+				strict,
+				override_protected,
+				namespace: namespace.to_owned(),
+			})
+		} else {
+			None
+		}
+	}
+}
+
+/// Cache of processed contexts.
+///
+/// Documents of the same kind (e.g. Verifiable Credentials) all start with
+/// the same `@context` entry, processed on top of an empty active context.
+/// This cache maps each such context (compared by content, along with the
+/// base IRI, processing options and loader namespace, see
+/// [`ContextCacheKey`]) to the result of its processing, so that it is
+/// processed only once across documents.
+///
+/// The remote contexts imported by a context are loaded with the loader
+/// passed to the expansion algorithm, so the same context may be processed
+/// differently with different loaders. Loaders cannot be identified
+/// reliably (the processor wraps them in short-lived adapters), hence the
+/// caller names the loader used with a cache with [`Self::with_namespace`].
+/// Contexts are only shared between the clones of a cache with the same
+/// namespace: a cache used with loaders serving different documents must be
+/// given a different namespace for each of them.
+///
+/// The cache holds at most [`Self::capacity`] contexts (by default
+/// [`Self::DEFAULT_CAPACITY`]). When it is full, the least recently used
+/// context is evicted.
+///
+/// The cache is attached to the initial active context of the expansion
+/// algorithm with [`Context::with_context_cache`], and shared between the
+/// clones of a cache. Processed contexts are interned in the vocabulary
+/// used to process them, hence a cache must always be used with the same
+/// vocabulary.
+///
+/// The cache is only enabled with the `std` or `spin` feature.
+pub struct ContextCache<T = IriBuf, B = BlankIdBuf> {
+	#[cfg(any(feature = "std", feature = "spin"))]
+	entries: Arc<crate::loader::SharedCache<Entries<T, B>>>,
+
+	#[cfg(not(any(feature = "std", feature = "spin")))]
+	entries: core::marker::PhantomData<Arc<Entries<T, B>>>,
+
+	namespace: String,
+}
+
+/// Statistics of a [`ContextCache`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContextCacheStats {
+	/// Number of lookups answered by the cache.
+	pub hits: usize,
+
+	/// Number of lookups not answered by the cache.
+	pub misses: usize,
+
+	/// Number of cached contexts.
+	pub len: usize,
+
+	/// Number of contexts evicted because the cache was full.
+	pub evictions: usize,
+}
+
+#[cfg_attr(not(any(feature = "std", feature = "spin")), allow(dead_code))]
+struct Entries<T, B> {
+	map: HashMap<ContextCacheKey<T>, Entry<T, B>>,
+	capacity: usize,
+
+	/// Logical clock, incremented on every access.
+	clock: u64,
+
+	hits: usize,
+	misses: usize,
+	evictions: usize,
+}
+
+#[cfg_attr(not(any(feature = "std", feature = "spin")), allow(dead_code))]
+struct Entry<T, B> {
+	context: Context<T, B>,
+
+	/// Time of the last access.
+	last_used: u64,
+}
+
+#[cfg_attr(not(any(feature = "std", feature = "spin")), allow(dead_code))]
+impl<T, B> Entries<T, B> {
+	fn new(capacity: usize) -> Self {
+		Self {
+			map: HashMap::new(),
+			capacity,
+			clock: 0,
+			hits: 0,
+			misses: 0,
+			evictions: 0,
+		}
+	}
+
+	fn tick(&mut self) -> u64 {
+		self.clock += 1;
+		self.clock
+	}
+
+	/// Evicts the least recently used contexts until at most `len` remain.
+	fn shrink_to(&mut self, len: usize) {
+		while self.map.len() > len {
+			let oldest = self.map.values().map(|e| e.last_used).min().unwrap();
+			self.map.retain(|_, e| e.last_used != oldest);
+			self.evictions += 1
+		}
+	}
+}
+
+impl<T, B> Default for ContextCache<T, B> {
+	fn default() -> Self {
+		Self::with_capacity(Self::DEFAULT_CAPACITY)
+	}
+}
+
+impl<T, B> Clone for ContextCache<T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			entries: self.entries.clone(),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			entries: core::marker::PhantomData,
+
+			namespace: self.namespace.clone(),
+		}
+	}
+}
+
+impl<T, B> ContextCache<T, B> {
+	/// Default maximum number of cached contexts.
+	pub const DEFAULT_CAPACITY: usize = 64;
+
+	/// Creates a new empty cache.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new empty cache holding at most `capacity` contexts.
+	///
+	/// A capacity of `0` disables the cache.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			entries: Arc::new(crate::loader::SharedCache::new(Entries::new(capacity))),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			entries: {
+				let _ = capacity;
+				core::marker::PhantomData
+			},
+
+			namespace: String::new(),
+		}
+	}
+
+	/// Sets the namespace of the loader used with this cache.
+	///
+	/// The cache keeps sharing its storage with its other clones, but only
+	/// the contexts processed with the same namespace are looked up. The
+	/// default namespace is the empty string.
+	pub fn with_namespace(self, namespace: impl Into<String>) -> Self {
+		Self {
+			namespace: namespace.into(),
+			..self
+		}
+	}
+
+	/// Returns the namespace of the loader used with this cache.
+	pub fn namespace(&self) -> &str {
+		&self.namespace
+	}
+
+	/// Returns the maximum number of cached contexts.
+	pub fn capacity(&self) -> usize {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| entries.capacity)
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			0
+		}
+	}
+
+	/// Sets the maximum number of cached contexts, for all the clones of
+	/// this cache.
+	///
+	/// The least recently used contexts are evicted if the cache holds more
+	/// than `capacity` contexts. A capacity of `0` disables the cache.
+	pub fn set_capacity(&self, capacity: usize) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| {
+			entries.capacity = capacity;
+			entries.shrink_to(capacity)
+		});
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = capacity;
+	}
+
+	/// Returns the cached processing result for the given `key`, if any.
+	///
+	/// The lookup is counted in the cache [statistics](Self::stats).
+	pub fn get(&self, key: &ContextCacheKey<T>) -> Option<Context<T, B>>
+	where
+		T: Clone + Hash + Eq,
+		B: Clone,
+	{
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| {
+				let now = entries.tick();
+				let result = entries.map.get_mut(key).map(|entry| {
+					entry.last_used = now;
+					entry.context.clone()
+				});
+
+				if result.is_some() {
+					entries.hits += 1
+				} else {
+					entries.misses += 1
+				}
+
+				result
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			let _ = key;
+			None
+		}
+	}
+
+	/// Stores the processing result for the given `key`.
+	///
+	/// The least recently used context is evicted first if the cache is
+	/// full.
+	pub fn insert(&self, key: ContextCacheKey<T>, context: Context<T, B>)
+	where
+		T: Hash + Eq,
+	{
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| {
+			if entries.capacity == 0 {
+				return;
+			}
+
+			if !entries.map.contains_key(&key) {
+				entries.shrink_to(entries.capacity - 1)
+			}
+
+			let last_used = entries.tick();
+			entries.map.insert(key, Entry { context, last_used });
+		});
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = (key, context);
+	}
+
+	/// Returns the cache statistics.
+	pub fn stats(&self) -> ContextCacheStats {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| ContextCacheStats {
+				hits: entries.hits,
+				misses: entries.misses,
+				len: entries.map.len(),
+				evictions: entries.evictions,
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			ContextCacheStats::default()
+		}
+	}
+
+	/// Removes every cached context, for all the clones of this cache.
+	///
+	/// The statistics are kept.
+	pub fn clear(&self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| entries.map.clear())
+	}
+}
+
+#[cfg(all(test, any(feature = "std", feature = "spin")))]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	fn key(context: &str, namespace: &str) -> ContextCacheKey<IriBuf> {
+		ContextCacheKey::new::<BlankIdBuf>(
+			&Context::new(None),
+			context.into(),
+			None,
+			ProcessingMode::default(),
+			false,
+			false,
+			namespace,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn least_recently_used() {
+		let cache: ContextCache = ContextCache::with_capacity(2);
+		cache.insert(key("a", ""), Context::new(None));
+		cache.insert(key("b", ""), Context::new(None));
+		assert!(cache.get(&key("a", "")).is_some());
+
+		cache.insert(key("c", ""), Context::new(None));
+		assert_eq!(cache.stats().len, 2);
+		assert_eq!(cache.stats().evictions, 1);
+		assert!(cache.get(&key("a", "")).is_some());
+		assert!(cache.get(&key("b", "")).is_none());
+		assert!(cache.get(&key("c", "")).is_some());
+	}
+
+	#[test]
+	fn set_capacity() {
+		let cache: ContextCache = ContextCache::new();
+		cache.insert(key("a", ""), Context::new(None));
+		cache.insert(key("b", ""), Context::new(None));
+		cache.set_capacity(1);
+		assert_eq!(cache.stats().len, 1);
+		assert!(cache.get(&key("b", "")).is_some());
+
+		cache.set_capacity(0);
+		cache.insert(key("a", ""), Context::new(None));
+		assert_eq!(cache.stats().len, 0);
+	}
+
+	#[test]
+	fn namespaces() {
+		let cache: ContextCache = ContextCache::new().with_namespace("a");
+		let other = cache.clone().with_namespace("b");
+		cache.insert(key("a", cache.namespace()), Context::new(None));
+		assert!(cache.get(&key("a", cache.namespace())).is_some());
+		assert!(other.get(&key("a", other.namespace())).is_none());
+		assert_eq!(other.stats().len, 1);
+	}
+
+	#[test]
+	fn base_iri() {
+		let cache: ContextCache = ContextCache::new();
+		cache.insert(key("a", ""), Context::new(None));
+
+		let other_base = ContextCacheKey::new::<BlankIdBuf>(
+			&Context::new(Some(iri!("https://example.org/").to_owned())),
+			"a".into(),
+			None,
+			ProcessingMode::default(),
+			false,
+			false,
+			"",
+		)
+		.unwrap();
+		assert!(cache.get(&other_base).is_none());
+	}
+}
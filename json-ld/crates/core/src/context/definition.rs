@@ -34,12 +34,14 @@ pub enum BindingRef<'a, T, B> {
 	Type(&'a TypeTermDefinition),
 }
 
+/// Term bound by a [`BindingRef`].
 pub enum BindingTerm<'a> {
 	Normal(&'a Key),
 	Type,
 }
 
 impl<'a> BindingTerm<'a> {
+	/// Returns the term as a string.
 	pub fn as_str(&self) -> &'a str {
 		match self {
 			Self::Normal(key) => key.as_str(),
@@ -198,7 +200,10 @@ impl<T, B> Definitions<T, B> {
 		core::mem::replace(&mut self.type_, definition)
 	}
 
-	/// Returns an iterator over the term definitions.
+	/// Returns an iterator over the term definitions, in no particular order.
+	///
+	/// See [`Context::terms`](super::Context::terms) to iterate over the
+	/// term definitions in order.
 	pub fn iter(&self) -> Iter<T, B> {
 		Iter {
 			type_: self.type_.as_ref(),
@@ -222,6 +227,8 @@ impl<T, B> Definitions<T, B> {
 	}
 }
 
+/// Iterator over the term definitions of a [`Definitions`], in no particular
+/// order.
 pub struct Iter<'a, T, B> {
 	type_: Option<&'a TypeTermDefinition>,
 	normal: hashbrown::hash_map::Iter<'a, Key, NormalTermDefinition<T, B>>,
@@ -247,6 +254,8 @@ impl<'a, T, B> IntoIterator for &'a Definitions<T, B> {
 	}
 }
 
+/// Owning iterator over the term definitions of a [`Definitions`], in no
+/// particular order.
 pub struct IntoIter<T, B> {
 	type_: Option<TypeTermDefinition>,
 	normal: hashbrown::hash_map::IntoIter<Key, NormalTermDefinition<T, B>>,
@@ -332,6 +341,7 @@ impl<T, B> TermDefinition<T, B> {
 		ModuloProtected(self.as_ref())
 	}
 
+	/// Returns the IRI mapping of the term, if any.
 	pub fn value(&self) -> Option<&Term<T, B>> {
 		match self {
 			Self::Type(_) => None,
@@ -339,6 +349,8 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the prefix flag, indicating whether the term can be used as the
+	/// prefix of compact IRIs.
 	pub fn prefix(&self) -> bool {
 		match self {
 			Self::Type(_) => false,
@@ -346,6 +358,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the protected flag.
 	pub fn protected(&self) -> bool {
 		match self {
 			Self::Type(d) => d.protected,
@@ -353,6 +366,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the reverse property flag (`@reverse`).
 	pub fn reverse_property(&self) -> bool {
 		match self {
 			Self::Type(_) => false,
@@ -360,6 +374,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the base URL used to process the scoped context, if any.
 	pub fn base_url(&self) -> Option<&T> {
 		match self {
 			Self::Type(_) => None,
@@ -367,6 +382,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the scoped context (`@context`), if any.
 	pub fn context(&self) -> Option<&json_ld_syntax::context::Context> {
 		match self {
 			Self::Type(_) => None,
@@ -374,6 +390,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the container mapping.
 	pub fn container(&self) -> Container {
 		match self {
 			Self::Type(d) => d.container.into(),
@@ -381,6 +398,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the direction mapping, if any.
 	pub fn direction(&self) -> Option<Nullable<Direction>> {
 		match self {
 			Self::Type(_) => None,
@@ -388,6 +406,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the index mapping, if any.
 	pub fn index(&self) -> Option<&Index> {
 		match self {
 			Self::Type(_) => None,
@@ -395,6 +414,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the language mapping, if any.
 	pub fn language(&self) -> Option<Nullable<&LenientLangTagBuf>> {
 		match self {
 			Self::Type(_) => None,
@@ -402,6 +422,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the nest value, if any.
 	pub fn nest(&self) -> Option<&Nest> {
 		match self {
 			Self::Type(_) => None,
@@ -409,6 +430,7 @@ impl<T, B> TermDefinition<T, B> {
 		}
 	}
 
+	/// Returns the type mapping, if any.
 	pub fn typ(&self) -> Option<&Type<T>> {
 		match self {
 			Self::Type(_) => None,
@@ -432,6 +454,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		ModuloProtected(*self)
 	}
 
+	/// Returns the IRI mapping of the term, if any.
 	pub fn value(&self) -> Option<&'a Term<T, B>> {
 		match self {
 			Self::Type(_) => None,
@@ -439,6 +462,8 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the prefix flag, indicating whether the term can be used as the
+	/// prefix of compact IRIs.
 	pub fn prefix(&self) -> bool {
 		match self {
 			Self::Type(_) => false,
@@ -446,6 +471,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the protected flag.
 	pub fn protected(&self) -> bool {
 		match self {
 			Self::Type(d) => d.protected,
@@ -453,6 +479,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the reverse property flag (`@reverse`).
 	pub fn reverse_property(&self) -> bool {
 		match self {
 			Self::Type(_) => false,
@@ -460,6 +487,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the base URL used to process the scoped context, if any.
 	pub fn base_url(&self) -> Option<&'a T> {
 		match self {
 			Self::Type(_) => None,
@@ -467,6 +495,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the scoped context (`@context`), if any.
 	pub fn context(&self) -> Option<&'a json_ld_syntax::context::Context> {
 		match self {
 			Self::Type(_) => None,
@@ -474,6 +503,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the container mapping.
 	pub fn container(&self) -> Container {
 		match self {
 			Self::Type(d) => d.container.into(),
@@ -481,6 +511,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the direction mapping, if any.
 	pub fn direction(&self) -> Option<Nullable<Direction>> {
 		match self {
 			Self::Type(_) => None,
@@ -488,6 +519,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the index mapping, if any.
 	pub fn index(&self) -> Option<&'a Index> {
 		match self {
 			Self::Type(_) => None,
@@ -495,6 +527,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the language mapping, if any.
 	pub fn language(&self) -> Option<Nullable<&'a LenientLangTagBuf>> {
 		match self {
 			Self::Type(_) => None,
@@ -502,6 +535,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the nest value, if any.
 	pub fn nest(&self) -> Option<&'a Nest> {
 		match self {
 			Self::Type(_) => None,
@@ -509,6 +543,7 @@ impl<'a, T, B> TermDefinitionRef<'a, T, B> {
 		}
 	}
 
+	/// Returns the type mapping, if any.
 	pub fn typ(&self) -> Option<&'a Type<T>> {
 		match self {
 			Self::Type(_) => None,
@@ -525,43 +560,45 @@ impl<'a, T, B> Clone for TermDefinitionRef<'a, T, B> {
 
 impl<'a, T, B> Copy for TermDefinitionRef<'a, T, B> {}
 
-// A term definition.
+/// Normal term definition.
+///
+/// Definition of any term other than `@type`.
 #[derive(PartialEq, Eq, Clone)]
 pub struct NormalTermDefinition<T = IriBuf, B = BlankIdBuf> {
-	// IRI mapping.
+	/// IRI mapping.
 	pub value: Option<Term<T, B>>,
 
-	// Prefix flag.
+	/// Prefix flag.
 	pub prefix: bool,
 
-	// Protected flag.
+	/// Protected flag.
 	pub protected: bool,
 
-	// Reverse property flag.
+	/// Reverse property flag.
 	pub reverse_property: bool,
 
-	// Optional base URL.
+	/// Base URL used to process the scoped context, if any.
 	pub base_url: Option<T>,
 
-	// Optional context.
+	/// Scoped context, if any.
 	pub context: Option<Box<json_ld_syntax::context::Context>>,
 
-	// Container mapping.
+	/// Container mapping.
 	pub container: Container,
 
-	// Optional direction mapping.
+	/// Direction mapping, if any.
 	pub direction: Option<Nullable<Direction>>,
 
-	// Optional index mapping.
+	/// Index mapping, if any.
 	pub index: Option<Index>,
 
-	// Optional language mapping.
+	/// Language mapping, if any.
 	pub language: Option<Nullable<LenientLangTagBuf>>,
 
-	// Optional nest value.
+	/// Nest value, if any.
 	pub nest: Option<Nest>,
 
-	// Optional type mapping.
+	/// Type mapping, if any.
 	pub typ: Option<Type<T>>,
 }
 
@@ -0,0 +1,226 @@
+//! JSON-LD 1.1 feature detection.
+use super::{builder::is_gen_delim, BindingRef, Context, Key, NormalTermDefinition};
+use crate::{Container, Id, ProcessingMode, Term, Type, ValidId};
+use alloc::vec::Vec;
+use json_ld_syntax::Keyword;
+use rdf_types::vocabulary::IriVocabulary;
+
+/// Feature introduced by JSON-LD 1.1, unsupported by JSON-LD 1.0 processors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+	/// Scoped context (`@context` entry of a term definition), used as a
+	/// property-scoped or type-scoped context.
+	ScopedContext,
+
+	/// Explicit prefix flag (`@prefix`).
+	Prefix,
+
+	/// Protected term definition (`@protected`).
+	Protected,
+
+	/// Base direction (`@direction`), either as the default base direction
+	/// or in a term definition.
+	Direction,
+
+	/// Non-propagated context (`@propagate`).
+	Propagate,
+
+	/// Nested properties (`@nest`).
+	Nest,
+
+	/// Property-based data indexing (`@index` in a term definition).
+	PropertyIndex,
+
+	/// `@graph`, `@id` or `@type` container, or a container combining
+	/// several kinds.
+	Container,
+
+	/// JSON literal type mapping (`@json`).
+	JsonType,
+
+	/// `@none` type mapping.
+	NoneType,
+
+	/// Alias of a keyword introduced by JSON-LD 1.1.
+	KeywordAlias,
+
+	/// `@type` term definition.
+	TypeDefinition,
+}
+
+/// Use of a [`Feature`] in a processed context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureUse {
+	/// Used feature.
+	pub feature: Feature,
+
+	/// Term definition using the feature, or `None` if the feature is used
+	/// by the context itself (e.g. the default base direction) or by the
+	/// `@type` term definition.
+	pub term: Option<Key>,
+}
+
+impl<T, B> Context<T, B> {
+	/// Lists the JSON-LD 1.1 features used by this context, in term order,
+	/// starting with the context-wide features.
+	///
+	/// Publishers targeting JSON-LD 1.0 processors can use it to check that
+	/// their context is compatible. Some features cannot be detected once
+	/// the context is processed: `@import` and `@version` entries, or a
+	/// `@prefix` flag set to its default value.
+	pub fn json_ld_1_1_features(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Vec<FeatureUse> {
+		let mut result = Vec::new();
+
+		if self.default_base_direction().is_some() {
+			result.push(FeatureUse {
+				feature: Feature::Direction,
+				term: None,
+			})
+		}
+
+		if self.previous_context().is_some() {
+			result.push(FeatureUse {
+				feature: Feature::Propagate,
+				term: None,
+			})
+		}
+
+		for binding in self.terms() {
+			match binding {
+				BindingRef::Normal(key, definition) => {
+					for feature in definition_features(vocabulary, key, definition) {
+						result.push(FeatureUse {
+							feature,
+							term: Some(key.clone()),
+						})
+					}
+				}
+				BindingRef::Type(definition) => {
+					result.push(FeatureUse {
+						feature: Feature::TypeDefinition,
+						term: None,
+					});
+
+					if definition.protected {
+						result.push(FeatureUse {
+							feature: Feature::Protected,
+							term: None,
+						})
+					}
+				}
+			}
+		}
+
+		result
+	}
+
+	/// Returns the minimum processing mode required to process the
+	/// `@context` this context results from.
+	///
+	/// This is [`ProcessingMode::JsonLd1_0`] unless the context uses
+	/// [JSON-LD 1.1 features](Self::json_ld_1_1_features).
+	pub fn required_processing_mode(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> ProcessingMode {
+		if self.json_ld_1_1_features(vocabulary).is_empty() {
+			ProcessingMode::JsonLd1_0
+		} else {
+			ProcessingMode::JsonLd1_1
+		}
+	}
+}
+
+/// Lists the JSON-LD 1.1 features used by the given term definition.
+fn definition_features<T, B>(
+	vocabulary: &impl IriVocabulary<Iri = T>,
+	key: &Key,
+	definition: &NormalTermDefinition<T, B>,
+) -> Vec<Feature> {
+	let mut features = Vec::new();
+
+	if definition.context.is_some() {
+		features.push(Feature::ScopedContext)
+	}
+
+	if definition.prefix && !is_implicit_prefix(vocabulary, key, definition) {
+		features.push(Feature::Prefix)
+	}
+
+	if definition.protected {
+		features.push(Feature::Protected)
+	}
+
+	if definition.direction.is_some() {
+		features.push(Feature::Direction)
+	}
+
+	if definition.nest.is_some() {
+		features.push(Feature::Nest)
+	}
+
+	if definition.index.is_some() {
+		features.push(Feature::PropertyIndex)
+	}
+
+	if !matches!(
+		definition.container,
+		Container::None | Container::Index | Container::Language | Container::List | Container::Set
+	) {
+		features.push(Feature::Container)
+	}
+
+	match definition.typ {
+		Some(Type::Json) => features.push(Feature::JsonType),
+		Some(Type::None) => features.push(Feature::NoneType),
+		_ => (),
+	}
+
+	if let Some(Term::Keyword(keyword)) = &definition.value {
+		if is_json_ld_1_1_keyword(*keyword) {
+			features.push(Feature::KeywordAlias)
+		}
+	}
+
+	features
+}
+
+/// Checks if the prefix flag of the given definition would be set by a
+/// simple term definition.
+fn is_implicit_prefix<T, B>(
+	vocabulary: &impl IriVocabulary<Iri = T>,
+	key: &Key,
+	definition: &NormalTermDefinition<T, B>,
+) -> bool {
+	if key.as_str().contains([':', '/']) {
+		return false;
+	}
+
+	match &definition.value {
+		Some(Term::Id(Id::Valid(ValidId::Iri(iri)))) => vocabulary
+			.iri(iri)
+			.and_then(|iri| iri.as_str().chars().last())
+			.map(is_gen_delim)
+			.unwrap_or(false),
+		_ => false,
+	}
+}
+
+fn is_json_ld_1_1_keyword(keyword: Keyword) -> bool {
+	matches!(
+		keyword,
+		Keyword::Direction
+			| Keyword::Import
+			| Keyword::Included
+			| Keyword::Json
+			| Keyword::Nest
+			| Keyword::None
+			| Keyword::Prefix
+			| Keyword::Propagate
+			| Keyword::Protected
+			| Keyword::Version
+	)
+}
@@ -5,6 +5,7 @@ use super::Context;
 use super::Key;
 use crate::{Container, Direction, LenientLangTag, LenientLangTagBuf, Nullable, Term, Type};
 use alloc::borrow::ToOwned;
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::cmp::Ordering;
 use core::fmt;
@@ -170,6 +171,18 @@ impl<T> InverseDefinition<T> {
 		self.map.get_mut(container).unwrap()
 	}
 
+	/// Returns the preferred term among all the terms of this definition.
+	///
+	/// This is the shortest term, or the lexicographically least one if there
+	/// are several.
+	pub fn preferred_term(&self) -> &Key {
+		self.map
+			.values()
+			.map(|c| &c.any.none)
+			.min_by(|a, b| compare_terms(a.as_str(), b.as_str()))
+			.unwrap()
+	}
+
 	pub fn select(&self, containers: &[Container], selection: &Selection<T>) -> Option<&Key>
 	where
 		T: Clone + Hash + Eq,
@@ -200,6 +213,12 @@ impl<T> InverseDefinition<T> {
 	}
 }
 
+/// Orders terms by length, then lexicographically, as the inverse context
+/// creation algorithm does.
+fn compare_terms(a: &str, b: &str) -> Ordering {
+	a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 /// Inverse context.
 pub struct InverseContext<T, B> {
 	map: HashMap<Term<T, B>, InverseDefinition<T>>,
@@ -283,21 +302,20 @@ impl<T, B> Default for InverseContext<T, B> {
 	}
 }
 
-impl<'a, T: Clone + Hash + Eq, B: Clone + Hash + Eq> From<Context<T, B>> for InverseContext<T, B> {
+impl<T: Clone + Hash + Eq, B: Clone + Hash + Eq> From<Context<T, B>> for InverseContext<T, B> {
 	fn from(context: Context<T, B>) -> Self {
+		Self::from(&context)
+	}
+}
+
+impl<'a, T: Clone + Hash + Eq, B: Clone + Hash + Eq> From<&'a Context<T, B>>
+	for InverseContext<T, B>
+{
+	fn from(context: &'a Context<T, B>) -> Self {
 		let mut result = InverseContext::new();
 
 		let mut definitions: Vec<_> = context.definitions().iter().collect();
-		definitions.sort_by(|a, b| {
-			let a = a.term().as_str();
-			let b = b.term().as_str();
-			let ord = a.len().cmp(&b.len());
-			if ord == Ordering::Equal {
-				a.cmp(b)
-			} else {
-				ord
-			}
-		});
+		definitions.sort_by(|a, b| compare_terms(a.term().as_str(), b.term().as_str()));
 
 		for binding in definitions {
 			if let BindingRef::Normal(term, term_definition) = binding {
@@ -409,3 +427,67 @@ impl<'a, T: Clone + Hash + Eq, B: Clone + Hash + Eq> From<Context<T, B>> for Inv
 		result
 	}
 }
+
+/// Lazily computed inverse of a [`Context`].
+///
+/// It is shared between the clones of a context, and reset when the context
+/// is modified.
+///
+/// The cache is only enabled with the `std` or `spin` feature. Otherwise the
+/// inverse context is computed each time it is requested.
+pub(crate) struct InverseCache<T, B> {
+	#[cfg(any(feature = "std", feature = "spin"))]
+	inverse: Arc<crate::loader::SharedCache<Option<Arc<InverseContext<T, B>>>>>,
+
+	#[cfg(not(any(feature = "std", feature = "spin")))]
+	inverse: core::marker::PhantomData<Arc<InverseContext<T, B>>>,
+}
+
+impl<T, B> Default for InverseCache<T, B> {
+	fn default() -> Self {
+		Self {
+			inverse: Default::default(),
+		}
+	}
+}
+
+impl<T, B> Clone for InverseCache<T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			inverse: self.inverse.clone(),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			inverse: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<T, B> InverseCache<T, B> {
+	/// Returns the cached inverse context, computing it with `f` if
+	/// necessary.
+	pub(crate) fn get_or_init(
+		&self,
+		f: impl FnOnce() -> InverseContext<T, B>,
+	) -> Arc<InverseContext<T, B>> {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.inverse
+				.with(|inverse| inverse.get_or_insert_with(|| Arc::new(f())).clone())
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			Arc::new(f())
+		}
+	}
+
+	/// Empties the cache, without affecting the clones sharing it.
+	pub(crate) fn reset(&mut self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		match Arc::get_mut(&mut self.inverse) {
+			Some(inverse) => inverse.with(|inverse| *inverse = None),
+			None => *self = Self::default(),
+		}
+	}
+}
@@ -0,0 +1,356 @@
+//! Expanded IRIs cache.
+use crate::Term;
+use alloc::string::String;
+use alloc::sync::Arc;
+use hashbrown::HashMap;
+
+/// Cache of the IRI expansions performed with a [`Context`](super::Context).
+///
+/// The same terms and compact IRIs are expanded over and over again when
+/// expanding a document. This cache maps each expanded string to the result
+/// of the IRI expansion algorithm, so that it is only run once per active
+/// context. It is reset when the context is modified.
+///
+/// The cache holds at most [`Self::capacity`] expansions (by default
+/// [`Self::DEFAULT_CAPACITY`]). It is emptied when this capacity is
+/// reached, so that documents using many distinct terms or IRIs cannot make
+/// it grow without bounds.
+///
+/// By default, clones of the context start with an empty cache. Use
+/// [`Self::set_shared`] to share the cached expansions with the clones
+/// instead, for instance when the same context is used to expand many
+/// documents.
+///
+/// Expanded IRIs are interned in the vocabulary used to expand them, hence
+/// a context (just like its term definitions) must always be used with the
+/// same vocabulary.
+///
+/// The cache is only enabled with the `std` or `spin` feature.
+pub struct IriCache<T, B> {
+	#[cfg(any(feature = "std", feature = "spin"))]
+	entries: Arc<crate::loader::SharedCache<Entries<T, B>>>,
+
+	#[cfg(not(any(feature = "std", feature = "spin")))]
+	entries: core::marker::PhantomData<Arc<Entries<T, B>>>,
+
+	/// Whether clones share the cached expansions.
+	shared: bool,
+}
+
+/// Statistics of an [`IriCache`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IriCacheStats {
+	/// Number of lookups answered by the cache.
+	pub hits: usize,
+
+	/// Number of lookups not answered by the cache.
+	pub misses: usize,
+
+	/// Number of cached expansions.
+	pub len: usize,
+
+	/// Number of times the cache was emptied because it was full.
+	pub evictions: usize,
+}
+
+#[cfg_attr(not(any(feature = "std", feature = "spin")), allow(dead_code))]
+struct Entries<T, B> {
+	/// Expansions, indexed by their `document_relative` and `vocab` flags.
+	maps: [HashMap<String, Option<Term<T, B>>>; 4],
+	capacity: usize,
+	hits: usize,
+	misses: usize,
+	evictions: usize,
+}
+
+#[cfg_attr(not(any(feature = "std", feature = "spin")), allow(dead_code))]
+impl<T, B> Entries<T, B> {
+	fn new(capacity: usize) -> Self {
+		Self {
+			maps: Default::default(),
+			capacity,
+			hits: 0,
+			misses: 0,
+			evictions: 0,
+		}
+	}
+
+	fn len(&self) -> usize {
+		self.maps.iter().map(HashMap::len).sum()
+	}
+
+	fn clear(&mut self) {
+		self.maps.iter_mut().for_each(HashMap::clear)
+	}
+
+	fn map_mut(
+		&mut self,
+		document_relative: bool,
+		vocab: bool,
+	) -> &mut HashMap<String, Option<Term<T, B>>> {
+		&mut self.maps[usize::from(document_relative) | (usize::from(vocab) << 1)]
+	}
+}
+
+impl<T, B> Default for IriCache<T, B> {
+	fn default() -> Self {
+		Self::with_capacity(Self::DEFAULT_CAPACITY)
+	}
+}
+
+impl<T, B> Clone for IriCache<T, B> {
+	/// Returns a cache sharing the expansions of this one if it is
+	/// [shared](Self::set_shared), or else an empty cache with the same
+	/// capacity.
+	fn clone(&self) -> Self {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		if self.shared {
+			return Self {
+				entries: self.entries.clone(),
+				shared: true,
+			};
+		}
+
+		Self::with_capacity(self.capacity())
+	}
+}
+
+impl<T, B> IriCache<T, B> {
+	/// Default maximum number of cached expansions.
+	pub const DEFAULT_CAPACITY: usize = 4096;
+
+	/// Creates a new empty cache holding at most `capacity` expansions.
+	///
+	/// A capacity of `0` disables the cache.
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			entries: Arc::new(crate::loader::SharedCache::new(Entries::new(capacity))),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			entries: {
+				let _ = capacity;
+				core::marker::PhantomData
+			},
+
+			shared: false,
+		}
+	}
+
+	/// Returns the maximum number of cached expansions.
+	pub fn capacity(&self) -> usize {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| entries.capacity)
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			0
+		}
+	}
+
+	/// Sets the maximum number of cached expansions.
+	///
+	/// The cache is emptied if it holds more than `capacity` expansions. A
+	/// capacity of `0` disables the cache.
+	pub fn set_capacity(&self, capacity: usize) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| {
+			entries.capacity = capacity;
+			if entries.len() > capacity {
+				entries.clear()
+			}
+		});
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = capacity;
+	}
+
+	/// Checks if the clones of this cache share its expansions.
+	pub fn is_shared(&self) -> bool {
+		self.shared
+	}
+
+	/// Sets whether the clones of this cache share its expansions.
+	///
+	/// Shared expansions are kept until the cache is full or
+	/// [cleared](Self::clear), which benefits every context cloned from the
+	/// same initial context. Clones made before this call are not affected.
+	pub fn set_shared(&mut self, shared: bool) {
+		self.shared = shared
+	}
+
+	/// Returns the cached expansion of `value` with the given
+	/// `document_relative` and `vocab` flags, if any.
+	///
+	/// The lookup is counted in the cache [statistics](Self::stats).
+	pub fn get(
+		&self,
+		value: &str,
+		document_relative: bool,
+		vocab: bool,
+	) -> Option<Option<Term<T, B>>>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| {
+				let result = entries
+					.map_mut(document_relative, vocab)
+					.get(value)
+					.cloned();
+
+				if result.is_some() {
+					entries.hits += 1
+				} else {
+					entries.misses += 1
+				}
+
+				result
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			let _ = (value, document_relative, vocab);
+			None
+		}
+	}
+
+	/// Stores the expansion of `value` with the given `document_relative` and
+	/// `vocab` flags.
+	///
+	/// The cache is emptied first if it is full.
+	pub fn insert(
+		&self,
+		value: &str,
+		document_relative: bool,
+		vocab: bool,
+		term: Option<Term<T, B>>,
+	) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| {
+			if entries.capacity == 0 {
+				return;
+			}
+
+			if entries.len() >= entries.capacity {
+				entries.clear();
+				entries.evictions += 1
+			}
+
+			entries
+				.map_mut(document_relative, vocab)
+				.insert(value.into(), term);
+		});
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = (value, document_relative, vocab, term);
+	}
+
+	/// Removes every cached expansion, including from the caches sharing
+	/// them.
+	///
+	/// The statistics are kept.
+	pub fn clear(&self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(Entries::clear)
+	}
+
+	/// Returns the cache statistics.
+	pub fn stats(&self) -> IriCacheStats {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| IriCacheStats {
+				hits: entries.hits,
+				misses: entries.misses,
+				len: entries.len(),
+				evictions: entries.evictions,
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			IriCacheStats::default()
+		}
+	}
+
+	/// Empties the cache, without affecting the clones sharing it.
+	pub(crate) fn reset(&mut self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			let capacity = self.capacity();
+			match Arc::get_mut(&mut self.entries) {
+				Some(entries) => entries.with(|entries| *entries = Entries::new(capacity)),
+				None => {
+					self.entries = Arc::new(crate::loader::SharedCache::new(Entries::new(capacity)))
+				}
+			}
+		}
+	}
+}
+
+#[cfg(all(test, any(feature = "std", feature = "spin")))]
+mod tests {
+	use super::*;
+	use iref::IriBuf;
+	use rdf_types::BlankIdBuf;
+
+	type Cache = IriCache<IriBuf, BlankIdBuf>;
+
+	#[test]
+	fn bounded() {
+		let cache = Cache::with_capacity(2);
+		cache.insert("a", false, true, None);
+		cache.insert("b", false, true, None);
+		assert_eq!(cache.stats().len, 2);
+
+		cache.insert("c", true, true, None);
+		assert_eq!(cache.stats().len, 1);
+		assert_eq!(cache.stats().evictions, 1);
+		assert_eq!(cache.get("a", false, true), None);
+		assert_eq!(cache.get("c", true, true), Some(None));
+	}
+
+	#[test]
+	fn disabled() {
+		let cache = Cache::with_capacity(0);
+		cache.insert("a", false, true, None);
+		assert_eq!(cache.get("a", false, true), None);
+	}
+
+	#[test]
+	fn clear() {
+		let cache = Cache::default();
+		cache.insert("a", false, true, None);
+		cache.clear();
+		assert_eq!(cache.stats().len, 0);
+	}
+
+	#[test]
+	fn not_shared_by_default() {
+		let cache = Cache::with_capacity(8);
+		cache.insert("a", false, true, None);
+
+		let clone = cache.clone();
+		assert_eq!(clone.capacity(), 8);
+		assert_eq!(clone.get("a", false, true), None);
+	}
+
+	#[test]
+	fn shared() {
+		let mut cache = Cache::default();
+		cache.set_shared(true);
+		cache.insert("a", false, true, None);
+
+		let clone = cache.clone();
+		assert!(clone.is_shared());
+		assert_eq!(clone.get("a", false, true), Some(None));
+
+		clone.clear();
+		assert_eq!(cache.get("a", false, true), None);
+	}
+}
@@ -1,25 +1,38 @@
 //! Context processing algorithm and related types.
+mod builder;
+mod cache;
 mod definition;
+mod features;
 pub mod inverse;
+pub mod iri_cache;
+pub mod scoped;
 
 use crate::{Direction, LenientLangTag, LenientLangTagBuf, Term};
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
 use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use contextual::WithContext;
 use core::borrow::Borrow;
 use core::hash::Hash;
 use iref::IriBuf;
-use json_ld_syntax::{KeywordType, Nullable};
-use rdf_types::{BlankIdBuf, Id, Vocabulary};
+use json_ld_syntax::{ErrorCode, IntoJson, KeywordType, Nullable};
+use rdf_types::{vocabulary::IriVocabulary, BlankId, BlankIdBuf, Id, Vocabulary, VocabularyMut};
 
 pub use json_ld_syntax::context::{
 	definition::{Key, KeyOrType, Type},
 	term_definition::Nest,
 };
 
+pub use builder::{ContextBuilder, TermDefinitionBuilder};
+pub use cache::{ContextCache, ContextCacheKey, ContextCacheStats};
 pub use definition::*;
+pub use features::{Feature, FeatureUse};
+use inverse::InverseCache;
 pub use inverse::InverseContext;
+pub use iri_cache::{IriCache, IriCacheStats};
+pub use scoped::{Scope, ScopedContextKey, ScopedContexts, TypeScopedContexts};
 
 /// Processed JSON-LD context.
 ///
@@ -36,6 +49,11 @@ pub struct Context<T = IriBuf, B = BlankIdBuf> {
 	default_base_direction: Option<Direction>,
 	previous_context: Option<Box<Self>>,
 	definitions: Definitions<T, B>,
+	scoped: ScopedContexts<T, B>,
+	type_scoped: TypeScopedContexts<T, B>,
+	iris: IriCache<T, B>,
+	inverse: InverseCache<T, B>,
+	context_cache: Option<ContextCache<T, B>>,
 }
 
 impl<T, B> Default for Context<T, B> {
@@ -48,6 +66,11 @@ impl<T, B> Default for Context<T, B> {
 			default_base_direction: None,
 			previous_context: None,
 			definitions: Definitions::default(),
+			scoped: ScopedContexts::default(),
+			type_scoped: TypeScopedContexts::default(),
+			iris: IriCache::default(),
+			inverse: InverseCache::default(),
+			context_cache: None,
 		}
 	}
 }
@@ -68,9 +91,57 @@ impl<T, B> Context<T, B> {
 			default_base_direction: None,
 			previous_context: None,
 			definitions: Definitions::default(),
+			scoped: ScopedContexts::default(),
+			type_scoped: TypeScopedContexts::default(),
+			iris: IriCache::default(),
+			inverse: InverseCache::default(),
+			context_cache: None,
 		}
 	}
 
+	/// Returns the cache of the scoped contexts processed on top of this
+	/// context.
+	pub fn scoped_contexts(&self) -> &ScopedContexts<T, B> {
+		&self.scoped
+	}
+
+	/// Returns the table of the type-scoped contexts pre-compiled on top of
+	/// this context.
+	pub fn type_scoped_contexts(&self) -> &TypeScopedContexts<T, B> {
+		&self.type_scoped
+	}
+
+	/// Returns the cache of the processed contexts attached to this context,
+	/// if any.
+	pub fn context_cache(&self) -> Option<&ContextCache<T, B>> {
+		self.context_cache.as_ref()
+	}
+
+	/// Attaches the given processed contexts cache to this context.
+	///
+	/// The expansion algorithm then uses the cache to process the `@context`
+	/// entries found while the active context is empty. The cache is kept by
+	/// the contexts derived from this one, so passing the same cache with
+	/// the initial context of every expanded document allows their common
+	/// contexts to be processed only once.
+	pub fn with_context_cache(self, cache: ContextCache<T, B>) -> Self {
+		Self {
+			context_cache: Some(cache),
+			..self
+		}
+	}
+
+	/// Returns the cache of the IRIs expanded with this context.
+	pub fn iri_cache(&self) -> &IriCache<T, B> {
+		&self.iris
+	}
+
+	/// Returns a mutable reference to the cache of the IRIs expanded with
+	/// this context, to configure its capacity or sharing.
+	pub fn iri_cache_mut(&mut self) -> &mut IriCache<T, B> {
+		&mut self.iris
+	}
+
 	/// Returns a reference to the given `term` definition, if any.
 	pub fn get<Q>(&self, term: &Q) -> Option<TermDefinitionRef<T, B>>
 	where
@@ -158,6 +229,20 @@ impl<T, B> Context<T, B> {
 		&self.definitions
 	}
 
+	/// Returns an iterator over the term definitions, ordered by term.
+	///
+	/// Each [`BindingRef`] gives access to the term and to its definition
+	/// metadata through [`TermDefinitionRef`]: IRI mapping, container, type,
+	/// language and direction mappings, prefix and protected flags, nest
+	/// value, etc.
+	///
+	/// Terms defined by scoped contexts are not included.
+	pub fn terms(&self) -> Terms<T, B> {
+		let mut bindings: Vec<_> = self.definitions.iter().collect();
+		bindings.sort_by(|a, b| a.term().as_str().cmp(b.term().as_str()));
+		Terms(bindings.into_iter())
+	}
+
 	/// Checks if the context has a protected definition.
 	pub fn has_protected_items(&self) -> bool {
 		for binding in self.definitions() {
@@ -170,12 +255,286 @@ impl<T, B> Context<T, B> {
 	}
 
 	/// Returns the inverse of this context.
-	pub fn inverse(&self) -> InverseContext<T, B>
+	///
+	/// The inverse context is computed by the [inverse context creation
+	/// algorithm][1] the first time it is requested, and then cached until
+	/// this context is modified. The cache is shared with the clones of this
+	/// context.
+	///
+	/// [1]: <https://www.w3.org/TR/json-ld11-api/#inverse-context-creation>
+	pub fn inverse(&self) -> Arc<InverseContext<T, B>>
 	where
 		T: Clone + Hash + Eq,
 		B: Clone + Hash + Eq,
 	{
-		self.clone().into()
+		self.inverse.get_or_init(|| self.into())
+	}
+
+	/// Returns the preferred term mapped to `var`, regardless of its
+	/// container, type or language mapping.
+	///
+	/// This is the shortest such term, or the lexicographically least one if
+	/// there are several.
+	pub fn term_for(&self, var: &Term<T, B>) -> Option<Key>
+	where
+		T: Clone + Hash + Eq,
+		B: Clone + Hash + Eq,
+	{
+		self.inverse().get(var).map(|d| d.preferred_term().clone())
+	}
+
+	/// Returns the terms whose IRI mapping is `iri`, or a prefix of `iri`.
+	///
+	/// Terms mapped to `iri` come first, ordered by term, followed by the
+	/// terms mapped to a prefix of `iri`, from the longest prefix to the
+	/// shortest. Terms mapped to a prefix are returned whether or not their
+	/// prefix flag is set.
+	pub fn definitions_for_iri(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+		iri: &iref::Iri,
+	) -> Vec<(&Key, &NormalTermDefinition<T, B>)> {
+		let mut result: Vec<_> = self
+			.definitions
+			.iter()
+			.filter_map(|binding| match binding {
+				BindingRef::Normal(
+					key,
+					definition @ NormalTermDefinition {
+						value: Some(Term::Id(crate::Id::Valid(Id::Iri(mapping)))),
+						..
+					},
+				) => {
+					let mapping = vocabulary.iri(mapping)?.as_str();
+					iri.as_str()
+						.starts_with(mapping)
+						.then_some((mapping.len(), key, definition))
+				}
+				_ => None,
+			})
+			.collect();
+
+		result.sort_by(|(a_len, a, _), (b_len, b, _)| b_len.cmp(a_len).then_with(|| a.cmp(b)));
+		result
+			.into_iter()
+			.map(|(_, key, definition)| (key, definition))
+			.collect()
+	}
+
+	/// Expands the given term, compact IRI, blank node identifier or IRI
+	/// reference against this context, following the [IRI expansion
+	/// algorithm][1].
+	///
+	/// If `vocab` is `true`, `value` is expanded as a vocabulary-relative
+	/// IRI (e.g. an entry key or a `@type` value): terms and the vocabulary
+	/// mapping apply. If `document_relative` is `true`, `value` is expanded
+	/// as a document-relative IRI (e.g. an `@id` value), and resolved against
+	/// the base IRI when nothing else applies.
+	///
+	/// Returns [`Term::Null`] if `value` looks like a keyword without being
+	/// one, or is a term explicitly mapped to `null`. If `value` cannot be
+	/// expanded to an IRI or blank node identifier, it is returned as an
+	/// [invalid](crate::Id::Invalid) identifier.
+	///
+	/// ```
+	/// use json_ld_core::context::ContextBuilder;
+	/// use json_ld_core::{Context, Id, Term};
+	/// use iref::IriBuf;
+	///
+	/// let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+	///
+	/// let context: Context = ContextBuilder::new()
+	///   .vocab(iri("https://schema.org/"))
+	///   .term("foaf", iri("http://xmlns.com/foaf/0.1/"))
+	///   .build(&());
+	///
+	/// assert_eq!(
+	///   context.expand_term(&mut (), "foaf:knows", true, false),
+	///   Term::Id(Id::iri(iri("http://xmlns.com/foaf/0.1/knows")))
+	/// );
+	///
+	/// assert_eq!(
+	///   context.expand_term(&mut (), "name", true, false),
+	///   Term::Id(Id::iri(iri("https://schema.org/name")))
+	/// );
+	/// ```
+	///
+	/// [1]: <https://www.w3.org/TR/json-ld11-api/#iri-expansion>
+	pub fn expand_term(
+		&self,
+		vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>,
+		value: &str,
+		vocab: bool,
+		document_relative: bool,
+	) -> Term<T, B>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		if let Ok(keyword) = json_ld_syntax::Keyword::try_from(value) {
+			return Term::Keyword(keyword);
+		}
+
+		if json_ld_syntax::is_keyword_like(value) {
+			return Term::Null;
+		}
+
+		if let Some(term_definition) = self.get(value) {
+			// If the term definition maps `value` to a keyword, return that
+			// keyword.
+			if let Some(value) = term_definition.value() {
+				if value.is_keyword() {
+					return value.clone();
+				}
+			}
+
+			if vocab {
+				return term_definition.value().cloned().unwrap_or(Term::Null);
+			}
+		}
+
+		if value.find(':').map(|i| i > 0).unwrap_or(false) {
+			if let Ok(blank_id) = BlankId::new(value) {
+				return Term::Id(crate::Id::blank(vocabulary.insert_blank_id(blank_id)));
+			}
+
+			if value == "_:" {
+				return Term::Id(crate::Id::Invalid(value.to_owned()));
+			}
+
+			if let Ok(compact_iri) = json_ld_syntax::CompactIri::new(value) {
+				// Prefix expansion, if the prefix is usable as such.
+				let prefix_key = Key::from(compact_iri.prefix().to_string());
+				if let Some(term_definition) = self.get_normal(&prefix_key) {
+					if term_definition.prefix {
+						if let Some(mapping) = &term_definition.value {
+							let mut result = mapping.with(&*vocabulary).as_str().to_string();
+							result.push_str(compact_iri.suffix());
+							return Term::Id(crate::Id::from_string_in(vocabulary, result));
+						}
+					}
+				}
+			}
+
+			if let Ok(iri) = iref::Iri::new(value) {
+				return Term::Id(crate::Id::iri(vocabulary.insert(iri)));
+			}
+		}
+
+		if vocab {
+			match self.vocabulary() {
+				Some(Term::Id(mapping)) => {
+					let mut result = mapping.with(&*vocabulary).as_str().to_string();
+					result.push_str(value);
+					return Term::Id(crate::Id::from_string_in(vocabulary, result));
+				}
+				Some(_) => return Term::Id(crate::Id::Invalid(value.to_owned())),
+				None => (),
+			}
+		}
+
+		if document_relative {
+			if let Ok(iri_ref) = iref::IriRef::new(value) {
+				let iri = match self.base_iri() {
+					Some(base_iri) => {
+						let resolved = iri_ref.resolved(vocabulary.iri(base_iri).unwrap());
+						Some(vocabulary.insert(resolved.as_iri()))
+					}
+					None => iri_ref.as_iri().map(|iri| vocabulary.insert(iri)),
+				};
+
+				if let Some(iri) = iri {
+					return Term::Id(crate::Id::iri(iri));
+				}
+			}
+		}
+
+		Term::Id(crate::Id::Invalid(value.to_owned()))
+	}
+
+	/// Merges `other` on top of this context.
+	///
+	/// This follows the semantics of processing an array of contexts: the
+	/// term definitions of `other` override those of this context, unless
+	/// they are protected. A protected term can only be redefined identically
+	/// (other than the protected flag), in which case it stays protected.
+	///
+	/// The base IRI, vocabulary mapping, default language and default base
+	/// direction of this context are only overridden when set in `other`.
+	///
+	/// On error, this context is left unchanged.
+	pub fn merge(&mut self, other: &Self) -> Result<(), ProtectedTermRedefinition>
+	where
+		T: Clone + PartialEq,
+		B: Clone + PartialEq,
+	{
+		for binding in other.definitions() {
+			match binding {
+				BindingRef::Normal(key, definition) => {
+					if let Some(previous_definition) = self.get_normal(key) {
+						if previous_definition.protected
+							&& previous_definition.modulo_protected_field()
+								!= definition.modulo_protected_field()
+						{
+							return Err(ProtectedTermRedefinition(key.clone()));
+						}
+					}
+				}
+				BindingRef::Type(definition) => {
+					if let Some(previous_definition) = self.get_type() {
+						if previous_definition.protected
+							&& previous_definition.modulo_protected_field()
+								!= definition.modulo_protected_field()
+						{
+							return Err(ProtectedTermRedefinition("@type".into()));
+						}
+					}
+				}
+			}
+		}
+
+		if other.previous_context.is_some() && self.previous_context.is_none() {
+			self.set_previous_context(self.clone())
+		}
+
+		if let Some(base_iri) = &other.base_iri {
+			self.set_base_iri(Some(base_iri.clone()))
+		}
+
+		if let Some(vocab) = &other.vocabulary {
+			self.set_vocabulary(Some(vocab.clone()))
+		}
+
+		if let Some(language) = &other.default_language {
+			self.set_default_language(Some(language.clone()))
+		}
+
+		if let Some(direction) = other.default_base_direction {
+			self.set_default_base_direction(Some(direction))
+		}
+
+		for binding in other.definitions() {
+			match binding {
+				BindingRef::Normal(key, definition) => {
+					let mut definition = definition.clone();
+					if let Some(previous_definition) = self.get_normal(key) {
+						definition.protected |= previous_definition.protected
+					}
+
+					self.set_normal(key.clone(), Some(definition));
+				}
+				BindingRef::Type(definition) => {
+					let mut definition = definition.clone();
+					if let Some(previous_definition) = self.get_type() {
+						definition.protected |= previous_definition.protected
+					}
+
+					self.set_type(Some(definition));
+				}
+			}
+		}
+
+		Ok(())
 	}
 
 	/// Sets the normal definition for the given term `key`.
@@ -184,39 +543,107 @@ impl<T, B> Context<T, B> {
 		key: Key,
 		definition: Option<NormalTermDefinition<T, B>>,
 	) -> Option<NormalTermDefinition<T, B>> {
+		self.invalidate();
 		self.definitions.set_normal(key, definition)
 	}
 
+	/// Replaces the definition of the given term `key`, even if it is
+	/// protected.
+	///
+	/// This is an escape hatch for controlled environments that need to
+	/// redefine protected terms deliberately: unlike [`Self::merge`] or
+	/// context processing, no protected term redefinition error is raised,
+	/// and the protected flag of the new definition is used as is.
+	///
+	/// Returns the previous definition, if any.
+	pub fn override_protected(
+		&mut self,
+		key: Key,
+		definition: Option<NormalTermDefinition<T, B>>,
+	) -> Option<NormalTermDefinition<T, B>> {
+		self.set_normal(key, definition)
+	}
+
 	/// Sets the `@type` definition.
 	pub fn set_type(&mut self, type_: Option<TypeTermDefinition>) -> Option<TypeTermDefinition> {
+		self.invalidate();
 		self.definitions.set_type(type_)
 	}
 
 	/// Sets the base IRI.
 	pub fn set_base_iri(&mut self, iri: Option<T>) {
+		self.invalidate();
 		self.base_iri = iri
 	}
 
 	/// Sets the `@vocab` value.
 	pub fn set_vocabulary(&mut self, vocab: Option<Term<T, B>>) {
+		self.invalidate();
 		self.vocabulary = vocab;
 	}
 
 	/// Sets the default `@language` value.
 	pub fn set_default_language(&mut self, lang: Option<LenientLangTagBuf>) {
+		self.invalidate();
 		self.default_language = lang;
 	}
 
 	/// Sets the default `@direction` value.
 	pub fn set_default_base_direction(&mut self, dir: Option<Direction>) {
+		self.invalidate();
 		self.default_base_direction = dir;
 	}
 
 	/// Sets the previous context.
 	pub fn set_previous_context(&mut self, previous: Self) {
+		self.invalidate();
 		self.previous_context = Some(Box::new(previous))
 	}
 
+	/// Detaches this context from the caches it shares with its clones, since
+	/// they no longer apply once it is modified.
+	fn invalidate(&mut self) {
+		self.scoped.reset();
+		self.type_scoped.reset();
+		self.iris.reset();
+		self.inverse.reset()
+	}
+
+	/// Converts this context into a syntactic `@context` value.
+	///
+	/// The result defines the same terms as this context when processed on
+	/// top of an empty active context. Definitions inherited from previous
+	/// contexts are included.
+	pub fn into_syntax(
+		self,
+		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
+	) -> json_ld_syntax::context::Context {
+		json_ld_syntax::context::Context::One(json_ld_syntax::ContextEntry::Definition(
+			self.into_syntax_definition(vocabulary),
+		))
+	}
+
+	/// Converts this context into a JSON `@context` value.
+	///
+	/// See [`Self::into_syntax`].
+	pub fn into_json(
+		self,
+		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
+	) -> json_syntax::Value {
+		self.into_syntax(vocabulary).into_json()
+	}
+
+	/// Returns the JSON `@context` value of this context.
+	///
+	/// See [`Self::into_syntax`].
+	pub fn to_json(&self, vocabulary: &impl Vocabulary<Iri = T, BlankId = B>) -> json_syntax::Value
+	where
+		T: Clone,
+		B: Clone,
+	{
+		self.clone().into_json(vocabulary)
+	}
+
 	/// Converts this context into its syntactic definition.
 	pub fn into_syntax_definition(
 		self,
@@ -224,6 +651,9 @@ impl<T, B> Context<T, B> {
 	) -> json_ld_syntax::context::Definition {
 		let (bindings, type_) = self.definitions.into_parts();
 
+		// A previous context is only kept for non-propagated contexts.
+		let propagate = self.previous_context.map(|_| false);
+
 		json_ld_syntax::context::Definition {
 			base: self
 				.base_iri
@@ -231,7 +661,7 @@ impl<T, B> Context<T, B> {
 			import: None,
 			language: self.default_language.map(Nullable::Some),
 			direction: self.default_base_direction.map(Nullable::Some),
-			propagate: None,
+			propagate,
 			protected: None,
 			type_: type_.map(TypeTermDefinition::into_syntax_definition),
 			version: None,
@@ -270,10 +700,53 @@ impl<T, B> Context<T, B> {
 				.previous_context
 				.map(|c| Box::new((*c).map_ids_with(map_iri, map_id))),
 			definitions: self.definitions.map_ids(map_iri, map_id),
+			scoped: ScopedContexts::default(),
+			type_scoped: TypeScopedContexts::default(),
+			iris: IriCache::default(),
+			inverse: InverseCache::default(),
+			context_cache: None,
 		}
 	}
 }
 
+/// Iterator over the term definitions of a [`Context`], ordered by term.
+///
+/// Returned by [`Context::terms`].
+pub struct Terms<'a, T, B>(alloc::vec::IntoIter<BindingRef<'a, T, B>>);
+
+impl<'a, T, B> Iterator for Terms<'a, T, B> {
+	type Item = BindingRef<'a, T, B>;
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next()
+	}
+}
+
+impl<'a, T, B> ExactSizeIterator for Terms<'a, T, B> {}
+
+impl<'a, T, B> DoubleEndedIterator for Terms<'a, T, B> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.0.next_back()
+	}
+}
+
+/// Protected term redefinition error.
+///
+/// Raised by [`Context::merge`] when a protected term is redefined.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Protected term `{0}` redefinition")]
+pub struct ProtectedTermRedefinition(pub Key);
+
+impl ProtectedTermRedefinition {
+	pub fn code(&self) -> ErrorCode {
+		ErrorCode::ProtectedTermRedefinition
+	}
+}
+
 /// Context fragment to syntax method.
 pub trait IntoSyntax<T = IriBuf, B = BlankIdBuf> {
 	fn into_syntax(
@@ -291,14 +764,12 @@ impl<T, B> IntoSyntax<T, B> for json_ld_syntax::context::Context {
 	}
 }
 
-impl<T, B: Clone> IntoSyntax<T, B> for Context<T, B> {
+impl<T, B> IntoSyntax<T, B> for Context<T, B> {
 	fn into_syntax(
 		self,
 		vocabulary: &impl Vocabulary<Iri = T, BlankId = B>,
 	) -> json_ld_syntax::context::Context {
-		json_ld_syntax::context::Context::One(json_ld_syntax::ContextEntry::Definition(
-			self.into_syntax_definition(vocabulary),
-		))
+		Context::into_syntax(self, vocabulary)
 	}
 }
 
@@ -312,6 +783,11 @@ impl<T: Clone, B: Clone> Clone for Context<T, B> {
 			default_base_direction: self.default_base_direction,
 			previous_context: self.previous_context.clone(),
 			definitions: self.definitions.clone(),
+			scoped: self.scoped.clone(),
+			type_scoped: self.type_scoped.clone(),
+			iris: self.iris.clone(),
+			inverse: self.inverse.clone(),
+			context_cache: self.context_cache.clone(),
 		}
 	}
 }
@@ -326,3 +802,222 @@ impl<T: PartialEq, B: PartialEq> PartialEq for Context<T, B> {
 			&& self.previous_context == other.previous_context
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Container, Type};
+	use json_syntax::json;
+
+	#[test]
+	fn to_json() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let context: Context = ContextBuilder::new()
+			.vocab(iri("https://schema.org/"))
+			.term("schema", iri("https://schema.org/"))
+			.define(
+				"knows",
+				TermDefinitionBuilder::new(iri("https://schema.org/knows"))
+					.typ(Type::Id)
+					.container(Container::Set)
+					.protected(true),
+			)
+			.build(&());
+
+		let value = context.to_json(&());
+		let object = value.as_object().unwrap();
+
+		assert_eq!(
+			object.get_unique("@vocab").unwrap(),
+			Some(&json!("https://schema.org/"))
+		);
+		assert_eq!(
+			object.get_unique("schema").unwrap(),
+			Some(&json!({ "@id": "https://schema.org/", "@prefix": true }))
+		);
+		assert_eq!(
+			object.get_unique("knows").unwrap(),
+			Some(&json!({
+				"@id": "https://schema.org/knows",
+				"@type": "@id",
+				"@container": "@set",
+				"@protected": true
+			}))
+		);
+	}
+
+	#[test]
+	fn merge() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let mut context: Context = ContextBuilder::new()
+			.term("name", iri("https://schema.org/name"))
+			.define(
+				"knows",
+				TermDefinitionBuilder::new(iri("https://schema.org/knows")).protected(true),
+			)
+			.build(&());
+
+		let overlay: Context = ContextBuilder::new()
+			.term("name", iri("https://example.org/name"))
+			.term("knows", iri("https://schema.org/knows"))
+			.build(&());
+
+		context.merge(&overlay).unwrap();
+		assert_eq!(
+			context.get_normal("name").unwrap().value,
+			Some(Term::Id(crate::Id::iri(iri("https://example.org/name"))))
+		);
+		assert!(context.get_normal("knows").unwrap().protected);
+
+		let conflicting: Context = ContextBuilder::new()
+			.term("knows", iri("https://example.org/knows"))
+			.build(&());
+
+		assert!(context.merge(&conflicting).is_err());
+		assert_eq!(
+			context.get_normal("name").unwrap().value,
+			Some(Term::Id(crate::Id::iri(iri("https://example.org/name"))))
+		);
+	}
+
+	#[test]
+	fn term_for() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let mut context: Context = ContextBuilder::new()
+			.term("name", iri("https://schema.org/name"))
+			.term("nm", iri("https://schema.org/name"))
+			.build(&());
+
+		let name = Term::Id(crate::Id::iri(iri("https://schema.org/name")));
+		assert_eq!(context.term_for(&name).unwrap().as_str(), "nm");
+
+		context.set_normal("nm".into(), None);
+		assert_eq!(context.term_for(&name).unwrap().as_str(), "name");
+	}
+
+	#[test]
+	fn terms() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let context: Context = ContextBuilder::new()
+			.term("name", iri("https://schema.org/name"))
+			.term("knows", iri("https://schema.org/knows"))
+			.alias("id", json_ld_syntax::Keyword::Id)
+			.build(&());
+
+		let terms: Vec<_> = context.terms().map(|b| b.term().as_str()).collect();
+		assert_eq!(terms, ["id", "knows", "name"]);
+	}
+
+	#[test]
+	fn definitions_for_iri() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let context: Context = ContextBuilder::new()
+			.term("schema", iri("https://schema.org/"))
+			.term("name", iri("https://schema.org/name"))
+			.term("title", iri("https://schema.org/name"))
+			.term("knows", iri("https://schema.org/knows"))
+			.build(&());
+
+		let terms: Vec<_> = context
+			.definitions_for_iri(&(), &iri("https://schema.org/name"))
+			.into_iter()
+			.map(|(key, _)| key.as_str())
+			.collect();
+		assert_eq!(terms, ["name", "title", "schema"]);
+	}
+
+	#[test]
+	fn expand_term() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+		let id = |s: &str| Term::Id(crate::Id::iri(iri(s)));
+
+		let context: Context = ContextBuilder::new()
+			.base(iri("https://example.org/doc/"))
+			.vocab(iri("https://schema.org/"))
+			.term("foaf", iri("http://xmlns.com/foaf/0.1/"))
+			.term("name", iri("http://xmlns.com/foaf/0.1/name"))
+			.alias("id", json_ld_syntax::Keyword::Id)
+			.build(&());
+
+		let mut v = ();
+		assert_eq!(
+			context.expand_term(&mut v, "name", true, false),
+			id("http://xmlns.com/foaf/0.1/name")
+		);
+		assert_eq!(
+			context.expand_term(&mut v, "foaf:knows", true, false),
+			id("http://xmlns.com/foaf/0.1/knows")
+		);
+		assert_eq!(
+			context.expand_term(&mut v, "knows", true, false),
+			id("https://schema.org/knows")
+		);
+		assert_eq!(
+			context.expand_term(&mut v, "id", true, false),
+			Term::Keyword(json_ld_syntax::Keyword::Id)
+		);
+		assert_eq!(
+			context.expand_term(&mut v, "alice", false, true),
+			id("https://example.org/doc/alice")
+		);
+		assert_eq!(context.expand_term(&mut v, "@foo", true, false), Term::Null);
+	}
+
+	#[test]
+	fn json_ld_1_1_features() {
+		let iri = |s: &str| IriBuf::new(s.to_owned()).unwrap();
+
+		let context: Context = ContextBuilder::new()
+			.term("schema", iri("https://schema.org/"))
+			.define(
+				"tags",
+				TermDefinitionBuilder::new(iri("https://schema.org/keywords"))
+					.container(Container::Set),
+			)
+			.build(&());
+
+		assert!(context.json_ld_1_1_features(&()).is_empty());
+		assert_eq!(
+			context.required_processing_mode(&()),
+			crate::ProcessingMode::JsonLd1_0
+		);
+
+		let context: Context = ContextBuilder::new()
+			.direction(crate::Direction::Ltr)
+			.term("schema", iri("https://schema.org/"))
+			.define(
+				"ex",
+				TermDefinitionBuilder::new(iri("https://example.org/ex")).prefix(true),
+			)
+			.define(
+				"knows",
+				TermDefinitionBuilder::new(iri("https://schema.org/knows"))
+					.container(Container::IdSet)
+					.protected(true),
+			)
+			.alias("nothing", json_ld_syntax::Keyword::None)
+			.build(&());
+
+		let features: Vec<_> = context
+			.json_ld_1_1_features(&())
+			.into_iter()
+			.map(|u| (u.feature, u.term.map(|t| t.as_str().to_owned())))
+			.collect();
+
+		assert_eq!(
+			features,
+			[
+				(Feature::Direction, None),
+				(Feature::Prefix, Some("ex".to_owned())),
+				(Feature::Protected, Some("knows".to_owned())),
+				(Feature::Container, Some("knows".to_owned())),
+				(Feature::KeywordAlias, Some("nothing".to_owned())),
+			]
+		);
+	}
+}
@@ -0,0 +1,258 @@
+//! Processed scoped contexts cache.
+use super::{Context, Key};
+use crate::ProcessingMode;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use hashbrown::HashMap;
+
+/// Scoped context kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+	/// Property-scoped context, processed with protected terms overriding.
+	Property,
+
+	/// Type-scoped context, processed without propagation.
+	Type,
+}
+
+/// Parameters of the processing of a scoped context on top of an active
+/// context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopedContextKey<T> {
+	/// Scope of the context.
+	pub scope: Scope,
+
+	/// Base URL of the term definition.
+	pub base_url: Option<T>,
+
+	/// Processing mode.
+	pub processing_mode: ProcessingMode,
+
+	/// Whether or not warnings are promoted to errors.
+	pub strict: bool,
+
+	/// Whether or not protected terms can be overridden.
+	pub override_protected: bool,
+}
+
+/// Cache of the scoped contexts processed on top of a [`Context`].
+///
+/// Type-scoped and property-scoped contexts are processed every time a node
+/// of the given type, or a value of the given property, is expanded. This
+/// cache allows the expansion algorithm to process them once per active
+/// context. It is shared between the clones of a context, and reset when
+/// the context is modified.
+///
+/// The cache is only enabled with the `std` or `spin` feature.
+pub struct ScopedContexts<T, B> {
+	#[cfg(any(feature = "std", feature = "spin"))]
+	entries: Arc<crate::loader::SharedCache<Entries<T, B>>>,
+
+	#[cfg(not(any(feature = "std", feature = "spin")))]
+	entries: core::marker::PhantomData<Arc<Entries<T, B>>>,
+}
+
+type Entries<T, B> = Vec<(
+	ScopedContextKey<T>,
+	json_ld_syntax::context::Context,
+	Context<T, B>,
+)>;
+
+impl<T, B> Default for ScopedContexts<T, B> {
+	fn default() -> Self {
+		Self {
+			entries: Default::default(),
+		}
+	}
+}
+
+impl<T, B> Clone for ScopedContexts<T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			entries: self.entries.clone(),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			entries: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<T, B> ScopedContexts<T, B> {
+	/// Returns the result of the processing of the `local` scoped context
+	/// with the given parameters, if cached.
+	pub fn get(
+		&self,
+		key: &ScopedContextKey<T>,
+		local: &json_ld_syntax::context::Context,
+	) -> Option<Context<T, B>>
+	where
+		T: Clone + PartialEq,
+		B: Clone,
+	{
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| {
+				entries
+					.iter()
+					.find(|(k, l, _)| k == key && l == local)
+					.map(|(_, _, context)| context.clone())
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			let _ = (key, local);
+			None
+		}
+	}
+
+	/// Stores the result of the processing of the `local` scoped context
+	/// with the given parameters.
+	pub fn insert(
+		&self,
+		key: ScopedContextKey<T>,
+		local: json_ld_syntax::context::Context,
+		context: Context<T, B>,
+	) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries
+			.with(|entries| entries.push((key, local, context)));
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = (key, local, context);
+	}
+
+	/// Returns the number of cached contexts.
+	pub fn len(&self) -> usize {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| entries.len())
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			0
+		}
+	}
+
+	/// Checks if the cache is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Empties the cache, without affecting the clones sharing it.
+	pub(crate) fn reset(&mut self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		match Arc::get_mut(&mut self.entries) {
+			Some(entries) => entries.with(Vec::clear),
+			None => *self = Self::default(),
+		}
+	}
+}
+
+/// Table of the pre-compiled type-scoped contexts of a [`Context`].
+///
+/// Maps each term to the result of the processing of its scoped context, as
+/// a type-scoped context, on top of the context holding the table. The
+/// expansion algorithm looks up this table directly when expanding a node
+/// with a single type-scoped context, instead of processing it or looking it
+/// up in the [`ScopedContexts`] cache. It is filled by
+/// `json_ld_expansion::compile_type_scoped_contexts`, shared between the
+/// clones of a context, and reset when the context is modified.
+///
+/// The table is only enabled with the `std` or `spin` feature.
+pub struct TypeScopedContexts<T, B> {
+	#[cfg(any(feature = "std", feature = "spin"))]
+	entries: Arc<crate::loader::SharedCache<TypeEntries<T, B>>>,
+
+	#[cfg(not(any(feature = "std", feature = "spin")))]
+	entries: core::marker::PhantomData<Arc<TypeEntries<T, B>>>,
+}
+
+type TypeEntries<T, B> = HashMap<Key, (ScopedContextKey<T>, Context<T, B>)>;
+
+impl<T, B> Default for TypeScopedContexts<T, B> {
+	fn default() -> Self {
+		Self {
+			entries: Default::default(),
+		}
+	}
+}
+
+impl<T, B> Clone for TypeScopedContexts<T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			#[cfg(any(feature = "std", feature = "spin"))]
+			entries: self.entries.clone(),
+
+			#[cfg(not(any(feature = "std", feature = "spin")))]
+			entries: core::marker::PhantomData,
+		}
+	}
+}
+
+impl<T, B> TypeScopedContexts<T, B> {
+	/// Returns the pre-compiled type-scoped context of `term`, if it has been
+	/// compiled with the given parameters.
+	pub fn get(&self, term: &str, key: &ScopedContextKey<T>) -> Option<Context<T, B>>
+	where
+		T: Clone + PartialEq,
+		B: Clone,
+	{
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| {
+				entries
+					.get(term)
+					.filter(|(k, _)| k == key)
+					.map(|(_, context)| context.clone())
+			})
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			let _ = (term, key);
+			None
+		}
+	}
+
+	/// Stores the pre-compiled type-scoped context of `term`, processed with
+	/// the given parameters.
+	pub fn insert(&self, term: Key, key: ScopedContextKey<T>, context: Context<T, B>) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		self.entries.with(|entries| {
+			entries.insert(term, (key, context));
+		});
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		let _ = (term, key, context);
+	}
+
+	/// Returns the number of pre-compiled contexts.
+	pub fn len(&self) -> usize {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		{
+			self.entries.with(|entries| entries.len())
+		}
+
+		#[cfg(not(any(feature = "std", feature = "spin")))]
+		{
+			0
+		}
+	}
+
+	/// Checks if the table is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Empties the table, without affecting the clones sharing it.
+	pub(crate) fn reset(&mut self) {
+		#[cfg(any(feature = "std", feature = "spin"))]
+		match Arc::get_mut(&mut self.entries) {
+			Some(entries) => entries.with(HashMap::clear),
+			None => *self = Self::default(),
+		}
+	}
+}
@@ -1,12 +1,17 @@
-use crate::object::{FragmentRef, InvalidExpandedJson, Traverse};
-use crate::{Id, Indexed, IndexedObject, Node, Object, Relabel, TryFromJson};
+use crate::object::{FragmentRef, InvalidExpandedJson, Traverse, TypeHierarchy};
+use crate::{AllocError, Id, Indexed, IndexedObject, Node, Object, Relabel, TryFromJson, ValidId};
 use ahash::RandomState;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::hash::Hash;
 use hashbrown::HashMap;
 use hashbrown::HashSet;
 use indexmap::IndexSet;
-use iref::IriBuf;
-use rdf_types::vocabulary::VocabularyMut;
+use iref::{Iri, IriBuf, IriRef};
+use json_ld_syntax::IntoJsonWithContext;
+use rdf_types::vocabulary::{IriVocabularyMut, VocabularyMut};
 use rdf_types::{BlankIdBuf, Generator, Vocabulary};
 
 /// Result of the document expansion algorithm.
@@ -183,6 +188,36 @@ impl<T, B> ExpandedDocument<T, B> {
 		self.canonicalize_with(&mut buffer)
 	}
 
+	/// Sorts the properties of every node object in this document using the
+	/// given comparison function on property identifiers.
+	///
+	/// See [`Node::sort_properties_by`].
+	pub fn sort_properties_by<F: FnMut(&Id<T, B>, &Id<T, B>) -> core::cmp::Ordering>(
+		&mut self,
+		compare: &mut F,
+	) where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		let objects = core::mem::take(&mut self.0);
+		for mut object in objects {
+			object.sort_properties_by(compare);
+			self.0.insert(object);
+		}
+	}
+
+	/// Sorts the properties of every node object in this document by
+	/// identifier.
+	///
+	/// See [`Node::sort_properties`].
+	pub fn sort_properties(&mut self)
+	where
+		T: Ord + Eq + Hash,
+		B: Ord + Eq + Hash,
+	{
+		self.sort_properties_by(&mut |a: &Id<T, B>, b: &Id<T, B>| a.cmp(b))
+	}
+
 	/// Map the identifiers present in this expanded document (recursively).
 	pub fn map_ids<U, C>(
 		self,
@@ -201,6 +236,136 @@ impl<T, B> ExpandedDocument<T, B> {
 		)
 	}
 
+	/// Resolves the remaining relative IRI references against the given
+	/// `base` IRI, using the given `vocabulary`.
+	///
+	/// The expansion algorithm keeps the identifiers it could not expand into
+	/// absolute IRIs as [`Id::Invalid`] (for instance relative IRIs produced
+	/// using `"@vocab": ""` without base IRI). This pass rewrites those that
+	/// are valid relative IRI references, which is useful when a document is
+	/// re-hosted under a different URL.
+	pub fn resolve_relative_iris_with(
+		&mut self,
+		vocabulary: &mut impl IriVocabularyMut<Iri = T>,
+		base: &Iri,
+	) where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		*self = core::mem::take(self).map_ids(
+			|iri| iri,
+			|id| match id {
+				Id::Invalid(s) => match IriRef::new(&s).map(|r| r.resolved(base)) {
+					Ok(iri) => Id::iri(vocabulary.insert(&iri)),
+					Err(_) => Id::Invalid(s),
+				},
+				id => id,
+			},
+		)
+	}
+
+	/// Resolves the remaining relative IRI references against the given
+	/// `base` IRI.
+	///
+	/// See [`Self::resolve_relative_iris_with`] for more details.
+	pub fn resolve_relative_iris(&mut self, base: &Iri)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+		(): IriVocabularyMut<Iri = T>,
+	{
+		self.resolve_relative_iris_with(&mut (), base)
+	}
+
+	/// Replaces every blank node identifier with an IRI (Skolem IRI), using
+	/// the given `vocabulary`.
+	///
+	/// Following [RDF 1.1 Concepts § 3.5](https://www.w3.org/TR/rdf11-concepts/#section-skolemization),
+	/// Skolem IRIs use the `genid` [well-known URI](https://www.rfc-editor.org/rfc/rfc8615)
+	/// prefix: the blank node `_:b0` is replaced with the IRI
+	/// `/.well-known/genid/b0` resolved against the given `base` IRI.
+	/// The resulting document can be shared with systems that do not share
+	/// the blank node scope of this one. Use [`Self::deskolemize_with`] to
+	/// revert this transformation.
+	///
+	/// Skolem IRIs are derived from the blank node identifiers: they are only
+	/// globally unique if the authority of `base` is controlled by the caller
+	/// and never used to skolemize other documents with the same blank node
+	/// identifiers. Documents can first be [relabeled](Self::relabel_with)
+	/// with unique identifiers to ensure it.
+	pub fn skolemize_with(
+		&mut self,
+		vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>,
+		base: &Iri,
+	) where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		*self = core::mem::take(self).map_ids(
+			|iri| iri,
+			|id| match id {
+				Id::Valid(ValidId::Blank(b)) => {
+					match vocabulary
+						.blank_id(&b)
+						.and_then(|b| skolem_iri(base, b.suffix()))
+					{
+						Some(iri) => Id::iri(vocabulary.insert(&iri)),
+						None => Id::blank(b),
+					}
+				}
+				id => id,
+			},
+		)
+	}
+
+	/// Replaces every blank node identifier with a Skolem IRI.
+	///
+	/// See [`Self::skolemize_with`] for more details.
+	pub fn skolemize(&mut self, base: &Iri)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+		(): VocabularyMut<Iri = T, BlankId = B>,
+	{
+		self.skolemize_with(&mut (), base)
+	}
+
+	/// Replaces every Skolem IRI (an IRI whose path starts with
+	/// `/.well-known/genid/`) with the blank node identifier it stands for,
+	/// using the given `vocabulary`.
+	///
+	/// This is the inverse of [`Self::skolemize_with`]. Skolem IRIs are
+	/// recognized by their path only, whatever their scheme and authority.
+	pub fn deskolemize_with(&mut self, vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		*self = core::mem::take(self).map_ids(
+			|iri| iri,
+			|id| match id {
+				Id::Valid(ValidId::Iri(i)) => match vocabulary.iri(&i).and_then(skolem_blank_id) {
+					Some(b) => Id::blank(vocabulary.insert_owned_blank_id(b)),
+					None => Id::iri(i),
+				},
+				id => id,
+			},
+		)
+	}
+
+	/// Replaces every Skolem IRI with the blank node identifier it stands
+	/// for.
+	///
+	/// See [`Self::deskolemize_with`] for more details.
+	pub fn deskolemize(&mut self)
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+		(): VocabularyMut<Iri = T, BlankId = B>,
+	{
+		self.deskolemize_with(&mut ())
+	}
+
 	/// Returns the set of all blank identifiers in the given document.
 	pub fn blank_ids(&self) -> HashSet<&B>
 	where
@@ -211,6 +376,128 @@ impl<T, B> ExpandedDocument<T, B> {
 			.collect()
 	}
 
+	/// Checks if this document is isomorphic to `other`, meaning that both
+	/// documents are equal up to a renaming of their blank node identifiers.
+	///
+	/// Returns the bijection mapping the blank node identifiers of this
+	/// document to the blank node identifiers of `other`, or `None` if the
+	/// documents are not isomorphic.
+	pub fn isomorphic_to(&self, other: &Self) -> Option<BTreeMap<B, B>>
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Ord + Hash,
+	{
+		let blank_ids: Vec<&B> = self.blank_ids().into_iter().collect();
+		let other_blank_ids: Vec<&B> = other.blank_ids().into_iter().collect();
+		if self.len() != other.len()
+			|| blank_ids.len() != other_blank_ids.len()
+			|| !self.is_isomorphic_under(other, &BTreeMap::new())
+		{
+			return None;
+		}
+
+		// Each blank node identifier can only be mapped to an identifier
+		// appearing in the same places, the other identifiers being ignored.
+		let other_labeled: Vec<_> = other_blank_ids
+			.iter()
+			.map(|&b| other.label_blank_ids(|c| (c == b).then_some(0)))
+			.collect();
+
+		let mut candidates: Vec<(&B, Vec<&B>)> = blank_ids
+			.into_iter()
+			.map(|a| {
+				let labeled = self.label_blank_ids(|c| (c == a).then_some(0));
+				let images = other_blank_ids
+					.iter()
+					.zip(&other_labeled)
+					.filter(|(_, l)| **l == labeled)
+					.map(|(b, _)| *b)
+					.collect();
+				(a, images)
+			})
+			.collect();
+
+		// Most constrained identifiers first.
+		candidates.sort_by_key(|(_, images)| images.len());
+
+		let mut mapping = BTreeMap::new();
+		if self.find_isomorphism(other, &candidates, &mut mapping) {
+			Some(
+				mapping
+					.into_iter()
+					.map(|(a, b)| (a.clone(), b.clone()))
+					.collect(),
+			)
+		} else {
+			None
+		}
+	}
+
+	/// Backtracking search of a blank node bijection extending `mapping`.
+	fn find_isomorphism<'a>(
+		&'a self,
+		other: &'a Self,
+		candidates: &[(&'a B, Vec<&'a B>)],
+		mapping: &mut BTreeMap<&'a B, &'a B>,
+	) -> bool
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Ord + Hash,
+	{
+		let Some(((a, images), rest)) = candidates.split_first() else {
+			return true;
+		};
+
+		for b in images {
+			if mapping.values().any(|c| c == b) {
+				continue;
+			}
+
+			mapping.insert(*a, *b);
+			if self.is_isomorphic_under(other, mapping)
+				&& self.find_isomorphism(other, rest, mapping)
+			{
+				return true;
+			}
+
+			mapping.remove(a);
+		}
+
+		false
+	}
+
+	/// Checks that both documents are equal once the blank node identifiers
+	/// of `mapping` are given the same label, ignoring the others.
+	fn is_isomorphic_under(&self, other: &Self, mapping: &BTreeMap<&B, &B>) -> bool
+	where
+		T: Clone + Eq + Hash,
+		B: Clone + Ord + Hash,
+	{
+		let labels: HashMap<&B, usize> = mapping.keys().copied().zip(0..).collect();
+		let other_labels: HashMap<&B, usize> = mapping.values().copied().zip(0..).collect();
+		self.label_blank_ids(|b| labels.get(b).copied())
+			== other.label_blank_ids(|b| other_labels.get(b).copied())
+	}
+
+	/// Replaces every blank node identifier with the given label.
+	fn label_blank_ids(
+		&self,
+		label: impl Fn(&B) -> Option<usize>,
+	) -> ExpandedDocument<T, Option<usize>>
+	where
+		T: Clone + Eq + Hash,
+		B: Clone,
+	{
+		self.clone().map_ids(
+			|t| t,
+			|id| match id {
+				Id::Valid(ValidId::Blank(b)) => Id::Valid(ValidId::Blank(label(&b))),
+				Id::Valid(ValidId::Iri(i)) => Id::Valid(ValidId::Iri(i)),
+				Id::Invalid(s) => Id::Invalid(s),
+			},
+		)
+	}
+
 	/// Returns the main node object of the document, if any.
 	///
 	/// The main node is the unique top level (root) node object. If multiple
@@ -252,11 +539,167 @@ impl<T, B> ExpandedDocument<T, B> {
 	}
 }
 
+/// Path prefix of Skolem IRIs: the `genid` well-known URI suffix registered
+/// by [RDF 1.1 Concepts § 3.5](https://www.w3.org/TR/rdf11-concepts/#section-skolemization),
+/// under the [RFC 8615](https://www.rfc-editor.org/rfc/rfc8615) well-known
+/// URI path prefix.
+const GENID_PATH: &str = "/.well-known/genid/";
+
+/// Builds the Skolem IRI of the blank node with the given `suffix`.
+fn skolem_iri(base: &Iri, suffix: &str) -> Option<IriBuf> {
+	let path = format!("{GENID_PATH}{suffix}");
+	IriRef::new(&path).ok().map(|r| r.resolved(base))
+}
+
+/// Returns the blank node identifier the given Skolem IRI stands for.
+fn skolem_blank_id(iri: &Iri) -> Option<BlankIdBuf> {
+	let suffix = iri.path().as_str().strip_prefix(GENID_PATH)?;
+	BlankIdBuf::from_suffix(suffix).ok()
+}
+
 impl<T: Hash + Eq, B: Hash + Eq> ExpandedDocument<T, B> {
 	#[inline(always)]
 	pub fn insert(&mut self, object: IndexedObject<T, B>) -> bool {
 		self.0.insert(object)
 	}
+
+	/// Inserts the given object, returning an error instead of aborting if
+	/// memory cannot be allocated.
+	pub fn try_insert(&mut self, object: IndexedObject<T, B>) -> Result<bool, AllocError> {
+		self.0.try_reserve(1)?;
+		Ok(self.0.insert(object))
+	}
+
+	/// Inserts the given objects, returning an error instead of aborting if
+	/// memory cannot be allocated.
+	///
+	/// If an allocation fails, the objects inserted before are kept.
+	pub fn try_extend(
+		&mut self,
+		objects: impl IntoIterator<Item = IndexedObject<T, B>>,
+	) -> Result<(), AllocError> {
+		let objects = objects.into_iter();
+		self.0.try_reserve(objects.size_hint().0)?;
+		for object in objects {
+			self.try_insert(object)?;
+		}
+
+		Ok(())
+	}
+
+	/// Replaces the index (`@index`) of every top-level object with the one
+	/// computed by `f` from the object.
+	pub fn reindex_with(&mut self, mut f: impl FnMut(&Object<T, B>) -> Option<String>) {
+		let objects = core::mem::take(&mut self.0);
+		for mut object in objects {
+			object.reindex_with(&mut f);
+			self.0.insert(object);
+		}
+	}
+
+	/// Removes the index (`@index`) of every top-level object.
+	pub fn strip_indexes(&mut self) {
+		self.reindex_with(|_| None)
+	}
+
+	/// Applies `f` to the top-level node object with the given identifier.
+	///
+	/// Objects of an expanded document are stored in a hash set, hence they
+	/// cannot be modified in place: the node is removed from the document
+	/// and inserted back once modified. Returns `false` if there is no such
+	/// node.
+	pub fn update_node(&mut self, id: &Id<T, B>, f: impl FnOnce(&mut Node<T, B>)) -> bool {
+		let Some(index) = self.0.iter().position(
+			|object| matches!(object.inner(), Object::Node(node) if node.id.as_ref() == Some(id)),
+		) else {
+			return false;
+		};
+
+		let mut object = self.0.shift_remove_index(index).unwrap();
+		if let Object::Node(node) = object.inner_mut() {
+			f(&mut **node)
+		}
+
+		let (new_index, _) = self.0.insert_full(object);
+		let last = self.0.len() - 1;
+		if new_index == last && index < last {
+			self.0.move_index(last, index)
+		}
+
+		true
+	}
+
+	/// Replaces the identifier `old` with `new` everywhere in the document.
+	///
+	/// This renames the node objects identified by `old` along with every
+	/// reference to them, including in types and reverse properties, so that
+	/// the document stays consistent. Returns the number of rewritten
+	/// identifiers.
+	///
+	/// ```
+	/// use json_ld_core::{ExpandedDocument, Id, Node, Object};
+	/// use iref::IriBuf;
+	///
+	/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+	///
+	/// let mut document: ExpandedDocument = ExpandedDocument::new();
+	/// document.insert(
+	///   Node::builder()
+	///     .id(iri("https://example.org/alice"))
+	///     .property(iri("https://schema.org/knows"), [Object::node_ref(iri("https://example.org/bob"))])
+	///     .build_object(),
+	/// );
+	///
+	/// let renamed = document.rename_id(&iri("https://example.org/bob"), iri("https://example.org/robert"));
+	/// assert_eq!(renamed, 1);
+	/// let alice = document.find_node(&iri("https://example.org/alice")).unwrap();
+	/// let knows = alice.get_any(&iri("https://schema.org/knows")).unwrap();
+	/// assert_eq!(knows.id(), Some(&iri("https://example.org/robert")));
+	/// ```
+	pub fn rename_id(&mut self, old: &Id<T, B>, new: Id<T, B>) -> usize
+	where
+		T: Clone,
+		B: Clone,
+	{
+		let mut count = 0;
+		*self = core::mem::take(self).map_ids(
+			|iri| iri,
+			|id| {
+				if id == *old {
+					count += 1;
+					new.clone()
+				} else {
+					id
+				}
+			},
+		);
+		count
+	}
+
+	/// Extracts the type hierarchy described by this document.
+	///
+	/// Every identified node of the document, including nested nodes,
+	/// declares its direct superclasses through the `subclass_of` property
+	/// (typically `rdfs:subClassOf`).
+	pub fn type_hierarchy(&self, subclass_of: &Id<T, B>) -> TypeHierarchy<T, B>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		let mut hierarchy = TypeHierarchy::new();
+
+		for node in super::query::nodes(self.traverse()) {
+			if let Some(id) = &node.id {
+				for superclass in node.get(subclass_of) {
+					if let Some(superclass) = superclass.id() {
+						hierarchy.insert(id.clone(), superclass.clone());
+					}
+				}
+			}
+		}
+
+		hierarchy
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> From<Indexed<Node<T, B>>> for ExpandedDocument<T, B> {
@@ -292,6 +735,12 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJson<T, B> for ExpandedDocument<T, B> {
 	}
 }
 
+impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> IntoJsonWithContext<N> for ExpandedDocument<T, B> {
+	fn into_json_with(self, vocabulary: &N) -> json_syntax::Value {
+		self.0.into_json_with(vocabulary)
+	}
+}
+
 impl<T: Eq + Hash, B: Eq + Hash> PartialEq for ExpandedDocument<T, B> {
 	/// Comparison between two expanded documents.
 	fn eq(&self, other: &Self) -> bool {
@@ -347,3 +796,73 @@ impl<T, B> From<IndexSet<IndexedObject<T, B>, RandomState>> for ExpandedDocument
 		Self(set)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	fn blank(id: &str) -> Id {
+		Id::blank(BlankIdBuf::new(id.to_owned()).unwrap())
+	}
+
+	fn document() -> ExpandedDocument {
+		let mut node = Node::with_id(blank("_:b0"));
+		node.insert(
+			Id::iri(iri!("https://schema.org/knows").to_owned()),
+			Indexed::new(Object::node_ref(blank("_:b1")), None),
+		);
+
+		let mut document = ExpandedDocument::new();
+		document.insert(Indexed::new(Object::node(node), None));
+		document
+	}
+
+	#[test]
+	fn skolemize() {
+		let mut document = document();
+		document.skolemize(iri!("https://example.org/app/"));
+
+		let node = document.objects().iter().next().unwrap().as_node().unwrap();
+		assert_eq!(
+			node.id,
+			Some(Id::iri(
+				iri!("https://example.org/.well-known/genid/b0").to_owned()
+			))
+		);
+
+		let knows = Id::iri(iri!("https://schema.org/knows").to_owned());
+		let target = node.get_any(&knows).unwrap().as_node().unwrap();
+		assert_eq!(
+			target.id,
+			Some(Id::iri(
+				iri!("https://example.org/.well-known/genid/b1").to_owned()
+			))
+		);
+	}
+
+	#[test]
+	fn skolemize_round_trip() {
+		let mut document = document();
+		document.skolemize(iri!("https://example.org/"));
+		assert_ne!(document, self::document());
+
+		document.deskolemize();
+		assert_eq!(document, self::document());
+	}
+
+	#[test]
+	fn deskolemize_ignores_other_iris() {
+		let mut document = ExpandedDocument::new();
+		document.insert(Indexed::new(
+			Object::node(Node::with_id(Id::iri(
+				iri!("https://example.org/genid/b0").to_owned(),
+			))),
+			None,
+		));
+
+		let expected = document.clone();
+		document.deskolemize();
+		assert_eq!(document, expected);
+	}
+}
@@ -0,0 +1,137 @@
+//! Merging of node objects sharing the same identifier.
+use super::ExpandedDocument;
+use crate::{Id, IndexedObject, Node, Object};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::HashMap;
+
+/// Policy used by [`ExpandedDocument::merge_duplicate_ids`] when two node
+/// objects with the same identifier both define the same entry.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+	/// Keep the values of both nodes, without duplicates.
+	///
+	/// The first index (`@index`) is kept.
+	#[default]
+	Union,
+
+	/// Keep the values of the node appearing first in the document.
+	KeepFirst,
+
+	/// Keep the values of the node appearing last in the document.
+	KeepLast,
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Collapses the top-level node objects sharing the same identifier into
+	/// one node object, at the position of the first one.
+	///
+	/// Types, properties, reverse properties, included nodes and graphs of
+	/// the duplicate nodes are merged. When both nodes define the same entry
+	/// (the same property for instance), the given `policy` decides which
+	/// values are kept. Reverse properties are always merged.
+	///
+	/// Returns the number of node objects removed from the document.
+	pub fn merge_duplicate_ids(&mut self, policy: MergePolicy) -> usize {
+		let mut objects: Vec<IndexedObject<T, B>> = Vec::with_capacity(self.len());
+		let mut positions: HashMap<Id<T, B>, usize> = HashMap::new();
+		let mut merged = 0;
+
+		for object in core::mem::take(self).into_objects() {
+			let position = match object.inner() {
+				Object::Node(node) => node.id.as_ref().and_then(|id| positions.get(id)).copied(),
+				_ => None,
+			};
+
+			match position {
+				Some(i) => {
+					let (object, index) = object.into_parts();
+					let target = &mut objects[i];
+
+					let keep_index = match policy {
+						MergePolicy::Union | MergePolicy::KeepFirst => target.index().is_none(),
+						MergePolicy::KeepLast => index.is_some(),
+					};
+
+					if keep_index {
+						target.set_index(index)
+					}
+
+					if let (Object::Node(target), Object::Node(node)) = (target.inner_mut(), object)
+					{
+						merge_node(target, *node, policy)
+					}
+
+					merged += 1
+				}
+				None => {
+					if let Object::Node(node) = object.inner() {
+						if let Some(id) = &node.id {
+							positions.insert(id.clone(), objects.len());
+						}
+					}
+
+					objects.push(object)
+				}
+			}
+		}
+
+		*self = objects.into_iter().collect();
+		merged
+	}
+}
+
+/// Merges `node` into `target`.
+fn merge_node<T: Clone + Eq + Hash, B: Clone + Eq + Hash>(
+	target: &mut Node<T, B>,
+	node: Node<T, B>,
+	policy: MergePolicy,
+) {
+	if let Some(types) = node.types {
+		match (&mut target.types, policy) {
+			(Some(_), MergePolicy::KeepFirst) => (),
+			(Some(target_types), MergePolicy::Union) => {
+				for ty in types {
+					if !target_types.contains(&ty) {
+						target_types.push(ty)
+					}
+				}
+			}
+			(target_types, _) => *target_types = Some(types),
+		}
+	}
+
+	if let Some(graph) = node.graph {
+		match (&mut target.graph, policy) {
+			(Some(_), MergePolicy::KeepFirst) => (),
+			(Some(target_graph), MergePolicy::Union) => target_graph.extend(graph),
+			(target_graph, _) => *target_graph = Some(graph),
+		}
+	}
+
+	if let Some(included) = node.included {
+		match (&mut target.included, policy) {
+			(Some(_), MergePolicy::KeepFirst) => (),
+			(Some(target_included), MergePolicy::Union) => target_included.extend(included),
+			(target_included, _) => *target_included = Some(included),
+		}
+	}
+
+	for (prop, values) in node.properties {
+		match policy {
+			MergePolicy::Union => target.properties.insert_all_unique(prop, values),
+			MergePolicy::KeepFirst => {
+				if !target.properties.contains(&prop) {
+					target.properties.set(prop, values)
+				}
+			}
+			MergePolicy::KeepLast => target.properties.set(prop, values),
+		}
+	}
+
+	if let Some(reverse_properties) = node.reverse_properties {
+		target
+			.reverse_properties_or_default()
+			.extend_unique(reverse_properties)
+	}
+}
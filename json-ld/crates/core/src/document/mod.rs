@@ -7,22 +7,83 @@ use rdf_types::{vocabulary::IriVocabularyMut, BlankIdBuf, Interpretation, Vocabu
 
 pub mod expanded;
 pub mod flattened;
+pub mod merge;
+pub mod provenance;
+pub mod query;
+pub mod reverse_index;
 
 pub use expanded::ExpandedDocument;
 pub use flattened::FlattenedDocument;
+pub use merge::MergePolicy;
+pub use provenance::Provenance;
+pub use query::{NodeView, Reachable, Select, TraversalOrder};
+pub use reverse_index::{IncomingEdge, ReverseIndex};
 
 use crate::RemoteDocument;
 
 /// JSON-LD document in both compact and expanded form.
+///
+/// Both views are kept alongside each other. Mutating one view (through
+/// [`Self::compact_mut`] or [`Self::expanded_mut`]) marks the other as
+/// stale, until it is updated with [`Self::update_expanded`] or
+/// [`Self::update_compact`]. This avoids re-expanding the whole document
+/// every time the expanded view is needed in editing workflows.
+///
+/// The `as_*` and `into_*` accessors, as well as the [`Deref`], [`Borrow`],
+/// [`PartialEq`] and [`LinkedData`] implementations, return the cached
+/// views whether or not they are up to date. The `try_as_*` accessors
+/// return `None` for a stale view instead, and a stale view cannot be
+/// mutated, so that the changes made to one view are never silently lost
+/// by mutating the other.
 #[derive(Debug, Clone)]
 pub struct Document<I = IriBuf, B = BlankIdBuf> {
 	remote: RemoteDocument<I>,
 	expanded: ExpandedDocument<I, B>,
+	up_to_date: Views,
+}
+
+/// Views of a [`Document`] that are up to date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Views {
+	Both,
+	Compact,
+	Expanded,
 }
 
 impl<I, B> Document<I, B> {
 	pub fn new(remote: RemoteDocument<I>, expanded: ExpandedDocument<I, B>) -> Self {
-		Self { remote, expanded }
+		Self {
+			remote,
+			expanded,
+			up_to_date: Views::Both,
+		}
+	}
+
+	/// Creates a new document from its compact form only.
+	///
+	/// The expanded view is empty and stale until it is set with
+	/// [`Self::update_expanded`].
+	pub fn from_compact(remote: RemoteDocument<I>) -> Self {
+		Self {
+			remote,
+			expanded: ExpandedDocument::new(),
+			up_to_date: Views::Compact,
+		}
+	}
+
+	/// Checks if the compact view is up to date with the expanded view.
+	pub fn is_compact_up_to_date(&self) -> bool {
+		self.up_to_date != Views::Expanded
+	}
+
+	/// Checks if the expanded view is up to date with the compact view.
+	pub fn is_expanded_up_to_date(&self) -> bool {
+		self.up_to_date != Views::Compact
+	}
+
+	/// Returns the URL of the document, if any.
+	pub fn url(&self) -> Option<&I> {
+		self.remote.url()
 	}
 
 	pub fn into_remote(self) -> RemoteDocument<I> {
@@ -53,6 +114,83 @@ impl<I, B> Document<I, B> {
 	pub fn as_expanded(&self) -> &ExpandedDocument<I, B> {
 		&self.expanded
 	}
+
+	/// Returns the remote document holding the compact view, if it is up to
+	/// date.
+	pub fn try_as_remote(&self) -> Option<&RemoteDocument<I>> {
+		if self.is_compact_up_to_date() {
+			Some(&self.remote)
+		} else {
+			None
+		}
+	}
+
+	/// Returns the compact view of the document, if it is up to date.
+	pub fn try_as_compact(&self) -> Option<&json_ld_syntax::Value> {
+		self.try_as_remote().map(RemoteDocument::document)
+	}
+
+	/// Returns the expanded view of the document, if it is up to date.
+	pub fn try_as_expanded(&self) -> Option<&ExpandedDocument<I, B>> {
+		if self.is_expanded_up_to_date() {
+			Some(&self.expanded)
+		} else {
+			None
+		}
+	}
+
+	/// Returns a mutable reference to the remote document, marking the
+	/// expanded view as stale.
+	///
+	/// Returns `None` if the compact view is stale.
+	pub fn remote_mut(&mut self) -> Option<&mut RemoteDocument<I>> {
+		if self.is_compact_up_to_date() {
+			self.up_to_date = Views::Compact;
+			Some(&mut self.remote)
+		} else {
+			None
+		}
+	}
+
+	/// Returns a mutable reference to the compact view, marking the expanded
+	/// view as stale.
+	///
+	/// Returns `None` if the compact view is stale.
+	pub fn compact_mut(&mut self) -> Option<&mut json_ld_syntax::Value> {
+		self.remote_mut().map(RemoteDocument::document_mut)
+	}
+
+	/// Returns a mutable reference to the expanded view, marking the compact
+	/// view as stale.
+	///
+	/// Returns `None` if the expanded view is stale.
+	pub fn expanded_mut(&mut self) -> Option<&mut ExpandedDocument<I, B>> {
+		if self.is_expanded_up_to_date() {
+			self.up_to_date = Views::Expanded;
+			Some(&mut self.expanded)
+		} else {
+			None
+		}
+	}
+
+	/// Replaces the expanded view with the given expansion of the compact
+	/// view, marking both views as in sync.
+	pub fn update_expanded(&mut self, expanded: ExpandedDocument<I, B>) {
+		self.expanded = expanded;
+		self.up_to_date = Views::Both
+	}
+
+	/// Replaces the compact view with the given compaction of the expanded
+	/// view, marking both views as in sync.
+	///
+	/// If the expanded view is stale, the compact view is replaced but the
+	/// expanded view remains stale.
+	pub fn update_compact(&mut self, compact: json_ld_syntax::Value) {
+		*self.remote.document_mut() = compact;
+		if self.is_expanded_up_to_date() {
+			self.up_to_date = Views::Both
+		}
+	}
 }
 
 impl<I, B> Deref for Document<I, B> {
@@ -91,11 +229,17 @@ impl<I: Eq + Hash, B: Eq + Hash> Eq for Document<I, B> {}
 
 #[cfg(feature = "serde")]
 impl<I, B> serde::Serialize for Document<I, B> {
+	/// Serializes the compact view of the document.
+	///
+	/// Fails if the compact view is stale.
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		self.remote.document().serialize(serializer)
+		match self.try_as_compact() {
+			Some(compact) => compact.serialize(serializer),
+			None => Err(serde::ser::Error::custom("stale compact view")),
+		}
 	}
 }
 
@@ -126,3 +270,63 @@ where
 		self.expanded.visit_graph(visitor)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn remote(value: &str) -> RemoteDocument {
+		RemoteDocument::new(None, None, value.into())
+	}
+
+	#[test]
+	fn from_compact() {
+		let document: Document = Document::from_compact(remote("compact"));
+		assert!(document.is_compact_up_to_date());
+		assert!(!document.is_expanded_up_to_date());
+		assert_eq!(document.try_as_compact(), Some(&"compact".into()));
+		assert!(document.try_as_expanded().is_none());
+		assert!(document.as_expanded().is_empty());
+	}
+
+	#[test]
+	fn mutate_expanded() {
+		let mut document: Document = Document::new(remote("compact"), ExpandedDocument::new());
+		assert!(document.expanded_mut().is_some());
+		assert!(document.try_as_compact().is_none());
+		assert!(document.compact_mut().is_none());
+		assert!(document.try_as_expanded().is_some());
+		assert_eq!(document.as_compact(), &"compact".into());
+
+		document.update_compact("updated".into());
+		assert_eq!(document.try_as_compact(), Some(&"updated".into()));
+		assert!(document.try_as_expanded().is_some());
+	}
+
+	#[test]
+	fn mutate_compact() {
+		let mut document: Document = Document::new(remote("compact"), ExpandedDocument::new());
+		*document.compact_mut().unwrap() = "updated".into();
+		assert!(document.try_as_expanded().is_none());
+		assert!(document.expanded_mut().is_none());
+		assert_eq!(document.try_as_compact(), Some(&"updated".into()));
+
+		let (remote, _) = document.into_parts();
+		assert_eq!(remote.document(), &"updated".into());
+	}
+
+	#[test]
+	fn update_compact_with_stale_expanded() {
+		let mut document: Document = Document::from_compact(remote("compact"));
+		document.update_compact("updated".into());
+		assert!(!document.is_expanded_up_to_date());
+	}
+
+	#[test]
+	fn compare_expanded_views() {
+		let a: Document = Document::new(remote("a"), ExpandedDocument::new());
+		let b: Document = Document::new(remote("b"), ExpandedDocument::new());
+		assert_eq!(a, b);
+		assert!(a.is_empty());
+	}
+}
@@ -0,0 +1,199 @@
+//! Provenance of the nodes of expanded documents.
+use super::{query::nodes, ExpandedDocument};
+use crate::{Id, IndexedObject};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use iref::IriBuf;
+use rdf_types::BlankIdBuf;
+
+/// Side table mapping node identifiers to the IRIs of the documents defining
+/// them.
+///
+/// Provenance is not part of the JSON-LD data model, hence it is kept next to
+/// the expanded documents instead of inside their node objects. Each source
+/// document is [recorded](Self::record) once expanded, before it is merged
+/// into others or flattened: nodes keep their identifier through these
+/// operations, and a node defined by several documents gets several sources.
+///
+/// Only nodes defining something other than their identifier are recorded,
+/// wherever they appear in the document (top level, `@graph`, `@included`,
+/// property values or reverse properties). Node references are not, since
+/// they do not say anything about the node. Anonymous nodes are not recorded
+/// either: they must be labeled first with
+/// [`ExpandedDocument::identify_all`].
+#[derive(Debug, Clone)]
+pub struct Provenance<T = IriBuf, B = BlankIdBuf> {
+	sources: HashMap<Id<T, B>, Vec<T>>,
+}
+
+impl<T, B> Default for Provenance<T, B> {
+	fn default() -> Self {
+		Self {
+			sources: HashMap::new(),
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> Provenance<T, B> {
+	/// Creates an empty provenance table.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records `source` as the source of every identified node defined by
+	/// `document`.
+	pub fn record(&mut self, document: &ExpandedDocument<T, B>, source: &T) {
+		for object in document {
+			self.record_object(object, source)
+		}
+	}
+
+	/// Records `source` as the source of every identified node defined by
+	/// `object`.
+	pub fn record_object(&mut self, object: &IndexedObject<T, B>, source: &T) {
+		for node in nodes(object.traverse()) {
+			if let Some(id) = &node.id {
+				if !node.is_empty() {
+					self.insert(id.clone(), source.clone());
+				}
+			}
+		}
+	}
+
+	/// Adds `source` to the sources of the node with the given identifier.
+	///
+	/// Returns `false` if it was already one of its sources.
+	pub fn insert(&mut self, id: Id<T, B>, source: T) -> bool {
+		let sources = self.sources.entry(id).or_default();
+		if sources.contains(&source) {
+			false
+		} else {
+			sources.push(source);
+			true
+		}
+	}
+
+	/// Returns the sources of the node with the given identifier, in
+	/// recording order.
+	pub fn sources(&self, id: &Id<T, B>) -> &[T] {
+		self.sources.get(id).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	/// Returns the first recorded source of the node with the given
+	/// identifier.
+	pub fn source(&self, id: &Id<T, B>) -> Option<&T> {
+		self.sources(id).first()
+	}
+
+	/// Adds the sources recorded by `other` to this table.
+	pub fn extend(&mut self, other: Self) {
+		for (id, sources) in other.sources {
+			for source in sources {
+				self.insert(id.clone(), source);
+			}
+		}
+	}
+
+	/// Returns the number of nodes with a recorded source.
+	pub fn len(&self) -> usize {
+		self.sources.len()
+	}
+
+	/// Checks if no source is recorded.
+	pub fn is_empty(&self) -> bool {
+		self.sources.is_empty()
+	}
+
+	/// Returns an iterator over the identifiers of the recorded nodes, with
+	/// their sources.
+	pub fn iter(&self) -> impl Iterator<Item = (&Id<T, B>, &[T])> {
+		self.sources
+			.iter()
+			.map(|(id, sources)| (id, sources.as_slice()))
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Builds a [provenance table](Provenance) recording `source` as the
+	/// source of every identified node defined by this document.
+	pub fn provenance(&self, source: &T) -> Provenance<T, B> {
+		let mut result = Provenance::new();
+		result.record(self, source);
+		result
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Indexed, Node, Object};
+	use static_iref::iri;
+
+	fn id(iri: &iref::Iri) -> Id {
+		Id::iri(iri.to_owned())
+	}
+
+	fn node(iri: &iref::Iri) -> Node {
+		let mut node = Node::with_id(id(iri));
+		node.insert(
+			id(iri!("http://example.org/p")),
+			Indexed::new(Object::string("v"), None),
+		);
+		node
+	}
+
+	#[test]
+	fn included_and_nested_nodes() {
+		let mut top = node(iri!("http://example.org/a"));
+		top.included_mut_or_default()
+			.insert(Indexed::new(node(iri!("http://example.org/b")), None));
+		top.insert(
+			id(iri!("http://example.org/q")),
+			Indexed::new(Object::node(node(iri!("http://example.org/c"))), None),
+		);
+		top.insert(
+			id(iri!("http://example.org/r")),
+			Indexed::new(Object::node_ref(id(iri!("http://example.org/d"))), None),
+		);
+
+		let mut document = ExpandedDocument::new();
+		document.insert(Indexed::new(Object::node(top), None));
+
+		let source = iri!("http://example.org/doc").to_owned();
+		let provenance = document.provenance(&source);
+		assert_eq!(provenance.len(), 3);
+		for defined in [
+			iri!("http://example.org/a"),
+			iri!("http://example.org/b"),
+			iri!("http://example.org/c"),
+		] {
+			assert_eq!(provenance.sources(&id(defined)), [source.clone()]);
+		}
+		assert!(provenance
+			.source(&id(iri!("http://example.org/d")))
+			.is_none());
+	}
+
+	#[test]
+	fn multiple_sources() {
+		let mut first = ExpandedDocument::new();
+		first.insert(Indexed::new(
+			Object::node(node(iri!("http://example.org/a"))),
+			None,
+		));
+
+		let mut provenance = first.provenance(&iri!("http://example.org/first").to_owned());
+		let second = first.clone();
+		provenance.record(&second, &iri!("http://example.org/second").to_owned());
+		provenance.record(&second, &iri!("http://example.org/second").to_owned());
+
+		assert_eq!(
+			provenance.sources(&id(iri!("http://example.org/a"))),
+			[
+				iri!("http://example.org/first").to_owned(),
+				iri!("http://example.org/second").to_owned()
+			]
+		);
+	}
+}
@@ -0,0 +1,437 @@
+//! Node selection and navigation over expanded documents.
+use super::ExpandedDocument;
+use crate::object::{FragmentRef, Ref, Traverse};
+use crate::{Id, Node, Object};
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::ops::Deref;
+use hashbrown::{HashMap, HashSet};
+use once_cell::race::OnceBox;
+
+/// Node selection filter.
+enum Filter<T, B> {
+	Id(Id<T, B>),
+	Type(Id<T, B>),
+	Property(Id<T, B>),
+	PropertyValue(Id<T, B>, Object<T, B>),
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> Filter<T, B> {
+	fn matches(&self, node: &Node<T, B>) -> bool {
+		match self {
+			Self::Id(id) => node.id.as_ref() == Some(id),
+			Self::Type(ty) => node.has_type(ty),
+			Self::Property(prop) => node.get_any(prop).is_some(),
+			Self::PropertyValue(prop, value) => {
+				node.get(prop).any(|v| value_matches(v.inner(), value))
+			}
+		}
+	}
+}
+
+/// Checks if `value` matches the given `pattern`.
+///
+/// A node reference pattern (a node object with only an `@id` entry)
+/// matches any node object with the same identifier. Other patterns must be
+/// equal to the value.
+fn value_matches<T: Eq + Hash, B: Eq + Hash>(value: &Object<T, B>, pattern: &Object<T, B>) -> bool {
+	match (value, pattern) {
+		(Object::Node(node), Object::Node(pattern))
+			if pattern.is_empty() && pattern.id.is_some() =>
+		{
+			node.id == pattern.id
+		}
+		_ => value == pattern,
+	}
+}
+
+/// Returns an iterator over all the node objects of the given traversal.
+pub(crate) fn nodes<'a, T: 'a, B: 'a>(
+	traverse: impl Iterator<Item = FragmentRef<'a, T, B>>,
+) -> impl Iterator<Item = &'a Node<T, B>> {
+	traverse.filter_map(|fragment| match fragment.into_ref() {
+		Some(Ref::Node(node)) => Some(node),
+		_ => None,
+	})
+}
+
+/// Index of the node objects of a document by identifier, used to resolve
+/// node references.
+///
+/// The index is shared by a [`Select`] and all the [`NodeView`]s and
+/// [`Reachable`] iterators derived from it, and is built the first time a
+/// node reference is resolved. Resolving a reference then takes constant
+/// time instead of a traversal of the whole document.
+struct NodeIndex<'a, T, B> {
+	document: &'a ExpandedDocument<T, B>,
+	nodes: OnceBox<HashMap<&'a Id<T, B>, &'a Node<T, B>>>,
+}
+
+impl<'a, T, B> NodeIndex<'a, T, B> {
+	fn new(document: &'a ExpandedDocument<T, B>) -> Arc<Self> {
+		Arc::new(Self {
+			document,
+			nodes: OnceBox::new(),
+		})
+	}
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> NodeIndex<'a, T, B> {
+	/// Resolves the given node reference to the node object with the same
+	/// identifier in the document.
+	///
+	/// Returns `node` itself if it is not a reference, or if the referenced
+	/// node cannot be found. If the document defines the node in several
+	/// places, the first one in traversal order is returned.
+	fn resolve(&self, node: &'a Node<T, B>) -> &'a Node<T, B> {
+		match &node.id {
+			Some(id) if node.is_empty() => self
+				.nodes
+				.get_or_init(|| {
+					let mut index = HashMap::new();
+					for node in nodes(self.document.traverse()) {
+						if let Some(id) = &node.id {
+							if !node.is_empty() {
+								index.entry(id).or_insert(node);
+							}
+						}
+					}
+
+					Box::new(index)
+				})
+				.get(id)
+				.copied()
+				.unwrap_or(node),
+			_ => node,
+		}
+	}
+}
+
+/// Selection of the nodes of an [`ExpandedDocument`].
+///
+/// Created by [`ExpandedDocument::select`]. Nodes are selected among every
+/// node object of the document, including nested nodes, and must match all
+/// the given filters. The selection is an iterator over the selected nodes.
+///
+/// ```
+/// use json_ld_core::{ExpandedDocument, Id, Node, Object};
+/// use iref::IriBuf;
+///
+/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+///
+/// let mut document: ExpandedDocument = ExpandedDocument::new();
+/// document.insert(
+///   Node::builder()
+///     .id(iri("https://example.org/alice"))
+///     .type_(iri("https://schema.org/Person"))
+///     .property(iri("https://schema.org/name"), [Object::string("Alice")])
+///     .build_object(),
+/// );
+///
+/// let alice = document
+///   .select()
+///   .with_type(iri("https://schema.org/Person"))
+///   .with_property(iri("https://schema.org/name"), Object::string("Alice"))
+///   .next()
+///   .unwrap();
+///
+/// assert_eq!(alice.id, Some(iri("https://example.org/alice")));
+/// ```
+pub struct Select<'a, T, B> {
+	index: Arc<NodeIndex<'a, T, B>>,
+	nodes: Traverse<'a, T, B>,
+	filters: Vec<Filter<T, B>>,
+}
+
+impl<'a, T, B> Select<'a, T, B> {
+	/// Only selects the node with the given identifier.
+	pub fn with_id(mut self, id: Id<T, B>) -> Self {
+		self.filters.push(Filter::Id(id));
+		self
+	}
+
+	/// Only selects the nodes with the given type.
+	pub fn with_type(mut self, ty: Id<T, B>) -> Self {
+		self.filters.push(Filter::Type(ty));
+		self
+	}
+
+	/// Only selects the nodes with at least one value for the given property.
+	pub fn has_property(mut self, prop: Id<T, B>) -> Self {
+		self.filters.push(Filter::Property(prop));
+		self
+	}
+
+	/// Only selects the nodes having the given value for the given property.
+	///
+	/// If `value` is a node reference (see [`Object::node_ref`]), any node
+	/// object with the same identifier matches.
+	pub fn with_property(mut self, prop: Id<T, B>, value: Object<T, B>) -> Self {
+		self.filters.push(Filter::PropertyValue(prop, value));
+		self
+	}
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> Iterator for Select<'a, T, B> {
+	type Item = NodeView<'a, T, B>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let filters = &self.filters;
+		let node = nodes(&mut self.nodes).find(|node| filters.iter().all(|f| f.matches(node)))?;
+		Some(NodeView {
+			index: self.index.clone(),
+			node,
+		})
+	}
+}
+
+/// Node of an [`ExpandedDocument`], allowing navigation through the node
+/// references of the document.
+///
+/// Node references are resolved using an index of the document nodes, built
+/// once and shared by all the views derived from the same
+/// [`ExpandedDocument::select`] call.
+pub struct NodeView<'a, T, B> {
+	index: Arc<NodeIndex<'a, T, B>>,
+	node: &'a Node<T, B>,
+}
+
+impl<'a, T, B> Clone for NodeView<'a, T, B> {
+	fn clone(&self) -> Self {
+		Self {
+			index: self.index.clone(),
+			node: self.node,
+		}
+	}
+}
+
+impl<'a, T, B> NodeView<'a, T, B> {
+	/// Returns the document of the node.
+	pub fn document(&self) -> &'a ExpandedDocument<T, B> {
+		self.index.document
+	}
+
+	/// Returns the node.
+	pub fn node(&self) -> &'a Node<T, B> {
+		self.node
+	}
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> NodeView<'a, T, B> {
+	/// Returns the nodes reached from this node through the given property.
+	///
+	/// Node references (node objects with only an `@id` entry) are resolved
+	/// to the node object with the same identifier found in the document,
+	/// if any. Values that are not node objects are ignored.
+	pub fn follow(&self, prop: &Id<T, B>) -> impl Iterator<Item = NodeView<'a, T, B>> + 'a {
+		let index = self.index.clone();
+		let subject: &'a Node<T, B> = self.node;
+		subject
+			.get(prop)
+			.filter_map(move |object| match object.inner() {
+				Object::Node(node) => Some(NodeView {
+					node: index.resolve(node),
+					index: index.clone(),
+				}),
+				_ => None,
+			})
+	}
+
+	/// Returns the nodes reached from this node by following the given
+	/// property path.
+	pub fn path(&self, props: &[Id<T, B>]) -> Vec<NodeView<'a, T, B>> {
+		let mut current = alloc::vec![self.clone()];
+
+		for prop in props {
+			current = current.iter().flat_map(|view| view.follow(prop)).collect()
+		}
+
+		current
+	}
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> NodeView<'a, T, B> {
+	/// Returns an iterator over the nodes reachable from this node, in
+	/// breadth-first order.
+	///
+	/// See [`Self::reachable`].
+	pub fn bfs(&self) -> Reachable<'a, T, B> {
+		self.reachable(TraversalOrder::BreadthFirst)
+	}
+
+	/// Returns an iterator over the nodes reachable from this node, in
+	/// depth-first order.
+	///
+	/// See [`Self::reachable`].
+	pub fn dfs(&self) -> Reachable<'a, T, B> {
+		self.reachable(TraversalOrder::DepthFirst)
+	}
+
+	/// Returns an iterator over the nodes reachable from this node, starting
+	/// with the node itself.
+	///
+	/// Nodes are reached through the property values (including list items),
+	/// the included nodes (`@included`) and the graph (`@graph`) of each
+	/// visited node. Node references are resolved as with [`Self::follow`].
+	/// Each node is visited only once, even if the graph has cycles.
+	///
+	/// ```
+	/// use json_ld_core::{ExpandedDocument, Id, Node, Object};
+	/// use iref::IriBuf;
+	///
+	/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+	/// let knows = iri("https://schema.org/knows");
+	///
+	/// let mut document: ExpandedDocument = ExpandedDocument::new();
+	/// for (a, b) in [("alice", "bob"), ("bob", "alice")] {
+	///   document.insert(
+	///     Node::builder()
+	///       .id(iri(&format!("https://example.org/{a}")))
+	///       .property(knows.clone(), [Object::node_ref(iri(&format!("https://example.org/{b}")))])
+	///       .build_object(),
+	///   );
+	/// }
+	///
+	/// let alice = document.select().with_id(iri("https://example.org/alice")).next().unwrap();
+	/// assert_eq!(alice.bfs().count(), 2);
+	/// ```
+	pub fn reachable(&self, order: TraversalOrder) -> Reachable<'a, T, B> {
+		let mut visited = HashSet::new();
+		visited.insert(self.node as *const Node<T, B>);
+
+		let mut pending = VecDeque::new();
+		pending.push_back(self.node);
+
+		Reachable {
+			index: self.index.clone(),
+			order,
+			pending,
+			visited,
+		}
+	}
+}
+
+/// Order in which the nodes of an [`ExpandedDocument`] are visited by
+/// [`NodeView::reachable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalOrder {
+	/// Breadth-first order: nodes closer to the starting node come first.
+	BreadthFirst,
+
+	/// Depth-first order: each branch is visited to the end before the
+	/// next one.
+	DepthFirst,
+}
+
+/// Iterator over the nodes reachable from a node.
+///
+/// Created by [`NodeView::reachable`], [`NodeView::bfs`] and
+/// [`NodeView::dfs`].
+pub struct Reachable<'a, T, B> {
+	index: Arc<NodeIndex<'a, T, B>>,
+	order: TraversalOrder,
+	pending: VecDeque<&'a Node<T, B>>,
+	visited: HashSet<*const Node<T, B>>,
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> Reachable<'a, T, B> {
+	fn push(&mut self, node: &'a Node<T, B>) {
+		let node = self.index.resolve(node);
+		if self.visited.insert(node as *const Node<T, B>) {
+			self.pending.push_back(node)
+		}
+	}
+}
+
+impl<'a, T: Eq + Hash, B: Eq + Hash> Iterator for Reachable<'a, T, B> {
+	type Item = NodeView<'a, T, B>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let node = match self.order {
+			TraversalOrder::BreadthFirst => self.pending.pop_front()?,
+			TraversalOrder::DepthFirst => self.pending.pop_back()?,
+		};
+
+		let mut successors = Vec::new();
+
+		for (_, objects) in node.properties() {
+			for object in objects {
+				match object.inner() {
+					Object::Node(n) => successors.push(&**n),
+					Object::List(list) => {
+						successors.extend(list.iter().filter_map(|item| match item.inner() {
+							Object::Node(n) => Some(&**n),
+							_ => None,
+						}))
+					}
+					Object::Value(_) => (),
+				}
+			}
+		}
+
+		if let Some(included) = node.included_entry() {
+			successors.extend(included.iter().map(|n| n.inner()))
+		}
+
+		if let Some(graph) = node.graph_entry() {
+			successors.extend(graph.iter().filter_map(|object| match object.inner() {
+				Object::Node(n) => Some(&**n),
+				_ => None,
+			}))
+		}
+
+		// Successors are pushed in reverse so that depth-first traversal
+		// visits them in order.
+		match self.order {
+			TraversalOrder::BreadthFirst => successors.into_iter().for_each(|n| self.push(n)),
+			TraversalOrder::DepthFirst => successors.into_iter().rev().for_each(|n| self.push(n)),
+		}
+
+		Some(NodeView {
+			index: self.index.clone(),
+			node,
+		})
+	}
+}
+
+impl<'a, T, B> Deref for NodeView<'a, T, B> {
+	type Target = Node<T, B>;
+
+	fn deref(&self) -> &Node<T, B> {
+		self.node
+	}
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Starts a selection of the nodes of this document.
+	///
+	/// Without filters, every node object of the document is selected,
+	/// including nested nodes.
+	pub fn select(&self) -> Select<T, B> {
+		Select {
+			index: NodeIndex::new(self),
+			nodes: self.traverse(),
+			filters: Vec::new(),
+		}
+	}
+
+	/// Returns the node object with the given identifier, if any.
+	///
+	/// Node references (node objects with only an `@id` entry) are ignored.
+	/// If the document defines the node in several places, any of them may
+	/// be returned.
+	///
+	/// This traverses the whole document. To resolve many identifiers, use
+	/// the views returned by [`Self::select`], which share an index of the
+	/// document nodes.
+	pub fn find_node(&self, id: &Id<T, B>) -> Option<&Node<T, B>>
+	where
+		T: PartialEq,
+		B: PartialEq,
+	{
+		nodes(self.traverse()).find(|node| node.id.as_ref() == Some(id) && !node.is_empty())
+	}
+}
@@ -0,0 +1,158 @@
+//! Reverse-edge index of expanded documents.
+use super::{query::nodes, ExpandedDocument};
+use crate::{Id, IndexedObject, Node, Object};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::{HashMap, HashSet};
+use iref::IriBuf;
+use rdf_types::BlankIdBuf;
+
+/// Edge pointing to a node, stored in a [`ReverseIndex`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IncomingEdge<T = IriBuf, B = BlankIdBuf> {
+	/// Identifier of the referencing node, or `None` if it is anonymous.
+	pub subject: Option<Id<T, B>>,
+
+	/// Property through which the node is referenced.
+	pub predicate: Id<T, B>,
+}
+
+/// Index mapping each node identifier of an [`ExpandedDocument`] to the
+/// edges pointing to it.
+///
+/// Edges are given by the properties of the nodes (including list items)
+/// and their reverse properties (`@reverse`). The index is built with
+/// [`ExpandedDocument::reverse_index`] and does not follow the changes of
+/// the document: objects inserted afterward must be added with
+/// [`Self::insert`], otherwise the index must be [rebuilt](Self::rebuild).
+#[derive(Debug, Clone)]
+pub struct ReverseIndex<T = IriBuf, B = BlankIdBuf> {
+	edges: HashMap<Id<T, B>, Vec<IncomingEdge<T, B>>>,
+}
+
+impl<T, B> Default for ReverseIndex<T, B> {
+	fn default() -> Self {
+		Self {
+			edges: HashMap::new(),
+		}
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ReverseIndex<T, B> {
+	/// Builds the reverse index of the given document.
+	pub fn new(document: &ExpandedDocument<T, B>) -> Self {
+		let mut result = Self::default();
+		result.rebuild(document);
+		result
+	}
+
+	/// Rebuilds the index from the current content of `document`.
+	pub fn rebuild(&mut self, document: &ExpandedDocument<T, B>) {
+		self.edges.clear();
+		for object in document {
+			self.insert(object)
+		}
+	}
+
+	/// Adds the edges of the given object, and of the nodes it contains, to
+	/// the index.
+	///
+	/// This keeps the index in sync with a document in which the object has
+	/// been inserted.
+	pub fn insert(&mut self, object: &IndexedObject<T, B>) {
+		for node in nodes(object.traverse()) {
+			self.insert_node(node)
+		}
+	}
+
+	fn insert_node(&mut self, node: &Node<T, B>) {
+		for (predicate, objects) in node.properties() {
+			for object in objects {
+				match object.inner() {
+					Object::Node(target) => self.insert_edge(&node.id, predicate, target),
+					Object::List(list) => {
+						for item in list {
+							if let Object::Node(target) = item.inner() {
+								self.insert_edge(&node.id, predicate, target)
+							}
+						}
+					}
+					Object::Value(_) => (),
+				}
+			}
+		}
+
+		if let Some(reverse_properties) = node.reverse_properties_entry() {
+			if let Some(id) = &node.id {
+				for (predicate, subjects) in reverse_properties {
+					for subject in subjects {
+						self.edges
+							.entry(id.clone())
+							.or_default()
+							.push(IncomingEdge {
+								subject: subject.id.clone(),
+								predicate: predicate.clone(),
+							})
+					}
+				}
+			}
+		}
+	}
+
+	fn insert_edge(
+		&mut self,
+		subject: &Option<Id<T, B>>,
+		predicate: &Id<T, B>,
+		target: &Node<T, B>,
+	) {
+		if let Some(id) = &target.id {
+			self.edges
+				.entry(id.clone())
+				.or_default()
+				.push(IncomingEdge {
+					subject: subject.clone(),
+					predicate: predicate.clone(),
+				})
+		}
+	}
+
+	/// Returns the edges pointing to the node with the given identifier.
+	pub fn incoming(&self, id: &Id<T, B>) -> &[IncomingEdge<T, B>] {
+		self.edges.get(id).map(Vec::as_slice).unwrap_or_default()
+	}
+
+	/// Checks if the node with the given identifier is referenced.
+	pub fn is_referenced(&self, id: &Id<T, B>) -> bool {
+		self.edges.contains_key(id)
+	}
+
+	/// Returns an iterator over the identifiers of the referenced nodes, with
+	/// the edges pointing to them.
+	pub fn iter(&self) -> impl Iterator<Item = (&Id<T, B>, &[IncomingEdge<T, B>])> {
+		self.edges.iter().map(|(id, edges)| (id, edges.as_slice()))
+	}
+
+	/// Returns the referenced identifiers that are not defined by any node
+	/// object of `document` (other than a node reference).
+	///
+	/// This is used to check the integrity of the identifiers of a
+	/// document.
+	pub fn dangling<'a>(&'a self, document: &'a ExpandedDocument<T, B>) -> Vec<&'a Id<T, B>> {
+		let defined: HashSet<&Id<T, B>> = nodes(document.traverse())
+			.filter(|node| !node.is_empty())
+			.filter_map(|node| node.id.as_ref())
+			.collect();
+
+		self.edges
+			.keys()
+			.filter(|id| !defined.contains(id))
+			.collect()
+	}
+}
+
+impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Builds the [reverse index](ReverseIndex) of this document.
+	pub fn reverse_index(&self) -> ReverseIndex<T, B> {
+		ReverseIndex::new(self)
+	}
+}
@@ -1,4 +1,12 @@
 //! Flattening algorithm and related types.
+//!
+//! Flattening an [`ExpandedDocument`] consumes it and moves its objects into
+//! the node map (see [`ExpandedDocument::into_node_map_with`]), while
+//! [`ExpandedDocument::generate_node_map_with`] borrows the document and
+//! copies its value objects. The object model does not use shared
+//! ownership: wrapping objects in `Rc` or `Arc` would change every public
+//! accessor, and neither flattening nor RDF serialization (which borrows
+//! the document) needs it.
 use crate::flattened::UnorderedFlattenedDocument;
 use crate::{ExpandedDocument, FlattenedDocument, IndexedNode, IndexedObject, Object};
 use alloc::vec::Vec;
@@ -68,7 +76,7 @@ impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for ExpandedDocum
 		V: Vocabulary<Iri = I, BlankId = B>,
 	{
 		Ok(self
-			.generate_node_map_with(vocabulary, generator)?
+			.into_node_map_with(vocabulary, generator)?
 			.flatten_with(vocabulary, ordered))
 	}
 
@@ -81,7 +89,7 @@ impl<I: Clone + Eq + Hash, B: Clone + Eq + Hash> Flatten<I, B> for ExpandedDocum
 		V: Vocabulary<Iri = I, BlankId = B>,
 	{
 		Ok(self
-			.generate_node_map_with(vocabulary, generator)?
+			.into_node_map_with(vocabulary, generator)?
 			.flatten_unordered())
 	}
 }
@@ -1,6 +1,6 @@
 use super::Environment;
 use crate::{object, ExpandedDocument, Id, Indexed, IndexedNode, IndexedObject, Node, Object};
-use alloc::borrow::ToOwned;
+use alloc::borrow::{Cow, ToOwned};
 use alloc::string::String;
 use alloc::string::ToString;
 use alloc::vec::Vec;
@@ -296,6 +296,11 @@ impl<'a, T, B> IntoIterator for &'a NodeMapGraph<T, B> {
 }
 
 impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
+	/// Generates the node map of this document.
+	///
+	/// The value objects of the document are copied into the node map. Use
+	/// [`Self::into_node_map_with`] to move them instead when the document is
+	/// not needed anymore.
 	pub fn generate_node_map_with<V: Vocabulary<Iri = T, BlankId = B>, G: Generator<V>>(
 		&self,
 		vocabulary: &mut V,
@@ -304,10 +309,77 @@ impl<T: Clone + Eq + Hash, B: Clone + Eq + Hash> ExpandedDocument<T, B> {
 		let mut node_map: NodeMap<T, B> = NodeMap::new();
 		let mut env: Environment<V, G> = Environment::new(vocabulary, generator);
 		for object in self {
-			extend_node_map(&mut env, &mut node_map, object, None)?;
+			extend_node_map(&mut env, &mut node_map, Cow::Borrowed(object), None)?;
 		}
 		Ok(node_map)
 	}
+
+	/// Generates the node map of this document, consuming it.
+	///
+	/// The objects of the document are moved into the node map instead of
+	/// being copied.
+	pub fn into_node_map_with<V: Vocabulary<Iri = T, BlankId = B>, G: Generator<V>>(
+		self,
+		vocabulary: &mut V,
+		generator: G,
+	) -> Result<NodeMap<T, B>, ConflictingIndexes<T, B>> {
+		let mut node_map: NodeMap<T, B> = NodeMap::new();
+		let mut env: Environment<V, G> = Environment::new(vocabulary, generator);
+		for object in self {
+			extend_node_map(&mut env, &mut node_map, Cow::Owned(object), None)?;
+		}
+		Ok(node_map)
+	}
+}
+
+/// Iterator over the items of a borrowed or owned collection.
+enum CowIter<R, O> {
+	Borrowed(R),
+	Owned(O),
+}
+
+impl<'a, T: 'a + Clone, R: Iterator<Item = &'a T>, O: Iterator<Item = T>> Iterator
+	for CowIter<R, O>
+{
+	type Item = Cow<'a, T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self {
+			Self::Borrowed(items) => items.next().map(Cow::Borrowed),
+			Self::Owned(items) => items.next().map(Cow::Owned),
+		}
+	}
+}
+
+/// Iterates over the items of the given borrowed or owned collection,
+/// borrowing or moving them.
+fn cow_items<'a, C, T>(
+	collection: Cow<'a, C>,
+) -> CowIter<<&'a C as IntoIterator>::IntoIter, <C as IntoIterator>::IntoIter>
+where
+	C: Clone + IntoIterator<Item = T>,
+	&'a C: IntoIterator<Item = &'a T>,
+	T: 'a + Clone,
+{
+	match collection {
+		Cow::Borrowed(collection) => CowIter::Borrowed(collection.into_iter()),
+		Cow::Owned(collection) => CowIter::Owned(collection.into_iter()),
+	}
+}
+
+/// Splits the given borrowed or owned indexed item into its inner item and
+/// index.
+fn split_indexed<T: Clone>(indexed: Cow<Indexed<T>>) -> (Cow<T>, Option<Cow<str>>) {
+	match indexed {
+		Cow::Borrowed(indexed) => (
+			Cow::Borrowed(indexed.inner()),
+			indexed.index().map(Cow::Borrowed),
+		),
+		Cow::Owned(indexed) => {
+			let (inner, index) = indexed.into_parts();
+			(Cow::Owned(inner), index.map(Cow::Owned))
+		}
+	}
 }
 
 pub type ExtendNodeMapResult<V> = Result<
@@ -316,50 +388,94 @@ pub type ExtendNodeMapResult<V> = Result<
 >;
 
 /// Extends the `NodeMap` with the given `element` of an expanded JSON-LD document.
+///
+/// Borrowed objects are copied into the node map, owned objects are moved.
 fn extend_node_map<N: Vocabulary, G: Generator<N>>(
 	env: &mut Environment<N, G>,
 	node_map: &mut NodeMap<N::Iri, N::BlankId>,
-	element: &IndexedObject<N::Iri, N::BlankId>,
+	element: Cow<IndexedObject<N::Iri, N::BlankId>>,
 	active_graph: Option<&Id<N::Iri, N::BlankId>>,
 ) -> ExtendNodeMapResult<N>
 where
 	N::Iri: Clone + Eq + Hash,
 	N::BlankId: Clone + Eq + Hash,
 {
-	match element.inner() {
-		Object::Value(value) => {
-			let flat_value = value.clone();
-			Ok(Indexed::new(
-				Object::Value(flat_value),
-				element.index().map(ToOwned::to_owned),
-			))
-		}
-		Object::List(list) => {
-			let mut flat_list = Vec::new();
-
-			for item in list {
-				flat_list.push(extend_node_map(env, node_map, item, active_graph)?);
-			}
-
-			Ok(Indexed::new(
-				Object::List(object::List::new(flat_list)),
-				element.index().map(ToOwned::to_owned),
-			))
+	let (object, index) = split_indexed(element);
+	match object {
+		Cow::Borrowed(Object::Value(value)) => Ok(Indexed::new(
+			Object::Value(value.clone()),
+			index.map(Cow::into_owned),
+		)),
+		Cow::Owned(Object::Value(value)) => Ok(Indexed::new(
+			Object::Value(value),
+			index.map(Cow::into_owned),
+		)),
+		Cow::Borrowed(Object::List(list)) => extend_node_map_from_list(
+			env,
+			node_map,
+			cow_items(Cow::Borrowed(list)),
+			index,
+			active_graph,
+		),
+		Cow::Owned(Object::List(list)) => extend_node_map_from_list(
+			env,
+			node_map,
+			cow_items(Cow::Owned(list)),
+			index,
+			active_graph,
+		),
+		Cow::Borrowed(Object::Node(node)) => {
+			let flat_node = extend_node_map_from_node(
+				env,
+				node_map,
+				Cow::Borrowed(&**node),
+				index.as_deref(),
+				active_graph,
+			)?;
+			Ok(flat_node.map_inner(Object::node))
 		}
-		Object::Node(node) => {
-			let flat_node =
-				extend_node_map_from_node(env, node_map, node, element.index(), active_graph)?;
+		Cow::Owned(Object::Node(node)) => {
+			let flat_node = extend_node_map_from_node(
+				env,
+				node_map,
+				Cow::Owned(*node),
+				index.as_deref(),
+				active_graph,
+			)?;
 			Ok(flat_node.map_inner(Object::node))
 		}
 	}
 }
 
+fn extend_node_map_from_list<'a, N: Vocabulary, G: Generator<N>>(
+	env: &mut Environment<N, G>,
+	node_map: &mut NodeMap<N::Iri, N::BlankId>,
+	items: impl Iterator<Item = Cow<'a, IndexedObject<N::Iri, N::BlankId>>>,
+	index: Option<Cow<str>>,
+	active_graph: Option<&Id<N::Iri, N::BlankId>>,
+) -> ExtendNodeMapResult<N>
+where
+	N::Iri: 'a + Clone + Eq + Hash,
+	N::BlankId: 'a + Clone + Eq + Hash,
+{
+	let mut flat_list = Vec::new();
+
+	for item in items {
+		flat_list.push(extend_node_map(env, node_map, item, active_graph)?);
+	}
+
+	Ok(Indexed::new(
+		Object::List(object::List::new(flat_list)),
+		index.map(Cow::into_owned),
+	))
+}
+
 type ExtendNodeMapFromNodeResult<T, B> = Result<Indexed<Node<T, B>>, ConflictingIndexes<T, B>>;
 
 fn extend_node_map_from_node<N: Vocabulary, G: Generator<N>>(
 	env: &mut Environment<N, G>,
 	node_map: &mut NodeMap<N::Iri, N::BlankId>,
-	node: &Node<N::Iri, N::BlankId>,
+	node: Cow<Node<N::Iri, N::BlankId>>,
 	index: Option<&str>,
 	active_graph: Option<&Id<N::Iri, N::BlankId>>,
 ) -> ExtendNodeMapFromNodeResult<N::Iri, N::BlankId>
@@ -385,11 +501,26 @@ where
 		}
 	}
 
-	if let Some(graph_entry) = node.graph_entry() {
+	let (graph, included, properties, reverse_properties) = match node {
+		Cow::Borrowed(node) => (
+			node.graph.as_ref().map(Cow::Borrowed),
+			node.included.as_ref().map(Cow::Borrowed),
+			Cow::Borrowed(&node.properties),
+			node.reverse_properties.as_ref().map(Cow::Borrowed),
+		),
+		Cow::Owned(node) => (
+			node.graph.map(Cow::Owned),
+			node.included.map(Cow::Owned),
+			Cow::Owned(node.properties),
+			node.reverse_properties.map(Cow::Owned),
+		),
+	};
+
+	if let Some(graph_entry) = graph {
 		node_map.declare_graph(id.clone());
 
 		let mut flat_graph = IndexSet::default();
-		for object in graph_entry.iter() {
+		for object in cow_items(graph_entry) {
 			let flat_object = extend_node_map(env, node_map, object, Some(&id))?;
 			flat_graph.insert(flat_object);
 		}
@@ -405,73 +536,194 @@ where
 		}
 	}
 
-	if let Some(included_entry) = node.included_entry() {
-		for inode in included_entry {
-			extend_node_map_from_node(env, node_map, inode.inner(), inode.index(), active_graph)?;
+	if let Some(included_entry) = included {
+		for inode in cow_items(included_entry) {
+			let (inode, iindex) = split_indexed(inode);
+			extend_node_map_from_node(env, node_map, inode, iindex.as_deref(), active_graph)?;
 		}
 	}
 
-	for (property, objects) in node.properties() {
-		let mut flat_objects = Vec::new();
-		for object in objects {
-			let flat_object = extend_node_map(env, node_map, object, active_graph)?;
-			flat_objects.push(flat_object);
+	match properties {
+		Cow::Borrowed(properties) => {
+			for (property, objects) in properties {
+				extend_node_map_from_property(
+					env,
+					node_map,
+					&id,
+					property.clone(),
+					objects.iter().map(Cow::Borrowed),
+					active_graph,
+				)?;
+			}
 		}
-		node_map
-			.graph_mut(active_graph)
-			.unwrap()
-			.get_mut(&id)
-			.unwrap()
-			.properties_mut()
-			.insert_all_unique(property.clone(), flat_objects)
-	}
-
-	if let Some(reverse_properties) = node.reverse_properties_entry() {
-		for (property, nodes) in reverse_properties.iter() {
-			for subject in nodes {
-				let flat_subject = extend_node_map_from_node(
+		Cow::Owned(properties) => {
+			for (property, objects) in properties {
+				extend_node_map_from_property(
 					env,
 					node_map,
-					subject.inner(),
-					subject.index(),
+					&id,
+					property,
+					objects.into_iter().map(Cow::Owned),
 					active_graph,
 				)?;
-
-				let subject_id = flat_subject.id.as_ref().unwrap();
-
-				let flat_subject = node_map
-					.graph_mut(active_graph)
-					.unwrap()
-					.get_mut(subject_id)
-					.unwrap();
-
-				flat_subject.properties_mut().insert_unique(
-					property.clone(),
-					Indexed::none(Object::node(Node::with_id(id.clone()))),
-				)
 			}
+		}
+	}
 
-			// let mut flat_nodes = Vec::new();
-			// for node in nodes {
-			// 	let flat_node = extend_node_map_from_node(
-			// 		env,
-			// 		node_map,
-			// 		node.inner(),
-			// 		node.index(),
-			// 		active_graph,
-			// 	)?;
-			// 	flat_nodes.push(flat_node);
-			// }
-
-			// node_map
-			// 	.graph_mut(active_graph)
-			// 	.unwrap()
-			// 	.get_mut(&id)
-			// 	.unwrap()
-			// 	.reverse_properties_mut()
-			// 	.insert_all_unique(property.clone(), flat_nodes)
+	if let Some(reverse_properties) = reverse_properties {
+		match reverse_properties {
+			Cow::Borrowed(reverse_properties) => {
+				for (property, subjects) in reverse_properties {
+					extend_node_map_from_reverse_property(
+						env,
+						node_map,
+						&id,
+						property,
+						subjects.iter().map(Cow::Borrowed),
+						active_graph,
+					)?;
+				}
+			}
+			Cow::Owned(reverse_properties) => {
+				for (property, subjects) in reverse_properties {
+					extend_node_map_from_reverse_property(
+						env,
+						node_map,
+						&id,
+						&property,
+						subjects.into_iter().map(Cow::Owned),
+						active_graph,
+					)?;
+				}
+			}
 		}
 	}
 
 	Ok(Indexed::new(Node::with_id(id), None))
 }
+
+/// Extends the node map with the given `objects` of the `property` of the
+/// node `id`.
+fn extend_node_map_from_property<'a, N: Vocabulary, G: Generator<N>>(
+	env: &mut Environment<N, G>,
+	node_map: &mut NodeMap<N::Iri, N::BlankId>,
+	id: &Id<N::Iri, N::BlankId>,
+	property: Id<N::Iri, N::BlankId>,
+	objects: impl Iterator<Item = Cow<'a, IndexedObject<N::Iri, N::BlankId>>>,
+	active_graph: Option<&Id<N::Iri, N::BlankId>>,
+) -> Result<(), ConflictingIndexes<N::Iri, N::BlankId>>
+where
+	N::Iri: 'a + Clone + Eq + Hash,
+	N::BlankId: 'a + Clone + Eq + Hash,
+{
+	let mut flat_objects = Vec::new();
+	for object in objects {
+		let flat_object = extend_node_map(env, node_map, object, active_graph)?;
+		flat_objects.push(flat_object);
+	}
+
+	node_map
+		.graph_mut(active_graph)
+		.unwrap()
+		.get_mut(id)
+		.unwrap()
+		.properties_mut()
+		.insert_all_unique(property, flat_objects);
+
+	Ok(())
+}
+
+/// Extends the node map with the given `subjects` of the reverse `property`
+/// of the node `id`.
+fn extend_node_map_from_reverse_property<'a, N: Vocabulary, G: Generator<N>>(
+	env: &mut Environment<N, G>,
+	node_map: &mut NodeMap<N::Iri, N::BlankId>,
+	id: &Id<N::Iri, N::BlankId>,
+	property: &Id<N::Iri, N::BlankId>,
+	subjects: impl Iterator<Item = Cow<'a, IndexedNode<N::Iri, N::BlankId>>>,
+	active_graph: Option<&Id<N::Iri, N::BlankId>>,
+) -> Result<(), ConflictingIndexes<N::Iri, N::BlankId>>
+where
+	N::Iri: 'a + Clone + Eq + Hash,
+	N::BlankId: 'a + Clone + Eq + Hash,
+{
+	for subject in subjects {
+		let (subject, subject_index) = split_indexed(subject);
+		let flat_subject = extend_node_map_from_node(
+			env,
+			node_map,
+			subject,
+			subject_index.as_deref(),
+			active_graph,
+		)?;
+
+		let subject_id = flat_subject.id.as_ref().unwrap();
+
+		let flat_subject = node_map
+			.graph_mut(active_graph)
+			.unwrap()
+			.get_mut(subject_id)
+			.unwrap();
+
+		flat_subject.properties_mut().insert_unique(
+			property.clone(),
+			Indexed::none(Object::node(Node::with_id(id.clone()))),
+		)
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::TryFromJson;
+	use json_syntax::Parse;
+	use rdf_types::generator;
+
+	fn document() -> ExpandedDocument {
+		let (json, _) = json_syntax::Value::parse_str(
+			r#"[
+				{
+					"@id": "http://example.org/a",
+					"@type": ["http://example.org/T"],
+					"http://example.org/p": [
+						{ "@value": "x", "@index": "i" },
+						{ "@list": [{ "@id": "http://example.org/b" }, { "@value": 1 }] },
+						{ "http://example.org/q": [{ "@value": "nested" }] }
+					],
+					"@reverse": {
+						"http://example.org/r": [{ "@id": "http://example.org/c" }]
+					},
+					"@included": [
+						{ "@id": "http://example.org/d", "http://example.org/p": [{ "@value": "y" }] }
+					]
+				},
+				{
+					"@id": "http://example.org/g",
+					"@graph": [
+						{ "@id": "http://example.org/e", "http://example.org/p": [{ "@value": "z" }] }
+					]
+				}
+			]"#,
+		)
+		.unwrap();
+
+		ExpandedDocument::try_from_json_in(&mut (), json).unwrap()
+	}
+
+	#[test]
+	fn owned_and_borrowed_node_maps() {
+		let document = document();
+		let borrowed = document
+			.generate_node_map_with(&mut (), generator::Blank::new())
+			.unwrap()
+			.flatten(true);
+		let owned = document
+			.into_node_map_with(&mut (), generator::Blank::new())
+			.unwrap()
+			.flatten(true);
+
+		assert_eq!(borrowed, owned)
+	}
+}
@@ -0,0 +1,78 @@
+//! Future utilities shared by the JSON-LD algorithms.
+//!
+//! The loaders and algorithms are defined using `async` functions and
+//! return-position `impl Future` in traits, so that no future is boxed at
+//! trait boundaries. Boxing only happens where the algorithms recurse.
+//!
+//! By default the returned futures are not required to be `Send`. When the
+//! `send` feature is enabled, every future is `Send`, provided the
+//! vocabulary, loader and identifier types are themselves `Send`/`Sync`. This
+//! allows using the processor inside multi-threaded executors (e.g. with
+//! `tokio::spawn`).
+use core::future::Future;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// Types that are `Send` if the `send` feature is enabled.
+///
+/// Without the `send` feature, this trait is implemented by every type.
+#[cfg(feature = "send")]
+pub trait MaybeSend: Send {}
+
+#[cfg(feature = "send")]
+impl<T: ?Sized + Send> MaybeSend for T {}
+
+/// Types that are `Send` if the `send` feature is enabled.
+///
+/// Without the `send` feature, this trait is implemented by every type.
+#[cfg(not(feature = "send"))]
+pub trait MaybeSend {}
+
+#[cfg(not(feature = "send"))]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// Types that are `Sync` if the `send` feature is enabled.
+///
+/// Without the `send` feature, this trait is implemented by every type.
+#[cfg(feature = "send")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "send")]
+impl<T: ?Sized + Sync> MaybeSync for T {}
+
+/// Types that are `Sync` if the `send` feature is enabled.
+///
+/// Without the `send` feature, this trait is implemented by every type.
+#[cfg(not(feature = "send"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "send"))]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Runs the given future to completion on the current thread.
+///
+/// This is a minimal executor meant to drive the JSON-LD algorithms when
+/// every loader involved is synchronous (see
+/// [`SyncLoaderAdapter`](crate::loader::SyncLoaderAdapter)), in which case
+/// the future is ready the first time it is polled. If the future is not
+/// ready, it is polled again in a busy loop, hence this function must not be
+/// used with loaders waiting on external events.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+	let mut future = core::pin::pin!(future);
+	let waker = noop_waker();
+	let mut cx = Context::from_waker(&waker);
+	loop {
+		if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+			break output;
+		}
+
+		core::hint::spin_loop()
+	}
+}
+
+fn noop_waker() -> Waker {
+	const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| NOOP, |_| {}, |_| {}, |_| {});
+	const NOOP: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
+
+	// SAFETY: the vtable functions do nothing and never touch the data pointer.
+	unsafe { Waker::from_raw(NOOP) }
+}
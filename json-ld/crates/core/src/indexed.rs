@@ -1,6 +1,8 @@
 use crate::object::{InvalidExpandedJson, TryFromJson, TryFromJsonObject};
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::string::ToString;
+use alloc::vec::Vec;
 use core::convert::{TryFrom, TryInto};
 use core::ops::{Deref, DerefMut};
 use json_ld_syntax::{IntoJson, IntoJsonWithContext};
@@ -64,6 +66,58 @@ impl<T> Indexed<T> {
 		self.index = index
 	}
 
+	/// Removes and returns the value index.
+	#[inline(always)]
+	pub fn take_index(&mut self) -> Option<String> {
+		self.index.take()
+	}
+
+	/// Replaces the value index with the one computed by `f` from the value.
+	pub fn reindex_with(&mut self, f: impl FnOnce(&T) -> Option<String>) {
+		self.index = f(&self.value)
+	}
+
+	/// Groups the given values by index.
+	///
+	/// Values without index are grouped under the `None` key. The values of
+	/// each group keep their relative order.
+	pub fn group_by_index<'a>(
+		values: impl IntoIterator<Item = &'a Self>,
+	) -> BTreeMap<Option<&'a str>, Vec<&'a Self>>
+	where
+		T: 'a,
+	{
+		let mut result: BTreeMap<Option<&'a str>, Vec<&'a Self>> = BTreeMap::new();
+
+		for value in values {
+			result.entry(value.index()).or_default().push(value)
+		}
+
+		result
+	}
+
+	/// Checks that no two of the given values share the same index.
+	///
+	/// Values without index are ignored.
+	pub fn check_unique_indexes<'a>(
+		values: impl IntoIterator<Item = &'a Self>,
+	) -> Result<(), DuplicateIndex>
+	where
+		T: 'a,
+	{
+		let mut indexes = BTreeSet::new();
+
+		for value in values {
+			if let Some(index) = value.index() {
+				if !indexes.insert(index) {
+					return Err(DuplicateIndex(index.to_string()));
+				}
+			}
+		}
+
+		Ok(())
+	}
+
 	/// Turn this indexed value into its components: inner value and index.
 	#[inline(always)]
 	pub fn into_parts(self) -> (T, Option<String>) {
@@ -95,6 +149,12 @@ impl<T> Indexed<T> {
 	}
 }
 
+/// Error returned by [`Indexed::check_unique_indexes`] when two values share
+/// the same index.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("duplicate index `{0}`")]
+pub struct DuplicateIndex(pub String);
+
 impl<T, B, O: TryFromJsonObject<T, B>> TryFromJson<T, B> for Indexed<O> {
 	fn try_from_json_in(
 		vocabulary: &mut impl VocabularyMut<Iri = T, BlankId = B>,
@@ -0,0 +1,276 @@
+//! Identifier interning.
+use crate::{ExpandedDocument, Id, ValidId};
+use alloc::borrow::ToOwned;
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::cell::RefCell;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+use hashbrown::HashSet;
+use iref::{Iri, IriBuf};
+use rdf_types::vocabulary::{
+	BlankIdVocabulary, BlankIdVocabularyMut, IriVocabulary, IriVocabularyMut, LiteralVocabulary,
+	LiteralVocabularyMut,
+};
+use rdf_types::{BlankId, BlankIdBuf, Literal, LiteralRef};
+
+macro_rules! shared {
+	($(#[$meta:meta])* $name:ident ($owned:ty) : $borrowed:ty, $as_ref:ident) => {
+		$(#[$meta])*
+		#[derive(Clone)]
+		pub struct $name(Arc<$owned>);
+
+		impl $name {
+			/// Creates a new shared identifier, not interned.
+			pub fn new(value: $owned) -> Self {
+				Self(Arc::new(value))
+			}
+
+			/// Returns a reference to the identifier.
+			pub fn $as_ref(&self) -> &$borrowed {
+				&self.0
+			}
+
+			/// Checks if both identifiers share the same allocation.
+			pub fn ptr_eq(&self, other: &Self) -> bool {
+				Arc::ptr_eq(&self.0, &other.0)
+			}
+		}
+
+		impl Deref for $name {
+			type Target = $borrowed;
+
+			fn deref(&self) -> &$borrowed {
+				self.$as_ref()
+			}
+		}
+
+		impl Borrow<$borrowed> for $name {
+			fn borrow(&self) -> &$borrowed {
+				self.$as_ref()
+			}
+		}
+
+		impl AsRef<$borrowed> for $name {
+			fn as_ref(&self) -> &$borrowed {
+				self.$as_ref()
+			}
+		}
+
+		impl PartialEq for $name {
+			fn eq(&self, other: &Self) -> bool {
+				self.ptr_eq(other) || self.$as_ref() == other.$as_ref()
+			}
+		}
+
+		impl Eq for $name {}
+
+		impl PartialOrd for $name {
+			fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+				Some(self.cmp(other))
+			}
+		}
+
+		impl Ord for $name {
+			fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+				self.$as_ref().cmp(other.$as_ref())
+			}
+		}
+
+		impl Hash for $name {
+			fn hash<H: Hasher>(&self, state: &mut H) {
+				self.$as_ref().hash(state)
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Display::fmt(self.$as_ref().as_str(), f)
+			}
+		}
+
+		impl fmt::Debug for $name {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Debug::fmt(self.$as_ref().as_str(), f)
+			}
+		}
+	};
+}
+
+shared! {
+	/// Reference-counted IRI, as interned by an [`InterningVocabulary`].
+	///
+	/// Cloning a shared IRI does not copy the IRI.
+	SharedIri(IriBuf): Iri, as_iri
+}
+
+shared! {
+	/// Reference-counted blank node identifier, as interned by an
+	/// [`InterningVocabulary`].
+	///
+	/// Cloning a shared blank node identifier does not copy it.
+	SharedBlankId(BlankIdBuf): BlankId, as_blank_id
+}
+
+/// Vocabulary interning IRIs and blank node identifiers.
+///
+/// Unlike an index-based vocabulary, the identifiers it produces
+/// ([`SharedIri`] and [`SharedBlankId`]) give access to their lexical
+/// representation without the vocabulary. Each distinct identifier is
+/// allocated only once: in a document expanded with this vocabulary, the
+/// repeated occurrences of an IRI (such as a property used by many nodes)
+/// all share the same allocation.
+///
+/// Literals are not interned.
+#[derive(Default)]
+pub struct InterningVocabulary {
+	iris: HashSet<SharedIri>,
+	blank_ids: HashSet<SharedBlankId>,
+}
+
+impl InterningVocabulary {
+	/// Creates a new empty vocabulary.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of interned IRIs.
+	pub fn iri_count(&self) -> usize {
+		self.iris.len()
+	}
+
+	/// Returns the number of interned blank node identifiers.
+	pub fn blank_id_count(&self) -> usize {
+		self.blank_ids.len()
+	}
+
+	/// Removes the identifiers that are no longer used outside of the
+	/// vocabulary.
+	pub fn shrink(&mut self) {
+		self.iris.retain(|iri| Arc::strong_count(&iri.0) > 1);
+		self.blank_ids.retain(|b| Arc::strong_count(&b.0) > 1);
+	}
+}
+
+impl IriVocabulary for InterningVocabulary {
+	type Iri = SharedIri;
+
+	fn iri<'i>(&'i self, id: &'i SharedIri) -> Option<&'i Iri> {
+		Some(id.as_iri())
+	}
+
+	fn owned_iri(&self, id: SharedIri) -> Result<IriBuf, SharedIri> {
+		Ok(id.as_iri().to_owned())
+	}
+
+	fn get(&self, iri: &Iri) -> Option<SharedIri> {
+		self.iris.get(iri).cloned()
+	}
+}
+
+impl IriVocabularyMut for InterningVocabulary {
+	fn insert(&mut self, iri: &Iri) -> SharedIri {
+		match self.iris.get(iri) {
+			Some(shared) => shared.clone(),
+			None => self.insert_owned(iri.to_owned()),
+		}
+	}
+
+	fn insert_owned(&mut self, iri: IriBuf) -> SharedIri {
+		match self.iris.get(iri.as_iri()) {
+			Some(shared) => shared.clone(),
+			None => {
+				let shared = SharedIri::new(iri);
+				self.iris.insert(shared.clone());
+				shared
+			}
+		}
+	}
+}
+
+impl BlankIdVocabulary for InterningVocabulary {
+	type BlankId = SharedBlankId;
+
+	fn blank_id<'b>(&'b self, id: &'b SharedBlankId) -> Option<&'b BlankId> {
+		Some(id.as_blank_id())
+	}
+
+	fn owned_blank_id(&self, id: SharedBlankId) -> Result<BlankIdBuf, SharedBlankId> {
+		Ok(id.as_blank_id().to_owned())
+	}
+
+	fn get_blank_id(&self, id: &BlankId) -> Option<SharedBlankId> {
+		self.blank_ids.get(id).cloned()
+	}
+}
+
+impl BlankIdVocabularyMut for InterningVocabulary {
+	fn insert_blank_id(&mut self, id: &BlankId) -> SharedBlankId {
+		match self.blank_ids.get(id) {
+			Some(shared) => shared.clone(),
+			None => self.insert_owned_blank_id(id.to_owned()),
+		}
+	}
+
+	fn insert_owned_blank_id(&mut self, id: BlankIdBuf) -> SharedBlankId {
+		match self.blank_ids.get(id.as_blank_id_ref()) {
+			Some(shared) => shared.clone(),
+			None => {
+				let shared = SharedBlankId::new(id);
+				self.blank_ids.insert(shared.clone());
+				shared
+			}
+		}
+	}
+}
+
+impl LiteralVocabulary for InterningVocabulary {
+	type Literal = Literal<SharedIri>;
+
+	fn literal<'l>(&'l self, id: &'l Self::Literal) -> Option<LiteralRef<'l, SharedIri>> {
+		Some(id.as_ref())
+	}
+
+	fn owned_literal(&self, id: Self::Literal) -> Result<Literal<SharedIri>, Self::Literal> {
+		Ok(id)
+	}
+
+	fn get_literal(&self, id: LiteralRef<SharedIri>) -> Option<Self::Literal> {
+		Some(id.into_owned())
+	}
+}
+
+impl LiteralVocabularyMut for InterningVocabulary {
+	fn insert_literal(&mut self, value: LiteralRef<SharedIri>) -> Self::Literal {
+		value.into_owned()
+	}
+
+	fn insert_owned_literal(&mut self, value: Literal<SharedIri>) -> Self::Literal {
+		value
+	}
+}
+
+impl ExpandedDocument {
+	/// Interns the identifiers of this document into the given vocabulary.
+	///
+	/// The repeated identifiers of the resulting document share the same
+	/// allocation, along with the identifiers of every other document
+	/// interned into the same vocabulary.
+	pub fn intern(
+		self,
+		vocabulary: &mut InterningVocabulary,
+	) -> ExpandedDocument<SharedIri, SharedBlankId> {
+		let vocabulary = RefCell::new(vocabulary);
+		self.map_ids(
+			|iri| vocabulary.borrow_mut().insert_owned(iri),
+			|id| match id {
+				Id::Valid(ValidId::Iri(iri)) => Id::iri(vocabulary.borrow_mut().insert_owned(iri)),
+				Id::Valid(ValidId::Blank(b)) => {
+					Id::blank(vocabulary.borrow_mut().insert_owned_blank_id(b))
+				}
+				Id::Invalid(s) => Id::Invalid(s),
+			},
+		)
+	}
+}
@@ -1,5 +1,5 @@
 use crate::{object::InvalidExpandedJson, Direction, LenientLangTag, LenientLangTagBuf};
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 
 /// Language string.
 ///
@@ -30,6 +30,35 @@ pub struct LangString {
 #[derive(Clone, Copy, Debug)]
 pub struct InvalidLangString;
 
+/// Error raised by the validating constructor of [`LangString`].
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum LangStringError {
+	/// Neither a language tag nor a direction is given.
+	#[error("missing language tag or direction")]
+	MissingLanguageAndDirection,
+
+	/// The language tag is not well-formed.
+	///
+	/// If the problem can be attributed to a single subtag (empty, too
+	/// long, or with invalid characters), it is given as well.
+	#[error("invalid language tag `{tag}`")]
+	InvalidLanguageTag { tag: String, subtag: Option<String> },
+}
+
+impl LangStringError {
+	fn invalid_language_tag(tag: &str) -> Self {
+		let subtag = tag
+			.split('-')
+			.find(|s| s.is_empty() || s.len() > 8 || !s.bytes().all(|c| c.is_ascii_alphanumeric()))
+			.map(ToString::to_string);
+
+		Self::InvalidLanguageTag {
+			tag: tag.to_string(),
+			subtag,
+		}
+	}
+}
+
 impl LangString {
 	/// Create a new language string.
 	pub fn new(
@@ -48,6 +77,62 @@ impl LangString {
 		}
 	}
 
+	/// Create a new language string, with its language tag in canonical form
+	/// if it is well-formed.
+	///
+	/// See [`LenientLangTag::canonicalize`].
+	pub fn new_canonical(
+		data: json_ld_syntax::String,
+		language: Option<LenientLangTagBuf>,
+		direction: Option<Direction>,
+	) -> Result<Self, json_ld_syntax::String> {
+		let mut result = Self::new(data, language, direction)?;
+		result.canonicalize_language();
+		Ok(result)
+	}
+
+	/// Create a new language string, rejecting language tags that are not
+	/// well-formed.
+	///
+	/// The language tag is put in canonical form.
+	pub fn new_strict(
+		data: json_ld_syntax::String,
+		language: Option<LenientLangTagBuf>,
+		direction: Option<Direction>,
+	) -> Result<Self, LangStringError> {
+		let result = Self::new(data, language, direction)
+			.map_err(|_| LangStringError::MissingLanguageAndDirection)?;
+		result.validate_language()?;
+		Ok(Self::new_canonical(result.data, result.language, result.direction).unwrap())
+	}
+
+	/// Checks that the language tag, if any, is well-formed.
+	pub fn validate_language(&self) -> Result<(), LangStringError> {
+		match &self.language {
+			Some(tag) if !tag.is_well_formed() => {
+				Err(LangStringError::invalid_language_tag(tag.as_str()))
+			}
+			_ => Ok(()),
+		}
+	}
+
+	/// Puts the language tag in canonical form, if it is well-formed.
+	///
+	/// Returns `false` if the language tag is not well-formed, in which case
+	/// it is left untouched.
+	pub fn canonicalize_language(&mut self) -> bool {
+		match &self.language {
+			Some(tag) => match tag.canonicalize() {
+				Some(canonical) => {
+					self.language = Some(canonical.into());
+					true
+				}
+				None => false,
+			},
+			None => true,
+		}
+	}
+
 	pub fn into_parts(
 		self,
 	) -> (
@@ -8,13 +8,16 @@ extern crate thiserror_nostd_notrait as thiserror;
 
 pub use json_ld_syntax::{Direction, LenientLangTag, LenientLangTagBuf, Nullable};
 
+mod alloc_error;
 mod container;
 pub mod context;
 mod deserialization;
 mod document;
 pub mod flattening;
+pub mod future;
 pub mod id;
 mod indexed;
+mod interning;
 mod lang_string;
 pub mod loader;
 mod mode;
@@ -22,23 +25,28 @@ pub mod object;
 pub mod print;
 pub mod quad;
 pub mod rdf;
+#[cfg(feature = "serde")]
+mod serde_support;
 mod serialization;
 mod term;
 mod ty;
 pub mod utils;
 pub mod warning;
 
+pub use alloc_error::AllocError;
 pub use container::{Container, ContainerKind};
 pub use context::Context;
 pub use document::*;
 pub use flattening::Flatten;
+pub use future::{MaybeSend, MaybeSync};
 pub use id::*;
 pub use indexed::*;
+pub use interning::*;
 pub use lang_string::*;
 pub use loader::*;
 pub use mode::*;
 pub use object::{IndexedNode, IndexedObject, Node, Nodes, Object, Objects, TryFromJson, Value};
-pub use print::Print;
+pub use print::{Print, PrintCanonical};
 pub use quad::LdQuads;
 pub use rdf::RdfQuads;
 pub use term::*;
@@ -0,0 +1,271 @@
+//! Offline context bundles.
+//!
+//! A bundle is a single JSON document holding a frozen snapshot of remote
+//! documents, so that deployments can ship every remote context they depend
+//! on and process documents without any network access. It has the
+//! following shape:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "documents": [
+//!     {
+//!       "iri": "https://www.w3.org/ns/credentials/v2",
+//!       "url": "https://www.w3.org/ns/credentials/v2",
+//!       "contentType": "application/ld+json",
+//!       "contextUrl": null,
+//!       "profile": [],
+//!       "document": { "@context": { ... } }
+//!     }
+//!   ]
+//! }
+//! ```
+use super::{Profile, RemoteDocument};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+use hashbrown::{HashMap, HashSet};
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+use mime::Mime;
+
+/// Current bundle format version.
+pub const BUNDLE_VERSION: u64 = 1;
+
+/// Bundle error.
+#[derive(Debug, thiserror::Error)]
+pub enum BundleError {
+	/// JSON parse error.
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error),
+
+	/// Unsupported bundle format version.
+	#[error("unsupported bundle version")]
+	UnsupportedVersion,
+
+	/// Missing or invalid entry.
+	#[error("invalid bundle entry `{0}`")]
+	InvalidEntry(&'static str),
+}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for BundleError {}
+
+/// Set of remote documents, indexed by the IRI they are loaded from.
+///
+/// Use [`Self::into_loader`] to turn a bundle into a [`HashMap`] loader.
+#[derive(Debug, Default, Clone)]
+pub struct Bundle {
+	documents: BTreeMap<IriBuf, RemoteDocument>,
+}
+
+impl Bundle {
+	/// Creates a new empty bundle.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the given document, loaded from `iri`, to the bundle.
+	///
+	/// Returns the previous document loaded from `iri`, if any.
+	pub fn insert(&mut self, iri: IriBuf, document: RemoteDocument) -> Option<RemoteDocument> {
+		self.documents.insert(iri, document)
+	}
+
+	/// Returns the document loaded from `iri`, if any.
+	pub fn get(&self, iri: &Iri) -> Option<&RemoteDocument> {
+		self.documents.get(iri)
+	}
+
+	/// Returns the number of documents in the bundle.
+	pub fn len(&self) -> usize {
+		self.documents.len()
+	}
+
+	/// Checks if the bundle is empty.
+	pub fn is_empty(&self) -> bool {
+		self.documents.is_empty()
+	}
+
+	/// Returns an iterator over the documents of the bundle, with the IRI
+	/// they are loaded from.
+	pub fn iter(&self) -> impl Iterator<Item = (&Iri, &RemoteDocument)> {
+		self.documents.iter().map(|(iri, doc)| (iri.as_iri(), doc))
+	}
+
+	/// Turns this bundle into a [`HashMap`] loader.
+	pub fn into_loader(self) -> HashMap<IriBuf, RemoteDocument> {
+		self.documents.into_iter().collect()
+	}
+
+	/// Serializes the bundle into JSON.
+	pub fn to_json(&self) -> json_syntax::Value {
+		let documents = self
+			.documents
+			.iter()
+			.map(|(iri, doc)| json_syntax::Value::Object(document_to_json(iri, doc)))
+			.collect();
+
+		let mut object = json_syntax::Object::new();
+		object.insert("version".into(), BUNDLE_VERSION.into());
+		object.insert("documents".into(), json_syntax::Value::Array(documents));
+		json_syntax::Value::Object(object)
+	}
+
+	/// Deserializes a bundle from JSON.
+	pub fn from_json(value: &json_syntax::Value) -> Result<Self, BundleError> {
+		let object = value
+			.as_object()
+			.ok_or(BundleError::InvalidEntry("bundle"))?;
+
+		let version = unique(object, "version")?
+			.and_then(|v| v.as_number())
+			.and_then(|n| n.as_u64())
+			.ok_or(BundleError::InvalidEntry("version"))?;
+
+		if version != BUNDLE_VERSION {
+			return Err(BundleError::UnsupportedVersion);
+		}
+
+		let mut result = Self::new();
+		for entry in unique(object, "documents")?
+			.and_then(json_syntax::Value::as_array)
+			.ok_or(BundleError::InvalidEntry("documents"))?
+		{
+			let entry = entry
+				.as_object()
+				.ok_or(BundleError::InvalidEntry("documents"))?;
+
+			let (iri, document) = document_from_json(entry)?;
+			result.insert(iri, document);
+		}
+
+		Ok(result)
+	}
+
+	/// Parses a bundle serialized with [`Self::to_json`].
+	pub fn parse(content: &str) -> Result<Self, BundleError> {
+		let (value, _) = json_syntax::Value::parse_str(content).map_err(BundleError::Parse)?;
+		Self::from_json(&value)
+	}
+}
+
+impl FromIterator<(IriBuf, RemoteDocument)> for Bundle {
+	fn from_iter<T: IntoIterator<Item = (IriBuf, RemoteDocument)>>(iter: T) -> Self {
+		Self {
+			documents: iter.into_iter().collect(),
+		}
+	}
+}
+
+impl Extend<(IriBuf, RemoteDocument)> for Bundle {
+	fn extend<T: IntoIterator<Item = (IriBuf, RemoteDocument)>>(&mut self, iter: T) {
+		self.documents.extend(iter)
+	}
+}
+
+impl From<HashMap<IriBuf, RemoteDocument>> for Bundle {
+	fn from(value: HashMap<IriBuf, RemoteDocument>) -> Self {
+		value.into_iter().collect()
+	}
+}
+
+/// Serializes a bundle entry, holding the given document loaded from `iri`.
+pub(crate) fn document_to_json(iri: &Iri, doc: &RemoteDocument) -> json_syntax::Object {
+	let mut entry = json_syntax::Object::new();
+	entry.insert("iri".into(), iri_to_json(iri));
+	entry.insert(
+		"url".into(),
+		doc.url()
+			.map(|i| iri_to_json(i))
+			.unwrap_or(json_syntax::Value::Null),
+	);
+	entry.insert(
+		"contentType".into(),
+		doc.content_type()
+			.map(|t| json_syntax::Value::String(t.as_ref().into()))
+			.unwrap_or(json_syntax::Value::Null),
+	);
+	entry.insert(
+		"contextUrl".into(),
+		doc.context_url()
+			.map(|i| iri_to_json(i))
+			.unwrap_or(json_syntax::Value::Null),
+	);
+	entry.insert(
+		"profile".into(),
+		json_syntax::Value::Array(doc.profile.iter().map(|p| iri_to_json(p.iri())).collect()),
+	);
+	entry.insert("document".into(), doc.document().clone());
+	entry
+}
+
+/// Deserializes a bundle entry, returning the IRI the document is loaded
+/// from along with the document.
+pub(crate) fn document_from_json(
+	entry: &json_syntax::Object,
+) -> Result<(IriBuf, RemoteDocument), BundleError> {
+	let iri = optional_iri(entry, "iri")?.ok_or(BundleError::InvalidEntry("iri"))?;
+	let url = optional_iri(entry, "url")?;
+	let context_url = optional_iri(entry, "contextUrl")?;
+
+	let content_type = match unique(entry, "contentType")? {
+		None | Some(json_syntax::Value::Null) => None,
+		Some(value) => Some(
+			value
+				.as_str()
+				.and_then(|s| Mime::from_str(s).ok())
+				.ok_or(BundleError::InvalidEntry("contentType"))?,
+		),
+	};
+
+	let mut profile = HashSet::new();
+	if let Some(value) = unique(entry, "profile")? {
+		for p in value
+			.as_array()
+			.ok_or(BundleError::InvalidEntry("profile"))?
+		{
+			let p = p
+				.as_str()
+				.and_then(|s| Iri::new(s).ok())
+				.ok_or(BundleError::InvalidEntry("profile"))?;
+			profile.insert(Profile::new(p));
+		}
+	}
+
+	let document = unique(entry, "document")?
+		.ok_or(BundleError::InvalidEntry("document"))?
+		.clone();
+
+	Ok((
+		iri,
+		RemoteDocument::new_full(url, content_type, context_url, profile, document),
+	))
+}
+
+fn iri_to_json(iri: &Iri) -> json_syntax::Value {
+	json_syntax::Value::String(iri.as_str().into())
+}
+
+pub(crate) fn unique<'a>(
+	object: &'a json_syntax::Object,
+	key: &'static str,
+) -> Result<Option<&'a json_syntax::Value>, BundleError> {
+	object
+		.get_unique(key)
+		.map_err(|_| BundleError::InvalidEntry(key))
+}
+
+pub(crate) fn optional_iri(
+	object: &json_syntax::Object,
+	key: &'static str,
+) -> Result<Option<IriBuf>, BundleError> {
+	match unique(object, key)? {
+		None | Some(json_syntax::Value::Null) => Ok(None),
+		Some(value) => value
+			.as_str()
+			.and_then(|s| Iri::new(s).ok())
+			.map(|iri| Some(iri.to_owned()))
+			.ok_or(BundleError::InvalidEntry(key)),
+	}
+}
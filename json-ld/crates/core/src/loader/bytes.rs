@@ -0,0 +1,483 @@
+//! Media-type aware document ingestion.
+use super::{Profile, RemoteDocument};
+use crate::{LoadError, MaybeSend, MaybeSync};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::future::Future;
+use hashbrown::HashSet;
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+use mime::Mime;
+
+/// Error raised by [`RemoteDocument::from_bytes`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromBytesError {
+	/// The content type is neither JSON nor HTML.
+	#[error("unsupported content type `{0}`")]
+	UnsupportedContentType(Mime),
+
+	/// JSON parse error.
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error),
+
+	/// No suitable `<script type="application/ld+json">` element was found
+	/// in the HTML document.
+	#[error("no JSON-LD script element found")]
+	NoScriptElement,
+
+	/// A JSON-LD script element does not contain valid JSON.
+	#[error("invalid script element: {0}")]
+	InvalidScriptElement(json_syntax::parse::Error),
+}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for FromBytesError {}
+
+/// Raw document loading result.
+pub type BytesLoadingResult<I = IriBuf> = Result<RemoteDocument<I, Vec<u8>>, LoadError>;
+
+/// Raw document loader.
+///
+/// Counterpart of [`Loader`](super::Loader) returning the content of the
+/// loaded documents as served, before it is parsed. This is required by
+/// loaders working on the exact bytes of the documents, such as the
+/// [`IntegrityLoader`](super::IntegrityLoader). Raw documents are parsed
+/// with [`RemoteDocument::parse`].
+pub trait BytesLoader: MaybeSync {
+	/// Loads the raw content of the document behind the given IRI.
+	fn load_bytes<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = BytesLoadingResult> + MaybeSend + 'a;
+}
+
+/// Synchronous raw document loader.
+///
+/// Blocking counterpart of [`BytesLoader`].
+pub trait SyncBytesLoader {
+	/// Loads the raw content of the document behind the given IRI.
+	fn load_bytes_sync(&self, url: &Iri) -> BytesLoadingResult;
+}
+
+impl RemoteDocument<IriBuf, Vec<u8>> {
+	/// Parses the raw content of this document according to its content
+	/// type, defaulting to `application/ld+json`.
+	///
+	/// See [`RemoteDocument::from_bytes_full`] for more details. The profiles
+	/// and caching metadata of this document are kept.
+	pub fn parse(self) -> Result<RemoteDocument, FromBytesError> {
+		let content_type = self
+			.content_type
+			.unwrap_or_else(|| "application/ld+json".parse().unwrap());
+		let mut document = RemoteDocument::from_bytes_full(
+			self.url,
+			content_type,
+			self.context_url,
+			&self.document,
+			false,
+		)?;
+
+		document.profile.extend(self.profile);
+		document.cache = self.cache;
+		Ok(document)
+	}
+}
+
+impl RemoteDocument {
+	/// Creates a remote document from its raw content, interpreted according
+	/// to the given `content_type`.
+	///
+	/// See [`Self::from_bytes_full`] for more details.
+	pub fn from_bytes(content_type: Mime, bytes: &[u8]) -> Result<Self, FromBytesError> {
+		Self::from_bytes_full(None, content_type, None, bytes, false)
+	}
+
+	/// Creates a remote document from its raw content, interpreted according
+	/// to the given `content_type`, following the behavior of the
+	/// [`LoadDocumentCallback`](https://www.w3.org/TR/json-ld11-api/#loaddocumentcallback).
+	///
+	///   - `application/ld+json` documents are parsed as is, ignoring the
+	///     given `context_url` (the HTTP `Link` header value using the
+	///     `http://www.w3.org/ns/json-ld#context` link relation).
+	///   - `application/json` documents, or any `+json` media type, are
+	///     parsed as is, using `context_url` as context.
+	///   - JSON-LD is extracted from the `<script type="application/ld+json">`
+	///     elements of `text/html` and `application/xhtml+xml` documents. If
+	///     `url` has a fragment, the script element with this identifier is
+	///     used. Otherwise, if `extract_all_scripts` is `false`, the first
+	///     script element is used, or else all the script elements are
+	///     combined into a single array.
+	///
+	/// Any `profile` parameter of `content_type` is kept.
+	pub fn from_bytes_full(
+		url: Option<IriBuf>,
+		content_type: Mime,
+		context_url: Option<IriBuf>,
+		bytes: &[u8],
+		extract_all_scripts: bool,
+	) -> Result<Self, FromBytesError> {
+		let profile: HashSet<Profile> = content_type
+			.get_param("profile")
+			.into_iter()
+			.flat_map(|p| p.as_str().split(' '))
+			.filter_map(|p| Iri::new(p).ok())
+			.map(Profile::new)
+			.collect();
+
+		let (document, context_url) = match (
+			content_type.type_().as_str(),
+			content_type.subtype().as_str(),
+			content_type.suffix().map(|s| s.as_str()),
+		) {
+			("application", "ld", Some("json")) => (parse(bytes)?, None),
+			("application", "json", None) | (_, _, Some("json")) => (parse(bytes)?, context_url),
+			("text", "html", None) | ("application", "xhtml", Some("xml")) => {
+				let fragment = url.as_ref().and_then(|url| url.fragment());
+				let document =
+					extract_scripts(bytes, fragment.map(|f| f.as_str()), extract_all_scripts)?;
+				(document, None)
+			}
+			_ => return Err(FromBytesError::UnsupportedContentType(content_type)),
+		};
+
+		Ok(Self::new_full(
+			url,
+			Some(content_type.essence_str().parse().unwrap()),
+			context_url,
+			profile,
+			document,
+		))
+	}
+}
+
+fn parse(bytes: &[u8]) -> Result<json_syntax::Value, FromBytesError> {
+	json_syntax::Value::parse_slice(bytes)
+		.map(|(value, _)| value)
+		.map_err(FromBytesError::Parse)
+}
+
+/// Extracts the JSON-LD script elements of an HTML document.
+fn extract_scripts(
+	html: &[u8],
+	id: Option<&str>,
+	extract_all_scripts: bool,
+) -> Result<json_syntax::Value, FromBytesError> {
+	let mut result = Vec::new();
+	for script in Scripts::new(html) {
+		match id {
+			Some(id) => {
+				if script.id.as_deref() == Some(id) {
+					return if script.is_json_ld {
+						parse_script(script.content)
+					} else {
+						Err(FromBytesError::NoScriptElement)
+					};
+				}
+			}
+			None if script.is_json_ld => {
+				let value = parse_script(script.content)?;
+				if !extract_all_scripts {
+					return Ok(value);
+				}
+
+				match value {
+					json_syntax::Value::Array(items) => result.extend(items),
+					value => result.push(value),
+				}
+			}
+			None => (),
+		}
+	}
+
+	if extract_all_scripts && id.is_none() {
+		Ok(json_syntax::Value::Array(result))
+	} else {
+		Err(FromBytesError::NoScriptElement)
+	}
+}
+
+fn parse_script(content: &[u8]) -> Result<json_syntax::Value, FromBytesError> {
+	json_syntax::Value::parse_slice(content)
+		.map(|(value, _)| value)
+		.map_err(FromBytesError::InvalidScriptElement)
+}
+
+/// HTML script element.
+struct Script<'a> {
+	/// Value of the `id` attribute.
+	id: Option<String>,
+
+	/// Checks if the `type` attribute is `application/ld+json`.
+	is_json_ld: bool,
+
+	/// Raw content of the element.
+	content: &'a [u8],
+}
+
+/// Iterator over the script elements of an HTML document.
+///
+/// This is not a complete HTML parser: it only skips comments, CDATA sections
+/// and other markup declarations, and finds the `<script>` start tags and
+/// their matching end tag, which is enough since script elements contain raw
+/// text.
+struct Scripts<'a> {
+	html: &'a [u8],
+	offset: usize,
+}
+
+impl<'a> Scripts<'a> {
+	fn new(html: &'a [u8]) -> Self {
+		Self { html, offset: 0 }
+	}
+}
+
+impl<'a> Iterator for Scripts<'a> {
+	type Item = Script<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let rest = &self.html[self.offset..];
+			let start = rest.iter().position(|&b| b == b'<')?;
+			let tag = &rest[start..];
+			self.offset += start + 1;
+
+			if let Some(comment) = tag.strip_prefix(b"<!--") {
+				// `<!-->` and `<!--->` are empty comments.
+				let end = if comment.starts_with(b">") {
+					1
+				} else if comment.starts_with(b"->") {
+					2
+				} else {
+					find(comment, b"-->")? + 3
+				};
+
+				self.offset += 3 + end;
+				continue;
+			}
+
+			if let Some(section) = tag.strip_prefix(b"<![CDATA[") {
+				self.offset += 8 + find(section, b"]]>")? + 3;
+				continue;
+			}
+
+			if tag.starts_with(b"<!") || tag.starts_with(b"<?") {
+				// Doctype, processing instruction or bogus comment.
+				self.offset += tag.iter().position(|&b| b == b'>')?;
+				continue;
+			}
+
+			if !starts_with_ignore_case(&tag[1..], b"script")
+				|| !tag.get(7).is_some_and(is_tag_name_end)
+			{
+				continue;
+			}
+
+			let (attributes, attributes_len) = parse_attributes(&tag[7..])?;
+			let tag_end = 7 + attributes_len;
+			let content = &tag[tag_end + 1..];
+			let content_end = find_end_tag(content)?;
+			self.offset += tag_end + content_end;
+
+			let mut id = None;
+			let mut ty = None;
+			for (name, value) in attributes {
+				// The first of duplicate attributes is used.
+				if name.eq_ignore_ascii_case("id") {
+					id.get_or_insert(value);
+				} else if name.eq_ignore_ascii_case("type") {
+					ty.get_or_insert(value);
+				}
+			}
+
+			let is_json_ld = ty.is_some_and(|ty| {
+				ty.split(';')
+					.next()
+					.unwrap()
+					.trim_matches(|c: char| c.is_ascii_whitespace())
+					.eq_ignore_ascii_case("application/ld+json")
+			});
+
+			break Some(Script {
+				id,
+				is_json_ld,
+				content: &content[..content_end],
+			});
+		}
+	}
+}
+
+fn is_tag_name_end(b: &u8) -> bool {
+	*b == b'>' || *b == b'/' || b.is_ascii_whitespace()
+}
+
+/// Finds the `</script>` end tag in the content of a script element.
+fn find_end_tag(content: &[u8]) -> Option<usize> {
+	let mut offset = 0;
+	loop {
+		let start = offset + find_ignore_case(&content[offset..], b"</script")?;
+		match content.get(start + 8) {
+			Some(b) if !is_tag_name_end(b) => offset = start + 1,
+			_ => break Some(start),
+		}
+	}
+}
+
+/// Parses the attributes of an HTML start tag, up to the closing `>`.
+///
+/// Attribute values may be double-quoted, single-quoted or unquoted. Quoted
+/// values may contain `>`. Returns the attributes with the position of the
+/// closing `>`, or `None` if the tag is not closed.
+fn parse_attributes(tag: &[u8]) -> Option<(Vec<(String, String)>, usize)> {
+	let mut result = Vec::new();
+	let mut input = tag;
+	loop {
+		input = trim_start(input);
+		match input.first()? {
+			b'>' => break Some((result, tag.len() - input.len())),
+			b'/' => {
+				input = &input[1..];
+				continue;
+			}
+			_ => (),
+		}
+
+		let name_len = input
+			.iter()
+			.position(|&b| b == b'=' || b == b'/' || b == b'>' || b.is_ascii_whitespace())
+			.unwrap_or(input.len())
+			.max(1);
+
+		let name = String::from_utf8_lossy(&input[..name_len]).to_string();
+		input = trim_start(&input[name_len..]);
+
+		let value = match input.split_first() {
+			Some((b'=', rest)) => {
+				let rest = trim_start(rest);
+				let (value, rest) = match rest.split_first() {
+					Some((&(quote @ (b'"' | b'\'')), rest)) => {
+						let end = rest.iter().position(|&b| b == quote)?;
+						(&rest[..end], &rest[end + 1..])
+					}
+					_ => {
+						let end = rest
+							.iter()
+							.position(|&b| b == b'>' || b.is_ascii_whitespace())
+							.unwrap_or(rest.len());
+						rest.split_at(end)
+					}
+				};
+
+				input = rest;
+				String::from_utf8_lossy(value).to_string()
+			}
+			_ => String::new(),
+		};
+
+		result.push((name, value))
+	}
+}
+
+fn trim_start(input: &[u8]) -> &[u8] {
+	let start = input
+		.iter()
+		.position(|b| !b.is_ascii_whitespace())
+		.unwrap_or(input.len());
+	&input[start..]
+}
+
+fn starts_with_ignore_case(input: &[u8], prefix: &[u8]) -> bool {
+	input.len() >= prefix.len() && input[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn find(input: &[u8], pattern: &[u8]) -> Option<usize> {
+	input.windows(pattern.len()).position(|w| w == pattern)
+}
+
+fn find_ignore_case(input: &[u8], pattern: &[u8]) -> Option<usize> {
+	input
+		.windows(pattern.len())
+		.position(|w| w.eq_ignore_ascii_case(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn extract(
+		html: &str,
+		id: Option<&str>,
+		all: bool,
+	) -> Result<json_syntax::Value, FromBytesError> {
+		extract_scripts(html.as_bytes(), id, all)
+	}
+
+	fn json(input: &str) -> json_syntax::Value {
+		json_syntax::Value::parse_str(input).unwrap().0
+	}
+
+	#[test]
+	fn comments() {
+		let html = r#"<!-- <script type="application/ld+json">{"a": 1}</script> -->
+			<script type="application/ld+json">{"b": 2}</script>"#;
+		assert_eq!(extract(html, None, false).unwrap(), json(r#"{"b": 2}"#));
+
+		let html = r#"<!--><script type="application/ld+json">{"a": 1}</script>"#;
+		assert_eq!(extract(html, None, false).unwrap(), json(r#"{"a": 1}"#));
+
+		let html = r#"<!--- <script type="application/ld+json">{"a": 1}</script>"#;
+		assert!(matches!(
+			extract(html, None, false),
+			Err(FromBytesError::NoScriptElement)
+		))
+	}
+
+	#[test]
+	fn cdata_and_declarations() {
+		let html = r#"<!DOCTYPE html><?xml-stylesheet href="s.css"?>
+			<![CDATA[ <script type="application/ld+json">{"a": 1}</script> ]]>
+			<script type="application/ld+json">{"b": 2}</script>"#;
+		assert_eq!(extract(html, None, false).unwrap(), json(r#"{"b": 2}"#))
+	}
+
+	#[test]
+	fn attribute_quoting() {
+		let html = r#"<script data-x="a>b" type = 'application/ld+json' id=first>{"a": 1}</script>
+			<script id="second" data-y='"' type=application/ld+json>{"b": 2}</script>"#;
+		assert_eq!(
+			extract(html, Some("first"), false).unwrap(),
+			json(r#"{"a": 1}"#)
+		);
+		assert_eq!(
+			extract(html, Some("second"), false).unwrap(),
+			json(r#"{"b": 2}"#)
+		);
+		assert_eq!(
+			extract(html, None, true).unwrap(),
+			json(r#"[{"a": 1}, {"b": 2}]"#)
+		)
+	}
+
+	#[test]
+	fn type_attribute() {
+		let html = r#"<SCRIPT TYPE=" Application/LD+JSON; charset=utf-8 ">{"a": 1}</SCRIPT>"#;
+		assert_eq!(extract(html, None, false).unwrap(), json(r#"{"a": 1}"#));
+
+		let html = r#"<script type="application/ld+json" type="text/plain">{"a": 1}</script>"#;
+		assert_eq!(extract(html, None, false).unwrap(), json(r#"{"a": 1}"#));
+
+		let html = r#"<script type="application/json">{"a": 1}</script><script>{"b": 2}</script>"#;
+		assert!(matches!(
+			extract(html, None, false),
+			Err(FromBytesError::NoScriptElement)
+		))
+	}
+
+	#[test]
+	fn end_tag() {
+		let html = r#"<script type="application/ld+json">{"a": "</scripts>"}</script >"#;
+		assert_eq!(
+			extract(html, None, false).unwrap(),
+			json(r#"{"a": "</scripts>"}"#)
+		)
+	}
+}
@@ -0,0 +1,150 @@
+//! Interior mutability for caching loaders.
+//!
+//! [`Loader::load`] only takes `&self`, so loaders keeping a cache need some
+//! form of interior mutability. [`SharedCache`] provides it on both `std`
+//! and `no_std` targets, and [`CachingLoader`] uses it to memoize the
+//! documents loaded by any other loader.
+use super::{Loader, RemoteDocument, SyncLoader};
+use crate::{LoadingResult, MaybeSend};
+use alloc::borrow::ToOwned;
+use core::future::Future;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+
+/// Shared mutable cell.
+///
+/// Backed by [`std::sync::Mutex`] with the `std` feature, or by a spin lock
+/// with the `spin` feature on `no_std` targets.
+///
+/// The content is only accessible through [`Self::with`], so that the lock
+/// can never be held across an `.await` point.
+#[derive(Debug, Default)]
+pub struct SharedCache<T> {
+	#[cfg(feature = "std")]
+	inner: std::sync::Mutex<T>,
+
+	#[cfg(not(feature = "std"))]
+	inner: spin::Mutex<T>,
+}
+
+impl<T> SharedCache<T> {
+	/// Creates a new cell holding the given value.
+	pub const fn new(value: T) -> Self {
+		Self {
+			#[cfg(feature = "std")]
+			inner: std::sync::Mutex::new(value),
+
+			#[cfg(not(feature = "std"))]
+			inner: spin::Mutex::new(value),
+		}
+	}
+
+	/// Calls `f` with a mutable reference to the content of the cell.
+	#[cfg(feature = "std")]
+	pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+		let mut guard = self
+			.inner
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner);
+		f(&mut guard)
+	}
+
+	/// Calls `f` with a mutable reference to the content of the cell.
+	#[cfg(not(feature = "std"))]
+	pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+		f(&mut self.inner.lock())
+	}
+
+	/// Returns the content of the cell.
+	#[cfg(feature = "std")]
+	pub fn into_inner(self) -> T {
+		self.inner
+			.into_inner()
+			.unwrap_or_else(std::sync::PoisonError::into_inner)
+	}
+
+	/// Returns the content of the cell.
+	#[cfg(not(feature = "std"))]
+	pub fn into_inner(self) -> T {
+		self.inner.into_inner()
+	}
+}
+
+/// Loader wrapper memoizing the documents loaded by an inner loader.
+///
+/// Successfully loaded documents are kept in memory and served from there the
+/// next time they are requested. Failed loads are not cached.
+pub struct CachingLoader<L> {
+	inner: L,
+	cache: SharedCache<HashMap<IriBuf, RemoteDocument>>,
+}
+
+impl<L> CachingLoader<L> {
+	/// Wraps the given loader with an empty cache.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			cache: SharedCache::default(),
+		}
+	}
+
+	/// Checks if a document is cached for the given IRI.
+	pub fn contains(&self, url: &Iri) -> bool {
+		self.cache.with(|cache| cache.contains_key(url))
+	}
+
+	/// Removes every cached document.
+	pub fn clear(&self) {
+		self.cache.with(HashMap::clear)
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Returns the inner loader and the cached documents.
+	pub fn into_parts(self) -> (L, HashMap<IriBuf, RemoteDocument>) {
+		(self.inner, self.cache.into_inner())
+	}
+
+	fn get(&self, url: &Iri) -> Option<RemoteDocument> {
+		self.cache.with(|cache| cache.get(url).cloned())
+	}
+
+	fn insert(&self, url: &Iri, document: &RemoteDocument) {
+		self.cache
+			.with(|cache| cache.insert(url.to_owned(), document.clone()));
+	}
+}
+
+impl<L: Loader> Loader for CachingLoader<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			match self.get(url) {
+				Some(document) => Ok(document),
+				None => {
+					let document = self.inner.load(url).await?;
+					self.insert(url, &document);
+					Ok(document)
+				}
+			}
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for CachingLoader<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.get(url) {
+			Some(document) => Ok(document),
+			None => {
+				let document = self.inner.load_sync(url)?;
+				self.insert(url, &document);
+				Ok(document)
+			}
+		}
+	}
+}
@@ -1,12 +1,11 @@
-use alloc::boxed::Box;
+use crate::MaybeSend;
 use core::fmt;
 use core::future::Future;
-use core::pin::Pin;
 
 use crate::{LoadError, LoadErrorCause, LoadingResult};
 use iref::{Iri, IriBuf};
 
-use super::Loader;
+use super::{Loader, SyncLoader};
 
 /// * [`ChainLoader`]: loads document from the first loader, otherwise falls back to the second one.
 ///
@@ -15,7 +14,8 @@ use super::Loader;
 /// and a [`ReqwestLoader`](super::ReqwestLoader) for loading any other context from the web.
 ///
 /// Note that it is also possible to nest several [`ChainLoader`]s,
-/// to combine more than two loaders.
+/// to combine more than two loaders, although a
+/// [`MultiLoader`](super::MultiLoader) is more convenient in this case.
 pub struct ChainLoader<L1, L2>(L1, L2);
 
 impl<L1, L2> ChainLoader<L1, L2> {
@@ -33,8 +33,8 @@ where
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + 'a>> {
-		Box::pin(async move {
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
 			match self.0.load(url).await {
 				Ok(doc) => Ok(doc),
 				Err(LoadError { cause: e1, .. }) => match self.1.load(url).await {
@@ -44,7 +44,23 @@ where
 					}
 				},
 			}
-		})
+		}
+	}
+}
+
+impl<L1, L2> SyncLoader for ChainLoader<L1, L2>
+where
+	L1: SyncLoader,
+	L2: SyncLoader,
+{
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.0.load_sync(url) {
+			Ok(doc) => Ok(doc),
+			Err(LoadError { cause: e1, .. }) => match self.1.load_sync(url) {
+				Ok(doc) => Ok(doc),
+				Err(LoadError { target, cause: e2 }) => Err(LoadError::new(target, Error(e1, e2))),
+			},
+		}
 	}
 }
 
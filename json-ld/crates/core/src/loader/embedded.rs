@@ -0,0 +1,137 @@
+//! Loader serving documents embedded in the binary.
+use super::{
+	map::EntryNotFound, BytesLoader, BytesLoadingResult, Loader, RemoteDocument, SyncBytesLoader,
+	SyncLoader,
+};
+use crate::MaybeSend;
+use crate::{LoadError, LoadingResult};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use core::future::Future;
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+
+/// Embedded document loader.
+///
+/// Serves documents embedded in the binary (typically with
+/// [`include_str`]), without any network or file system access. This makes
+/// it suitable for air-gapped and embedded deployments. Documents are parsed
+/// each time they are loaded.
+///
+/// No context is bundled with this crate: the loader starts empty and
+/// callers register the documents they need, for instance their own copies
+/// of the schema.org or W3C Verifiable Credentials contexts. Embedding those
+/// copies is left to the application so that it controls exactly which
+/// version of each context is trusted.
+///
+/// ```
+/// use json_ld_core::{loader::StaticLoader, SyncLoader};
+/// use static_iref::iri;
+///
+/// let mut loader = StaticLoader::new();
+/// loader.insert(
+///   iri!("https://example.org/context.jsonld").to_owned(),
+///   r#"{ "@context": { "name": "https://schema.org/name" } }"#,
+/// );
+///
+/// assert!(loader.load_sync(iri!("https://example.org/context.jsonld")).is_ok());
+/// assert!(loader.load_sync(iri!("https://example.org/other.jsonld")).is_err());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct StaticLoader {
+	documents: BTreeMap<IriBuf, &'static str>,
+}
+
+impl StaticLoader {
+	/// Creates a new empty loader.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Registers the given JSON `content` as the document behind `iri`.
+	///
+	/// Returns the previously registered content, if any.
+	pub fn insert(&mut self, iri: IriBuf, content: &'static str) -> Option<&'static str> {
+		self.documents.insert(iri, content)
+	}
+
+	/// Checks if a document is registered for the given IRI.
+	pub fn contains(&self, iri: &Iri) -> bool {
+		self.documents.contains_key(iri)
+	}
+}
+
+/// Error raised by [`StaticLoader`] when an embedded document is not valid
+/// JSON.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid embedded document: {0}")]
+pub struct InvalidEmbeddedDocument(pub json_syntax::parse::Error);
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for InvalidEmbeddedDocument {}
+
+impl SyncLoader for StaticLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.documents.get(url) {
+			Some(content) => {
+				let (doc, _) = json_syntax::Value::parse_str(content)
+					.map_err(|e| LoadError::new(url.to_owned(), InvalidEmbeddedDocument(e)))?;
+				Ok(RemoteDocument::new(
+					Some(url.to_owned()),
+					Some("application/ld+json".parse().unwrap()),
+					doc,
+				))
+			}
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+		}
+	}
+}
+
+impl Loader for StaticLoader {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
+	}
+}
+
+impl SyncBytesLoader for StaticLoader {
+	fn load_bytes_sync(&self, url: &Iri) -> BytesLoadingResult {
+		match self.documents.get(url) {
+			Some(content) => Ok(RemoteDocument::new(
+				Some(url.to_owned()),
+				Some("application/ld+json".parse().unwrap()),
+				content.as_bytes().to_vec(),
+			)),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+		}
+	}
+}
+
+impl BytesLoader for StaticLoader {
+	fn load_bytes<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = BytesLoadingResult> + MaybeSend + 'a {
+		core::future::ready(self.load_bytes_sync(url))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	#[test]
+	fn load_invalid_document() {
+		let mut loader = StaticLoader::new();
+		loader.insert(
+			iri!("https://example.org/broken").to_owned(),
+			"{ \"@context\": ",
+		);
+		assert!(loader
+			.load_sync(iri!("https://example.org/broken"))
+			.is_err())
+	}
+}
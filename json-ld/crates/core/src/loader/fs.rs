@@ -1,28 +1,126 @@
-use super::{Loader, RemoteDocument};
+use super::{
+	BytesLoadingResult, ErrorCause, FromBytesError, Loader, RemoteDocument, SyncBytesLoader,
+	SyncLoader,
+};
 use crate::{LoadError, LoadingResult};
-use alloc::boxed::Box;
+use crate::{MaybeSend, MaybeSync};
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::future::Future;
 use iref::{Iri, IriBuf};
-use json_syntax::Parse;
-use std::fs::File;
-use std::future::Future;
-use std::io::{BufReader, Read};
-use std::path::{Path, PathBuf};
-use std::pin::Pin;
+use mime::Mime;
 
 /// Loading error.
 #[derive(Debug, thiserror::Error)]
-pub enum Error {
+pub enum Error<E> {
 	/// No mount point found for the given IRI.
 	#[error("no mount point")]
 	NoMountPoint,
 
 	/// IO error.
 	#[error("IO: {0}")]
-	IO(std::io::Error),
+	IO(E),
 
-	/// Parse error.
+	/// The file exceeds the maximum document size.
+	#[error("document too large")]
+	DocumentTooLarge,
+
+	/// JSON parse error.
 	#[error("parse error: {0}")]
 	Parse(json_syntax::parse::Error),
+
+	/// The file content cannot be interpreted according to its media type.
+	#[error(transparent)]
+	Content(FromBytesError),
+}
+
+impl<E> From<FromBytesError> for Error<E> {
+	fn from(value: FromBytesError) -> Self {
+		match value {
+			FromBytesError::Parse(e) => Self::Parse(e),
+			e => Self::Content(e),
+		}
+	}
+}
+
+#[cfg(not(feature = "std"))]
+impl<E: crate::Convenient> crate::Convenient for Error<E> {}
+
+/// File system used by [`FsLoader`].
+///
+/// With the `std` feature enabled, [`StdFileSystem`] gives access to the
+/// host file system. Embedded targets can implement this trait on top of
+/// their own storage driver (littlefs, FAT, etc.).
+pub trait FileSystem: MaybeSync {
+	/// File path.
+	type Path: Clone;
+
+	/// Error raised when reading a file.
+	type Error: ErrorCause;
+
+	/// Returns the path of the entry named `segment` in directory `dir`.
+	fn join(&self, dir: &Self::Path, segment: &str) -> Self::Path;
+
+	/// Reads the content of the file at the given `path`.
+	fn read(&self, path: &Self::Path) -> Result<Vec<u8>, Self::Error>;
+
+	/// Returns the size, in bytes, of the file at the given `path`, if known.
+	///
+	/// This is used to reject files larger than the loader limit before
+	/// reading them. The default implementation returns `None`.
+	fn size(&self, path: &Self::Path) -> Result<Option<u64>, Self::Error> {
+		let _ = path;
+		Ok(None)
+	}
+}
+
+/// Host file system, accessed through [`std::fs`].
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdFileSystem;
+
+#[cfg(feature = "std")]
+impl FileSystem for StdFileSystem {
+	type Path = std::path::PathBuf;
+
+	type Error = std::io::Error;
+
+	fn join(&self, dir: &Self::Path, segment: &str) -> Self::Path {
+		dir.join(segment)
+	}
+
+	fn read(&self, path: &Self::Path) -> Result<Vec<u8>, Self::Error> {
+		std::fs::read(path)
+	}
+
+	fn size(&self, path: &Self::Path) -> Result<Option<u64>, Self::Error> {
+		std::fs::metadata(path).map(|m| Some(m.len()))
+	}
+}
+
+/// File-system loader.
+///
+/// This is a special JSON-LD document loader that can load document from the file system by
+/// attaching a directory to specific URLs.
+///
+/// Loaded documents are not cached: a new file system read is made each time
+/// an URL is loaded even if it has already been queried before.
+///
+/// Directory IRIs (ending with `/`) are resolved to an index file,
+/// `index.jsonld` by default (see [`FsLoader::set_index`]). The media type of
+/// each file is deduced from its extension (see
+/// [`FsLoader::map_extension`]), falling back to `application/ld+json`.
+///
+/// The file system is abstracted by the [`FileSystem`] trait, defaulting to
+/// [`StdFileSystem`] when the `std` feature is enabled.
+#[cfg(feature = "std")]
+pub struct FsLoader<F: FileSystem = StdFileSystem> {
+	file_system: F,
+	mount_points: Vec<(F::Path, IriBuf)>,
+	index: String,
+	media_types: Vec<(String, Mime)>,
+	max_document_bytes: Option<usize>,
 }
 
 /// File-system loader.
@@ -32,68 +130,213 @@ pub enum Error {
 ///
 /// Loaded documents are not cached: a new file system read is made each time
 /// an URL is loaded even if it has already been queried before.
-#[derive(Default)]
-pub struct FsLoader {
-	mount_points: Vec<(PathBuf, IriBuf)>,
+///
+/// Directory IRIs (ending with `/`) are resolved to an index file,
+/// `index.jsonld` by default (see [`FsLoader::set_index`]). The media type of
+/// each file is deduced from its extension (see
+/// [`FsLoader::map_extension`]), falling back to `application/ld+json`.
+///
+/// The file system is abstracted by the [`FileSystem`] trait.
+#[cfg(not(feature = "std"))]
+pub struct FsLoader<F: FileSystem> {
+	file_system: F,
+	mount_points: Vec<(F::Path, IriBuf)>,
+	index: String,
+	media_types: Vec<(String, Mime)>,
+	max_document_bytes: Option<usize>,
 }
 
+#[cfg(feature = "std")]
 impl FsLoader {
-	/// Creates a new file system loader with the given content `parser`.
+	/// Creates a new file system loader on the host file system.
 	pub fn new() -> Self {
 		Self::default()
 	}
+}
+
+#[cfg(feature = "std")]
+impl Default for FsLoader {
+	fn default() -> Self {
+		Self::with_file_system(StdFileSystem)
+	}
+}
+
+impl<F: FileSystem> FsLoader<F> {
+	/// Creates a new file system loader on the given file system.
+	pub fn with_file_system(file_system: F) -> Self {
+		Self {
+			file_system,
+			mount_points: Vec::new(),
+			index: "index.jsonld".to_string(),
+			media_types: [
+				("jsonld", "application/ld+json"),
+				("json", "application/json"),
+				("html", "text/html"),
+			]
+			.into_iter()
+			.map(|(ext, ty)| (ext.to_string(), ty.parse().unwrap()))
+			.collect(),
+			max_document_bytes: None,
+		}
+	}
+
+	/// Returns the underlying file system.
+	pub fn file_system(&self) -> &F {
+		&self.file_system
+	}
 
 	/// Bind the given IRI prefix to the given path.
 	///
 	/// Any document with an IRI matching the given prefix will be loaded from
-	/// the referenced local directory.
+	/// the referenced local directory. If mount points overlap, the longest
+	/// matching prefix wins.
 	#[inline(always)]
-	pub fn mount<P: AsRef<Path>>(&mut self, url: IriBuf, path: P) {
-		self.mount_points.push((path.as_ref().into(), url));
+	pub fn mount(&mut self, url: IriBuf, path: impl Into<F::Path>) {
+		self.mount_points.push((path.into(), url));
+	}
+
+	/// Sets the name of the file loaded for directory IRIs (ending with a
+	/// `/`).
+	///
+	/// Defaults to `index.jsonld`.
+	pub fn set_index(&mut self, name: impl Into<String>) {
+		self.index = name.into()
+	}
+
+	/// Sets the maximum size, in bytes, of a loaded file.
+	///
+	/// Larger files are rejected with [`Error::DocumentTooLarge`], before
+	/// being read when the file system knows their size. Defaults to `None`,
+	/// meaning no limit.
+	pub fn set_max_document_bytes(&mut self, max_document_bytes: Option<usize>) {
+		self.max_document_bytes = max_document_bytes
+	}
+
+	/// Sets the media type of the files with the given extension (without the
+	/// leading `.`).
+	///
+	/// By default, `jsonld` files are `application/ld+json`, `json` files are
+	/// `application/json` and `html` files are `text/html`. Files with any
+	/// other extension are `application/ld+json`.
+	pub fn map_extension(&mut self, extension: impl Into<String>, media_type: Mime) {
+		let extension = extension.into();
+		match self.media_types.iter_mut().find(|(e, _)| *e == extension) {
+			Some((_, ty)) => *ty = media_type,
+			None => self.media_types.push((extension, media_type)),
+		}
+	}
+
+	/// Returns the media type of the given file name, according to its
+	/// extension.
+	pub fn media_type(&self, file_name: &str) -> Mime {
+		file_name
+			.rsplit_once('.')
+			.and_then(|(_, ext)| {
+				self.media_types
+					.iter()
+					.find(|(e, _)| e.eq_ignore_ascii_case(ext))
+			})
+			.map(|(_, ty)| ty.clone())
+			.unwrap_or_else(|| "application/ld+json".parse().unwrap())
 	}
 
 	/// Returns the local file path associated to the given `url` if any.
-	pub fn filepath(&self, url: &Iri) -> Option<PathBuf> {
-		for (path, target_url) in &self.mount_points {
-			if let Some((suffix, _, _)) = url.as_iri_ref().suffix(target_url) {
-				let mut filepath = path.clone();
-				for seg in suffix.as_path().segments() {
-					filepath.push(seg.as_str())
-				}
+	pub fn filepath(&self, url: &Iri) -> Option<F::Path> {
+		self.resolve(url).map(|(path, _)| path)
+	}
 
-				return Some(filepath);
+	/// Returns the local file path associated to the given `url`, with the
+	/// file name used to deduce the media type.
+	fn resolve(&self, url: &Iri) -> Option<(F::Path, String)> {
+		let (path, suffix) = self
+			.mount_points
+			.iter()
+			.filter_map(|(path, target_url)| {
+				url.as_iri_ref()
+					.suffix(target_url)
+					.map(|(suffix, _, _)| (target_url.as_str().len(), path, suffix))
+			})
+			.max_by_key(|(len, _, _)| *len)
+			.map(|(_, path, suffix)| (path, suffix))?;
+
+		let mut filepath = path.clone();
+		for seg in suffix.as_path().segments() {
+			if !seg.as_str().is_empty() {
+				filepath = self.file_system.join(&filepath, seg.as_str())
 			}
 		}
 
-		None
+		match suffix.as_path().file_name() {
+			Some(name) => Some((filepath, name.as_str().to_owned())),
+			None => {
+				filepath = self.file_system.join(&filepath, &self.index);
+				Some((filepath, self.index.clone()))
+			}
+		}
+	}
+}
+
+impl<F: FileSystem> SyncLoader for FsLoader<F> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.load_bytes_sync(url)?
+			.parse()
+			.map_err(|e| LoadError::new(url.to_owned(), Error::<F::Error>::from(e)))
+	}
+}
+
+impl<F: FileSystem> SyncBytesLoader for FsLoader<F> {
+	fn load_bytes_sync(&self, url: &Iri) -> BytesLoadingResult {
+		match self.resolve(url) {
+			Some((filepath, file_name)) => {
+				let too_large =
+					|len: u64| self.max_document_bytes.is_some_and(|max| len > max as u64);
+
+				let size = self
+					.file_system
+					.size(&filepath)
+					.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
+
+				if size.is_some_and(too_large) {
+					return Err(LoadError::new(
+						url.to_owned(),
+						Error::<F::Error>::DocumentTooLarge,
+					));
+				}
+
+				let bytes = self
+					.file_system
+					.read(&filepath)
+					.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
+
+				if too_large(bytes.len() as u64) {
+					return Err(LoadError::new(
+						url.to_owned(),
+						Error::<F::Error>::DocumentTooLarge,
+					));
+				}
+
+				Ok(RemoteDocument::new(
+					Some(url.to_owned()),
+					Some(self.media_type(&file_name)),
+					bytes,
+				))
+			}
+			None => Err(LoadError::new(
+				url.to_owned(),
+				Error::<F::Error>::NoMountPoint,
+			)),
+		}
 	}
 }
 
-impl Loader for FsLoader {
+impl<F: FileSystem> Loader for FsLoader<F>
+where
+	F::Path: MaybeSync,
+{
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + 'a>> {
-		Box::pin(async move {
-			match self.filepath(url) {
-				Some(filepath) => {
-					let file = File::open(filepath)
-						.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
-					let mut buf_reader = BufReader::new(file);
-					let mut contents = String::new();
-					buf_reader
-						.read_to_string(&mut contents)
-						.map_err(|e| LoadError::new(url.to_owned(), Error::IO(e)))?;
-					let (doc, _) = json_syntax::Value::parse_str(&contents)
-						.map_err(|e| LoadError::new(url.to_owned(), Error::Parse(e)))?;
-					Ok(RemoteDocument::new(
-						Some(url.to_owned()),
-						Some("application/ld+json".parse().unwrap()),
-						doc,
-					))
-				}
-				None => Err(LoadError::new(url.to_owned(), Error::NoMountPoint)),
-			}
-		})
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
 	}
 }
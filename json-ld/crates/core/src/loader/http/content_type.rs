@@ -1,16 +1,16 @@
-use std::str::FromStr;
+use alloc::vec::Vec;
+use core::str::FromStr;
 
 use hashbrown::HashMap;
 use mime::Mime;
-use reqwest::header::HeaderValue;
 
-pub struct ContentType {
+pub(crate) struct ContentType {
 	media_type: Mime,
 	params: HashMap<Vec<u8>, Vec<u8>>,
 }
 
 impl ContentType {
-	pub fn new(value: &HeaderValue) -> Option<Self> {
+	pub fn new(value: &[u8]) -> Option<Self> {
 		enum State {
 			Mime,
 			NextParam,
@@ -27,7 +27,7 @@ impl ContentType {
 		let mut current_value = Vec::new();
 		let mut params = HashMap::new();
 
-		let mut bytes = value.as_bytes().iter();
+		let mut bytes = value.iter();
 
 		loop {
 			match state {
@@ -97,14 +97,20 @@ impl ContentType {
 			}
 		}
 
-		match Mime::from_str(std::str::from_utf8(&mime).ok()?) {
+		match Mime::from_str(core::str::from_utf8(&mime).ok()?) {
 			Ok(media_type) => Some(Self { media_type, params }),
 			Err(_) => None,
 		}
 	}
 
+	/// Checks if this is a JSON media type (`application/json` or any media
+	/// type with a `+json` suffix), that can be processed as JSON-LD.
 	pub fn is_json_ld(&self) -> bool {
-		self.media_type == "application/json" || self.media_type == "application/ld+json"
+		self.media_type == "application/json"
+			|| self
+				.media_type
+				.suffix()
+				.is_some_and(|suffix| suffix == "json")
 	}
 
 	pub fn media_type(&self) -> &Mime {
@@ -127,10 +133,7 @@ mod tests {
 	#[test]
 	fn parse_content_type_1() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json;profile=http://www.w3.org/ns/json-ld#expanded",
-			)
-			.unwrap(),
+			"application/ld+json;profile=http://www.w3.org/ns/json-ld#expanded".as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -143,10 +146,7 @@ mod tests {
 	#[test]
 	fn parse_content_type_2() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json; profile=http://www.w3.org/ns/json-ld#expanded",
-			)
-			.unwrap(),
+			"application/ld+json; profile=http://www.w3.org/ns/json-ld#expanded".as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -159,10 +159,7 @@ mod tests {
 	#[test]
 	fn parse_content_type_3() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json; profile=http://www.w3.org/ns/json-ld#expanded; q=1",
-			)
-			.unwrap(),
+			"application/ld+json; profile=http://www.w3.org/ns/json-ld#expanded; q=1".as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -175,10 +172,8 @@ mod tests {
 	#[test]
 	fn parse_content_type_4() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json; profile=\"http://www.w3.org/ns/json-ld#expanded\"; q=1",
-			)
-			.unwrap(),
+			"application/ld+json; profile=\"http://www.w3.org/ns/json-ld#expanded\"; q=1"
+				.as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -191,10 +186,7 @@ mod tests {
 	#[test]
 	fn parse_content_type_5() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json; profile=\"http://www.w3.org/ns/json-ld#expanded\"",
-			)
-			.unwrap(),
+			"application/ld+json; profile=\"http://www.w3.org/ns/json-ld#expanded\"".as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -207,10 +199,7 @@ mod tests {
 	#[test]
 	fn parse_content_type_6() {
 		let content_type = ContentType::new(
-			&HeaderValue::from_str(
-				"application/ld+json;profile=\"http://www.w3.org/ns/json-ld#expanded\"; q=1",
-			)
-			.unwrap(),
+			"application/ld+json;profile=\"http://www.w3.org/ns/json-ld#expanded\"; q=1".as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
@@ -222,7 +211,7 @@ mod tests {
 
 	#[test]
 	fn parse_content_type_7() {
-		let content_type = ContentType::new(&HeaderValue::from_str("application/ld+json; profile=\"http://www.w3.org/ns/json-ld#flattened http://www.w3.org/ns/json-ld#compacted\"; q=1").unwrap()).unwrap();
+		let content_type = ContentType::new("application/ld+json; profile=\"http://www.w3.org/ns/json-ld#flattened http://www.w3.org/ns/json-ld#compacted\"; q=1".as_bytes()).unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
 		assert_eq!(
 			content_type.profile(),
@@ -235,8 +224,15 @@ mod tests {
 
 	#[test]
 	fn parse_content_type_8() {
-		let content_type =
-			ContentType::new(&HeaderValue::from_str("application/ld+json").unwrap()).unwrap();
+		let content_type = ContentType::new("application/ld+json".as_bytes()).unwrap();
 		assert_eq!(*content_type.media_type(), "application/ld+json");
 	}
+
+	#[test]
+	fn json_suffix_is_json_ld() {
+		let content_type = ContentType::new("application/activity+json".as_bytes()).unwrap();
+		assert!(content_type.is_json_ld());
+		let content_type = ContentType::new("text/html".as_bytes()).unwrap();
+		assert!(!content_type.is_json_ld())
+	}
 }
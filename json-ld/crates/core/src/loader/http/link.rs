@@ -1,14 +1,14 @@
+use alloc::vec::Vec;
 use hashbrown::HashMap;
 use iref::{IriRef, IriRefBuf};
-use reqwest::header::HeaderValue;
 
-pub struct Link {
+pub(crate) struct Link {
 	href: IriRefBuf,
 	params: HashMap<Vec<u8>, Vec<u8>>,
 }
 
 impl Link {
-	pub fn new(value: &HeaderValue) -> Option<Self> {
+	pub fn new(value: &[u8]) -> Option<Self> {
 		enum State {
 			BeginHref,
 			Href,
@@ -25,7 +25,7 @@ impl Link {
 		let mut current_value = Vec::new();
 		let mut params = HashMap::new();
 
-		let mut bytes = value.as_bytes().iter();
+		let mut bytes = value.iter();
 
 		loop {
 			match state {
@@ -103,10 +103,8 @@ mod tests {
 	#[test]
 	fn parse_link_1() {
 		let link = Link::new(
-			&HeaderValue::from_str(
-				"<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\"",
-			)
-			.unwrap(),
+			"<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\""
+				.as_bytes(),
 		)
 		.unwrap();
 		assert_eq!(link.href(), "http://www.example.org/context");
@@ -116,7 +114,7 @@ mod tests {
 
 	#[test]
 	fn parse_link_2() {
-		let link = Link::new(&HeaderValue::from_str("<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\"; foo=\"bar\"").unwrap()).unwrap();
+		let link = Link::new("<http://www.example.org/context>; rel=\"context\"; type=\"application/ld+json\"; foo=\"bar\"".as_bytes()).unwrap();
 		assert_eq!(link.href(), "http://www.example.org/context");
 		assert_eq!(link.rel(), Some(b"context".as_slice()));
 		assert_eq!(link.type_(), Some(b"application/ld+json".as_slice()))
@@ -124,8 +122,7 @@ mod tests {
 
 	#[test]
 	fn parse_link_3() {
-		let link =
-			Link::new(&HeaderValue::from_str("<http://www.example.org/context>").unwrap()).unwrap();
+		let link = Link::new("<http://www.example.org/context>".as_bytes()).unwrap();
 		assert_eq!(link.href(), "http://www.example.org/context")
 	}
 }
@@ -0,0 +1,611 @@
+//! Transport-agnostic HTTP document loader.
+//!
+//! The [`HttpLoader`] implements the JSON-LD specific part of the
+//! [LoadDocumentCallback](https://www.w3.org/TR/json-ld11-api/#loaddocumentcallback)
+//! (`Accept` header, redirections, `Link` headers, etc.) on top of any
+//! [`HttpTransport`]. This allows bringing your own network stack, for
+//! instance on embedded targets.
+use super::{BytesLoader, BytesLoadingResult, CacheMetadata, ErrorCause, Loader, RemoteDocument};
+use crate::{LoadError, LoadingResult, Profile};
+use crate::{MaybeSend, MaybeSync};
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::future::Future;
+use hashbrown::HashSet;
+use iref::{Iri, IriBuf, IriRef};
+use json_syntax::Parse;
+
+mod content_type;
+mod link;
+#[cfg(feature = "std")]
+mod revalidating;
+
+pub(crate) use content_type::ContentType;
+pub(crate) use link::Link;
+#[cfg(feature = "std")]
+pub use revalidating::RevalidatingLoader;
+
+/// `Link` header relation pointing to the context of a JSON document.
+pub(crate) const CONTEXT_LINK_REL: &[u8] = b"http://www.w3.org/ns/json-ld#context";
+
+/// HTTP response.
+#[derive(Debug, Clone, Default)]
+pub struct HttpResponse {
+	/// Status code.
+	pub status: u16,
+
+	/// Headers, with their name and (raw) value.
+	pub headers: Vec<(String, Vec<u8>)>,
+
+	/// Body.
+	pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+	/// Returns the values of the header with the given name, compared
+	/// case-insensitively.
+	pub fn header<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a [u8]> {
+		self.headers
+			.iter()
+			.filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, v)| v.as_slice())
+	}
+
+	/// Returns the value of the `Content-Length` header, if any and valid.
+	pub fn content_length(&self) -> Option<usize> {
+		let value = self.header("content-length").next()?;
+		core::str::from_utf8(value).ok()?.trim().parse().ok()
+	}
+}
+
+/// HTTP transport.
+///
+/// Minimal interface to the network stack used by [`HttpLoader`].
+pub trait HttpTransport: MaybeSync {
+	/// Transport error.
+	type Error: ErrorCause;
+
+	/// Performs a `GET` request on the given `url`, with the given `Accept`
+	/// header value.
+	///
+	/// Redirections must not be followed: they are handled by the
+	/// [`HttpLoader`].
+	///
+	/// If `max_body_bytes` is set, the transport must not buffer more than
+	/// `max_body_bytes + 1` bytes of body: it must stop reading as soon as
+	/// the `Content-Length` header announces a larger body, or once more than
+	/// `max_body_bytes` bytes have been read, and return the response
+	/// received so far. The [`HttpLoader`] then rejects the document with
+	/// [`Error::DocumentTooLarge`].
+	fn get<'a>(
+		&'a self,
+		url: &'a Iri,
+		accept: &'a str,
+		max_body_bytes: Option<usize>,
+	) -> impl Future<Output = Result<HttpResponse, Self::Error>> + MaybeSend + 'a;
+
+	/// Performs a conditional `GET` request on the given `url`, with the
+	/// given `Accept` header value.
+	///
+	/// The `If-None-Match` and `If-Modified-Since` headers should be set
+	/// from the `etag` and `last_modified` values of `validators`, so that
+	/// the server can answer with a `304 Not Modified` status. The
+	/// `max_body_bytes` limit is enforced as in [`Self::get`].
+	///
+	/// The default implementation ignores the validators and performs a
+	/// regular [`Self::get`] request.
+	fn get_conditional<'a>(
+		&'a self,
+		url: &'a Iri,
+		accept: &'a str,
+		validators: &'a CacheMetadata,
+		max_body_bytes: Option<usize>,
+	) -> impl Future<Output = Result<HttpResponse, Self::Error>> + MaybeSend + 'a {
+		let _ = validators;
+		self.get(url, accept, max_body_bytes)
+	}
+}
+
+/// Loader options.
+#[derive(Debug, Clone)]
+pub struct Options {
+	/// One or more IRIs to use in the request as a profile parameter.
+	///
+	/// (See [IANA Considerations](https://www.w3.org/TR/json-ld11/#iana-considerations)).
+	pub request_profile: Vec<Profile>,
+
+	/// Maximum number of allowed redirections (HTTP redirections and `Link`
+	/// header redirections) before the loader fails.
+	///
+	/// Defaults to 8.
+	pub max_redirections: usize,
+
+	/// Maximum size, in bytes, of a loaded document.
+	///
+	/// The limit is passed to the [`HttpTransport`], which stops reading the
+	/// body once it is exceeded (see [`HttpTransport::get`]). The body is
+	/// still buffered whole, up to the limit, before being parsed: the
+	/// document is not parsed as it is streamed. Defaults to `None`, meaning
+	/// no limit.
+	pub max_document_bytes: Option<usize>,
+}
+
+impl Default for Options {
+	fn default() -> Self {
+		Self {
+			request_profile: Vec::new(),
+			max_redirections: 8,
+			max_document_bytes: None,
+		}
+	}
+}
+
+/// Loading error.
+#[derive(Debug, thiserror::Error)]
+pub enum Error<E> {
+	#[error("transport error: {0}")]
+	Transport(E),
+
+	#[error("query failed: status code {0}")]
+	QueryFailed(u16),
+
+	#[error("invalid content type")]
+	InvalidContentType,
+
+	#[error("multiple context link headers")]
+	MultipleContextLinkHeaders,
+
+	#[error("too many redirections")]
+	TooManyRedirections,
+
+	#[error("document too large")]
+	DocumentTooLarge,
+
+	#[error("invalid encoding")]
+	InvalidEncoding,
+
+	#[error("JSON parse error: {0}")]
+	Parse(json_syntax::parse::Error),
+}
+
+#[cfg(not(feature = "std"))]
+impl<E: crate::Convenient> crate::Convenient for Error<E> {}
+
+/// HTTP document loader, generic over the [`HttpTransport`].
+///
+/// The loader follows HTTP redirections and `Link` headers.
+///
+/// Loaded documents are not cached: a new network query is made each time
+/// an URL is loaded even if it has already been queried before.
+pub struct HttpLoader<T> {
+	transport: T,
+	options: Options,
+	accept_header: String,
+}
+
+impl<T> HttpLoader<T> {
+	/// Creates a new loader using the given transport.
+	pub fn new(transport: T) -> Self {
+		Self::new_using(transport, Options::default())
+	}
+
+	/// Creates a new loader using the given transport and options.
+	pub fn new_using(transport: T, options: Options) -> Self {
+		Self {
+			transport,
+			accept_header: accept_header(&options.request_profile),
+			options,
+		}
+	}
+
+	/// Returns the underlying transport.
+	pub fn transport(&self) -> &T {
+		&self.transport
+	}
+
+	/// Returns the loader options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+}
+
+impl<T: HttpTransport> HttpLoader<T> {
+	/// Fetches and parses the document at the given `url`.
+	///
+	/// See [`Self::fetch_bytes`].
+	pub(crate) async fn fetch(
+		&self,
+		url: &Iri,
+		validators: Option<&CacheMetadata>,
+	) -> Result<Fetched, LoadError> {
+		match self.fetch_bytes(url, validators).await? {
+			Fetched::Document(document) => {
+				let url = document.url.clone().unwrap();
+				document
+					.try_map(|bytes| {
+						let content = core::str::from_utf8(&bytes)
+							.map_err(|_| Error::<T::Error>::InvalidEncoding)?;
+						json_syntax::Value::parse_str(content)
+							.map(|(document, _)| document)
+							.map_err(Error::<T::Error>::Parse)
+					})
+					.map(Fetched::Document)
+					.map_err(|e| LoadError::new(url, e))
+			}
+			Fetched::NotModified => Ok(Fetched::NotModified),
+		}
+	}
+
+	/// Fetches the raw content of the document at the given `url`.
+	///
+	/// If `validators` are given, a conditional request is made and
+	/// [`Fetched::NotModified`] is returned if the server answers with a
+	/// `304 Not Modified` status.
+	pub(crate) async fn fetch_bytes(
+		&self,
+		url: &Iri,
+		validators: Option<&CacheMetadata>,
+	) -> Result<Fetched<Vec<u8>>, LoadError> {
+		let mut redirection_number = 0;
+		let mut url = url.to_owned();
+		loop {
+			if redirection_number > self.options.max_redirections {
+				return Err(LoadError::new(url, Error::<T::Error>::TooManyRedirections));
+			}
+
+			log::debug!("downloading: {}", url);
+			let response = match validators {
+				Some(validators) => {
+					self.transport
+						.get_conditional(
+							&url,
+							&self.accept_header,
+							validators,
+							self.options.max_document_bytes,
+						)
+						.await
+				}
+				None => {
+					self.transport
+						.get(&url, &self.accept_header, self.options.max_document_bytes)
+						.await
+				}
+			}
+			.map_err(|e| LoadError::new(url.clone(), Error::Transport(e)))?;
+
+			match response.status {
+				304 if validators.is_some() => break Ok(Fetched::NotModified),
+				301 | 302 | 303 | 307 | 308 => match location(&response) {
+					Some(location) => {
+						url = location.resolved(&url);
+						redirection_number += 1;
+					}
+					None => {
+						return Err(LoadError::new(
+							url,
+							Error::<T::Error>::QueryFailed(response.status),
+						))
+					}
+				},
+				200 => {
+					let mut content_types =
+						response.header("content-type").filter_map(ContentType::new);
+
+					match content_types.find(ContentType::is_json_ld) {
+						Some(content_type) => {
+							let context_url = if *content_type.media_type() != "application/ld+json"
+							{
+								context_link(&url, response.header("link")).map_err(|e| {
+									LoadError::new(url.clone(), Error::<T::Error>::from(e))
+								})?
+							} else {
+								None
+							};
+
+							let too_large = |len: usize| {
+								self.options.max_document_bytes.is_some_and(|max| len > max)
+							};
+
+							if response.content_length().is_some_and(too_large)
+								|| too_large(response.body.len())
+							{
+								return Err(LoadError::new(
+									url,
+									Error::<T::Error>::DocumentTooLarge,
+								));
+							}
+
+							let cache = CacheMetadata::from_headers(
+								response.header("etag").next(),
+								response.header("last-modified").next(),
+								response.header("cache-control"),
+							);
+
+							let profile = profiles(&content_type);
+							let mut document = RemoteDocument::new_full(
+								Some(url),
+								Some(content_type.into_media_type()),
+								context_url,
+								profile,
+								response.body,
+							);
+
+							document.set_cache(cache);
+
+							break Ok(Fetched::Document(document));
+						}
+						None => {
+							log::debug!("no valid media type found");
+							match alternate_link(&url, response.header("link")) {
+								Some(alternate) => {
+									url = alternate;
+									redirection_number += 1;
+								}
+								None => {
+									break Err(LoadError::new(
+										url,
+										Error::<T::Error>::InvalidContentType,
+									))
+								}
+							}
+						}
+					}
+				}
+				status => break Err(LoadError::new(url, Error::<T::Error>::QueryFailed(status))),
+			}
+		}
+	}
+}
+
+/// Result of [`HttpLoader::fetch`].
+pub(crate) enum Fetched<T = json_syntax::Value> {
+	/// The document has been (re)loaded.
+	Document(RemoteDocument<IriBuf, T>),
+
+	/// The document has not been modified since the given validators.
+	NotModified,
+}
+
+impl<T: HttpTransport> Loader for HttpLoader<T> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			match self.fetch(url, None).await? {
+				Fetched::Document(document) => Ok(document),
+				Fetched::NotModified => unreachable!("unconditional request"),
+			}
+		}
+	}
+}
+
+impl<T: HttpTransport> BytesLoader for HttpLoader<T> {
+	fn load_bytes<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = BytesLoadingResult> + MaybeSend + 'a {
+		async move {
+			match self.fetch_bytes(url, None).await? {
+				Fetched::Document(document) => Ok(document),
+				Fetched::NotModified => unreachable!("unconditional request"),
+			}
+		}
+	}
+}
+
+/// Error raised when a response has more than one context `Link` header.
+pub(crate) struct MultipleContextLinkHeaders;
+
+impl<E> From<MultipleContextLinkHeaders> for Error<E> {
+	fn from(_: MultipleContextLinkHeaders) -> Self {
+		Self::MultipleContextLinkHeaders
+	}
+}
+
+/// Builds the `Accept` header value requesting JSON-LD documents with the
+/// given profiles.
+pub(crate) fn accept_header(request_profile: &[Profile]) -> String {
+	let mut json_ld_params = String::new();
+
+	if !request_profile.is_empty() {
+		json_ld_params.push_str("; profile=");
+
+		if request_profile.len() > 1 {
+			json_ld_params.push('"');
+		}
+
+		for (i, p) in request_profile.iter().enumerate() {
+			if i > 0 {
+				json_ld_params.push(' ');
+			}
+
+			json_ld_params.push_str(p.iri().as_str());
+		}
+
+		if request_profile.len() > 1 {
+			json_ld_params.push('"');
+		}
+	}
+
+	format!("application/ld+json{json_ld_params}, application/json")
+}
+
+/// Returns the profiles listed in the `profile` parameter of the given
+/// content type.
+pub(crate) fn profiles(content_type: &ContentType) -> HashSet<Profile> {
+	let mut profile = HashSet::new();
+	for p in content_type
+		.profile()
+		.into_iter()
+		.flat_map(|p| p.split(|b| *b == b' '))
+	{
+		if let Ok(p) = core::str::from_utf8(p) {
+			if let Ok(iri) = Iri::new(p) {
+				profile.insert(Profile::new(iri));
+			}
+		}
+	}
+
+	profile
+}
+
+/// Finds the context `Link` header among the given `Link` header values,
+/// resolved against `url`.
+pub(crate) fn context_link<'a>(
+	url: &Iri,
+	links: impl IntoIterator<Item = &'a [u8]>,
+) -> Result<Option<IriBuf>, MultipleContextLinkHeaders> {
+	let mut context_url = None;
+	for link in links.into_iter().filter_map(Link::new) {
+		if link.rel() == Some(CONTEXT_LINK_REL) {
+			if context_url.is_some() {
+				return Err(MultipleContextLinkHeaders);
+			}
+
+			context_url = Some(link.href().resolved(url));
+		}
+	}
+
+	Ok(context_url)
+}
+
+/// Finds the `alternate` JSON-LD `Link` header among the given `Link`
+/// header values, resolved against `url`.
+pub(crate) fn alternate_link<'a>(
+	url: &Iri,
+	links: impl IntoIterator<Item = &'a [u8]>,
+) -> Option<IriBuf> {
+	links.into_iter().filter_map(Link::new).find_map(|link| {
+		if link.rel() == Some(b"alternate") && link.type_() == Some(b"application/ld+json") {
+			log::debug!("link found");
+			Some(link.href().resolved(url))
+		} else {
+			None
+		}
+	})
+}
+
+fn location(response: &HttpResponse) -> Option<&IriRef> {
+	let value = response.header("location").next()?;
+	IriRef::new(core::str::from_utf8(value).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::future::block_on;
+	use core::sync::atomic::{AtomicUsize, Ordering};
+	use static_iref::iri;
+
+	#[derive(Debug, thiserror::Error)]
+	#[error("transport error")]
+	struct TransportError;
+
+	#[cfg(not(feature = "std"))]
+	impl crate::Convenient for TransportError {}
+
+	/// Transport serving the same body, honoring the body size limit.
+	struct MockTransport {
+		body: &'static str,
+		content_length: bool,
+		read: AtomicUsize,
+	}
+
+	impl MockTransport {
+		fn new(body: &'static str, content_length: bool) -> Self {
+			Self {
+				body,
+				content_length,
+				read: AtomicUsize::new(0),
+			}
+		}
+	}
+
+	impl HttpTransport for MockTransport {
+		type Error = TransportError;
+
+		fn get<'a>(
+			&'a self,
+			_url: &'a Iri,
+			_accept: &'a str,
+			max_body_bytes: Option<usize>,
+		) -> impl Future<Output = Result<HttpResponse, Self::Error>> + MaybeSend + 'a {
+			let mut headers =
+				alloc::vec![("Content-Type".to_owned(), b"application/ld+json".to_vec())];
+
+			if self.content_length {
+				headers.push((
+					"Content-Length".to_owned(),
+					format!("{}", self.body.len()).into_bytes(),
+				));
+			}
+
+			let announced_too_large =
+				self.content_length && max_body_bytes.is_some_and(|max| self.body.len() > max);
+
+			let body = if announced_too_large {
+				Vec::new()
+			} else {
+				let len = match max_body_bytes {
+					Some(max) => self.body.len().min(max + 1),
+					None => self.body.len(),
+				};
+
+				self.body.as_bytes()[..len].to_vec()
+			};
+
+			self.read.store(body.len(), Ordering::Relaxed);
+			core::future::ready(Ok(HttpResponse {
+				status: 200,
+				headers,
+				body,
+			}))
+		}
+	}
+
+	fn loader(
+		transport: MockTransport,
+		max_document_bytes: Option<usize>,
+	) -> HttpLoader<MockTransport> {
+		HttpLoader::new_using(
+			transport,
+			Options {
+				max_document_bytes,
+				..Options::default()
+			},
+		)
+	}
+
+	const BODY: &str = r#"{ "@id": "https://example.org/#subject" }"#;
+
+	#[test]
+	fn load() {
+		let loader = loader(MockTransport::new(BODY, true), Some(BODY.len()));
+		assert!(block_on(loader.load(iri!("https://example.org/"))).is_ok());
+	}
+
+	#[test]
+	fn announced_too_large() {
+		let loader = loader(MockTransport::new(BODY, true), Some(8));
+		assert!(block_on(loader.load(iri!("https://example.org/"))).is_err());
+		assert_eq!(loader.transport().read.load(Ordering::Relaxed), 0)
+	}
+
+	#[test]
+	fn too_large() {
+		let loader = loader(MockTransport::new(BODY, false), Some(8));
+		assert!(block_on(loader.load(iri!("https://example.org/"))).is_err());
+		assert_eq!(loader.transport().read.load(Ordering::Relaxed), 9)
+	}
+
+	#[test]
+	fn load_bytes() {
+		let loader = loader(MockTransport::new(BODY, false), None);
+		let document = block_on(loader.load_bytes(iri!("https://example.org/"))).unwrap();
+		assert_eq!(document.document(), BODY.as_bytes());
+		assert!(document.parse().is_ok())
+	}
+}
@@ -0,0 +1,103 @@
+use super::{Fetched, HttpLoader, HttpTransport};
+use crate::loader::SharedCache;
+use crate::{Loader, LoadingResult, MaybeSend, RemoteDocument};
+use alloc::borrow::ToOwned;
+use hashbrown::HashMap;
+use iref::{Iri, IriBuf};
+use std::future::Future;
+use std::time::Instant;
+
+/// Cached document.
+struct Entry {
+	document: RemoteDocument,
+
+	/// Instant the document was last (re)validated.
+	validated_at: Instant,
+}
+
+/// HTTP loader caching documents and revalidating them with conditional
+/// requests.
+///
+/// Documents are served from the cache as long as they are fresh according
+/// to their `max-age`. Stale documents are revalidated with a conditional
+/// request (see [`HttpTransport::get_conditional`]) using their `ETag` and
+/// `Last-Modified` values: if the server answers with a `304 Not Modified`
+/// status, the cached copy is served, otherwise it is replaced by the newly
+/// loaded document. Documents without any validator are reloaded.
+pub struct RevalidatingLoader<T> {
+	loader: HttpLoader<T>,
+	cache: SharedCache<HashMap<IriBuf, Entry>>,
+}
+
+impl<T> RevalidatingLoader<T> {
+	/// Creates a new revalidating loader on top of the given HTTP loader.
+	pub fn new(loader: HttpLoader<T>) -> Self {
+		Self {
+			loader,
+			cache: SharedCache::default(),
+		}
+	}
+
+	/// Returns the underlying HTTP loader.
+	pub fn loader(&self) -> &HttpLoader<T> {
+		&self.loader
+	}
+
+	/// Removes every cached document.
+	pub fn clear(&self) {
+		self.cache.with(HashMap::clear)
+	}
+
+	fn cached(&self, url: &Iri) -> Option<(RemoteDocument, Instant)> {
+		self.cache.with(|cache| {
+			cache
+				.get(url)
+				.map(|entry| (entry.document.clone(), entry.validated_at))
+		})
+	}
+
+	fn store(&self, url: &Iri, document: RemoteDocument) {
+		let entry = Entry {
+			document,
+			validated_at: Instant::now(),
+		};
+
+		self.cache.with(|cache| cache.insert(url.to_owned(), entry));
+	}
+}
+
+impl<T: HttpTransport> Loader for RevalidatingLoader<T> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			let cached = self.cached(url);
+
+			if let Some((document, validated_at)) = &cached {
+				if document.cache().is_fresh(validated_at.elapsed()) {
+					return Ok(document.clone());
+				}
+			}
+
+			let validators = cached
+				.as_ref()
+				.map(|(document, _)| document.cache())
+				.filter(|cache| cache.has_validators());
+
+			let fetched = self.loader.fetch(url, validators).await?;
+			match fetched {
+				Fetched::Document(document) => {
+					self.store(url, document.clone());
+					Ok(document)
+				}
+				Fetched::NotModified => {
+					// `NotModified` is only returned for conditional requests.
+					let (document, _) = cached.unwrap();
+					self.store(url, document.clone());
+					Ok(document)
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,259 @@
+//! Content-addressed loading.
+use super::{BytesLoader, Loader, RemoteDocument, SyncBytesLoader, SyncLoader};
+use crate::utils::base58;
+use crate::{LoadError, LoadingResult, MaybeSend};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::marker::PhantomData;
+use iref::{Iri, IriBuf};
+use ssi_crypto::hashes::sha::Sha;
+
+/// Multihash code of SHA-256.
+const MULTIHASH_SHA2_256: u8 = 0x12;
+
+/// Loader wrapper verifying the digest of loaded documents.
+///
+/// The expected digest of a document is either registered with
+/// [`Self::expect`], or given by the IRI itself with a
+/// [hashlink](https://datatracker.ietf.org/doc/html/draft-sporny-hashlink)
+/// `hl` query parameter (a `z`-prefixed base58btc SHA-256 multihash, in which
+/// case `H` must compute SHA-256 digests).
+///
+/// The digest is computed with `H` over the raw content of the document, as
+/// served, before it is parsed. The inner loader must hence be a
+/// [`BytesLoader`] (or [`SyncBytesLoader`]). Documents whose digest does not
+/// match fail with a [`IntegrityError::DigestMismatch`] error, without being
+/// parsed. Documents without expected digest are parsed as is, unless
+/// [`Self::require_digest`] is set.
+pub struct IntegrityLoader<L, H> {
+	inner: L,
+	digests: BTreeMap<IriBuf, Vec<u8>>,
+	require_digest: bool,
+	hash: PhantomData<fn() -> H>,
+}
+
+impl<L, H> IntegrityLoader<L, H> {
+	/// Wraps the given loader.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			digests: BTreeMap::new(),
+			require_digest: false,
+			hash: PhantomData,
+		}
+	}
+
+	/// Registers the expected digest of the document behind `iri`.
+	pub fn expect(&mut self, iri: IriBuf, digest: Vec<u8>) {
+		self.digests.insert(iri, digest);
+	}
+
+	/// Returns this loader with the given expected digest registered.
+	///
+	/// See [`Self::expect`].
+	pub fn with_digest(mut self, iri: IriBuf, digest: Vec<u8>) -> Self {
+		self.expect(iri, digest);
+		self
+	}
+
+	/// Rejects the documents without expected digest.
+	pub fn require_digest(mut self) -> Self {
+		self.require_digest = true;
+		self
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Returns the expected digest of the document behind `iri`, if any.
+	pub fn expected_digest(&self, iri: &Iri) -> Result<Option<Vec<u8>>, IntegrityError> {
+		match self.digests.get(iri) {
+			Some(digest) => Ok(Some(digest.clone())),
+			None => hashlink_digest(iri),
+		}
+	}
+}
+
+impl<L, H: Sha> IntegrityLoader<L, H> {
+	/// Computes the digest of the given raw document content.
+	pub fn digest(content: &[u8]) -> Vec<u8> {
+		H::hash(content)
+	}
+
+	fn verify(
+		&self,
+		url: &Iri,
+		document: RemoteDocument<IriBuf, Vec<u8>>,
+	) -> LoadingResult<IriBuf> {
+		let expected = self
+			.expected_digest(url)
+			.map_err(|e| LoadError::new(url.to_owned(), e))?;
+
+		match expected {
+			Some(expected) => {
+				if Self::digest(document.document()) != expected {
+					return Err(LoadError::new(
+						url.to_owned(),
+						IntegrityError::DigestMismatch,
+					));
+				}
+			}
+			None if self.require_digest => {
+				return Err(LoadError::new(
+					url.to_owned(),
+					IntegrityError::MissingDigest,
+				))
+			}
+			None => (),
+		}
+
+		document
+			.parse()
+			.map_err(|e| LoadError::new(url.to_owned(), e))
+	}
+}
+
+/// Integrity error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum IntegrityError {
+	/// The digest of the loaded document does not match the expected one.
+	#[error("digest mismatch")]
+	DigestMismatch,
+
+	/// No digest is expected for the document.
+	#[error("missing digest")]
+	MissingDigest,
+
+	/// The `hl` query parameter is not a supported hashlink.
+	#[error("invalid hashlink")]
+	InvalidHashlink,
+}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for IntegrityError {}
+
+impl<L: BytesLoader, H: Sha> Loader for IntegrityLoader<L, H> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			let document = self.inner.load_bytes(url).await?;
+			self.verify(url, document)
+		}
+	}
+}
+
+impl<L: SyncBytesLoader, H: Sha> SyncLoader for IntegrityLoader<L, H> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		let document = self.inner.load_bytes_sync(url)?;
+		self.verify(url, document)
+	}
+}
+
+/// Returns the SHA-256 digest given by the `hl` query parameter of `iri`, if
+/// any.
+fn hashlink_digest(iri: &Iri) -> Result<Option<Vec<u8>>, IntegrityError> {
+	let Some(query) = iri.query() else {
+		return Ok(None);
+	};
+
+	let Some(value) = query
+		.as_str()
+		.split('&')
+		.find_map(|param| param.strip_prefix("hl="))
+	else {
+		return Ok(None);
+	};
+
+	let multihash = value
+		.strip_prefix('z')
+		.and_then(base58::decode)
+		.ok_or(IntegrityError::InvalidHashlink)?;
+
+	match multihash.as_slice() {
+		[MULTIHASH_SHA2_256, len, digest @ ..] if *len as usize == digest.len() => {
+			Ok(Some(digest.to_vec()))
+		}
+		_ => Err(IntegrityError::InvalidHashlink),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::loader::StaticLoader;
+	use static_iref::iri;
+
+	struct Sha256;
+
+	impl Sha for Sha256 {
+		fn hash(data: &[u8]) -> Vec<u8> {
+			use sha2::Digest;
+			sha2::Sha256::digest(data).to_vec()
+		}
+	}
+
+	const CONTENT: &str = r#"{ "@context": { "name": "https://schema.org/name" } }"#;
+
+	fn loader() -> StaticLoader {
+		let mut loader = StaticLoader::new();
+		loader.insert(iri!("https://example.org/a").to_owned(), CONTENT);
+		loader.insert(
+			iri!("https://example.org/b").to_owned(),
+			r#"{"@context":{"name":"https://schema.org/name"}}"#,
+		);
+		loader
+	}
+
+	#[test]
+	fn digest_of_raw_content() {
+		let loader = IntegrityLoader::<_, Sha256>::new(loader())
+			.with_digest(
+				iri!("https://example.org/a").to_owned(),
+				Sha256::hash(CONTENT.as_bytes()),
+			)
+			.with_digest(
+				iri!("https://example.org/b").to_owned(),
+				Sha256::hash(CONTENT.as_bytes()),
+			);
+
+		assert!(loader.load_sync(iri!("https://example.org/a")).is_ok());
+
+		// Same JSON value, different bytes.
+		assert!(loader.load_sync(iri!("https://example.org/b")).is_err());
+	}
+
+	#[test]
+	fn missing_digest() {
+		let loader = IntegrityLoader::<_, Sha256>::new(loader());
+		assert!(loader.load_sync(iri!("https://example.org/a")).is_ok());
+
+		let loader = loader.require_digest();
+		assert!(loader.load_sync(iri!("https://example.org/a")).is_err());
+	}
+
+	#[test]
+	fn hashlink() {
+		let mut multihash = alloc::vec![MULTIHASH_SHA2_256, 32];
+		multihash.extend(Sha256::hash(CONTENT.as_bytes()));
+		let url = IriBuf::new(alloc::format!(
+			"https://example.org/a?hl=z{}",
+			base58::encode(&multihash)
+		))
+		.unwrap();
+
+		assert_eq!(
+			IntegrityLoader::<StaticLoader, Sha256>::new(StaticLoader::new()).expected_digest(&url),
+			Ok(Some(Sha256::hash(CONTENT.as_bytes())))
+		);
+		assert_eq!(
+			hashlink_digest(iri!("https://example.org/a?hl=z0")),
+			Err(IntegrityError::InvalidHashlink)
+		)
+	}
+}
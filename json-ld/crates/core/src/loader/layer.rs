@@ -0,0 +1,119 @@
+//! Loader middleware layering.
+//!
+//! Loader wrappers (caching, policy, rewriting, etc.) can be composed as
+//! layers on top of a base loader instead of nesting their constructors:
+//!
+//! ```
+//! use json_ld_core::loader::{LoaderExt, NoLoader, Policy, layer::{LimitLayer, RewriteLayer}};
+//! use static_iref::iri;
+//!
+//! let loader = NoLoader
+//!   .layer(RewriteLayer::new().with_rule(
+//!     iri!("https://w3id.org/").to_owned(),
+//!     iri!("https://mirror.example.org/w3id/").to_owned()
+//!   ))
+//!   .layer(Policy::new().https_only())
+//!   .layer(LimitLayer(32));
+//! ```
+//!
+//! Any `FnOnce(L) -> M` closure is also a layer.
+use super::{LimitedLoader, Loader, Policy, PolicyLoader, RewriteLoader};
+use alloc::vec::Vec;
+use iref::IriBuf;
+
+/// Loader layer, wrapping a loader of type `L` into another loader.
+pub trait LoaderLayer<L> {
+	/// Resulting loader.
+	type Loader;
+
+	/// Wraps the given `inner` loader.
+	fn layer(self, inner: L) -> Self::Loader;
+}
+
+impl<L, M, F: FnOnce(L) -> M> LoaderLayer<L> for F {
+	type Loader = M;
+
+	fn layer(self, inner: L) -> M {
+		self(inner)
+	}
+}
+
+/// Layering methods for loaders.
+pub trait LoaderExt: Sized {
+	/// Wraps this loader with the given layer.
+	fn layer<Y: LoaderLayer<Self>>(self, layer: Y) -> Y::Loader {
+		layer.layer(self)
+	}
+}
+
+impl<L: Loader> LoaderExt for L {}
+
+impl<L> LoaderLayer<L> for Policy {
+	type Loader = PolicyLoader<L>;
+
+	fn layer(self, inner: L) -> Self::Loader {
+		PolicyLoader::with_policy(inner, self)
+	}
+}
+
+/// Layer limiting the number of loaded documents, producing a
+/// [`LimitedLoader`].
+#[derive(Debug, Clone, Copy)]
+pub struct LimitLayer(pub usize);
+
+impl<L> LoaderLayer<L> for LimitLayer {
+	type Loader = LimitedLoader<L>;
+
+	fn layer(self, inner: L) -> Self::Loader {
+		LimitedLoader::new(inner, Some(self.0))
+	}
+}
+
+/// Layer rewriting IRI prefixes, producing a [`RewriteLoader`].
+#[derive(Debug, Default, Clone)]
+pub struct RewriteLayer {
+	rules: Vec<(IriBuf, IriBuf)>,
+}
+
+impl RewriteLayer {
+	/// Creates a new layer without any rewrite rule.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns this layer with the given rewrite rule added.
+	///
+	/// See [`RewriteLoader::add_rule`].
+	pub fn with_rule(mut self, prefix: IriBuf, replacement: IriBuf) -> Self {
+		self.rules.push((prefix, replacement));
+		self
+	}
+}
+
+impl<L> LoaderLayer<L> for RewriteLayer {
+	type Loader = RewriteLoader<L>;
+
+	fn layer(self, inner: L) -> Self::Loader {
+		let mut result = RewriteLoader::new(inner);
+		for (prefix, replacement) in self.rules {
+			result.add_rule(prefix, replacement)
+		}
+
+		result
+	}
+}
+
+/// Layer memoizing loaded documents, producing a
+/// [`CachingLoader`](super::CachingLoader).
+#[cfg(any(feature = "std", feature = "spin"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CacheLayer;
+
+#[cfg(any(feature = "std", feature = "spin"))]
+impl<L> LoaderLayer<L> for CacheLayer {
+	type Loader = super::CachingLoader<L>;
+
+	fn layer(self, inner: L) -> Self::Loader {
+		super::CachingLoader::new(inner)
+	}
+}
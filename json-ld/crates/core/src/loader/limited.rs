@@ -0,0 +1,79 @@
+use super::{Loader, SyncLoader};
+use crate::MaybeSend;
+use crate::{LoadError, LoadingResult};
+use alloc::borrow::ToOwned;
+use core::future::Future;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use iref::{Iri, IriBuf};
+
+/// Loader wrapper limiting the number of loaded documents.
+///
+/// Every load attempt counts against the limit, whether it succeeds or not.
+/// Once the limit is reached, any further attempt fails with
+/// [`TooManyLoads`] without calling the inner loader. This protects the
+/// processing algorithms against documents importing an unbounded number of
+/// remote contexts.
+pub struct LimitedLoader<L> {
+	inner: L,
+	max_loads: Option<usize>,
+	loads: AtomicUsize,
+}
+
+impl<L> LimitedLoader<L> {
+	/// Wraps the given loader, allowing at most `max_loads` documents to be
+	/// loaded. If `max_loads` is `None`, no limit is enforced.
+	pub fn new(inner: L, max_loads: Option<usize>) -> Self {
+		Self {
+			inner,
+			max_loads,
+			loads: AtomicUsize::new(0),
+		}
+	}
+
+	/// Returns the number of load attempts so far.
+	pub fn loads(&self) -> usize {
+		self.loads.load(Ordering::Relaxed)
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	fn check(&self, url: &Iri) -> Result<(), LoadError> {
+		let loads = self.loads.fetch_add(1, Ordering::Relaxed);
+		match self.max_loads {
+			Some(max_loads) if loads >= max_loads => {
+				Err(LoadError::new(url.to_owned(), TooManyLoads(max_loads)))
+			}
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Error raised by [`LimitedLoader`] when the limit is reached.
+#[derive(Debug, thiserror::Error)]
+#[error("too many loaded documents (limit is {0})")]
+pub struct TooManyLoads(pub usize);
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for TooManyLoads {}
+
+impl<L: Loader> Loader for LimitedLoader<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			self.check(url)?;
+			self.inner.load(url).await
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for LimitedLoader<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.check(url)?;
+		self.inner.load_sync(url)
+	}
+}
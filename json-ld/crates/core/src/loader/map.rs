@@ -1,10 +1,9 @@
-use super::{Loader, RemoteDocument};
+use super::{Loader, RemoteDocument, SyncLoader};
+use crate::MaybeSend;
 use crate::{LoadError, LoadingResult};
 use alloc::borrow::ToOwned;
-use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
 use core::future::Future;
-use core::pin::Pin;
 use hashbrown::HashMap;
 use iref::{Iri, IriBuf};
 
@@ -17,17 +16,30 @@ pub struct EntryNotFound;
 #[cfg(not(feature = "std"))]
 impl crate::Convenient for EntryNotFound {}
 
+impl SyncLoader for HashMap<IriBuf, RemoteDocument> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.get(url) {
+			Some(document) => Ok(document.clone()),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+		}
+	}
+}
+
 impl Loader for HashMap<IriBuf, RemoteDocument> {
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + 'a>> {
-		Box::pin(async move {
-			match self.get(url) {
-				Some(document) => Ok(document.clone()),
-				None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
-			}
-		})
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
+	}
+}
+
+impl SyncLoader for BTreeMap<IriBuf, RemoteDocument> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.get(url) {
+			Some(document) => Ok(document.clone()),
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+		}
 	}
 }
 
@@ -35,12 +47,7 @@ impl Loader for BTreeMap<IriBuf, RemoteDocument> {
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + 'a>> {
-		Box::pin(async move {
-			match self.get(url) {
-				Some(document) => Ok(document.clone()),
-				None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
-			}
-		})
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
 	}
 }
@@ -1,25 +1,65 @@
+use crate::{MaybeSend, MaybeSync};
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
+use alloc::string::String;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
 use hashbrown::HashSet;
 use iref::{Iri, IriBuf};
 use mime::Mime;
 use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
 use static_iref::iri;
 
+pub mod bundle;
+pub mod bytes;
+#[cfg(any(feature = "std", feature = "spin"))]
+pub mod cache;
 pub mod chain;
-#[cfg(feature = "std")]
+pub mod embedded;
 pub mod fs;
+pub mod http;
+pub mod integrity;
+pub mod layer;
+pub mod limited;
 pub mod map;
+pub mod multi;
 pub mod none;
-
+pub mod policy;
+pub mod prefetch;
+#[cfg(any(feature = "std", feature = "spin"))]
+pub mod recording;
+pub mod rewrite;
+pub mod sync;
+pub mod validate;
+pub mod vocabulary;
+
+pub use bundle::Bundle;
+pub use bytes::{BytesLoader, BytesLoadingResult, FromBytesError, SyncBytesLoader};
 pub use chain::ChainLoader;
-#[cfg(feature = "std")]
-pub use fs::FsLoader;
+pub use embedded::StaticLoader;
+pub use fs::{FileSystem, FsLoader};
+pub use http::{HttpLoader, HttpTransport};
+pub use integrity::IntegrityLoader;
+pub use layer::{LoaderExt, LoaderLayer};
+pub use limited::LimitedLoader;
+pub use multi::MultiLoader;
 pub use none::NoLoader;
+pub use policy::{Policy, PolicyLoader};
+pub use prefetch::{context_iris, prefetch_contexts};
+pub use rewrite::RewriteLoader;
+pub use sync::{SyncLoader, SyncLoaderAdapter};
+pub use validate::ValidatingLoader;
+pub use vocabulary::{Lexical, LoaderWith};
+
+#[cfg(any(feature = "std", feature = "spin"))]
+pub use cache::{CachingLoader, SharedCache};
+
+#[cfg(any(feature = "std", feature = "spin"))]
+pub use recording::{Recording, Replay};
+
+#[cfg(feature = "std")]
+pub use http::RevalidatingLoader;
 
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
@@ -60,8 +100,8 @@ impl<I> RemoteDocumentReference<I> {
 	/// [`RemoteDocument`].
 	pub async fn load_with<V>(self, vocabulary: &mut V, loader: &impl Loader) -> LoadingResult<I>
 	where
-		V: IriVocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
+		V: IriVocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend,
 	{
 		match self {
 			Self::Iri(r) => Ok(loader.load_with(vocabulary, r).await?.map(Into::into)),
@@ -81,8 +121,8 @@ impl<I> RemoteDocumentReference<I> {
 		loader: &impl Loader,
 	) -> Result<Cow<'_, RemoteDocument<V::Iri>>, LoadError>
 	where
-		V: IriVocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
+		V: IriVocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend,
 	{
 		match self {
 			Self::Iri(r) => Ok(Cow::Owned(
@@ -116,8 +156,8 @@ impl<I> RemoteContextReference<I> {
 		loader: &L,
 	) -> Result<RemoteContext<I>, ContextLoadError>
 	where
-		V: IriVocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
+		V: IriVocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend,
 	{
 		match self {
 			Self::Iri(r) => Ok(loader
@@ -140,8 +180,8 @@ impl<I> RemoteContextReference<I> {
 		loader: &L,
 	) -> Result<Cow<'_, RemoteContext<I>>, ContextLoadError>
 	where
-		V: IriVocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
+		V: IriVocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend,
 	{
 		match self {
 			Self::Iri(r) => Ok(Cow::Owned(
@@ -180,6 +220,9 @@ pub struct RemoteDocument<I = IriBuf, T = json_syntax::Value> {
 
 	pub profile: HashSet<Profile<I>>,
 
+	/// HTTP caching metadata of the response, if any.
+	pub cache: CacheMetadata,
+
 	/// The retrieved document.
 	pub document: T,
 }
@@ -222,6 +265,7 @@ impl<I, T> RemoteDocument<I, T> {
 			content_type,
 			context_url,
 			profile,
+			cache: CacheMetadata::default(),
 			document,
 		}
 	}
@@ -233,6 +277,7 @@ impl<I, T> RemoteDocument<I, T> {
 			content_type: self.content_type,
 			context_url: self.context_url,
 			profile: self.profile,
+			cache: self.cache,
 			document: f(self.document),
 		}
 	}
@@ -244,6 +289,7 @@ impl<I, T> RemoteDocument<I, T> {
 			content_type: self.content_type,
 			context_url: self.context_url,
 			profile: self.profile,
+			cache: self.cache,
 			document: f(self.document)?,
 		})
 	}
@@ -262,6 +308,7 @@ impl<I, T> RemoteDocument<I, T> {
 				.into_iter()
 				.map(|p| p.map_iri(&mut f))
 				.collect(),
+			cache: self.cache,
 			document: self.document,
 		}
 	}
@@ -291,6 +338,17 @@ impl<I, T> RemoteDocument<I, T> {
 		self.context_url.as_ref()
 	}
 
+	/// Checks if the `profile` parameter of the document content type
+	/// includes the given standard profile.
+	///
+	/// See: <https://www.w3.org/TR/json-ld11/#iana-considerations>
+	pub fn has_profile(&self, profile: StandardProfile) -> bool
+	where
+		I: Eq + Hash,
+	{
+		self.profile.contains(&Profile::Standard(profile))
+	}
+
 	/// Returns a reference to the content of the document.
 	pub fn document(&self) -> &T {
 		&self.document
@@ -315,6 +373,91 @@ impl<I, T> RemoteDocument<I, T> {
 	pub fn set_url(&mut self, url: Option<I>) {
 		self.url = url
 	}
+
+	/// Returns the HTTP caching metadata of the document.
+	pub fn cache(&self) -> &CacheMetadata {
+		&self.cache
+	}
+
+	/// Sets the HTTP caching metadata of the document.
+	pub fn set_cache(&mut self, cache: CacheMetadata) {
+		self.cache = cache
+	}
+}
+
+/// HTTP caching metadata of a remote document.
+///
+/// Loaders fill this from the `ETag`, `Last-Modified` and `Cache-Control`
+/// response headers, so that the document can later be revalidated with a
+/// conditional request (see [`RevalidatingLoader`](http::RevalidatingLoader)).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CacheMetadata {
+	/// Value of the `ETag` header.
+	pub etag: Option<String>,
+
+	/// Value of the `Last-Modified` header.
+	pub last_modified: Option<String>,
+
+	/// `max-age` directive of the `Cache-Control` header, in seconds.
+	///
+	/// The `no-cache` and `no-store` directives are represented as a
+	/// `max-age` of `0`.
+	pub max_age: Option<u64>,
+}
+
+impl CacheMetadata {
+	/// Parses the caching metadata from the given `ETag`, `Last-Modified` and
+	/// `Cache-Control` header values.
+	pub fn from_headers<'a>(
+		etag: Option<&[u8]>,
+		last_modified: Option<&[u8]>,
+		cache_control: impl IntoIterator<Item = &'a [u8]>,
+	) -> Self {
+		let header_string = |value: &[u8]| {
+			core::str::from_utf8(value)
+				.ok()
+				.map(|v| v.trim().to_owned())
+		};
+
+		let mut max_age = None;
+		for directive in cache_control
+			.into_iter()
+			.filter_map(|v| core::str::from_utf8(v).ok())
+			.flat_map(|v| v.split(','))
+		{
+			let directive = directive.trim();
+			if directive.eq_ignore_ascii_case("no-cache")
+				|| directive.eq_ignore_ascii_case("no-store")
+			{
+				max_age = Some(0);
+				break;
+			}
+
+			if let Some((name, value)) = directive.split_once('=') {
+				if name.trim().eq_ignore_ascii_case("max-age") {
+					max_age = value.trim().trim_matches('"').parse().ok().or(max_age);
+				}
+			}
+		}
+
+		Self {
+			etag: etag.and_then(header_string),
+			last_modified: last_modified.and_then(header_string),
+			max_age,
+		}
+	}
+
+	/// Checks if a conditional request can be made using this metadata
+	/// (an `ETag` or `Last-Modified` value is present).
+	pub fn has_validators(&self) -> bool {
+		self.etag.is_some() || self.last_modified.is_some()
+	}
+
+	/// Checks if a document of the given `age` is still fresh according to
+	/// its `max-age`.
+	pub fn is_fresh(&self, age: core::time::Duration) -> bool {
+		self.max_age.is_some_and(|max_age| age.as_secs() < max_age)
+	}
 }
 
 /// Standard `profile` parameter values defined for the `application/ld+json`.
@@ -428,6 +571,24 @@ pub trait Convenient: core::fmt::Display + core::fmt::Debug + Send + Sync {}
 #[cfg(not(feature = "std"))]
 pub type LoadErrorCause = Box<dyn Convenient>;
 
+/// Error type that can be used as a [`LoadError`] cause.
+///
+/// This is automatically implemented by every suitable type.
+#[cfg(feature = "std")]
+pub trait ErrorCause: 'static + std::error::Error + Send + Sync {}
+
+#[cfg(feature = "std")]
+impl<E: 'static + std::error::Error + Send + Sync> ErrorCause for E {}
+
+/// Error type that can be used as a [`LoadError`] cause.
+///
+/// This is automatically implemented by every suitable type.
+#[cfg(not(feature = "std"))]
+pub trait ErrorCause: 'static + Convenient {}
+
+#[cfg(not(feature = "std"))]
+impl<E: 'static + Convenient> ErrorCause for E {}
+
 /// Loading error.
 #[derive(Debug, thiserror::Error)]
 #[error("loading document `{target}` failed: {cause}")]
@@ -467,35 +628,47 @@ impl LoadError {
 ///     [`BTreeMap`](std::collection::BTreeMap) mapping IRIs to pre-loaded
 ///     documents. This way no network calls are performed and the loaded
 ///     content can be trusted.
+///   - [`StaticLoader`] serving documents embedded in the binary (e.g. with
+///     [`include_str`]). This requires neither network nor file system
+///     access.
 ///   - [`FsLoader`] that redirecting registered IRI prefixes to a local
 ///     directory on the file system. This also avoids network calls. The loaded
 ///     content can be trusted as long as the file system is trusted.
+///   - [`HttpLoader`] downloading the remote documents through any
+///     [`HttpTransport`], for targets bringing their own network stack.
 ///   - `ReqwestLoader` actually downloading the remote documents using the
 ///     [`reqwest`](https://crates.io/crates/reqwest) library.
-///     This requires the `reqwest` feature to be enabled.
-pub trait Loader {
+///     This requires the `reqwest` feature to be enabled (which implies
+///     `std`).
+///
+/// Implementations can define [`Loader::load`] as a regular `async fn`; the
+/// returned future is never boxed.
+///
+/// When the `send` feature is enabled, loaders must be `Sync` so that the
+/// futures they return are `Send`.
+pub trait Loader: MaybeSync {
 	/// Loads the document behind the given IRI, using the given vocabulary.
 	fn load_with<'a, V>(
 		&'a self,
 		vocabulary: &'a mut V,
 		url: V::Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<V::Iri>> + 'a>>
+	) -> impl Future<Output = LoadingResult<V::Iri>> + MaybeSend + 'a
 	where
-		V: IriVocabularyMut,
-		V::Iri: Clone + Eq + Hash,
+		V: IriVocabularyMut + MaybeSend,
+		V::Iri: Clone + Eq + Hash + MaybeSend,
 	{
-		Box::pin(async move {
+		async move {
 			let lexical_url = vocabulary.iri(&url).unwrap();
 			let document = self.load(lexical_url).await?;
 			Ok(document.map_iris(|i| vocabulary.insert_owned(i)))
-		})
+		}
 	}
 
 	/// Loads the document behind the given IRI.
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + 'a>>;
+	) -> impl Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + MaybeSend + 'a;
 }
 
 impl<'l, L: Loader> Loader for &'l L {
@@ -503,19 +676,19 @@ impl<'l, L: Loader> Loader for &'l L {
 		&'a self,
 		vocabulary: &'a mut V,
 		url: V::Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<V::Iri>> + 'a>>
+	) -> impl Future<Output = LoadingResult<V::Iri>> + MaybeSend + 'a
 	where
-		V: IriVocabularyMut,
-		V::Iri: Clone + Eq + Hash,
+		V: IriVocabularyMut + MaybeSend,
+		V::Iri: Clone + Eq + Hash + MaybeSend,
 	{
-		Box::pin(async move { L::load_with(self, vocabulary, url).await })
+		L::load_with(self, vocabulary, url)
 	}
 
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + 'a>> {
-		Box::pin(async move { L::load(self, url).await })
+	) -> impl Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + MaybeSend + 'a {
+		L::load(self, url)
 	}
 }
 
@@ -524,19 +697,19 @@ impl<'l, L: Loader> Loader for &'l mut L {
 		&'a self,
 		vocabulary: &'a mut V,
 		url: V::Iri,
-	) -> Pin<Box<dyn Future<Output = LoadingResult<V::Iri>> + 'a>>
+	) -> impl Future<Output = LoadingResult<V::Iri>> + MaybeSend + 'a
 	where
-		V: IriVocabularyMut,
-		V::Iri: Clone + Eq + Hash,
+		V: IriVocabularyMut + MaybeSend,
+		V::Iri: Clone + Eq + Hash + MaybeSend,
 	{
-		Box::pin(async move { L::load_with(self, vocabulary, url).await })
+		L::load_with(self, vocabulary, url)
 	}
 
 	fn load<'a>(
 		&'a self,
 		url: &'a Iri,
-	) -> Pin<Box<dyn Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + 'a>> {
-		Box::pin(async move { L::load(self, url).await })
+	) -> impl Future<Output = Result<RemoteDocument<IriBuf>, LoadError>> + MaybeSend + 'a {
+		L::load(self, url)
 	}
 }
 
@@ -0,0 +1,177 @@
+use super::Loader;
+use crate::{LoadError, LoadErrorCause, LoadingResult, MaybeSend, MaybeSync};
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use iref::{Iri, IriBuf};
+
+#[cfg(feature = "send")]
+type BoxedLoad<'a> = Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + Send + 'a>>;
+
+#[cfg(not(feature = "send"))]
+type BoxedLoad<'a> = Pin<Box<dyn Future<Output = LoadingResult<IriBuf>> + 'a>>;
+
+/// Object-safe version of [`Loader`], used to store loaders of different
+/// types.
+trait DynLoader: MaybeSync {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxedLoad<'a>;
+}
+
+impl<L: Loader> DynLoader for L {
+	fn load_dyn<'a>(&'a self, url: &'a Iri) -> BoxedLoad<'a> {
+		Box::pin(self.load(url))
+	}
+}
+
+/// IRI predicate guarding a [`MultiLoader`] delegate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+	/// Matches IRIs starting with the given prefix.
+	Prefix(IriBuf),
+
+	/// Matches IRIs with the given scheme (compared case-insensitively).
+	Scheme(String),
+}
+
+impl Predicate {
+	/// Checks if the given IRI matches this predicate.
+	pub fn matches(&self, url: &Iri) -> bool {
+		match self {
+			Self::Prefix(prefix) => url.as_str().starts_with(prefix.as_str()),
+			Self::Scheme(scheme) => url.scheme().as_str().eq_ignore_ascii_case(scheme),
+		}
+	}
+}
+
+struct Delegate {
+	predicate: Option<Predicate>,
+	loader: Box<dyn DynLoader>,
+}
+
+/// * [`MultiLoader`]: loads documents from a list of loaders, each one being
+///   tried in order until one succeeds.
+///
+/// This generalizes [`ChainLoader`](super::ChainLoader) to any number of
+/// loaders of any type. Each loader can be guarded by a [`Predicate`] so
+/// that it is only tried on the IRIs it can serve, for instance:
+///
+///   - a [`StaticLoader`](super::StaticLoader) for the contexts embedded
+///     in the application,
+///   - an [`FsLoader`](super::FsLoader) for IRIs starting with the prefix of
+///     a local mirror,
+///   - an HTTP loader for any `https` IRI.
+///
+/// If every loader fails (or none matches), the returned error lists every
+/// attempted loader with its error.
+#[derive(Default)]
+pub struct MultiLoader {
+	delegates: Vec<Delegate>,
+}
+
+impl MultiLoader {
+	/// Creates a new loader without any delegate.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a loader tried on every IRI.
+	pub fn push(&mut self, loader: impl 'static + Loader) {
+		self.delegates.push(Delegate {
+			predicate: None,
+			loader: Box::new(loader),
+		})
+	}
+
+	/// Adds a loader only tried on the IRIs matching the given predicate.
+	pub fn push_guarded(&mut self, predicate: Predicate, loader: impl 'static + Loader) {
+		self.delegates.push(Delegate {
+			predicate: Some(predicate),
+			loader: Box::new(loader),
+		})
+	}
+
+	/// Returns this loader with the given loader added.
+	///
+	/// See [`Self::push`].
+	pub fn with(mut self, loader: impl 'static + Loader) -> Self {
+		self.push(loader);
+		self
+	}
+
+	/// Returns this loader with the given guarded loader added.
+	///
+	/// See [`Self::push_guarded`].
+	pub fn with_guarded(mut self, predicate: Predicate, loader: impl 'static + Loader) -> Self {
+		self.push_guarded(predicate, loader);
+		self
+	}
+
+	/// Returns the number of delegate loaders.
+	pub fn len(&self) -> usize {
+		self.delegates.len()
+	}
+
+	/// Checks if there is no delegate loader.
+	pub fn is_empty(&self) -> bool {
+		self.delegates.is_empty()
+	}
+}
+
+impl Loader for MultiLoader {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			let mut attempts = Vec::new();
+
+			for (i, delegate) in self.delegates.iter().enumerate() {
+				if delegate.predicate.as_ref().is_some_and(|p| !p.matches(url)) {
+					continue;
+				}
+
+				match delegate.loader.load_dyn(url).await {
+					Ok(document) => return Ok(document),
+					Err(e) => attempts.push((i, e.cause)),
+				}
+			}
+
+			Err(LoadError::new(url.to_owned(), Error(attempts)))
+		}
+	}
+}
+
+/// Error listing every loader attempted by a [`MultiLoader`], by index, with
+/// the error it returned.
+///
+/// The list is empty if no loader matches the IRI.
+#[derive(Debug)]
+pub struct Error(pub Vec<(usize, LoadErrorCause)>);
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.0.is_empty() {
+			return write!(f, "no matching loader");
+		}
+
+		for (n, (i, e)) in self.0.iter().enumerate() {
+			if n > 0 {
+				write!(f, ", then ")?;
+			}
+
+			write!(f, "loader #{i}: {e}")?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for Error {}
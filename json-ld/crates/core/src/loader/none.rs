@@ -1,9 +1,8 @@
-use super::Loader;
+use super::{Loader, SyncLoader};
+use crate::MaybeSend;
 use crate::{LoadError, LoadingResult};
 use alloc::borrow::ToOwned;
-use alloc::boxed::Box;
 use core::future::Future;
-use core::pin::Pin;
 use iref::Iri;
 
 /// Dummy loader.
@@ -19,9 +18,15 @@ pub struct NoLoader;
 #[error("no loader")]
 pub struct CannotLoad;
 
+impl SyncLoader for NoLoader {
+	fn load_sync(&self, url: &Iri) -> LoadingResult {
+		Err(LoadError::new(url.to_owned(), CannotLoad))
+	}
+}
+
 impl Loader for NoLoader {
-	fn load<'a>(&'a self, url: &'a Iri) -> Pin<Box<dyn Future<Output = LoadingResult> + 'a>> {
-		Box::pin(async move { Err(LoadError::new(url.to_owned(), CannotLoad)) })
+	fn load<'a>(&'a self, url: &'a Iri) -> impl Future<Output = LoadingResult> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
 	}
 }
 
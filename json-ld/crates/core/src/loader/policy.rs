@@ -0,0 +1,489 @@
+use super::{Loader, SyncLoader};
+use crate::MaybeSend;
+use crate::{LoadError, LoadingResult};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use iref::{Iri, IriBuf};
+
+/// Loading security policy.
+///
+/// The policy is made of:
+///   - a list of allowed IRI prefixes (if empty, every IRI is allowed), see
+///     [`Self::allow_prefix`];
+///   - a list of denied hosts;
+///   - an `https`-only restriction;
+///   - a maximum number of loads per processing run.
+///
+/// It is enforced by a [`PolicyLoader`].
+#[derive(Debug, Default, Clone)]
+pub struct Policy {
+	allowed_prefixes: Vec<IriBuf>,
+	denied_hosts: Vec<String>,
+	https_only: bool,
+	max_loads: Option<usize>,
+}
+
+impl Policy {
+	/// Creates a new policy allowing everything.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Only allows IRIs under the given prefix (or any other allowed prefix).
+	///
+	/// An IRI is under the prefix if it has the same scheme, host and port,
+	/// and its path starts with the prefix path on a segment boundary. Hence
+	/// `https://example.org/contexts` allows
+	/// `https://example.org/contexts/v1`, but neither
+	/// `https://example.org/contexts-v1` nor
+	/// `https://example.org.evil.com/contexts`. IRIs with user information
+	/// are only allowed if the prefix has the same user information.
+	///
+	/// Paths are compared after decoding the percent-encoded unreserved
+	/// characters and removing the dot segments (as described by
+	/// [RFC 3986 section 5.2.4](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4)),
+	/// so `https://example.org/contexts/../admin` and
+	/// `https://example.org/contexts/%2e%2e/admin` are not allowed. IRIs whose
+	/// path contains an encoded `/` or `\` are never allowed, since servers
+	/// may decode them into segment separators.
+	pub fn allow_prefix(mut self, prefix: IriBuf) -> Self {
+		self.allowed_prefixes.push(prefix);
+		self
+	}
+
+	/// Denies any IRI with the given host.
+	///
+	/// Hosts are compared case-insensitively.
+	pub fn deny_host(mut self, host: impl Into<String>) -> Self {
+		self.denied_hosts.push(host.into());
+		self
+	}
+
+	/// Denies any IRI whose scheme is not `https`.
+	pub fn https_only(mut self) -> Self {
+		self.https_only = true;
+		self
+	}
+
+	/// Allows at most `max_loads` load attempts per processing run.
+	pub fn max_loads(mut self, max_loads: usize) -> Self {
+		self.max_loads = Some(max_loads);
+		self
+	}
+
+	/// Checks the given IRI against the policy, without counting it as a
+	/// load attempt.
+	pub fn check(&self, url: &Iri) -> Result<(), Blocked> {
+		if self.https_only && !url.scheme().as_str().eq_ignore_ascii_case("https") {
+			return Err(Blocked::Scheme);
+		}
+
+		if let Some(authority) = url.authority() {
+			let host = authority.host().as_str();
+			if self
+				.denied_hosts
+				.iter()
+				.any(|h| h.eq_ignore_ascii_case(host))
+			{
+				return Err(Blocked::Host);
+			}
+		}
+
+		if !self.allowed_prefixes.is_empty()
+			&& !self
+				.allowed_prefixes
+				.iter()
+				.any(|p| is_under_prefix(url, p))
+		{
+			return Err(Blocked::NotAllowed);
+		}
+
+		Ok(())
+	}
+}
+
+/// Checks if `url` is under `prefix`, comparing their scheme, authority and
+/// path.
+fn is_under_prefix(url: &Iri, prefix: &Iri) -> bool {
+	if !url
+		.scheme()
+		.as_str()
+		.eq_ignore_ascii_case(prefix.scheme().as_str())
+	{
+		return false;
+	}
+
+	match (url.authority(), prefix.authority()) {
+		(Some(a), Some(b)) => {
+			if a.user_info().map(|u| u.as_str()) != b.user_info().map(|u| u.as_str())
+				|| !a.host().as_str().eq_ignore_ascii_case(b.host().as_str())
+				|| a.port() != b.port()
+			{
+				return false;
+			}
+		}
+		(None, None) => (),
+		_ => return false,
+	}
+
+	let path = normalize_path(url.path().as_str());
+	if path.contains("%2F") || path.contains("%5C") {
+		return false;
+	}
+
+	let prefix = normalize_path(prefix.path().as_str());
+	let prefix = prefix.as_str();
+	match path.strip_prefix(prefix) {
+		Some(rest) => {
+			prefix.is_empty() || prefix.ends_with('/') || rest.is_empty() || rest.starts_with('/')
+		}
+		None => false,
+	}
+}
+
+/// Normalizes the given IRI path by decoding the percent-encoded unreserved
+/// characters, upper-casing the remaining percent-encodings, and removing
+/// the dot segments.
+fn normalize_path(path: &str) -> String {
+	let mut decoded = String::with_capacity(path.len());
+	let mut rest = path;
+	while let Some(i) = rest.find('%') {
+		decoded.push_str(&rest[..i]);
+		let escape = &rest[i..];
+		match escape
+			.get(1..3)
+			.filter(|h| h.bytes().all(|b| b.is_ascii_hexdigit()))
+		{
+			Some(hex) => {
+				let b = u8::from_str_radix(hex, 16).unwrap_or_default();
+				if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+					decoded.push(b as char)
+				} else {
+					decoded.push('%');
+					decoded.push_str(&hex.to_ascii_uppercase())
+				}
+
+				rest = &escape[3..]
+			}
+			None => {
+				decoded.push('%');
+				rest = &escape[1..]
+			}
+		}
+	}
+	decoded.push_str(rest);
+
+	remove_dot_segments(&decoded)
+}
+
+/// Removes the dot segments of the given path, following the algorithm of
+/// [RFC 3986 section 5.2.4](https://www.rfc-editor.org/rfc/rfc3986#section-5.2.4).
+fn remove_dot_segments(mut input: &str) -> String {
+	fn pop_segment(output: &mut String) {
+		let len = output.rfind('/').unwrap_or(0);
+		output.truncate(len)
+	}
+
+	let mut output = String::with_capacity(input.len());
+	while !input.is_empty() {
+		if let Some(rest) = input.strip_prefix("../") {
+			input = rest
+		} else if let Some(rest) = input.strip_prefix("./") {
+			input = rest
+		} else if input.starts_with("/./") {
+			input = &input[2..]
+		} else if input == "/." {
+			output.push('/');
+			break;
+		} else if input.starts_with("/../") {
+			input = &input[3..];
+			pop_segment(&mut output)
+		} else if input == "/.." {
+			pop_segment(&mut output);
+			output.push('/');
+			break;
+		} else if input == "." || input == ".." {
+			break;
+		} else {
+			let start = usize::from(input.starts_with('/'));
+			let end = input[start..]
+				.find('/')
+				.map(|i| i + start)
+				.unwrap_or(input.len());
+			output.push_str(&input[..end]);
+			input = &input[end..]
+		}
+	}
+
+	output
+}
+
+/// Loader wrapper enforcing a security [`Policy`].
+///
+/// Every IRI is checked against the policy before being passed to the inner
+/// loader. Blocked loads fail with a [`Blocked`] cause, without calling the
+/// inner loader. This protects servers expanding untrusted documents
+/// against Server-Side Request Forgery (SSRF), where a document imports
+/// contexts hosted on internal services.
+///
+/// The load counter used to enforce the maximum number of loads is reset with
+/// [`Self::reset`] at the beginning of each processing run.
+///
+/// Redirections followed by the inner loader are not checked: the inner
+/// loader should be configured to not follow redirections.
+pub struct PolicyLoader<L> {
+	inner: L,
+	policy: Policy,
+	loads: AtomicUsize,
+}
+
+impl<L> PolicyLoader<L> {
+	/// Wraps the given loader with a policy allowing everything.
+	pub fn new(inner: L) -> Self {
+		Self::with_policy(inner, Policy::new())
+	}
+
+	/// Wraps the given loader with the given policy.
+	pub fn with_policy(inner: L, policy: Policy) -> Self {
+		Self {
+			inner,
+			policy,
+			loads: AtomicUsize::new(0),
+		}
+	}
+
+	/// Only allows IRIs under the given prefix (or any other allowed prefix).
+	///
+	/// See [`Policy::allow_prefix`].
+	pub fn allow_prefix(mut self, prefix: IriBuf) -> Self {
+		self.policy = self.policy.allow_prefix(prefix);
+		self
+	}
+
+	/// Denies any IRI with the given host.
+	///
+	/// Hosts are compared case-insensitively.
+	pub fn deny_host(mut self, host: impl Into<String>) -> Self {
+		self.policy = self.policy.deny_host(host);
+		self
+	}
+
+	/// Denies any IRI whose scheme is not `https`.
+	pub fn https_only(mut self) -> Self {
+		self.policy = self.policy.https_only();
+		self
+	}
+
+	/// Allows at most `max_loads` load attempts, until the next
+	/// [`Self::reset`].
+	pub fn max_loads(mut self, max_loads: usize) -> Self {
+		self.policy = self.policy.max_loads(max_loads);
+		self
+	}
+
+	/// Returns the enforced policy.
+	pub fn policy(&self) -> &Policy {
+		&self.policy
+	}
+
+	/// Returns the number of load attempts since the last reset.
+	pub fn loads(&self) -> usize {
+		self.loads.load(Ordering::Relaxed)
+	}
+
+	/// Resets the load counter, to start a new processing run.
+	pub fn reset(&self) {
+		self.loads.store(0, Ordering::Relaxed)
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Checks the given IRI against the policy, without counting it as a
+	/// load attempt.
+	pub fn check(&self, url: &Iri) -> Result<(), Blocked> {
+		self.policy.check(url)
+	}
+
+	fn enforce(&self, url: &Iri) -> Result<(), LoadError> {
+		self.check(url)
+			.map_err(|e| LoadError::new(url.to_owned(), e))?;
+
+		let loads = self.loads.fetch_add(1, Ordering::Relaxed);
+		match self.policy.max_loads {
+			Some(max_loads) if loads >= max_loads => Err(LoadError::new(
+				url.to_owned(),
+				Blocked::TooManyLoads(max_loads),
+			)),
+			_ => Ok(()),
+		}
+	}
+}
+
+/// Error raised by [`PolicyLoader`] when a load is blocked by the policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum Blocked {
+	/// The IRI is not under any allowed prefix.
+	#[error("IRI not allowed by the loading policy")]
+	NotAllowed,
+
+	/// The IRI host is denied.
+	#[error("host denied by the loading policy")]
+	Host,
+
+	/// The IRI scheme is not `https`.
+	#[error("non-https IRI denied by the loading policy")]
+	Scheme,
+
+	/// The maximum number of loads is reached.
+	#[error("too many loaded documents (limit is {0})")]
+	TooManyLoads(usize),
+}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for Blocked {}
+
+impl<L: Loader> Loader for PolicyLoader<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			self.enforce(url)?;
+			self.inner.load(url).await
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for PolicyLoader<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		self.enforce(url)?;
+		self.inner.load_sync(url)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use static_iref::iri;
+
+	fn policy() -> Policy {
+		Policy::new().allow_prefix(iri!("https://trusted.example/contexts").to_owned())
+	}
+
+	#[test]
+	fn allowed_prefix() {
+		let policy = policy();
+		assert!(policy
+			.check(iri!("https://trusted.example/contexts"))
+			.is_ok());
+		assert!(policy
+			.check(iri!("https://trusted.example/contexts/v1"))
+			.is_ok());
+		assert!(policy
+			.check(iri!("HTTPS://TRUSTED.EXAMPLE/contexts/v1"))
+			.is_ok());
+	}
+
+	#[test]
+	fn denied_path_suffix() {
+		let policy = policy();
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts-evil")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/other")),
+			Err(Blocked::NotAllowed)
+		);
+	}
+
+	#[test]
+	fn denied_dot_segments() {
+		let policy = policy();
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/../admin")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/v1/../../admin")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/..")),
+			Err(Blocked::NotAllowed)
+		);
+		assert!(policy
+			.check(iri!("https://trusted.example/contexts/./v1"))
+			.is_ok());
+		assert!(policy
+			.check(iri!("https://trusted.example/other/../contexts/v1"))
+			.is_ok());
+	}
+
+	#[test]
+	fn denied_encoded_dot_segments() {
+		let policy = policy();
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/%2e%2e/admin")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/.%2E/admin")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example/contexts/..%2fadmin")),
+			Err(Blocked::NotAllowed)
+		);
+		assert!(policy
+			.check(iri!("https://trusted.example/%63ontexts/v1"))
+			.is_ok());
+	}
+
+	#[test]
+	fn remove_dot_segments() {
+		assert_eq!(super::remove_dot_segments("/a/b/c/./../../g"), "/a/g");
+		assert_eq!(super::remove_dot_segments("mid/content=5/../6"), "mid/6");
+		assert_eq!(super::remove_dot_segments("/a/.."), "/");
+		assert_eq!(super::remove_dot_segments("/../a"), "/a");
+	}
+
+	#[test]
+	fn denied_user_info() {
+		let policy = Policy::new().allow_prefix(iri!("https://trusted.example").to_owned());
+		assert!(policy
+			.check(iri!("https://trusted.example/context"))
+			.is_ok());
+		assert_eq!(
+			policy.check(iri!("https://trusted.example@evil.com/")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://user@trusted.example/")),
+			Err(Blocked::NotAllowed)
+		);
+	}
+
+	#[test]
+	fn denied_host_suffix() {
+		let policy = Policy::new().allow_prefix(iri!("https://trusted.example").to_owned());
+		assert_eq!(
+			policy.check(iri!("https://trusted.example.evil.com/")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("https://trusted.example:8080/")),
+			Err(Blocked::NotAllowed)
+		);
+		assert_eq!(
+			policy.check(iri!("http://trusted.example/")),
+			Err(Blocked::NotAllowed)
+		);
+	}
+}
@@ -0,0 +1,137 @@
+//! Remote context prefetching.
+use super::{Loader, RemoteDocument};
+use crate::LoadError;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use hashbrown::{HashMap, HashSet};
+use iref::{Iri, IriBuf, IriRef};
+
+/// Collects the IRIs of the remote contexts referenced by the given JSON
+/// document.
+///
+/// Every `@context` entry of the document is visited, including nested
+/// node objects and scoped contexts defined in term definitions, as well as
+/// `@import` entries of context definitions. Relative IRI references are
+/// resolved against `base`, and ignored if no base is given.
+///
+/// The returned IRIs are deduplicated and listed in the order they are first
+/// found.
+pub fn context_iris(document: &json_syntax::Value, base: Option<&Iri>) -> Vec<IriBuf> {
+	let mut result = Vec::new();
+	let mut visited = HashSet::new();
+	collect_context_iris(document, base, false, &mut visited, &mut result);
+	result
+}
+
+fn collect_context_iris(
+	value: &json_syntax::Value,
+	base: Option<&Iri>,
+	in_context: bool,
+	visited: &mut HashSet<IriBuf>,
+	result: &mut Vec<IriBuf>,
+) {
+	match value {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				collect_context_iris(item, base, in_context, visited, result)
+			}
+		}
+		json_syntax::Value::Object(object) => {
+			for entry in object.iter() {
+				match entry.key.as_str() {
+					"@context" => collect_context_value(&entry.value, base, visited, result),
+					"@import" if in_context => {
+						collect_context_reference(&entry.value, base, visited, result)
+					}
+					_ => collect_context_iris(&entry.value, base, in_context, visited, result),
+				}
+			}
+		}
+		_ => (),
+	}
+}
+
+/// Collects the IRIs referenced by the value of a `@context` entry.
+fn collect_context_value(
+	value: &json_syntax::Value,
+	base: Option<&Iri>,
+	visited: &mut HashSet<IriBuf>,
+	result: &mut Vec<IriBuf>,
+) {
+	match value {
+		json_syntax::Value::Array(items) => {
+			for item in items {
+				collect_context_value(item, base, visited, result)
+			}
+		}
+		json_syntax::Value::String(_) => collect_context_reference(value, base, visited, result),
+		json_syntax::Value::Object(_) => collect_context_iris(value, base, true, visited, result),
+		_ => (),
+	}
+}
+
+/// Collects the IRI of a remote context reference.
+fn collect_context_reference(
+	value: &json_syntax::Value,
+	base: Option<&Iri>,
+	visited: &mut HashSet<IriBuf>,
+	result: &mut Vec<IriBuf>,
+) {
+	if let Some(reference) = value.as_str().and_then(|s| IriRef::new(s).ok()) {
+		let iri = match reference.as_iri() {
+			Some(iri) => iri.to_owned(),
+			None => match base {
+				Some(base) => reference.resolved(base),
+				None => return,
+			},
+		};
+
+		if visited.insert(iri.clone()) {
+			result.push(iri)
+		}
+	}
+}
+
+/// Loads every remote context referenced by the given JSON document.
+///
+/// The contexts found by [`context_iris`] are loaded concurrently using the
+/// given `loader`. The loaded contexts are themselves scanned for other
+/// remote contexts, which are loaded in turn, until every referenced context
+/// is loaded.
+///
+/// The returned map can then be used as a [`Loader`] to process the
+/// document without any further network access, the loading latency being
+/// paid once, in parallel.
+pub async fn prefetch_contexts(
+	loader: &impl Loader,
+	document: &json_syntax::Value,
+	base: Option<&Iri>,
+) -> Result<HashMap<IriBuf, RemoteDocument>, LoadError> {
+	let mut result: HashMap<IriBuf, RemoteDocument> = HashMap::new();
+	let mut pending = context_iris(document, base);
+
+	while !pending.is_empty() {
+		let loaded =
+			futures::future::try_join_all(pending.iter().map(|iri| loader.load(iri))).await?;
+
+		let mut next = Vec::new();
+		for (iri, remote) in pending.into_iter().zip(loaded) {
+			let remote_base = remote.url().cloned().unwrap_or_else(|| iri.clone());
+			for context_iri in context_iris(remote.document(), Some(&remote_base)) {
+				if context_iri != iri
+					&& !result.contains_key(&context_iri)
+					&& !next.contains(&context_iri)
+				{
+					next.push(context_iri)
+				}
+			}
+
+			result.insert(iri, remote);
+		}
+
+		next.retain(|iri| !result.contains_key(iri));
+		pending = next;
+	}
+
+	Ok(result)
+}
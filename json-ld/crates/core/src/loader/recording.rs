@@ -0,0 +1,259 @@
+//! Record/replay loaders for deterministic tests.
+use super::bundle::{document_from_json, document_to_json, optional_iri, unique, BundleError};
+use super::{map::EntryNotFound, Loader, RemoteDocument, SharedCache, SyncLoader};
+use crate::{LoadError, LoadingResult, MaybeSend};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::future::Future;
+use iref::{Iri, IriBuf};
+use json_syntax::Parse;
+
+/// Current replay format version.
+pub const REPLAY_VERSION: u64 = 1;
+
+/// Recorded load.
+#[derive(Debug, Clone)]
+pub enum Record {
+	/// The document was successfully loaded.
+	Loaded(IriBuf, RemoteDocument),
+
+	/// The load failed with the given error message.
+	Failed(IriBuf, String),
+}
+
+impl Record {
+	/// Returns the loaded IRI.
+	pub fn iri(&self) -> &Iri {
+		match self {
+			Self::Loaded(iri, _) | Self::Failed(iri, _) => iri,
+		}
+	}
+}
+
+/// Loader wrapper recording every load made through the inner loader.
+///
+/// Once the run is over, the recorded loads can be turned into a [`Replay`]
+/// loader, serialized and replayed later without the inner loader. This
+/// allows writing deterministic integration tests against documents
+/// referencing live contexts.
+pub struct Recording<L> {
+	inner: L,
+	records: SharedCache<Vec<Record>>,
+}
+
+impl<L> Recording<L> {
+	/// Wraps the given loader.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			records: SharedCache::default(),
+		}
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Returns the loads recorded so far, in order.
+	pub fn records(&self) -> Vec<Record> {
+		self.records.with(|records| records.clone())
+	}
+
+	/// Returns a replay of the loads recorded so far.
+	pub fn replay(&self) -> Replay {
+		self.records
+			.with(|records| records.iter().cloned().collect())
+	}
+
+	/// Turns the recorded loads into a replay.
+	pub fn into_replay(self) -> Replay {
+		self.records.into_inner().into_iter().collect()
+	}
+
+	fn record(&self, url: &Iri, result: &LoadingResult<IriBuf>) {
+		let record = match result {
+			Ok(document) => Record::Loaded(url.to_owned(), document.clone()),
+			Err(e) => Record::Failed(url.to_owned(), e.cause.to_string()),
+		};
+
+		self.records.with(|records| records.push(record))
+	}
+}
+
+impl<L: Loader> Loader for Recording<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			let result = self.inner.load(url).await;
+			self.record(url, &result);
+			result
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for Recording<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		let result = self.inner.load_sync(url);
+		self.record(url, &result);
+		result
+	}
+}
+
+/// Loader replaying recorded loads.
+///
+/// Each IRI is answered with the last recorded load of this IRI, including
+/// failures (with a [`ReplayedError`]). IRIs that were never recorded fail
+/// with [`EntryNotFound`].
+#[derive(Debug, Default, Clone)]
+pub struct Replay {
+	records: Vec<Record>,
+	index: BTreeMap<IriBuf, usize>,
+}
+
+impl Replay {
+	/// Creates an empty replay.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a record to the replay.
+	pub fn push(&mut self, record: Record) {
+		self.index
+			.insert(record.iri().to_owned(), self.records.len());
+		self.records.push(record)
+	}
+
+	/// Returns the records of the replay, in order.
+	pub fn records(&self) -> &[Record] {
+		&self.records
+	}
+
+	/// Serializes the replay into JSON.
+	///
+	/// Loaded documents use the same format as the entries of a
+	/// [`Bundle`](super::Bundle).
+	pub fn to_json(&self) -> json_syntax::Value {
+		let records = self
+			.records
+			.iter()
+			.map(|record| {
+				let entry = match record {
+					Record::Loaded(iri, document) => document_to_json(iri, document),
+					Record::Failed(iri, error) => {
+						let mut entry = json_syntax::Object::new();
+						entry.insert(
+							"iri".into(),
+							json_syntax::Value::String(iri.as_str().into()),
+						);
+						entry.insert(
+							"error".into(),
+							json_syntax::Value::String(error.as_str().into()),
+						);
+						entry
+					}
+				};
+
+				json_syntax::Value::Object(entry)
+			})
+			.collect();
+
+		let mut object = json_syntax::Object::new();
+		object.insert("version".into(), REPLAY_VERSION.into());
+		object.insert("records".into(), json_syntax::Value::Array(records));
+		json_syntax::Value::Object(object)
+	}
+
+	/// Deserializes a replay from JSON.
+	pub fn from_json(value: &json_syntax::Value) -> Result<Self, BundleError> {
+		let object = value
+			.as_object()
+			.ok_or(BundleError::InvalidEntry("replay"))?;
+
+		let version = unique(object, "version")?
+			.and_then(|v| v.as_number())
+			.and_then(|n| n.as_u64())
+			.ok_or(BundleError::InvalidEntry("version"))?;
+
+		if version != REPLAY_VERSION {
+			return Err(BundleError::UnsupportedVersion);
+		}
+
+		let mut result = Self::new();
+		for entry in unique(object, "records")?
+			.and_then(json_syntax::Value::as_array)
+			.ok_or(BundleError::InvalidEntry("records"))?
+		{
+			let entry = entry
+				.as_object()
+				.ok_or(BundleError::InvalidEntry("records"))?;
+
+			let record = match unique(entry, "error")? {
+				Some(error) => {
+					let iri =
+						optional_iri(entry, "iri")?.ok_or(BundleError::InvalidEntry("iri"))?;
+					let error = error.as_str().ok_or(BundleError::InvalidEntry("error"))?;
+					Record::Failed(iri, error.to_owned())
+				}
+				None => {
+					let (iri, document) = document_from_json(entry)?;
+					Record::Loaded(iri, document)
+				}
+			};
+
+			result.push(record);
+		}
+
+		Ok(result)
+	}
+
+	/// Parses a replay serialized with [`Self::to_json`].
+	pub fn parse(content: &str) -> Result<Self, BundleError> {
+		let (value, _) = json_syntax::Value::parse_str(content).map_err(BundleError::Parse)?;
+		Self::from_json(&value)
+	}
+}
+
+impl FromIterator<Record> for Replay {
+	fn from_iter<T: IntoIterator<Item = Record>>(iter: T) -> Self {
+		let mut result = Self::new();
+		for record in iter {
+			result.push(record)
+		}
+
+		result
+	}
+}
+
+/// Error replayed by a [`Replay`] loader.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{0}")]
+pub struct ReplayedError(pub String);
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for ReplayedError {}
+
+impl SyncLoader for Replay {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.index.get(url).map(|i| &self.records[*i]) {
+			Some(Record::Loaded(_, document)) => Ok(document.clone()),
+			Some(Record::Failed(_, error)) => {
+				Err(LoadError::new(url.to_owned(), ReplayedError(error.clone())))
+			}
+			None => Err(LoadError::new(url.to_owned(), EntryNotFound)),
+		}
+	}
+}
+
+impl Loader for Replay {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.load_sync(url))
+	}
+}
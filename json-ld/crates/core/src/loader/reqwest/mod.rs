@@ -3,23 +3,17 @@ use crate::LoadError;
 use crate::LoadingResult;
 use crate::Profile;
 
-use super::{Loader, RemoteDocument};
-use hashbrown::HashSet;
+use super::http::{accept_header, alternate_link, context_link, profiles, ContentType};
+use super::{CacheMetadata, Loader, RemoteDocument};
 use iref::{Iri, IriBuf};
 use json_syntax::Parse;
 use reqwest::{
-	header::{ACCEPT, CONTENT_TYPE, LINK},
+	header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE, ETAG, LAST_MODIFIED, LINK},
 	StatusCode,
 };
 use reqwest_middleware::ClientWithMiddleware;
 use std::string::FromUtf8Error;
 
-mod content_type;
-mod link;
-
-use content_type::*;
-use link::*;
-
 /// Loader options.
 pub struct Options {
 	/// One or more IRIs to use in the request as a profile parameter.
@@ -38,6 +32,13 @@ pub struct Options {
 	/// [`client`](Self::client).
 	pub max_redirections: usize,
 
+	/// Maximum size, in bytes, of a loaded document.
+	///
+	/// Documents announcing or having a larger body are rejected before being
+	/// parsed. The body is downloaded incrementally, so that no more than
+	/// this limit is ever buffered. Defaults to `None`, meaning no limit.
+	pub max_document_bytes: Option<usize>,
+
 	/// HTTP client.
 	pub client: ClientWithMiddleware,
 }
@@ -47,6 +48,7 @@ impl Default for Options {
 		Self {
 			request_profile: Vec::new(),
 			max_redirections: 8,
+			max_document_bytes: None,
 			client: reqwest_middleware::ClientBuilder::new(reqwest::Client::default()).build(),
 		}
 	}
@@ -70,6 +72,9 @@ pub enum Error {
 	#[error("too many redirections")]
 	TooManyRedirections,
 
+	#[error("document too large")]
+	DocumentTooLarge,
+
 	#[error("JSON parse error: {0}")]
 	Parse(json_syntax::parse::Error<std::io::Error>),
 }
@@ -78,7 +83,11 @@ pub enum Error {
 ///
 /// Only works with the [`tokio`](https://tokio.rs/) runtime.
 ///
-/// The loader will follow indirections and `Link` headers.
+/// The loader will follow indirections and `Link` headers. Responses are
+/// accepted if their media type is `application/json` or has a `+json`
+/// suffix. Other responses are only accepted if they provide an `alternate`
+/// `Link` header pointing to an `application/ld+json` document, which is
+/// then loaded instead.
 ///
 /// Loaded documents are not cached: a new network query is made each time
 /// an URL is loaded even if it has already been queried before.
@@ -101,31 +110,9 @@ impl ReqwestLoader {
 
 	/// Creates a new leader with the given options.
 	pub fn new_using(options: Options) -> Self {
-		let mut json_ld_params = String::new();
-
-		if !options.request_profile.is_empty() {
-			json_ld_params.push_str("; profile=");
-
-			if options.request_profile.len() > 1 {
-				json_ld_params.push('"');
-			}
-
-			for (i, p) in options.request_profile.iter().enumerate() {
-				if i > 0 {
-					json_ld_params.push(' ');
-				}
-
-				json_ld_params.push_str(p.iri().as_str());
-			}
-
-			if options.request_profile.len() > 1 {
-				json_ld_params.push('"');
-			}
-		}
-
 		Self {
+			accept_header: accept_header(&options.request_profile),
 			options,
-			accept_header: format!("application/ld+json{json_ld_params}, application/json"),
 		}
 	}
 }
@@ -158,7 +145,7 @@ impl Loader for ReqwestLoader {
 				.get(url.as_str())
 				.header(ACCEPT, &self.accept_header);
 
-			let response = request
+			let mut response = request
 				.send()
 				.await
 				.map_err(|e| LoadError::new(url.clone(), e))?;
@@ -169,72 +156,78 @@ impl Loader for ReqwestLoader {
 						.headers()
 						.get_all(CONTENT_TYPE)
 						.into_iter()
-						.filter_map(ContentType::new);
+						.filter_map(|v| ContentType::new(v.as_bytes()));
 
 					match content_types.find(ContentType::is_json_ld) {
 						Some(content_type) => {
-							let mut context_url = None;
-							if *content_type.media_type() != "application/ld+json" {
-								for link in response.headers().get_all(LINK).into_iter() {
-									if let Some(link) = Link::new(link) {
-										if link.rel()
-											== Some(b"http://www.w3.org/ns/json-ld#context")
-										{
-											if context_url.is_some() {
-												return Err(LoadError::new(
-													url,
-													Error::MultipleContextLinkHeaders,
-												));
-											}
-
-											context_url = Some(link.href().resolved(&url));
-										}
-									}
-								}
-							}
-
-							let mut profile = HashSet::new();
-							for p in content_type
-								.profile()
-								.into_iter()
-								.flat_map(|p| p.split(|b| *b == b' '))
+							let context_url = if *content_type.media_type() != "application/ld+json"
 							{
-								if let Ok(p) = std::str::from_utf8(p) {
-									if let Ok(iri) = Iri::new(p) {
-										profile.insert(Profile::new(iri));
-									}
-								}
+								let links = response.headers().get_all(LINK).into_iter();
+								context_link(&url, links.map(|v| v.as_bytes())).map_err(|_| {
+									LoadError::new(url.clone(), Error::MultipleContextLinkHeaders)
+								})?
+							} else {
+								None
+							};
+
+							let profile = profiles(&content_type);
+
+							let headers = response.headers();
+							let cache = CacheMetadata::from_headers(
+								headers.get(ETAG).map(|v| v.as_bytes()),
+								headers.get(LAST_MODIFIED).map(|v| v.as_bytes()),
+								headers.get_all(CACHE_CONTROL).iter().map(|v| v.as_bytes()),
+							);
+
+							let too_large = |len: usize| {
+								self.options.max_document_bytes.is_some_and(|max| len > max)
+							};
+
+							if response
+								.content_length()
+								.is_some_and(|len| too_large(len as usize))
+							{
+								return Err(LoadError::new(url, Error::DocumentTooLarge));
 							}
 
-							let bytes = response.bytes().await.map_err(|e| {
+							// Read the body chunk by chunk so that a document
+							// exceeding the limit is rejected before being
+							// entirely downloaded.
+							let mut bytes = Vec::new();
+							while let Some(chunk) = response.chunk().await.map_err(|e| {
 								LoadError::new(url.clone(), Error::Reqwest(e.into()))
-							})?;
+							})? {
+								if too_large(bytes.len() + chunk.len()) {
+									return Err(LoadError::new(url, Error::DocumentTooLarge));
+								}
+
+								bytes.extend_from_slice(&chunk);
+							}
 
 							let decoder = utf8_decode::Decoder::new(bytes.iter().copied());
 							let (document, _) = json_syntax::Value::parse_utf8(decoder)
 								.map_err(|e| LoadError::new(url.clone(), Error::Parse(e)))?;
 
-							break Ok(RemoteDocument::new_full(
+							let mut document = RemoteDocument::new_full(
 								Some(url),
 								Some(content_type.into_media_type()),
 								context_url,
 								profile,
 								document,
-							));
+							);
+
+							document.set_cache(cache);
+							break Ok(document);
 						}
 						None => {
 							log::debug!("no valid media type found");
-							for link in response.headers().get_all(LINK).into_iter() {
-								if let Some(link) = Link::new(link) {
-									if link.rel() == Some(b"alternate")
-										&& link.type_() == Some(b"application/ld+json")
-									{
-										log::debug!("link found");
-										url = link.href().resolved(&url);
-										redirection_number += 1;
-										continue 'next_url;
-									}
-								}
+							let links = response.headers().get_all(LINK).into_iter();
+							if let Some(alternate) =
+								alternate_link(&url, links.map(|v| v.as_bytes()))
+							{
+								url = alternate;
+								redirection_number += 1;
+								continue 'next_url;
 							}
 
 							break Err(LoadError::new(url, Error::InvalidContentType));
@@ -0,0 +1,102 @@
+use super::{Loader, SyncLoader};
+use crate::MaybeSend;
+use crate::{LoadError, LoadingResult, RemoteDocument};
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::vec::Vec;
+use core::future::Future;
+use iref::{Iri, IriBuf};
+
+/// Loader wrapper rewriting IRIs before delegating to an inner loader.
+///
+/// Each rewrite rule maps an IRI prefix to another, for instance to
+/// redirect `https://w3id.org/` to a mirror or to a local IRI mounted on a
+/// [`FsLoader`](super::FsLoader). Rules are tried in the order they were
+/// added and only the first matching rule is applied.
+///
+/// The loaded [`RemoteDocument`] keeps the original IRI as URL (even if the
+/// inner loader followed redirections), so that relative IRI references in
+/// the document are resolved as if it was loaded from its original
+/// location.
+pub struct RewriteLoader<L> {
+	inner: L,
+	rules: Vec<(IriBuf, IriBuf)>,
+}
+
+impl<L> RewriteLoader<L> {
+	/// Wraps the given loader, without any rewrite rule.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			rules: Vec::new(),
+		}
+	}
+
+	/// Adds a rule rewriting the IRIs starting with `prefix` by replacing
+	/// this prefix with `replacement`.
+	pub fn add_rule(&mut self, prefix: IriBuf, replacement: IriBuf) {
+		self.rules.push((prefix, replacement))
+	}
+
+	/// Returns this loader with the given rewrite rule added.
+	///
+	/// See [`Self::add_rule`].
+	pub fn with_rule(mut self, prefix: IriBuf, replacement: IriBuf) -> Self {
+		self.add_rule(prefix, replacement);
+		self
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Rewrites the given IRI using the first matching rule, if any.
+	pub fn rewrite(&self, url: &Iri) -> Option<IriBuf> {
+		self.rules.iter().find_map(|(prefix, replacement)| {
+			let suffix = url.as_str().strip_prefix(prefix.as_str())?;
+			IriBuf::new(format!("{replacement}{suffix}")).ok()
+		})
+	}
+}
+
+impl<L: Loader> Loader for RewriteLoader<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			match self.rewrite(url) {
+				Some(target) => match self.inner.load(&target).await {
+					Ok(document) => Ok(restore_url(url, document)),
+					Err(e) => Err(LoadError {
+						target: url.to_owned(),
+						cause: e.cause,
+					}),
+				},
+				None => self.inner.load(url).await,
+			}
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for RewriteLoader<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		match self.rewrite(url) {
+			Some(target) => match self.inner.load_sync(&target) {
+				Ok(document) => Ok(restore_url(url, document)),
+				Err(e) => Err(LoadError {
+					target: url.to_owned(),
+					cause: e.cause,
+				}),
+			},
+			None => self.inner.load_sync(url),
+		}
+	}
+}
+
+/// Sets the original `url` of a document loaded from a rewritten IRI.
+fn restore_url(url: &Iri, mut document: RemoteDocument) -> RemoteDocument {
+	document.set_url(Some(url.to_owned()));
+	document
+}
@@ -0,0 +1,54 @@
+use super::{Loader, LoadingResult};
+use crate::{MaybeSend, MaybeSync};
+use core::future::Future;
+use iref::{Iri, IriBuf};
+
+/// Synchronous document loader.
+///
+/// Blocking counterpart of [`Loader`], for targets without an async executor.
+/// Every synchronous loader can be used where a [`Loader`] is expected by
+/// wrapping it into a [`SyncLoaderAdapter`].
+pub trait SyncLoader {
+	/// Loads the document behind the given IRI.
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf>;
+}
+
+impl<'l, L: SyncLoader> SyncLoader for &'l L {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		L::load_sync(self, url)
+	}
+}
+
+impl<'l, L: SyncLoader> SyncLoader for &'l mut L {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		L::load_sync(self, url)
+	}
+}
+
+/// Asynchronous [`Loader`] backed by a [`SyncLoader`].
+///
+/// The futures returned by this loader are always ready the first time they
+/// are polled, which makes it possible to drive the asynchronous algorithms
+/// to completion using [`block_on`](crate::future::block_on).
+pub struct SyncLoaderAdapter<L>(pub L);
+
+impl<L> SyncLoaderAdapter<L> {
+	/// Wraps the given synchronous loader.
+	pub fn new(loader: L) -> Self {
+		Self(loader)
+	}
+
+	/// Returns the inner synchronous loader.
+	pub fn into_inner(self) -> L {
+		self.0
+	}
+}
+
+impl<L: SyncLoader + MaybeSync> Loader for SyncLoaderAdapter<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		core::future::ready(self.0.load_sync(url))
+	}
+}
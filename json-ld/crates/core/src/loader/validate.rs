@@ -0,0 +1,97 @@
+use super::{ExtractContextError, Loader, SyncLoader};
+use crate::{LoadError, LoadingResult, MaybeSend, RemoteDocument};
+use alloc::borrow::ToOwned;
+use core::future::Future;
+use iref::{Iri, IriBuf};
+use json_ld_syntax::TryFromJson;
+
+/// Loader wrapper validating the context of loaded documents.
+///
+/// The `@context` entry of every loaded document is checked against the
+/// JSON-LD context grammar before the document is returned. Invalid contexts
+/// fail with an [`InvalidContextDocument`] error pointing at the loaded IRI,
+/// instead of failing later, deep inside the context processing algorithm.
+///
+/// By default, documents without `@context` entry are returned as is. Use
+/// [`Self::require_context`] when the loader is only used to load remote
+/// contexts.
+pub struct ValidatingLoader<L> {
+	inner: L,
+	require_context: bool,
+}
+
+impl<L> ValidatingLoader<L> {
+	/// Wraps the given loader.
+	pub fn new(inner: L) -> Self {
+		Self {
+			inner,
+			require_context: false,
+		}
+	}
+
+	/// Rejects loaded documents without `@context` entry.
+	pub fn require_context(mut self) -> Self {
+		self.require_context = true;
+		self
+	}
+
+	/// Returns the inner loader.
+	pub fn into_inner(self) -> L {
+		self.inner
+	}
+
+	/// Validates the context of the given document.
+	pub fn validate(&self, document: &json_syntax::Value) -> Result<(), ExtractContextError> {
+		match document {
+			json_syntax::Value::Object(o) => match o
+				.get_unique("@context")
+				.map_err(|_| ExtractContextError::DuplicateContext)?
+			{
+				Some(context) => {
+					json_ld_syntax::context::Context::try_from_json(context.clone())
+						.map_err(ExtractContextError::Syntax)?;
+					Ok(())
+				}
+				None if self.require_context => Err(ExtractContextError::NoContext),
+				None => Ok(()),
+			},
+			other if self.require_context => Err(ExtractContextError::Unexpected(other.kind())),
+			_ => Ok(()),
+		}
+	}
+
+	fn check(&self, url: &Iri, document: RemoteDocument) -> LoadingResult<IriBuf> {
+		match self.validate(document.document()) {
+			Ok(()) => Ok(document),
+			Err(e) => Err(LoadError::new(url.to_owned(), InvalidContextDocument(e))),
+		}
+	}
+}
+
+/// Error raised by [`ValidatingLoader`] when a loaded document has an invalid
+/// context.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid context document: {0}")]
+pub struct InvalidContextDocument(pub ExtractContextError);
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for InvalidContextDocument {}
+
+impl<L: Loader> Loader for ValidatingLoader<L> {
+	fn load<'a>(
+		&'a self,
+		url: &'a Iri,
+	) -> impl Future<Output = LoadingResult<IriBuf>> + MaybeSend + 'a {
+		async move {
+			let document = self.inner.load(url).await?;
+			self.check(url, document)
+		}
+	}
+}
+
+impl<L: SyncLoader> SyncLoader for ValidatingLoader<L> {
+	fn load_sync(&self, url: &Iri) -> LoadingResult<IriBuf> {
+		let document = self.inner.load_sync(url)?;
+		self.check(url, document)
+	}
+}
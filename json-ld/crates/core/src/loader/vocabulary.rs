@@ -0,0 +1,107 @@
+//! Vocabulary-aware loaders.
+use super::{map::EntryNotFound, Loader, RemoteDocument, RemoteDocumentReference};
+use crate::{LoadError, LoadingResult, MaybeSend, MaybeSync};
+use alloc::borrow::ToOwned;
+use alloc::collections::BTreeMap;
+use core::future::Future;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
+
+/// Loader working directly with the IRIs of a vocabulary.
+///
+/// Unlike [`Loader`], which loads documents by lexical IRI and converts the
+/// result IRIs with [`Loader::load_with`], implementations of this trait
+/// load documents by vocabulary IRI and return a [`RemoteDocument`] using
+/// vocabulary IRIs, without any conversion.
+///
+/// Any [`Loader`] can be used as a [`LoaderWith`] through the [`Lexical`]
+/// adapter.
+pub trait LoaderWith<V: IriVocabulary>: MaybeSync {
+	/// Loads the document behind the given vocabulary IRI.
+	fn load_iri<'a>(
+		&'a self,
+		vocabulary: &'a mut V,
+		url: V::Iri,
+	) -> impl Future<Output = LoadingResult<V::Iri>> + MaybeSend + 'a;
+}
+
+/// Adapter using a lexical [`Loader`] as a [`LoaderWith`].
+///
+/// Each load resolves the vocabulary IRI and converts the IRIs of the
+/// loaded document, as [`Loader::load_with`] does.
+pub struct Lexical<L>(pub L);
+
+impl<V, L> LoaderWith<V> for Lexical<L>
+where
+	V: IriVocabularyMut + MaybeSend,
+	V::Iri: Clone + Eq + Hash + MaybeSend,
+	L: Loader,
+{
+	fn load_iri<'a>(
+		&'a self,
+		vocabulary: &'a mut V,
+		url: V::Iri,
+	) -> impl Future<Output = LoadingResult<V::Iri>> + MaybeSend + 'a {
+		self.0.load_with(vocabulary, url)
+	}
+}
+
+impl<V, I> LoaderWith<V> for HashMap<I, RemoteDocument<I>>
+where
+	V: IriVocabulary<Iri = I>,
+	I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+{
+	fn load_iri<'a>(
+		&'a self,
+		vocabulary: &'a mut V,
+		url: I,
+	) -> impl Future<Output = LoadingResult<I>> + MaybeSend + 'a {
+		core::future::ready(match self.get(&url) {
+			Some(document) => Ok(document.clone()),
+			None => Err(not_found(vocabulary, &url)),
+		})
+	}
+}
+
+impl<V, I> LoaderWith<V> for BTreeMap<I, RemoteDocument<I>>
+where
+	V: IriVocabulary<Iri = I>,
+	I: Clone + Ord + Hash + MaybeSend + MaybeSync,
+{
+	fn load_iri<'a>(
+		&'a self,
+		vocabulary: &'a mut V,
+		url: I,
+	) -> impl Future<Output = LoadingResult<I>> + MaybeSend + 'a {
+		core::future::ready(match self.get(&url) {
+			Some(document) => Ok(document.clone()),
+			None => Err(not_found(vocabulary, &url)),
+		})
+	}
+}
+
+fn not_found<V: IriVocabulary>(vocabulary: &V, url: &V::Iri) -> LoadError {
+	LoadError::new(vocabulary.iri(url).unwrap().to_owned(), EntryNotFound)
+}
+
+impl<I> RemoteDocumentReference<I> {
+	/// Loads the remote document with the given `vocabulary` and
+	/// vocabulary-aware `loader`.
+	///
+	/// If the document is already [`Self::Loaded`], simply returns the inner
+	/// [`RemoteDocument`].
+	pub async fn load_iri_with<V>(
+		self,
+		vocabulary: &mut V,
+		loader: &impl LoaderWith<V>,
+	) -> LoadingResult<I>
+	where
+		V: IriVocabulary<Iri = I>,
+	{
+		match self {
+			Self::Iri(r) => loader.load_iri(vocabulary, r).await,
+			Self::Loaded(doc) => Ok(doc),
+		}
+	}
+}
@@ -1,5 +1,5 @@
-use super::{Any, InvalidExpandedJson, MappedEq};
-use crate::{Id, IndexedObject, Relabel, TryFromJson};
+use super::{Any, InvalidExpandedJson, MappedEq, Object};
+use crate::{AllocError, Id, Indexed, IndexedObject, Relabel, TryFromJson};
 use alloc::vec::Vec;
 use contextual::WithContext;
 use core::hash::Hash;
@@ -59,6 +59,31 @@ impl<T, B> List<T, B> {
 		self.entry.push(object)
 	}
 
+	/// Appends the given object to the list, returning an error instead of
+	/// aborting if memory cannot be allocated.
+	pub fn try_push(&mut self, object: IndexedObject<T, B>) -> Result<(), AllocError> {
+		self.entry.try_reserve(1)?;
+		self.entry.push(object);
+		Ok(())
+	}
+
+	/// Appends the given objects to the list, returning an error instead of
+	/// aborting if memory cannot be allocated.
+	///
+	/// If an allocation fails, the objects appended before are kept.
+	pub fn try_extend(
+		&mut self,
+		objects: impl IntoIterator<Item = IndexedObject<T, B>>,
+	) -> Result<(), AllocError> {
+		let objects = objects.into_iter();
+		self.entry.try_reserve(objects.size_hint().0)?;
+		for object in objects {
+			self.try_push(object)?
+		}
+
+		Ok(())
+	}
+
 	pub fn pop(&mut self) -> Option<IndexedObject<T, B>> {
 		self.entry.pop()
 	}
@@ -71,6 +96,26 @@ impl<T, B> List<T, B> {
 		self.entry.iter_mut()
 	}
 
+	/// Turns this list into the sequence of its items, replacing every item
+	/// that is itself a list with its own items, recursively.
+	///
+	/// The index of nested lists, if any, is lost.
+	pub fn flatten(self) -> Vec<IndexedObject<T, B>> {
+		let mut result = Vec::with_capacity(self.entry.len());
+		self.flatten_into(&mut result);
+		result
+	}
+
+	fn flatten_into(self, result: &mut Vec<IndexedObject<T, B>>) {
+		for item in self.entry {
+			let (object, index) = item.into_parts();
+			match object {
+				Object::List(list) => list.flatten_into(result),
+				object => result.push(Indexed::new(object, index)),
+			}
+		}
+	}
+
 	/// Puts this list object literals into canonical form using the given
 	/// `buffer`.
 	///
@@ -87,6 +132,22 @@ impl<T, B> List<T, B> {
 		self.canonicalize_with(&mut buffer)
 	}
 
+	/// Sorts the properties of every node object in this list using the
+	/// given comparison function on property identifiers.
+	///
+	/// See [`Node::sort_properties_by`](crate::Node::sort_properties_by).
+	pub fn sort_properties_by<F: FnMut(&Id<T, B>, &Id<T, B>) -> core::cmp::Ordering>(
+		&mut self,
+		compare: &mut F,
+	) where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		for object in self {
+			object.sort_properties_by(compare)
+		}
+	}
+
 	/// Map the identifiers present in this list (recursively).
 	pub fn map_ids<U, C>(
 		self,
@@ -168,6 +229,24 @@ impl<T: Eq + Hash, B: Eq + Hash> MappedEq for List<T, B> {
 	}
 }
 
+impl<T, B> From<Vec<IndexedObject<T, B>>> for List<T, B> {
+	fn from(objects: Vec<IndexedObject<T, B>>) -> Self {
+		Self::new(objects)
+	}
+}
+
+impl<T, B> From<List<T, B>> for Vec<IndexedObject<T, B>> {
+	fn from(list: List<T, B>) -> Self {
+		list.entry
+	}
+}
+
+impl<T, B> FromIterator<IndexedObject<T, B>> for List<T, B> {
+	fn from_iter<I: IntoIterator<Item = IndexedObject<T, B>>>(iter: I) -> Self {
+		Self::new(iter.into_iter().collect())
+	}
+}
+
 impl<'a, T, B> IntoIterator for &'a List<T, B> {
 	type Item = &'a IndexedObject<T, B>;
 	type IntoIter = core::slice::Iter<'a, IndexedObject<T, B>>;
@@ -21,7 +21,7 @@ pub mod value;
 
 pub use list::List;
 pub use mapped_eq::MappedEq;
-pub use node::{Graph, IndexedNode, Node, Nodes};
+pub use node::{Graph, IndexedNode, Node, NodeBuilder, Nodes, TypeHierarchy};
 pub use typ::{Type, TypeRef};
 pub use value::{Literal, Value};
 
@@ -126,6 +126,59 @@ impl<T, B> Object<T, B> {
 		Self::Node(Box::new(n))
 	}
 
+	/// Creates a node object referencing the node with the given identifier.
+	pub fn node_ref(id: Id<T, B>) -> Self {
+		Self::node(Node::with_id(id))
+	}
+
+	/// Creates a plain string value object.
+	pub fn string(s: impl Into<json_syntax::String>) -> Self {
+		Self::Value(Value::Literal(Literal::String(s.into()), None))
+	}
+
+	/// Creates a string value object with the given datatype.
+	pub fn typed_string(s: impl Into<json_syntax::String>, ty: T) -> Self {
+		Self::Value(Value::Literal(Literal::String(s.into()), Some(ty)))
+	}
+
+	/// Creates a boolean value object.
+	pub fn boolean(b: bool) -> Self {
+		Self::Value(Value::Literal(Literal::Boolean(b), None))
+	}
+
+	/// Creates a number value object.
+	pub fn number(n: json_syntax::NumberBuf) -> Self {
+		Self::Value(Value::Literal(Literal::Number(n), None))
+	}
+
+	/// Creates a language-tagged string value object.
+	///
+	/// The result is a plain string if neither a language nor a direction is
+	/// given.
+	pub fn lang_string(
+		s: impl Into<json_syntax::String>,
+		language: Option<crate::LenientLangTagBuf>,
+		direction: Option<crate::Direction>,
+	) -> Self {
+		match crate::LangString::new(s.into(), language, direction) {
+			Ok(s) => Self::Value(Value::LangString(s)),
+			Err(s) => Self::string(s),
+		}
+	}
+
+	/// Creates a JSON literal value object (`@json`).
+	pub fn json(value: json_syntax::Value) -> Self {
+		Self::Value(Value::Json(value))
+	}
+
+	/// Creates a list object with the given items.
+	pub fn list<V>(items: impl IntoIterator<Item = V>) -> Self
+	where
+		V: Into<IndexedObject<T, B>>,
+	{
+		Self::List(List::new(items.into_iter().map(Into::into).collect()))
+	}
+
 	/// Identifier of the object, if it is a node object.
 	#[inline(always)]
 	pub fn id(&self) -> Option<&Id<T, B>> {
@@ -184,6 +237,24 @@ impl<T, B> Object<T, B> {
 		self.canonicalize_with(&mut buffer)
 	}
 
+	/// Sorts the properties of every node object in this object using the
+	/// given comparison function on property identifiers.
+	///
+	/// See [`Node::sort_properties_by`].
+	pub fn sort_properties_by<F: FnMut(&Id<T, B>, &Id<T, B>) -> core::cmp::Ordering>(
+		&mut self,
+		compare: &mut F,
+	) where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		match self {
+			Self::List(l) => l.sort_properties_by(compare),
+			Self::Node(n) => n.sort_properties_by(compare),
+			Self::Value(_) => (),
+		}
+	}
+
 	/// Returns an iterator over the types of the object.
 	pub fn types(&self) -> Types<T, B> {
 		match self {
@@ -888,19 +959,42 @@ impl<T: Eq + Hash, B: Eq + Hash> TryFromJsonObject<T, B> for Object<T, B> {
 ///
 /// This can be raised when trying to directly convert a JSON value into an
 /// expanded JSON-LD object without using the expansion algorithm.
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum InvalidExpandedJson {
+	#[error("invalid object")]
 	InvalidObject,
+
+	#[error("invalid list")]
 	InvalidList,
+
+	#[error("invalid `@index`")]
 	InvalidIndex,
+
+	#[error("invalid `@id`")]
 	InvalidId,
+
+	#[error("invalid value type")]
 	InvalidValueType,
+
+	#[error("invalid literal")]
 	InvalidLiteral,
+
+	#[error("invalid `@language`")]
 	InvalidLanguage,
+
+	#[error("invalid `@direction`")]
 	InvalidDirection,
+
+	#[error("not expanded")]
 	NotExpanded,
+
+	#[error("unexpected entry")]
 	UnexpectedEntry,
+
+	#[error("duplicate key `{0}`")]
 	DuplicateKey(json_syntax::object::Key),
+
+	#[error("unexpected {0}, expected {1}")]
 	Unexpected(json_syntax::Kind, json_syntax::Kind),
 }
 
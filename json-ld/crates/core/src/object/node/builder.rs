@@ -0,0 +1,136 @@
+use super::{Graph, IndexedNode, Node};
+use crate::{Id, Indexed, IndexedObject};
+use alloc::string::String;
+use core::hash::Hash;
+use iref::IriBuf;
+use rdf_types::BlankIdBuf;
+
+/// Node object builder.
+///
+/// Returned by [`Node::builder`].
+///
+/// ```
+/// use json_ld_core::{Id, Node, Object};
+/// use iref::IriBuf;
+///
+/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+///
+/// let node: Node = Node::builder()
+///   .id(iri("https://example.org/alice"))
+///   .type_(iri("https://schema.org/Person"))
+///   .property(iri("https://schema.org/name"), [Object::string("Alice")])
+///   .property(
+///     iri("https://schema.org/knows"),
+///     [Object::node_ref(iri("https://example.org/bob"))],
+///   )
+///   .build();
+///
+/// assert_eq!(node.properties().len(), 2);
+/// ```
+pub struct NodeBuilder<T = IriBuf, B = BlankIdBuf> {
+	node: Node<T, B>,
+	index: Option<String>,
+}
+
+impl<T, B> Default for NodeBuilder<T, B> {
+	fn default() -> Self {
+		Self {
+			node: Node::new(),
+			index: None,
+		}
+	}
+}
+
+impl<T, B> NodeBuilder<T, B> {
+	/// Creates a new builder for an empty node.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the node identifier (`@id`).
+	pub fn id(mut self, id: Id<T, B>) -> Self {
+		self.node.id = Some(id);
+		self
+	}
+
+	/// Adds a type (`@type`) to the node.
+	pub fn type_(mut self, ty: Id<T, B>) -> Self {
+		self.node.types_mut_or_default().push(ty);
+		self
+	}
+
+	/// Sets the index (`@index`) of the node, used by [`Self::build_indexed`]
+	/// and [`Self::build_object`].
+	pub fn index(mut self, index: impl Into<String>) -> Self {
+		self.index = Some(index.into());
+		self
+	}
+
+	/// Sets the graph (`@graph`) of the node.
+	pub fn graph(mut self, graph: Graph<T, B>) -> Self {
+		self.node.set_graph_entry(Some(graph));
+		self
+	}
+
+	/// Builds the node.
+	///
+	/// The [index](Self::index), if any, is ignored.
+	pub fn build(self) -> Node<T, B> {
+		self.node
+	}
+
+	/// Builds the indexed node.
+	pub fn build_indexed(self) -> IndexedNode<T, B> {
+		Indexed::new(self.node, self.index)
+	}
+
+	/// Builds the indexed node object.
+	pub fn build_object(self) -> IndexedObject<T, B> {
+		Indexed::new(self.node.into(), self.index)
+	}
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> NodeBuilder<T, B> {
+	/// Adds the given values to the property `prop`.
+	pub fn property<V>(mut self, prop: Id<T, B>, values: impl IntoIterator<Item = V>) -> Self
+	where
+		V: Into<IndexedObject<T, B>>,
+	{
+		self.node
+			.insert_all(prop, values.into_iter().map(Into::into));
+		self
+	}
+
+	/// Adds the given nodes to the reverse property `prop` (`@reverse`).
+	pub fn reverse_property<V>(mut self, prop: Id<T, B>, nodes: impl IntoIterator<Item = V>) -> Self
+	where
+		V: Into<IndexedNode<T, B>>,
+	{
+		self.node
+			.reverse_properties_or_default()
+			.insert_all(prop, nodes.into_iter().map(Into::into));
+		self
+	}
+
+	/// Adds the given node to the included nodes (`@included`).
+	pub fn included(mut self, node: impl Into<IndexedNode<T, B>>) -> Self {
+		self.node.included_mut_or_default().insert(node.into());
+		self
+	}
+
+	/// Adds the given object to the graph (`@graph`) of the node.
+	pub fn graph_object(mut self, object: impl Into<IndexedObject<T, B>>) -> Self {
+		self.node
+			.graph
+			.get_or_insert_with(Graph::default)
+			.insert(object.into());
+		self
+	}
+}
+
+impl<T, B> Node<T, B> {
+	/// Returns a builder for a new node.
+	pub fn builder() -> NodeBuilder<T, B> {
+		NodeBuilder::new()
+	}
+}
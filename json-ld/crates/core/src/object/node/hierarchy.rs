@@ -0,0 +1,134 @@
+//! Type hierarchy.
+use crate::Id;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::{HashMap, HashSet};
+use iref::IriBuf;
+use rdf_types::BlankIdBuf;
+
+/// Subclass relation between types.
+///
+/// Maps each type to its direct superclasses, as given by an ontology (e.g.
+/// `rdfs:subClassOf` statements). Used by [`Node::has_type_with`] to test if
+/// a node is an instance of a type through one of its subclasses.
+///
+/// The relation is reflexive and transitive: a type is a subclass of itself
+/// and of the superclasses of its superclasses. Cycles are allowed.
+///
+/// ```
+/// use json_ld_core::{object::node::TypeHierarchy, Id, Node};
+/// use iref::IriBuf;
+///
+/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+///
+/// let mut hierarchy = TypeHierarchy::new();
+/// hierarchy.insert(iri("https://schema.org/Article"), iri("https://schema.org/CreativeWork"));
+/// hierarchy.insert(iri("https://schema.org/NewsArticle"), iri("https://schema.org/Article"));
+///
+/// let node: Node = Node::builder()
+///   .type_(iri("https://schema.org/NewsArticle"))
+///   .build();
+///
+/// assert!(node.has_type_with(&hierarchy, &iri("https://schema.org/CreativeWork")));
+/// assert!(!node.has_type_with(&hierarchy, &iri("https://schema.org/Person")));
+/// ```
+///
+/// [`Node::has_type_with`]: super::Node::has_type_with
+#[derive(Debug, Clone)]
+pub struct TypeHierarchy<T = IriBuf, B = BlankIdBuf> {
+	superclasses: HashMap<Id<T, B>, Vec<Id<T, B>>>,
+}
+
+impl<T, B> Default for TypeHierarchy<T, B> {
+	fn default() -> Self {
+		Self {
+			superclasses: HashMap::new(),
+		}
+	}
+}
+
+impl<T, B> TypeHierarchy<T, B> {
+	/// Creates an empty hierarchy, where each type is only a subclass of
+	/// itself.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of types with at least one direct superclass.
+	pub fn len(&self) -> usize {
+		self.superclasses.len()
+	}
+
+	/// Checks if the hierarchy is empty.
+	pub fn is_empty(&self) -> bool {
+		self.superclasses.is_empty()
+	}
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> TypeHierarchy<T, B> {
+	/// Declares `subclass` as a direct subclass of `superclass`.
+	///
+	/// Returns `false` if it was already declared.
+	pub fn insert(&mut self, subclass: Id<T, B>, superclass: Id<T, B>) -> bool {
+		let superclasses = self.superclasses.entry(subclass).or_default();
+		if superclasses.contains(&superclass) {
+			false
+		} else {
+			superclasses.push(superclass);
+			true
+		}
+	}
+
+	/// Returns the direct superclasses of the given type.
+	pub fn direct_superclasses(&self, ty: &Id<T, B>) -> &[Id<T, B>] {
+		self.superclasses.get(ty).map(Vec::as_slice).unwrap_or(&[])
+	}
+
+	/// Returns every superclass of the given type, including itself.
+	pub fn superclasses<'a>(&'a self, ty: &'a Id<T, B>) -> HashSet<&'a Id<T, B>> {
+		let mut result = HashSet::new();
+		let mut stack = alloc::vec![ty];
+
+		while let Some(ty) = stack.pop() {
+			if result.insert(ty) {
+				stack.extend(self.direct_superclasses(ty))
+			}
+		}
+
+		result
+	}
+
+	/// Checks if `subclass` is a subclass of `superclass`.
+	pub fn is_subclass_of(&self, subclass: &Id<T, B>, superclass: &Id<T, B>) -> bool {
+		let mut visited = HashSet::new();
+		let mut stack = alloc::vec![subclass];
+
+		while let Some(ty) = stack.pop() {
+			if ty == superclass {
+				return true;
+			}
+
+			if visited.insert(ty) {
+				stack.extend(self.direct_superclasses(ty))
+			}
+		}
+
+		false
+	}
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> Extend<(Id<T, B>, Id<T, B>)> for TypeHierarchy<T, B> {
+	fn extend<I: IntoIterator<Item = (Id<T, B>, Id<T, B>)>>(&mut self, iter: I) {
+		for (subclass, superclass) in iter {
+			self.insert(subclass, superclass);
+		}
+	}
+}
+
+impl<T: Eq + Hash, B: Eq + Hash> FromIterator<(Id<T, B>, Id<T, B>)> for TypeHierarchy<T, B> {
+	fn from_iter<I: IntoIterator<Item = (Id<T, B>, Id<T, B>)>>(iter: I) -> Self {
+		let mut result = Self::new();
+		result.extend(iter);
+		result
+	}
+}
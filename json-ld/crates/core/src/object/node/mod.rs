@@ -1,9 +1,12 @@
 use super::{InvalidExpandedJson, Traverse, TryFromJson, TryFromJsonObject};
-use crate::{object, utils, Id, Indexed, IndexedObject, Object, Objects, Relabel, Term};
+use crate::{
+	object, utils, AllocError, Id, Indexed, IndexedObject, Object, Objects, Relabel, Term,
+};
 use ahash::RandomState;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use contextual::{IntoRefWithContext, WithContext};
+use core::cmp::Ordering;
 use core::convert::TryFrom;
 use core::hash::{Hash, Hasher};
 use educe::Educe;
@@ -12,12 +15,16 @@ use iref::IriBuf;
 use json_ld_syntax::{IntoJson, IntoJsonWithContext, Keyword};
 use rdf_types::{BlankIdBuf, Generator, Subject, Vocabulary, VocabularyMut};
 
+mod builder;
+pub mod hierarchy;
 pub mod multiset;
 pub mod properties;
 pub mod reverse_properties;
 
+pub use builder::NodeBuilder;
+pub use hierarchy::TypeHierarchy;
 pub use multiset::Multiset;
-pub use properties::Properties;
+pub use properties::{Properties, PropertyObjects};
 pub use reverse_properties::ReverseProperties;
 
 pub type Graph<T, B> = IndexSet<IndexedObject<T, B>, RandomState>;
@@ -290,6 +297,18 @@ impl<T, B> Node<T, B> {
 		false
 	}
 
+	/// Checks if the node has the given type, or one of its subclasses
+	/// according to the given type `hierarchy`.
+	pub fn has_type_with(&self, hierarchy: &TypeHierarchy<T, B>, ty: &Id<T, B>) -> bool
+	where
+		T: Eq + Hash,
+		B: Eq + Hash,
+	{
+		self.types()
+			.iter()
+			.any(|self_ty| hierarchy.is_subclass_of(self_ty, ty))
+	}
+
 	/// Tests if the node is empty.
 	///
 	/// It is empty is every field other than `@id` is empty.
@@ -604,6 +623,117 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 		self.properties.insert_all(prop, values)
 	}
 
+	/// Associates the given object to the node through the given property,
+	/// returning an error instead of aborting if memory cannot be allocated.
+	#[inline(always)]
+	pub fn try_insert(
+		&mut self,
+		prop: Id<T, B>,
+		value: IndexedObject<T, B>,
+	) -> Result<(), AllocError> {
+		self.properties.try_insert(prop, value)
+	}
+
+	/// Associates all the given objects to the node through the given
+	/// property, returning an error instead of aborting if memory cannot be
+	/// allocated.
+	#[inline(always)]
+	pub fn try_insert_all<Objects: IntoIterator<Item = IndexedObject<T, B>>>(
+		&mut self,
+		prop: Id<T, B>,
+		values: Objects,
+	) -> Result<(), AllocError>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		self.properties.try_insert_all(prop, values)
+	}
+
+	/// Replaces the objects associated to the node through the given
+	/// property.
+	///
+	/// If `values` is empty, the property is removed.
+	pub fn set<Objects: IntoIterator<Item = IndexedObject<T, B>>>(
+		&mut self,
+		prop: Id<T, B>,
+		values: Objects,
+	) {
+		let values: PropertyObjects<T, B> = values.into_iter().collect();
+		if values.is_empty() {
+			self.properties.remove(&prop);
+		} else {
+			self.properties.set(prop, values)
+		}
+	}
+
+	/// Replaces the objects associated to the node through the given
+	/// property with a single list object (`@list`) containing them.
+	///
+	/// The list items follow the current order of the values. Returns
+	/// `false` if the node has no value for this property, or if the
+	/// property value is already a single list.
+	pub fn property_into_list(&mut self, prop: &Id<T, B>) -> bool
+	where
+		T: Clone,
+		B: Clone,
+	{
+		match self.properties.remove(prop) {
+			Some(values) => {
+				if values.len() == 1 && values.iter().all(|v| v.is_list()) {
+					self.properties.set(prop.clone(), values);
+					return false;
+				}
+
+				let list = Object::List(object::List::new(values.into_iter().collect()));
+				self.properties.insert(prop.clone(), Indexed::none(list));
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Replaces the list objects (`@list`) associated to the node through
+	/// the given property with their items, turning an ordered property into
+	/// a plain multi-valued property.
+	///
+	/// Nested lists are flattened (see [`List::flatten`](object::List::flatten)).
+	/// Returns `false` if the property has no list value.
+	pub fn property_from_list(&mut self, prop: &Id<T, B>) -> bool
+	where
+		T: Clone,
+		B: Clone,
+	{
+		match self.properties.remove(prop) {
+			Some(values) => {
+				let has_list = values.iter().any(|v| v.is_list());
+
+				let mut result = Vec::with_capacity(values.len());
+				for value in values {
+					let (object, index) = value.into_parts();
+					match object {
+						Object::List(list) => result.extend(list.flatten()),
+						object => result.push(Indexed::new(object, index)),
+					}
+				}
+
+				self.properties.insert_all(prop.clone(), result);
+				has_list
+			}
+			None => false,
+		}
+	}
+
+	/// Removes and returns all the objects associated to the node through the
+	/// given property.
+	#[inline(always)]
+	pub fn remove<Q: ?Sized + Hash + indexmap::Equivalent<Id<T, B>>>(
+		&mut self,
+		prop: &Q,
+	) -> Option<PropertyObjects<T, B>> {
+		self.properties.remove(prop)
+	}
+
 	pub fn reverse_properties_or_insert(
 		&mut self,
 		props: ReverseProperties<T, B>,
@@ -623,6 +753,58 @@ impl<T: Eq + Hash, B: Eq + Hash> Node<T, B> {
 		self.reverse_properties.get_or_insert_with(f)
 	}
 
+	/// Sorts the properties and reverse properties of this node object, and
+	/// of every node object it contains, using the given comparison function
+	/// on property identifiers.
+	pub fn sort_properties_by<F: FnMut(&Id<T, B>, &Id<T, B>) -> Ordering>(
+		&mut self,
+		compare: &mut F,
+	) {
+		self.properties.sort_by(&mut *compare);
+		for (_, objects) in self.properties_mut() {
+			for object in objects {
+				object.sort_properties_by(compare)
+			}
+		}
+
+		if let Some(reverse_properties) = self.reverse_properties_mut() {
+			reverse_properties.sort_by(&mut *compare);
+			for (_, nodes) in reverse_properties.iter_mut() {
+				for node in nodes {
+					node.sort_properties_by(compare)
+				}
+			}
+		}
+
+		if let Some(graph) = self.graph_mut() {
+			for mut object in core::mem::take(graph) {
+				object.sort_properties_by(compare);
+				graph.insert(object);
+			}
+		}
+
+		if let Some(included) = self.included_mut() {
+			for mut node in core::mem::take(included) {
+				node.sort_properties_by(compare);
+				included.insert(node);
+			}
+		}
+	}
+
+	/// Sorts the properties and reverse properties of this node object, and
+	/// of every node object it contains, by identifier.
+	///
+	/// Properties are otherwise kept in insertion order. Sorting them gives
+	/// a representation of the node that does not depend on how it was
+	/// built, which is useful to compare the printed form of nodes.
+	pub fn sort_properties(&mut self)
+	where
+		T: Ord,
+		B: Ord,
+	{
+		self.sort_properties_by(&mut |a: &Id<T, B>, b: &Id<T, B>| a.cmp(b))
+	}
+
 	/// Equivalence operator.
 	///
 	/// Equivalence is different from equality for anonymous objects.
@@ -1,31 +1,64 @@
+use crate::AllocError;
 use alloc::vec::Vec;
 use core::hash::{BuildHasher, Hash, Hasher};
-use hashbrown::hash_map::DefaultHashBuilder;
 
+/// Hasher builder using fixed keys.
+///
+/// Every hasher it builds hashes values the same way, within a process and
+/// across runs, so that the hash of a [`Multiset`] (combining the hash of
+/// each item) only depends on its content.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct DeterministicHasherBuilder;
 
+/// Keys of the hashers built by [`DeterministicHasherBuilder`].
+const DETERMINISTIC_HASHER_KEYS: [u64; 4] = [
+	0x243f_6a88_85a3_08d3,
+	0x1319_8a2e_0370_7344,
+	0xa409_3822_299f_31d0,
+	0x082e_fa98_ec4e_6c89,
+];
+
 impl BuildHasher for DeterministicHasherBuilder {
 	type Hasher = ahash::AHasher;
 
 	fn build_hasher(&self) -> Self::Hasher {
-		DefaultHashBuilder::default().build_hasher()
+		let [k0, k1, k2, k3] = DETERMINISTIC_HASHER_KEYS;
+		ahash::RandomState::with_seeds(k0, k1, k2, k3).build_hasher()
 	}
 }
 
 use json_ld_syntax::IntoJsonWithContext;
 
+/// Storage of the values of a [`Multiset`].
+///
+/// With the `small-values` feature, up to two values are stored inline,
+/// since most properties have only one or two values.
+#[cfg(feature = "small-values")]
+type Data<T> = smallvec::SmallVec<[T; 2]>;
+
+/// Storage of the values of a [`Multiset`].
+#[cfg(not(feature = "small-values"))]
+type Data<T> = Vec<T>;
+
+/// Owning iterator over the values of a [`Multiset`].
+#[cfg(feature = "small-values")]
+pub type IntoIter<T> = smallvec::IntoIter<[T; 2]>;
+
+/// Owning iterator over the values of a [`Multiset`].
+#[cfg(not(feature = "small-values"))]
+pub type IntoIter<T> = alloc::vec::IntoIter<T>;
+
 /// Multi-set of values.
 #[derive(Debug, Clone)]
 pub struct Multiset<T, S = DeterministicHasherBuilder> {
-	data: Vec<T>,
+	data: Data<T>,
 	hasher: S,
 }
 
 impl<T, S: Default> Default for Multiset<T, S> {
 	fn default() -> Self {
 		Self {
-			data: Vec::new(),
+			data: Data::new(),
 			hasher: S::default(),
 		}
 	}
@@ -44,7 +77,7 @@ impl<T, S> Multiset<T, S> {
 		S: Default,
 	{
 		Self {
-			data: Vec::with_capacity(cap),
+			data: Data::with_capacity(cap),
 			hasher: S::default(),
 		}
 	}
@@ -72,8 +105,14 @@ impl<T, S> Multiset<T, S> {
 		self.data.iter_mut()
 	}
 
+	/// Reserves capacity for at least `additional` more values.
+	pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+		self.data.try_reserve(additional)?;
+		Ok(())
+	}
+
 	pub fn as_slice(&self) -> &[T] {
-		&self.data
+		self.data.as_slice()
 	}
 
 	// pub fn into_stripped(self) -> Multiset<locspan::Stripped<T>, S> {
@@ -95,6 +134,14 @@ impl<T: Hash, S: BuildHasher> Multiset<T, S> {
 		self.data.push(value);
 	}
 
+	/// Adds the given value, returning an error instead of aborting if
+	/// memory cannot be allocated.
+	pub fn try_insert(&mut self, value: T) -> Result<(), AllocError> {
+		self.data.try_reserve(1)?;
+		self.data.push(value);
+		Ok(())
+	}
+
 	pub fn insert_unique(&mut self, value: T) -> bool
 	where
 		T: PartialEq,
@@ -128,7 +175,7 @@ impl<'a, T, S> IntoIterator for &'a mut Multiset<T, S> {
 
 impl<T, S> IntoIterator for Multiset<T, S> {
 	type Item = T;
-	type IntoIter = alloc::vec::IntoIter<T>;
+	type IntoIter = IntoIter<T>;
 
 	fn into_iter(self) -> Self::IntoIter {
 		self.data.into_iter()
@@ -157,7 +204,7 @@ impl<T: Hash, S: BuildHasher> Extend<T> for Multiset<T, S> {
 
 impl<T: PartialEq<U>, U, S, P> PartialEq<Multiset<U, P>> for Multiset<T, S> {
 	fn eq(&self, other: &Multiset<U, P>) -> bool {
-		compare_unordered(&self.data, &other.data)
+		compare_unordered(self.data.as_slice(), other.data.as_slice())
 	}
 }
 
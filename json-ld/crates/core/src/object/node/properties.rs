@@ -1,4 +1,5 @@
 use super::{Multiset, Objects};
+use crate::AllocError;
 use crate::{
 	object::{InvalidExpandedJson, TryFromJson, TryFromJsonObject},
 	Id, IndexedObject,
@@ -6,6 +7,7 @@ use crate::{
 use ahash::RandomState;
 use alloc::string::ToString;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 use educe::Educe;
 use indexmap::IndexMap;
@@ -64,6 +66,12 @@ impl<T, B> Properties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the properties using the given comparison function.
+	#[inline(always)]
+	pub fn sort_by(&mut self, mut compare: impl FnMut(&Id<T, B>, &Id<T, B>) -> Ordering) {
+		self.0.sort_by(|a, _, b, _| compare(a, b))
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> Properties<T, B> {
@@ -164,6 +172,45 @@ impl<T: Eq + Hash, B: Eq + Hash> Properties<T, B> {
 		}
 	}
 
+	/// Associates the given object to the given property, returning an error
+	/// instead of aborting if memory cannot be allocated.
+	pub fn try_insert(
+		&mut self,
+		prop: Id<T, B>,
+		value: IndexedObject<T, B>,
+	) -> Result<(), AllocError> {
+		match self.0.get_mut(&prop) {
+			Some(node_values) => node_values.try_insert(value),
+			None => {
+				let mut node_values = PropertyObjects::new();
+				node_values.try_insert(value)?;
+				self.0.try_reserve(1)?;
+				self.0.insert(prop, node_values);
+				Ok(())
+			}
+		}
+	}
+
+	/// Associates all the given objects to the given property, returning an
+	/// error instead of aborting if memory cannot be allocated.
+	///
+	/// If an allocation fails, the objects inserted before are kept.
+	pub fn try_insert_all<Objects: IntoIterator<Item = IndexedObject<T, B>>>(
+		&mut self,
+		prop: Id<T, B>,
+		values: Objects,
+	) -> Result<(), AllocError>
+	where
+		T: Clone,
+		B: Clone,
+	{
+		for value in values {
+			self.try_insert(prop.clone(), value)?
+		}
+
+		Ok(())
+	}
+
 	pub fn set(&mut self, prop: Id<T, B>, values: PropertyObjects<T, B>) {
 		self.0.insert(prop, values);
 	}
@@ -7,6 +7,7 @@ use ahash::RandomState;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use contextual::WithContext;
+use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 use educe::Educe;
 use indexmap::IndexMap;
@@ -69,6 +70,12 @@ impl<T, B> ReverseProperties<T, B> {
 	pub fn clear(&mut self) {
 		self.0.clear()
 	}
+
+	/// Sorts the reverse properties using the given comparison function.
+	#[inline(always)]
+	pub fn sort_by(&mut self, mut compare: impl FnMut(&Id<T, B>, &Id<T, B>) -> Ordering) {
+		self.0.sort_by(|a, _, b, _| compare(a, b))
+	}
 }
 
 impl<T: Eq + Hash, B: Eq + Hash> ReverseProperties<T, B> {
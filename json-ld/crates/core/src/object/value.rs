@@ -1,9 +1,11 @@
-use crate::{object, Direction, LangString, LenientLangTag};
+use crate::{object, Direction, LangString, LenientLangTag, PrintCanonical};
+use alloc::string::{String, ToString};
 use core::{hash::Hash, marker::PhantomData};
 use educe::Educe;
 use iref::{Iri, IriBuf};
 use json_ld_syntax::{IntoJsonWithContext, Keyword};
 use json_syntax::{Number, NumberBuf};
+use linked_data::xsd_types::{self, Datatype, DecimalDatatype, ParseXsd};
 use rdf_types::vocabulary::{IriVocabulary, IriVocabularyMut};
 
 use super::InvalidExpandedJson;
@@ -200,6 +202,26 @@ impl<T> Value<T> {
 		}
 	}
 
+	/// Returns the embedded JSON value, if this is a JSON literal (`@json`).
+	#[inline(always)]
+	pub fn as_json(&self) -> Option<&json_syntax::Value> {
+		match self {
+			Value::Json(json) => Some(json),
+			_ => None,
+		}
+	}
+
+	/// Returns the canonical ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785))
+	/// serialization of the embedded JSON value, if this is a JSON literal
+	/// (`@json`).
+	///
+	/// This is the lexical form of the literal when the value is serialized
+	/// to RDF.
+	pub fn canonical_json_string(&self) -> Option<String> {
+		self.as_json()
+			.map(|json| json.canonical_print().to_string())
+	}
+
 	/// Return the type of the value if any.
 	///
 	/// This will return `Some(Type::Json)` for JSON literal values.
@@ -233,6 +255,149 @@ impl<T> Value<T> {
 		}
 	}
 
+	/// Returns the lexical form of this literal and its XSD datatype.
+	///
+	/// The datatype is `None` for untyped numbers. Returns `None` if this
+	/// value is not a number or typed string, or if its type is not an XSD
+	/// datatype.
+	fn xsd_lexical(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Option<(&str, Option<Datatype>)> {
+		let (lexical, ty) = match self {
+			Self::Literal(Literal::String(s), Some(ty)) => (s.as_str(), Some(ty)),
+			Self::Literal(Literal::Number(n), ty) => (n.as_str(), ty.as_ref()),
+			_ => return None,
+		};
+
+		match ty {
+			Some(ty) => Some((lexical, Some(Datatype::from_iri(vocabulary.iri(ty)?)?))),
+			None => Some((lexical, None)),
+		}
+	}
+
+	/// Parses this literal according to its XSD datatype.
+	///
+	/// Untyped booleans and numbers are also converted, numbers as
+	/// `xsd:integer` if they are written as such, `xsd:double` otherwise.
+	/// Returns `None` if this value is not a literal with an XSD datatype,
+	/// or if its lexical form is invalid.
+	pub fn as_xsd(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<xsd_types::Value> {
+		if let Self::Literal(Literal::Boolean(b), None) = self {
+			return Some(xsd_types::Value::Boolean((*b).into()));
+		}
+
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(datatype)) => datatype.parse(lexical).ok(),
+			(lexical, None) => match xsd_types::Integer::parse_xsd(lexical) {
+				Ok(i) => Some(xsd_types::Value::Integer(i)),
+				Err(_) => xsd_types::Double::parse_xsd(lexical)
+					.ok()
+					.map(xsd_types::Value::Double),
+			},
+		}
+	}
+
+	/// Returns this value as an integer, if it is an integral number or a
+	/// valid literal of `xsd:integer` or one of its derived datatypes.
+	pub fn as_integer(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Option<xsd_types::Integer> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, None | Some(Datatype::Decimal(DecimalDatatype::Integer(_)))) => {
+				xsd_types::Integer::parse_xsd(lexical).ok()
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a decimal, if it is a number without exponent or
+	/// a valid literal of `xsd:decimal` or one of its derived datatypes.
+	pub fn as_decimal(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Option<xsd_types::Decimal> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, None | Some(Datatype::Decimal(_))) => {
+				xsd_types::Decimal::parse_xsd(lexical).ok()
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a double, if it is a number or a valid
+	/// `xsd:double` literal.
+	pub fn as_double(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<xsd_types::Double> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, None | Some(Datatype::Double)) => xsd_types::Double::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a float, if it is a valid `xsd:float` literal.
+	pub fn as_float(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<xsd_types::Float> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::Float)) => xsd_types::Float::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a boolean, if it is a boolean or a valid
+	/// `xsd:boolean` literal.
+	pub fn as_boolean(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<bool> {
+		if let Self::Literal(Literal::Boolean(b), _) = self {
+			return Some(*b);
+		}
+
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::Boolean)) => {
+				xsd_types::Boolean::parse_xsd(lexical).ok().map(|b| b.0)
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a date and time, if it is a valid
+	/// `xsd:dateTime` or `xsd:dateTimeStamp` literal.
+	pub fn as_date_time(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Option<xsd_types::DateTime> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::DateTime(_))) => xsd_types::DateTime::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a date, if it is a valid `xsd:date` literal.
+	pub fn as_date(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<xsd_types::Date> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::Date)) => xsd_types::Date::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a time, if it is a valid `xsd:time` literal.
+	pub fn as_time(&self, vocabulary: &impl IriVocabulary<Iri = T>) -> Option<xsd_types::Time> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::Time)) => xsd_types::Time::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
+	/// Returns this value as a duration, if it is a valid `xsd:duration`
+	/// literal, or of one of its derived datatypes.
+	pub fn as_duration(
+		&self,
+		vocabulary: &impl IriVocabulary<Iri = T>,
+	) -> Option<xsd_types::Duration> {
+		match self.xsd_lexical(vocabulary)? {
+			(lexical, Some(Datatype::Duration(_))) => xsd_types::Duration::parse_xsd(lexical).ok(),
+			_ => None,
+		}
+	}
+
 	#[inline(always)]
 	pub fn entries(&self) -> Entries<T> {
 		match self {
@@ -634,3 +799,40 @@ impl<T, N: IriVocabulary<Iri = T>> IntoJsonWithContext<N> for Value<T> {
 		obj.into()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::borrow::ToOwned;
+
+	fn typed(s: &str, ty: &str) -> Value {
+		Value::Literal(
+			Literal::String(s.into()),
+			Some(IriBuf::new(ty.to_owned()).unwrap()),
+		)
+	}
+
+	#[test]
+	fn xsd_accessors() {
+		let xsd = |name: &str| alloc::format!("http://www.w3.org/2001/XMLSchema#{name}");
+
+		let int = typed("42", &xsd("int"));
+		assert_eq!(int.as_integer(&()), Some(42i32.into()));
+		assert!(int.as_decimal(&()).is_some());
+		assert_eq!(int.as_date(&()), None);
+
+		let number: Value = Value::Literal(Literal::Number(NumberBuf::from(7i32)), None);
+		assert_eq!(number.as_integer(&()), Some(7i32.into()));
+
+		assert_eq!(typed("1", &xsd("boolean")).as_boolean(&()), Some(true));
+		assert!(typed("2024-02-29", &xsd("date")).as_date(&()).is_some());
+		assert!(typed("29/02/2024", &xsd("date")).as_date(&()).is_none());
+		assert!(typed("2024-02-29T10:00:00Z", &xsd("dateTime"))
+			.as_date_time(&())
+			.is_some());
+		assert_eq!(
+			typed("12", "https://example.org/Unknown").as_integer(&()),
+			None
+		);
+	}
+}
@@ -663,3 +663,74 @@ impl<T, B, N: Vocabulary<Iri = T, BlankId = B>> PrintWithSizeAndContext<N>
 		)
 	}
 }
+
+/// Canonical JSON printing, following the
+/// [JSON Canonicalization Scheme (JCS)](https://www.rfc-editor.org/rfc/rfc8785).
+///
+/// Object entries are sorted by key (comparing their UTF-16 code units),
+/// numbers are printed in their ECMAScript form and no whitespace is emitted.
+/// This is the lexical form of `@json` literals, and can be used to hash or
+/// sign compacted documents reproducibly.
+pub trait PrintCanonical {
+	/// Returns a value displaying `self` in canonical form.
+	fn canonical_print(&self) -> Canonical<Self> {
+		Canonical(self)
+	}
+}
+
+impl PrintCanonical for json_syntax::Value {}
+
+impl PrintCanonical for json_syntax::Object {}
+
+/// Value displayed in canonical form.
+///
+/// See [`PrintCanonical`].
+pub struct Canonical<'a, T: ?Sized>(pub &'a T);
+
+impl<'a> core::fmt::Display for Canonical<'a, json_syntax::Value> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		match self.0 {
+			json_syntax::Value::Null => f.write_str("null"),
+			json_syntax::Value::Boolean(true) => f.write_str("true"),
+			json_syntax::Value::Boolean(false) => f.write_str("false"),
+			json_syntax::Value::Number(n) => f.write_str(&n.canonical()),
+			json_syntax::Value::String(s) => string_literal(s, f),
+			json_syntax::Value::Array(items) => {
+				f.write_str("[")?;
+				for (i, item) in items.iter().enumerate() {
+					if i > 0 {
+						f.write_str(",")?;
+					}
+
+					core::fmt::Display::fmt(&Canonical(item), f)?
+				}
+				f.write_str("]")
+			}
+			json_syntax::Value::Object(object) => core::fmt::Display::fmt(&Canonical(object), f),
+		}
+	}
+}
+
+impl<'a> core::fmt::Display for Canonical<'a, json_syntax::Object> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		let mut entries: Vec<_> = self.0.iter().collect();
+		entries.sort_by(|a, b| {
+			a.key
+				.as_str()
+				.encode_utf16()
+				.cmp(b.key.as_str().encode_utf16())
+		});
+
+		f.write_str("{")?;
+		for (i, entry) in entries.into_iter().enumerate() {
+			if i > 0 {
+				f.write_str(",")?;
+			}
+
+			string_literal(entry.key.as_str(), f)?;
+			f.write_str(":")?;
+			core::fmt::Display::fmt(&Canonical(&entry.value), f)?
+		}
+		f.write_str("}")
+	}
+}
@@ -0,0 +1,932 @@
+//! RDF Dataset Canonicalization.
+//!
+//! Implementation of the [RDFC-1.0] canonicalization algorithm (formerly
+//! known as URDNA2015), computing a canonical labeling of the blank nodes of
+//! an RDF dataset. Two isomorphic datasets have the same canonical form,
+//! which makes it suitable for hashing and signing (e.g. for Data Integrity
+//! proofs).
+//!
+//! The hash function is provided by the caller through the [`Sha`] trait.
+//! RDFC-1.0 uses SHA-256 by default.
+//!
+//! The input is treated as a set: duplicate quads are ignored. Since some
+//! datasets ("poison graphs") require an exponential amount of work to be
+//! canonicalized, the algorithm is bounded by [`Limits`], and fails with
+//! [`LimitExceeded`] when they are reached.
+//!
+//! [RDFC-1.0]: <https://www.w3.org/TR/rdf-canon/>
+use super::nquads::{write_labeled_quad, write_lexical_quad};
+use super::{RdfDirection, RdfQuads};
+use crate::{ExpandedDocument, PrintCanonical};
+use ahash::RandomState;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::fmt::Write;
+use hashbrown::{HashMap, HashSet};
+use indexmap::IndexMap;
+use json_ld_syntax::IntoJsonWithContext;
+use rdf_types::vocabulary::{ExtractFromVocabulary, IriVocabularyMut, LiteralVocabularyMut};
+use rdf_types::{BlankId, BlankIdBuf, Generator, Id, LexicalQuad, Term, Vocabulary};
+
+pub use ssi_crypto::hashes::sha::Sha;
+
+/// Prefix of the canonical blank node identifiers.
+pub const CANONICAL_PREFIX: &str = "c14n";
+
+/// Blank node identifier issuer.
+///
+/// Issues new blank node identifiers using a prefix and an incrementing
+/// counter, remembering the order in which identifiers were issued.
+///
+/// See <https://www.w3.org/TR/rdf-canon/#issue-identifier>.
+#[derive(Debug, Clone)]
+pub struct IdentifierIssuer {
+	prefix: &'static str,
+	counter: usize,
+	issued: IndexMap<BlankIdBuf, BlankIdBuf, RandomState>,
+}
+
+impl IdentifierIssuer {
+	/// Creates a new issuer using the given identifier prefix.
+	pub fn new(prefix: &'static str) -> Self {
+		Self {
+			prefix,
+			counter: 0,
+			issued: IndexMap::default(),
+		}
+	}
+
+	/// Returns the identifier issued for `id`, if any.
+	pub fn get(&self, id: &BlankId) -> Option<&BlankIdBuf> {
+		self.issued.get(id)
+	}
+
+	/// Checks if an identifier has been issued for `id`.
+	pub fn contains(&self, id: &BlankId) -> bool {
+		self.issued.contains_key(id)
+	}
+
+	/// Issues a new identifier for `id`, or returns the one previously
+	/// issued.
+	pub fn issue(&mut self, id: &BlankId) -> &BlankIdBuf {
+		if !self.issued.contains_key(id) {
+			let issued =
+				BlankIdBuf::from_suffix(&format!("{}{}", self.prefix, self.counter)).unwrap();
+			self.counter += 1;
+			self.issued.insert(id.to_owned(), issued);
+		}
+
+		self.issued.get(id).unwrap()
+	}
+
+	/// Returns an iterator over the `(existing, issued)` identifier pairs, in
+	/// the order they were issued.
+	pub fn issued(&self) -> indexmap::map::Iter<'_, BlankIdBuf, BlankIdBuf> {
+		self.issued.iter()
+	}
+
+	/// Turns this issuer into the map of issued identifiers.
+	pub fn into_map(self) -> IndexMap<BlankIdBuf, BlankIdBuf, RandomState> {
+		self.issued
+	}
+}
+
+/// Limits bounding the work of the canonicalization algorithm.
+///
+/// The Hash N-Degree Quads algorithm recurses through blank nodes sharing
+/// the same first degree hash, and examines every permutation of the related
+/// blank nodes with the same hash. Its cost is exponential in the worst
+/// case, which untrusted datasets can exploit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+	/// Maximum recursion depth of the Hash N-Degree Quads algorithm.
+	pub max_depth: usize,
+
+	/// Maximum number of steps, where each Hash N-Degree Quads call and
+	/// each permutation examined is a step.
+	///
+	/// A group of more than 16 related blank nodes with the same hash
+	/// always exceeds this limit.
+	pub max_steps: usize,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Self {
+			max_depth: 128,
+			max_steps: 100_000,
+		}
+	}
+}
+
+/// Error raised when the canonicalization algorithm exceeds its [`Limits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum LimitExceeded {
+	/// The maximum recursion depth is exceeded.
+	#[error("canonicalization recursion deeper than {0}")]
+	Depth(usize),
+
+	/// The maximum number of steps is exceeded.
+	#[error("canonicalization requires more than {0} steps")]
+	Steps(usize),
+}
+
+#[cfg(not(feature = "std"))]
+impl crate::Convenient for LimitExceeded {}
+
+/// Position of a blank node in a quad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+	Subject,
+	Object,
+	Graph,
+}
+
+impl Position {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Subject => "s",
+			Self::Object => "o",
+			Self::Graph => "g",
+		}
+	}
+}
+
+/// Returns the blank node components of the given quad with their position.
+fn blank_components(quad: &LexicalQuad) -> impl Iterator<Item = (&BlankId, Position)> {
+	let subject = match &quad.0 {
+		Id::Blank(b) => Some((b.as_blank_id_ref(), Position::Subject)),
+		Id::Iri(_) => None,
+	};
+
+	let object = match &quad.2 {
+		Term::Id(Id::Blank(b)) => Some((b.as_blank_id_ref(), Position::Object)),
+		_ => None,
+	};
+
+	let graph = match &quad.3 {
+		Some(Id::Blank(b)) => Some((b.as_blank_id_ref(), Position::Graph)),
+		_ => None,
+	};
+
+	subject.into_iter().chain(object).chain(graph)
+}
+
+/// Canonicalization state.
+///
+/// See <https://www.w3.org/TR/rdf-canon/#canon-state>.
+struct State<'a, H> {
+	quads: Vec<&'a LexicalQuad>,
+	blank_node_to_quads: HashMap<&'a BlankId, Vec<usize>>,
+	canonical_issuer: IdentifierIssuer,
+	limits: Limits,
+	steps: Cell<usize>,
+	hash: core::marker::PhantomData<H>,
+}
+
+impl<'a, H: Sha> State<'a, H> {
+	fn new(quads: &'a [LexicalQuad], limits: Limits) -> Self {
+		// The input dataset is a set.
+		let mut seen = HashSet::new();
+		let quads: Vec<_> = quads.iter().filter(|quad| seen.insert(*quad)).collect();
+
+		let mut blank_node_to_quads: HashMap<&'a BlankId, Vec<usize>> = HashMap::new();
+		for (i, quad) in quads.iter().enumerate() {
+			for (b, _) in blank_components(quad) {
+				let list = blank_node_to_quads.entry(b).or_default();
+				if list.last() != Some(&i) {
+					list.push(i)
+				}
+			}
+		}
+
+		Self {
+			quads,
+			blank_node_to_quads,
+			canonical_issuer: IdentifierIssuer::new(CANONICAL_PREFIX),
+			limits,
+			steps: Cell::new(0),
+			hash: core::marker::PhantomData,
+		}
+	}
+
+	/// Counts one step of work, failing if the limit is exceeded.
+	fn step(&self) -> Result<(), LimitExceeded> {
+		let steps = self.steps.get() + 1;
+		if steps > self.limits.max_steps {
+			return Err(LimitExceeded::Steps(self.limits.max_steps));
+		}
+
+		self.steps.set(steps);
+		Ok(())
+	}
+
+	fn hash(data: &str) -> String {
+		hex(&H::hash(data.as_bytes()))
+	}
+
+	fn quads_of(&self, id: &BlankId) -> impl Iterator<Item = &'a LexicalQuad> + '_ {
+		self.blank_node_to_quads
+			.get(id)
+			.into_iter()
+			.flatten()
+			.map(|&i| self.quads[i])
+	}
+
+	/// Hash First Degree Quads algorithm.
+	///
+	/// See <https://www.w3.org/TR/rdf-canon/#hash-1d-quads>.
+	fn hash_first_degree_quads(&self, reference: &BlankId) -> String {
+		let mut nquads: Vec<String> = self
+			.quads_of(reference)
+			.map(|quad| {
+				let mut line = String::new();
+				write_labeled_quad(&mut line, quad, |b| if b == reference { "a" } else { "z" })
+					.unwrap();
+				line
+			})
+			.collect();
+
+		nquads.sort_unstable();
+		Self::hash(&nquads.concat())
+	}
+
+	/// Hash Related Blank Node algorithm.
+	///
+	/// See <https://www.w3.org/TR/rdf-canon/#hash-related-blank-node>.
+	fn hash_related_blank_node(
+		&self,
+		related: &BlankId,
+		quad: &LexicalQuad,
+		issuer: &IdentifierIssuer,
+		position: Position,
+	) -> String {
+		let mut input = String::from(position.as_str());
+
+		if position != Position::Graph {
+			input.push('<');
+			input.push_str(quad.1.as_str());
+			input.push('>');
+		}
+
+		match self
+			.canonical_issuer
+			.get(related)
+			.or_else(|| issuer.get(related))
+		{
+			Some(id) => input.push_str(id.as_str()),
+			None => input.push_str(&self.hash_first_degree_quads(related)),
+		}
+
+		Self::hash(&input)
+	}
+
+	/// Hash N-Degree Quads algorithm.
+	///
+	/// See <https://www.w3.org/TR/rdf-canon/#hash-nd-quads>.
+	fn hash_n_degree_quads(
+		&self,
+		identifier: &BlankId,
+		mut issuer: IdentifierIssuer,
+		depth: usize,
+	) -> Result<(String, IdentifierIssuer), LimitExceeded> {
+		if depth > self.limits.max_depth {
+			return Err(LimitExceeded::Depth(self.limits.max_depth));
+		}
+
+		self.step()?;
+
+		let mut hn: BTreeMap<String, Vec<&'a BlankId>> = BTreeMap::new();
+
+		for quad in self.quads_of(identifier) {
+			for (related, position) in blank_components(quad) {
+				if related != identifier {
+					let hash = self.hash_related_blank_node(related, quad, &issuer, position);
+					hn.entry(hash).or_default().push(related)
+				}
+			}
+		}
+
+		let mut data_to_hash = String::new();
+
+		for (related_hash, mut blank_node_list) in hn {
+			data_to_hash.push_str(&related_hash);
+
+			if blank_node_list.len() > permutohedron::MAXHEAP {
+				return Err(LimitExceeded::Steps(self.limits.max_steps));
+			}
+
+			let mut chosen_path = String::new();
+			let mut chosen_issuer = None;
+
+			let mut permutations = permutohedron::Heap::new(&mut blank_node_list);
+			'permutations: while let Some(permutation) = permutations.next_permutation() {
+				self.step()?;
+
+				let mut issuer_copy = issuer.clone();
+				let mut path = String::new();
+				let mut recursion_list = Vec::new();
+
+				for &related in permutation.iter() {
+					match self.canonical_issuer.get(related) {
+						Some(id) => path.push_str(id.as_str()),
+						None => {
+							if !issuer_copy.contains(related) {
+								recursion_list.push(related)
+							}
+
+							path.push_str(issuer_copy.issue(related).as_str())
+						}
+					}
+
+					if !chosen_path.is_empty()
+						&& path.len() >= chosen_path.len()
+						&& path > chosen_path
+					{
+						continue 'permutations;
+					}
+				}
+
+				for related in recursion_list {
+					let (result_hash, result_issuer) =
+						self.hash_n_degree_quads(related, issuer_copy.clone(), depth + 1)?;
+					path.push_str(issuer_copy.issue(related).as_str());
+					path.push('<');
+					path.push_str(&result_hash);
+					path.push('>');
+					issuer_copy = result_issuer;
+
+					if !chosen_path.is_empty()
+						&& path.len() >= chosen_path.len()
+						&& path > chosen_path
+					{
+						continue 'permutations;
+					}
+				}
+
+				if chosen_path.is_empty() || path < chosen_path {
+					chosen_path = path;
+					chosen_issuer = Some(issuer_copy)
+				}
+			}
+
+			data_to_hash.push_str(&chosen_path);
+			if let Some(chosen_issuer) = chosen_issuer {
+				issuer = chosen_issuer
+			}
+		}
+
+		Ok((Self::hash(&data_to_hash), issuer))
+	}
+
+	/// Canonicalization algorithm.
+	///
+	/// See <https://www.w3.org/TR/rdf-canon/#canon-algorithm>.
+	fn canonicalize(mut self) -> Result<IdentifierIssuer, LimitExceeded> {
+		let mut hash_to_blank_nodes: BTreeMap<String, Vec<&'a BlankId>> = BTreeMap::new();
+
+		for &b in self.blank_node_to_quads.keys() {
+			let hash = self.hash_first_degree_quads(b);
+			hash_to_blank_nodes.entry(hash).or_default().push(b)
+		}
+
+		let mut non_unique = Vec::new();
+		for identifier_list in hash_to_blank_nodes.into_values() {
+			if identifier_list.len() == 1 {
+				self.canonical_issuer.issue(identifier_list[0]);
+			} else {
+				non_unique.push(identifier_list)
+			}
+		}
+
+		for identifier_list in non_unique {
+			let mut hash_path_list = Vec::new();
+
+			for b in identifier_list {
+				if self.canonical_issuer.contains(b) {
+					continue;
+				}
+
+				let mut temporary_issuer = IdentifierIssuer::new("b");
+				temporary_issuer.issue(b);
+				hash_path_list.push(self.hash_n_degree_quads(b, temporary_issuer, 0)?)
+			}
+
+			hash_path_list.sort_by(|a, b| a.0.cmp(&b.0));
+			for (_, issuer) in hash_path_list {
+				for (existing, _) in issuer.issued() {
+					self.canonical_issuer.issue(existing);
+				}
+			}
+		}
+
+		Ok(self.canonical_issuer)
+	}
+}
+
+/// Computes the canonical labeling of the blank nodes of the given dataset.
+///
+/// Returns the issuer mapping each blank node identifier of `quads` to its
+/// canonical identifier. Fails if the default [`Limits`] are exceeded.
+pub fn canonical_labeling<H: Sha>(
+	quads: &[LexicalQuad],
+) -> Result<IdentifierIssuer, LimitExceeded> {
+	canonical_labeling_with_limits::<H>(quads, Limits::default())
+}
+
+/// Computes the canonical labeling of the blank nodes of the given dataset,
+/// within the given `limits`.
+///
+/// See [`canonical_labeling`].
+pub fn canonical_labeling_with_limits<H: Sha>(
+	quads: &[LexicalQuad],
+	limits: Limits,
+) -> Result<IdentifierIssuer, LimitExceeded> {
+	State::<H>::new(quads, limits).canonicalize()
+}
+
+/// Canonicalizes the given dataset.
+///
+/// Returns the quads with their blank nodes relabeled with their canonical
+/// identifier, sorted in canonical N-Quads order and without duplicates.
+/// Fails if the default [`Limits`] are exceeded.
+pub fn canonicalize<H: Sha>(quads: &[LexicalQuad]) -> Result<Vec<LexicalQuad>, LimitExceeded> {
+	canonicalize_with_limits::<H>(quads, Limits::default())
+}
+
+/// Canonicalizes the given dataset, within the given `limits`.
+///
+/// See [`canonicalize`].
+pub fn canonicalize_with_limits<H: Sha>(
+	quads: &[LexicalQuad],
+	limits: Limits,
+) -> Result<Vec<LexicalQuad>, LimitExceeded> {
+	let issuer = canonical_labeling_with_limits::<H>(quads, limits)?;
+	let relabel = |b: &BlankIdBuf| issuer.get(b).unwrap().clone();
+
+	let mut result: Vec<(String, LexicalQuad)> = quads
+		.iter()
+		.map(|quad| {
+			let quad = LexicalQuad(
+				match &quad.0 {
+					Id::Blank(b) => Id::Blank(relabel(b)),
+					other => other.clone(),
+				},
+				quad.1.clone(),
+				match &quad.2 {
+					Term::Id(Id::Blank(b)) => Term::Id(Id::Blank(relabel(b))),
+					other => other.clone(),
+				},
+				match &quad.3 {
+					Some(Id::Blank(b)) => Some(Id::Blank(relabel(b))),
+					other => other.clone(),
+				},
+			);
+
+			let mut line = String::new();
+			write_lexical_quad(&mut line, &quad).unwrap();
+			(line, quad)
+		})
+		.collect();
+
+	result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+	result.dedup_by(|a, b| a.0 == b.0);
+	Ok(result.into_iter().map(|(_, quad)| quad).collect())
+}
+
+/// Serializes the given quads in canonical N-Quads form, one quad per line.
+///
+/// The quads are serialized in the given order.
+pub fn to_canonical_nquads(quads: &[LexicalQuad]) -> String {
+	let mut result = String::new();
+	for quad in quads {
+		write_lexical_quad(&mut result, quad).unwrap()
+	}
+
+	result
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Returns the canonical form of the RDF dataset defined by this document,
+	/// using the given `vocabulary` and `generator`.
+	///
+	/// The `generator` is only used to label the blank nodes introduced by the
+	/// RDF serialization (e.g. for lists). It does not influence the
+	/// canonical labeling.
+	///
+	/// The returned quads are relabeled using the canonical blank node
+	/// identifiers computed by the [RDFC-1.0](https://www.w3.org/TR/rdf-canon/)
+	/// algorithm, and sorted in canonical N-Quads order. Fails if the default
+	/// canonicalization [`Limits`] are exceeded.
+	pub fn canonical_quads_with<H, V, G>(
+		&self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Vec<LexicalQuad>, LimitExceeded>
+	where
+		H: Sha,
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let quads = self.lexical_quads_with(vocabulary, generator, rdf_direction);
+		canonicalize::<H>(&quads)
+	}
+
+	/// Converts this document into lexical RDF quads, leaving out the quads
+	/// with a blank node predicate.
+	fn lexical_quads_with<V, G>(
+		&self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Vec<LexicalQuad>
+	where
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let quads: Vec<_> = self
+			.rdf_quads_with(vocabulary, generator, rdf_direction)
+			.cloned()
+			.collect();
+
+		quads
+			.into_iter()
+			.filter_map(|rdf_types::Quad(s, p, o, g)| {
+				let p = match p {
+					Id::Iri(p) => vocabulary.owned_iri(p).ok().unwrap(),
+					Id::Blank(_) => return None,
+				};
+
+				let o = match o {
+					Term::Id(id) => Term::Id(id.extract_from_vocabulary(vocabulary)),
+					Term::Literal(l) => Term::Literal(
+						vocabulary
+							.owned_literal(l)
+							.ok()
+							.unwrap()
+							.extract_from_vocabulary(vocabulary),
+					),
+				};
+
+				Some(LexicalQuad(
+					s.extract_from_vocabulary(vocabulary),
+					p,
+					o,
+					g.map(|g| g.extract_from_vocabulary(vocabulary)),
+				))
+			})
+			.collect()
+	}
+
+	/// Returns the canonical form of the RDF dataset defined by this document.
+	///
+	/// See [`Self::canonical_quads_with`] for more details.
+	pub fn canonical_quads<H, G>(
+		&self,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Vec<LexicalQuad>, LimitExceeded>
+	where
+		H: Sha,
+		(): Vocabulary<Iri = T, BlankId = B>,
+		T: Clone,
+		B: Clone,
+		G: Generator,
+	{
+		self.canonical_quads_with::<H, _, _>(
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			rdf_direction,
+		)
+	}
+
+	/// Computes the hash of the canonical N-Quads serialization of the RDF
+	/// dataset defined by this document, using the given `vocabulary` and
+	/// `generator`.
+	///
+	/// This is the hash of the output of [`Self::canonical_quads_with`]
+	/// serialized with [`to_canonical_nquads`]. It is typically used as input
+	/// of Data Integrity proofs.
+	pub fn canonical_hash_with<H, V, G>(
+		&self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Vec<u8>, LimitExceeded>
+	where
+		H: Sha,
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let quads = self.canonical_quads_with::<H, _, _>(vocabulary, generator, rdf_direction)?;
+		Ok(H::hash(to_canonical_nquads(&quads).as_bytes()))
+	}
+
+	/// Computes the hash of the canonical N-Quads serialization of the RDF
+	/// dataset defined by this document.
+	///
+	/// See [`Self::canonical_hash_with`] for more details.
+	pub fn canonical_hash<H, G>(
+		&self,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Vec<u8>, LimitExceeded>
+	where
+		H: Sha,
+		(): Vocabulary<Iri = T, BlankId = B>,
+		T: Clone,
+		B: Clone,
+		G: Generator,
+	{
+		self.canonical_hash_with::<H, _, _>(
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			rdf_direction,
+		)
+	}
+}
+
+impl ExpandedDocument {
+	/// Returns the expanded JSON text of this document in canonical form.
+	///
+	/// The blank nodes (including anonymous nodes) are relabeled with the
+	/// canonical identifiers computed by the
+	/// [RDFC-1.0](https://www.w3.org/TR/rdf-canon/) algorithm on the RDF
+	/// dataset defined by the document, using the hash function `H`. Blank
+	/// nodes that do not appear in the dataset are labeled afterward, in
+	/// document order. The JSON text is then printed following the
+	/// [JSON Canonicalization Scheme](crate::PrintCanonical), with object
+	/// entries sorted by key and numbers in canonical form. Since expanded
+	/// documents have no ordering except in lists, every other array is
+	/// sorted as well.
+	///
+	/// Two documents expanding to isomorphic objects have the same canonical
+	/// form, which makes it suitable for content hashing and caching keys.
+	///
+	/// Fails if the default canonicalization [`Limits`] are exceeded.
+	pub fn canonical_form<H: Sha>(&self) -> Result<String, LimitExceeded> {
+		let mut document = self.clone();
+		document.relabel(&mut rdf_types::generator::Blank::new_with_prefix(
+			"b".into(),
+		));
+
+		let quads = document.lexical_quads_with(
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			&mut rdf_types::generator::Blank::new_with_prefix("l".into()),
+			Some(RdfDirection::I18nDatatype),
+		);
+		let mut issuer = canonical_labeling::<H>(&quads)?;
+
+		let document = document.map_ids(
+			|iri| iri,
+			|id| match id {
+				crate::Id::Valid(Id::Blank(b)) => {
+					crate::Id::Valid(Id::Blank(issuer.issue(&b).clone()))
+				}
+				id => id,
+			},
+		);
+
+		let mut json = document.into_json_with(&());
+		sort_unordered_arrays(&mut json);
+		Ok(json.canonical_print().to_string())
+	}
+}
+
+/// Sorts the arrays of the given expanded JSON value by canonical form,
+/// except for `@list` arrays and `@value` contents whose order matters.
+fn sort_unordered_arrays(value: &mut json_syntax::Value) {
+	match value {
+		json_syntax::Value::Array(items) => {
+			for item in items.iter_mut() {
+				sort_unordered_arrays(item)
+			}
+
+			items.sort_by_cached_key(|item| item.canonical_print().to_string())
+		}
+		json_syntax::Value::Object(object) => {
+			for (key, value) in object.iter_mut() {
+				match key.as_str() {
+					"@value" => (),
+					"@list" => {
+						if let json_syntax::Value::Array(items) = value {
+							for item in items.iter_mut() {
+								sort_unordered_arrays(item)
+							}
+						}
+					}
+					_ => sort_unordered_arrays(value),
+				}
+			}
+		}
+		_ => (),
+	}
+}
+
+fn hex(bytes: &[u8]) -> String {
+	let mut result = String::with_capacity(bytes.len() * 2);
+	for b in bytes {
+		write!(result, "{b:02x}").unwrap()
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::rdf::nquads::parse_quads;
+
+	struct Sha256;
+
+	impl Sha for Sha256 {
+		fn hash(data: &[u8]) -> Vec<u8> {
+			use sha2::Digest;
+			sha2::Sha256::digest(data).to_vec()
+		}
+	}
+
+	struct Sha384;
+
+	impl Sha for Sha384 {
+		fn hash(data: &[u8]) -> Vec<u8> {
+			use sha2::Digest;
+			sha2::Sha384::digest(data).to_vec()
+		}
+	}
+
+	fn parse(input: &str) -> Vec<LexicalQuad> {
+		parse_quads(input)
+			.map(|quad| {
+				let rdf_types::Quad(s, p, o, g) = quad.unwrap();
+				let p = match p {
+					Id::Iri(p) => p,
+					Id::Blank(_) => panic!("blank node predicate"),
+				};
+				LexicalQuad(s, p, o, g)
+			})
+			.collect()
+	}
+
+	fn canonical_nquads<H: Sha>(input: &str) -> Result<String, LimitExceeded> {
+		canonicalize::<H>(&parse(input)).map(|quads| to_canonical_nquads(&quads))
+	}
+
+	#[test]
+	fn duplicate_quads() {
+		let input = "_:x <http://example.org/p> \"1\" .\n\
+			_:y <http://example.org/p> \"2\" .\n\
+			_:x <http://example.org/q> _:y .\n";
+		let duplicated = "_:x <http://example.org/p> \"1\" .\n\
+			_:x <http://example.org/p> \"1\" .\n\
+			_:y <http://example.org/p> \"2\" .\n\
+			_:x <http://example.org/q> _:y .\n\
+			_:x <http://example.org/q> _:y .\n";
+
+		let quads = parse(input);
+		let labeling = canonical_labeling::<Sha256>(&quads).unwrap();
+		let duplicated_labeling = canonical_labeling::<Sha256>(&parse(duplicated)).unwrap();
+		for (id, canonical_id) in labeling.issued() {
+			assert_eq!(duplicated_labeling.get(id), Some(canonical_id))
+		}
+
+		assert_eq!(
+			canonical_nquads::<Sha256>(input),
+			canonical_nquads::<Sha256>(duplicated)
+		)
+	}
+
+	#[test]
+	fn isomorphic_datasets() {
+		let a = "_:a <http://example.org/p> _:b .\n\
+			_:b <http://example.org/p> _:c .\n\
+			_:c <http://example.org/p> _:a .\n\
+			_:a <http://example.org/name> \"a\" .\n";
+		let b = "_:z <http://example.org/name> \"a\" .\n\
+			_:y <http://example.org/p> _:z .\n\
+			_:x <http://example.org/p> _:y .\n\
+			_:z <http://example.org/p> _:x .\n";
+
+		assert_eq!(canonical_nquads::<Sha256>(a), canonical_nquads::<Sha256>(b))
+	}
+
+	#[test]
+	fn depth_limit() {
+		let quads = parse(
+			"_:a <http://example.org/p> _:b .\n\
+			_:b <http://example.org/p> _:a .\n",
+		);
+
+		let limits = Limits {
+			max_depth: 0,
+			..Default::default()
+		};
+
+		assert_eq!(
+			canonical_labeling_with_limits::<Sha256>(&quads, limits).map(|_| ()),
+			Err(LimitExceeded::Depth(0))
+		);
+		assert!(canonical_labeling::<Sha256>(&quads).is_ok())
+	}
+
+	#[test]
+	fn poison_graph() {
+		// Clique of indistinguishable blank nodes.
+		let mut input = String::new();
+		for i in 0..10 {
+			for j in 0..10 {
+				if i != j {
+					writeln!(input, "_:n{i} <http://example.org/p> _:n{j} .").unwrap()
+				}
+			}
+		}
+
+		let limits = Limits {
+			max_steps: 1000,
+			..Default::default()
+		};
+
+		assert_eq!(
+			canonical_labeling_with_limits::<Sha256>(&parse(&input), limits).map(|_| ()),
+			Err(LimitExceeded::Steps(1000))
+		)
+	}
+
+	/// Runs the W3C [RDF Dataset Canonicalization test
+	/// suite](https://w3c.github.io/rdf-canon/tests/).
+	///
+	/// The suite is read from a local copy of the
+	/// [`rdf-canon`](https://github.com/w3c/rdf-canon) repository `tests`
+	/// directory, given by the `RDF_CANON_TESTS` environment variable. The
+	/// test is skipped if the variable is not set.
+	#[cfg(feature = "std")]
+	#[test]
+	fn w3c_test_suite() {
+		use json_syntax::Parse;
+		use std::path::PathBuf;
+
+		let Some(root) = std::env::var_os("RDF_CANON_TESTS").map(PathBuf::from) else {
+			return;
+		};
+
+		let manifest = std::fs::read_to_string(root.join("manifest.jsonld")).unwrap();
+		let (manifest, _) = json_syntax::Value::parse_str(&manifest).unwrap();
+		let entries = manifest
+			.as_object()
+			.and_then(|m| m.get_unique("entries").ok().flatten())
+			.and_then(|e| e.as_array())
+			.expect("invalid manifest");
+
+		let mut failures = Vec::new();
+		for entry in entries {
+			let entry = entry.as_object().expect("invalid manifest entry");
+			let field = |key: &str| {
+				entry
+					.get_unique(key)
+					.ok()
+					.flatten()
+					.and_then(|v| v.as_str())
+			};
+
+			let id = field("id").unwrap_or_default();
+			let ty = field("type").unwrap_or_default();
+			let input = std::fs::read_to_string(root.join(field("action").unwrap())).unwrap();
+			let sha384 = field("hashAlgorithm") == Some("SHA384");
+
+			let result = if sha384 {
+				canonical_nquads::<Sha384>(&input)
+			} else {
+				canonical_nquads::<Sha256>(&input)
+			};
+
+			let passed = match ty {
+				"rdfc:RDFC10EvalTest" => {
+					let expected =
+						std::fs::read_to_string(root.join(field("result").unwrap())).unwrap();
+					result.as_deref() == Ok(expected.as_str())
+				}
+				"rdfc:RDFC10NegativeEvalTest" => result.is_err(),
+				_ => continue,
+			};
+
+			if !passed {
+				failures.push(id.to_owned())
+			}
+		}
+
+		assert!(failures.is_empty(), "failed tests: {failures:?}")
+	}
+}
@@ -0,0 +1,206 @@
+//! Structural difference between expanded documents.
+//!
+//! Documents are compared through the canonical form of the RDF dataset they
+//! define (see [`canonicalization`](super::canonicalization)), so that blank
+//! nodes are matched by structure rather than by label.
+use super::canonicalization::{to_canonical_nquads, LimitExceeded, Sha};
+use super::RdfDirection;
+use crate::ExpandedDocument;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use iref::IriBuf;
+use rdf_types::vocabulary::{IriVocabularyMut, LiteralVocabularyMut};
+use rdf_types::{Generator, Id, LexicalQuad, Vocabulary};
+
+/// Change of the object of a quad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+	/// Quad before the change.
+	pub old: LexicalQuad,
+
+	/// Quad after the change, with the same subject, predicate and graph.
+	pub new: LexicalQuad,
+}
+
+/// Difference between two RDF datasets.
+///
+/// All the quads are labeled with canonical blank node identifiers, computed
+/// independently for each dataset. A blank node whose neighborhood changed may
+/// get a different canonical label, in which case all the quads mentioning it
+/// are reported as removed and added.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+	/// Quads only present in the new dataset.
+	pub added: Vec<LexicalQuad>,
+
+	/// Quads only present in the old dataset.
+	pub removed: Vec<LexicalQuad>,
+
+	/// Quads whose object changed.
+	///
+	/// A change is reported when the old and new datasets each have exactly
+	/// one differing quad with the same subject, predicate and graph. Such
+	/// quads do not appear in [`Self::added`] and [`Self::removed`].
+	pub changed: Vec<Change>,
+}
+
+impl Diff {
+	/// Computes the difference between the `old` and `new` datasets, given in
+	/// canonical form (see
+	/// [`canonicalize`](super::canonicalization::canonicalize)).
+	pub fn new(old: &[LexicalQuad], new: &[LexicalQuad]) -> Self {
+		let old = keyed(old);
+		let new = keyed(new);
+
+		let mut removed = Vec::new();
+		let mut added = Vec::new();
+		let (mut i, mut j) = (0, 0);
+		while i < old.len() || j < new.len() {
+			let ordering = match (old.get(i), new.get(j)) {
+				(Some(a), Some(b)) => a.0.cmp(&b.0),
+				(Some(_), None) => Ordering::Less,
+				(None, _) => Ordering::Greater,
+			};
+
+			match ordering {
+				Ordering::Less => {
+					removed.push(old[i].1);
+					i += 1
+				}
+				Ordering::Greater => {
+					added.push(new[j].1);
+					j += 1
+				}
+				Ordering::Equal => {
+					i += 1;
+					j += 1
+				}
+			}
+		}
+
+		let mut groups: BTreeMap<(&Id, &IriBuf, Option<&Id>), (Vec<usize>, Vec<usize>)> =
+			BTreeMap::new();
+		for (k, quad) in removed.iter().enumerate() {
+			groups.entry(key(quad)).or_default().0.push(k)
+		}
+		for (k, quad) in added.iter().enumerate() {
+			groups.entry(key(quad)).or_default().1.push(k)
+		}
+
+		let mut changes: Vec<(usize, usize)> = groups
+			.into_values()
+			.filter_map(|(r, a)| match (r.as_slice(), a.as_slice()) {
+				([r], [a]) => Some((*r, *a)),
+				_ => None,
+			})
+			.collect();
+		changes.sort_unstable();
+
+		let changed = changes
+			.iter()
+			.map(|&(r, a)| Change {
+				old: LexicalQuad::clone(removed[r]),
+				new: LexicalQuad::clone(added[a]),
+			})
+			.collect();
+
+		let is_removed_changed = |k: &usize| changes.iter().any(|(r, _)| r == k);
+		let is_added_changed = |k: &usize| changes.iter().any(|(_, a)| a == k);
+
+		Self {
+			added: added
+				.iter()
+				.enumerate()
+				.filter(|(k, _)| !is_added_changed(k))
+				.map(|(_, q)| LexicalQuad::clone(q))
+				.collect(),
+			removed: removed
+				.iter()
+				.enumerate()
+				.filter(|(k, _)| !is_removed_changed(k))
+				.map(|(_, q)| LexicalQuad::clone(q))
+				.collect(),
+			changed,
+		}
+	}
+
+	/// Checks that both datasets are equal.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// Pairs each quad with its canonical N-Quads serialization, sorted.
+fn keyed(quads: &[LexicalQuad]) -> Vec<(String, &LexicalQuad)> {
+	let mut result: Vec<_> = quads
+		.iter()
+		.map(|quad| (to_canonical_nquads(core::slice::from_ref(quad)), quad))
+		.collect();
+
+	result.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+	result.dedup_by(|a, b| a.0 == b.0);
+	result
+}
+
+/// Subject, predicate and graph of a quad.
+fn key(quad: &LexicalQuad) -> (&Id, &IriBuf, Option<&Id>) {
+	(&quad.0, &quad.1, quad.3.as_ref())
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Computes the structural difference between this document and the
+	/// `other` one, using the given `vocabulary` and `generator`.
+	///
+	/// The documents are compared through the canonical form of the RDF
+	/// datasets they define (see [`Self::canonical_quads_with`]). This
+	/// document is considered to be the old version, and `other` the new one.
+	///
+	/// Fails if the canonicalization of either dataset exceeds the default
+	/// [`Limits`](super::canonicalization::Limits).
+	pub fn diff_with<H, V, G>(
+		&self,
+		other: &Self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Diff, LimitExceeded>
+	where
+		H: Sha,
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let old = self.canonical_quads_with::<H, _, _>(vocabulary, generator, rdf_direction)?;
+		let new = other.canonical_quads_with::<H, _, _>(vocabulary, generator, rdf_direction)?;
+		Ok(Diff::new(&old, &new))
+	}
+
+	/// Computes the structural difference between this document and the
+	/// `other` one.
+	///
+	/// See [`Self::diff_with`] for more details.
+	pub fn diff<H, G>(
+		&self,
+		other: &Self,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Diff, LimitExceeded>
+	where
+		H: Sha,
+		(): Vocabulary<Iri = T, BlankId = B>,
+		T: Clone,
+		B: Clone,
+		G: Generator,
+	{
+		self.diff_with::<H, _, _>(
+			other,
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			rdf_direction,
+		)
+	}
+}
@@ -3,20 +3,29 @@ use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::ToString;
 use core::str::FromStr;
+use json_syntax::Parse;
 
-use crate::{object::value, Direction, Id, Indexed, IndexedObject, Node, Object, ValidId};
+use crate::PrintCanonical;
+use crate::{
+	object::value, Direction, Id, Indexed, IndexedObject, LangString, LenientLangTagBuf, Node,
+	Object, ValidId,
+};
 use iref::{Iri, IriBuf};
-use json_syntax::Print;
 use langtag::LangTagBuf;
 use rdf_types::{
-	vocabulary::{IriVocabularyMut, LiteralVocabularyMut},
-	Generator, Literal, Vocabulary,
+	vocabulary::{IriVocabulary, IriVocabularyMut, LiteralVocabularyMut},
+	Generator, Literal, LiteralType, Vocabulary,
 };
 use smallvec::SmallVec;
 use static_iref::iri;
 
+pub mod canonicalization;
+pub mod diff;
+pub mod nquads;
 mod quad;
+mod stream;
 pub use quad::*;
+pub use stream::*;
 
 pub const RDF_TYPE: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
 pub const RDF_FIRST: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
@@ -29,6 +38,7 @@ pub const RDF_NIL: &Iri = iri!("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil")
 
 pub const XSD_BOOLEAN: &Iri = iri!("http://www.w3.org/2001/XMLSchema#boolean");
 pub const XSD_INTEGER: &Iri = iri!("http://www.w3.org/2001/XMLSchema#integer");
+pub const XSD_DECIMAL: &Iri = iri!("http://www.w3.org/2001/XMLSchema#decimal");
 pub const XSD_DOUBLE: &Iri = iri!("http://www.w3.org/2001/XMLSchema#double");
 pub const XSD_STRING: &Iri = iri!("http://www.w3.org/2001/XMLSchema#string");
 
@@ -136,104 +146,44 @@ pub struct CompoundLiteral<T, B, L> {
 }
 
 impl<T: Clone> crate::object::Value<T> {
-	fn rdf_value_with<V, G: Generator<V>>(
+	/// Converts this value into an RDF literal.
+	///
+	/// This is the literal mapping used by the [object to RDF
+	/// conversion](https://www.w3.org/TR/json-ld11-api/#object-to-rdf-conversion).
+	/// Returns `None` if the value is a language tagged string whose language
+	/// tag is not well-formed, or a string with a direction while
+	/// `rdf_direction` is [`RdfDirection::CompoundLiteral`], since it is then
+	/// represented by a blank node and not a literal.
+	pub fn to_rdf_literal(
 		&self,
-		vocabulary: &mut V,
-		generator: &mut G,
+		vocabulary: &mut impl IriVocabularyMut<Iri = T>,
 		rdf_direction: Option<RdfDirection>,
-	) -> Option<CompoundLiteral<T, V::BlankId, V::Literal>>
-	where
-		V: Vocabulary<Iri = T> + IriVocabularyMut + LiteralVocabularyMut,
-	{
+	) -> Option<Literal<T>> {
 		match self {
-			Self::Json(json) => {
-				let ty = vocabulary.insert(RDF_JSON);
-				Some(CompoundLiteral {
-					value: Value::Literal(vocabulary.insert_owned_literal(Literal::new(
-						json.compact_print().to_string(),
-						rdf_types::LiteralType::Any(ty),
-					))),
-					triples: None,
-				})
-			}
+			Self::Json(json) => Some(Literal::new(
+				json.canonical_print().to_string(),
+				LiteralType::Any(vocabulary.insert(RDF_JSON)),
+			)),
 			Self::LangString(lang_string) => {
 				let (string, language, direction) = lang_string.parts();
 
 				let language = match language {
-					Some(language) => match language.as_well_formed() {
-						Some(tag) => Some(tag.to_owned()),
-						None => return None,
-					},
+					Some(language) => Some(language.as_well_formed()?.to_owned()),
 					None => None,
 				};
 
-				match direction {
-					Some(direction) => match rdf_direction {
-						Some(RdfDirection::I18nDatatype) => {
-							let ty = vocabulary.insert(i18n(language, *direction).as_iri());
-							Some(CompoundLiteral {
-								value: Value::Literal(vocabulary.insert_owned_literal(
-									Literal::new(
-										string.to_string(),
-										rdf_types::LiteralType::Any(ty),
-									),
-								)),
-								triples: None,
-							})
-						}
-						Some(RdfDirection::CompoundLiteral) => {
-							let id = generator.next(vocabulary);
-							Some(CompoundLiteral {
-								value: id.into_term(),
-								triples: None,
-							})
-						}
-						None => match language {
-							Some(tag) => Some(CompoundLiteral {
-								value: Value::Literal(vocabulary.insert_owned_literal(
-									Literal::new(
-										string.to_string(),
-										rdf_types::LiteralType::LangString(tag),
-									),
-								)),
-								triples: None,
-							}),
-							None => {
-								let ty = vocabulary.insert(XSD_STRING);
-								Some(CompoundLiteral {
-									value: Value::Literal(vocabulary.insert_owned_literal(
-										Literal::new(
-											string.to_string(),
-											rdf_types::LiteralType::Any(ty),
-										),
-									)),
-									triples: None,
-								})
-							}
-						},
-					},
-					None => match language {
-						Some(tag) => Some(CompoundLiteral {
-							value: Value::Literal(vocabulary.insert_owned_literal(Literal::new(
-								string.to_string(),
-								rdf_types::LiteralType::LangString(tag),
-							))),
-							triples: None,
-						}),
-						None => {
-							let ty = vocabulary.insert(XSD_STRING);
-							Some(CompoundLiteral {
-								value: Value::Literal(vocabulary.insert_owned_literal(
-									Literal::new(
-										string.to_string(),
-										rdf_types::LiteralType::Any(ty),
-									),
-								)),
-								triples: None,
-							})
-						}
+				let ty = match (direction, rdf_direction) {
+					(Some(direction), Some(RdfDirection::I18nDatatype)) => {
+						LiteralType::Any(vocabulary.insert(i18n(language, *direction).as_iri()))
+					}
+					(Some(_), Some(RdfDirection::CompoundLiteral)) => return None,
+					_ => match language {
+						Some(tag) => LiteralType::LangString(tag),
+						None => LiteralType::Any(vocabulary.insert(XSD_STRING)),
 					},
-				}
+				};
+
+				Some(Literal::new(string.to_string(), ty))
 			}
 			Self::Literal(lit, ty) => {
 				let (rdf_lit, prefered_rdf_ty) = match lit {
@@ -266,28 +216,100 @@ impl<T: Clone> crate::object::Value<T> {
 				};
 
 				let rdf_ty = match ty {
-					Some(id) => Some(id.clone()),
-					None => prefered_rdf_ty,
+					Some(id) => id.clone(),
+					None => match prefered_rdf_ty {
+						Some(ty) => ty,
+						None => vocabulary.insert(XSD_STRING),
+					},
 				};
 
-				Some(CompoundLiteral {
-					value: match rdf_ty {
-						Some(ty) => Value::Literal(vocabulary.insert_owned_literal(Literal::new(
-							rdf_lit,
-							rdf_types::LiteralType::Any(ty),
-						))),
-						None => {
-							let ty = vocabulary.insert(XSD_STRING);
-							Value::Literal(vocabulary.insert_owned_literal(Literal::new(
-								rdf_lit,
-								rdf_types::LiteralType::Any(ty),
-							)))
-						}
-					},
+				Some(Literal::new(rdf_lit, LiteralType::Any(rdf_ty)))
+			}
+		}
+	}
+
+	/// Converts an RDF literal into a value.
+	///
+	/// This is the inverse of [`Self::to_rdf_literal`], following the [RDF to
+	/// object conversion](https://www.w3.org/TR/json-ld11-api/#rdf-to-object-conversion)
+	/// without native types: `rdf:JSON` literals are parsed, `xsd:string`
+	/// literals become plain strings and other typed literals are kept as
+	/// strings with their datatype. If `rdf_direction` is
+	/// [`RdfDirection::I18nDatatype`], literals with an
+	/// `https://www.w3.org/ns/i18n#` datatype become strings with a direction.
+	///
+	/// Fails if the lexical form of an `rdf:JSON` literal is not valid JSON.
+	pub fn from_rdf_literal(
+		vocabulary: &impl IriVocabulary<Iri = T>,
+		literal: Literal<T>,
+		rdf_direction: Option<RdfDirection>,
+	) -> Result<Self, json_syntax::parse::Error> {
+		let (lexical, ty) = literal.into_parts();
+		match ty {
+			LiteralType::LangString(tag) => Ok(Self::LangString(
+				LangString::new(lexical.into(), Some(tag.into()), None).unwrap(),
+			)),
+			LiteralType::Any(ty) => {
+				let iri = vocabulary.iri(&ty);
+
+				if iri == Some(RDF_JSON) {
+					let (json, _) = json_syntax::Value::parse_str(&lexical)?;
+					return Ok(Self::Json(json));
+				}
+
+				if iri == Some(XSD_STRING) {
+					return Ok(Self::Literal(value::Literal::String(lexical.into()), None));
+				}
+
+				if rdf_direction == Some(RdfDirection::I18nDatatype) {
+					if let Some((language, direction)) = iri.and_then(parse_i18n) {
+						return Ok(Self::LangString(
+							LangString::new(lexical.into(), language, Some(direction)).unwrap(),
+						));
+					}
+				}
+
+				Ok(Self::Literal(
+					value::Literal::String(lexical.into()),
+					Some(ty),
+				))
+			}
+		}
+	}
+
+	fn rdf_value_with<V, G: Generator<V>>(
+		&self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> Option<CompoundLiteral<T, V::BlankId, V::Literal>>
+	where
+		V: Vocabulary<Iri = T> + IriVocabularyMut + LiteralVocabularyMut,
+	{
+		if let Self::LangString(lang_string) = self {
+			if lang_string.direction().is_some()
+				&& rdf_direction == Some(RdfDirection::CompoundLiteral)
+			{
+				if lang_string
+					.language()
+					.is_some_and(|language| language.as_well_formed().is_none())
+				{
+					return None;
+				}
+
+				let id = generator.next(vocabulary);
+				return Some(CompoundLiteral {
+					value: id.into_term(),
 					triples: None,
-				})
+				});
 			}
 		}
+
+		let literal = self.to_rdf_literal(vocabulary, rdf_direction)?;
+		Some(CompoundLiteral {
+			value: Value::Literal(vocabulary.insert_owned_literal(literal)),
+			triples: None,
+		})
 	}
 }
 
@@ -638,4 +660,19 @@ fn i18n(language: Option<LangTagBuf>, direction: Direction) -> IriBuf {
 	IriBuf::new(iri).unwrap()
 }
 
+/// Parses an i18n datatype IRI, as produced by [`i18n`].
+fn parse_i18n(iri: &Iri) -> Option<(Option<LenientLangTagBuf>, Direction)> {
+	let suffix = iri.as_str().strip_prefix("https://www.w3.org/ns/i18n#")?;
+	let (language, direction) = match suffix.rsplit_once('_') {
+		Some((language, direction)) => (Some(language), direction),
+		None => (None, suffix),
+	};
+
+	let direction = direction.parse().ok()?;
+	let language = language
+		.filter(|language| !language.is_empty())
+		.map(|language| LenientLangTagBuf::new(language.to_owned()).0);
+	Some((language, direction))
+}
+
 pub type Value<T, B, L> = rdf_types::Object<ValidId<T, B>, L>;
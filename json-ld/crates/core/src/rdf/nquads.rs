@@ -0,0 +1,1025 @@
+//! N-Quads serialization and parsing.
+//!
+//! Writes RDF quads in the [N-Quads](https://www.w3.org/TR/n-quads/) format
+//! into any [`fmt::Write`], and parses them back into `rdf_types` quads with
+//! [`Parser`]. Parsed quads are plain RDF: converting them back into a
+//! JSON-LD document (the `fromRdf` algorithm) is not supported by this crate.
+//!
+//! Literals are escaped as in the
+//! [canonical N-Quads](https://www.w3.org/TR/rdf-canon/#canonical-quads)
+//! form, and `xsd:string` datatypes are omitted, so that the output is
+//! canonical as long as the blank node labels are.
+//!
+//! N-Quads cannot represent generalized RDF: quads with a blank node
+//! predicate (produced with the `produceGeneralizedRdf` option) are skipped
+//! by the functions writing several quads, and rejected by the ones writing
+//! a single quad.
+//!
+//! ```
+//! use json_ld_core::{rdf::nquads, ExpandedDocument, Id, Node, RdfQuads};
+//! use iref::IriBuf;
+//! use rdf_types::generator;
+//!
+//! let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+//!
+//! let mut document: ExpandedDocument = ExpandedDocument::new();
+//! document.insert(
+//!   Node::builder()
+//!     .id(iri("https://example.org/alice"))
+//!     .type_(iri("https://schema.org/Person"))
+//!     .build_object(),
+//! );
+//!
+//! let mut generator = generator::Blank::new();
+//! let mut output = String::new();
+//! nquads::write_quads(&mut output, document.rdf_quads(&mut generator, None)).unwrap();
+//! assert_eq!(
+//!   output,
+//!   "<https://example.org/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://schema.org/Person> .\n"
+//! );
+//! ```
+use super::{Quad, Quads, RdfDirection, RdfQuads, Value, XSD_STRING};
+use crate::{ExpandedDocument, ValidId};
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use iref::{Iri, IriBuf};
+use langtag::{LangTag, LangTagBuf};
+use rdf_types::vocabulary::{IriVocabularyMut, LiteralVocabularyMut, VocabularyMut};
+use rdf_types::{
+	BlankId, BlankIdBuf, Generator, Id, LexicalQuad, Literal, LiteralType, LiteralTypeRef, Term,
+	Vocabulary,
+};
+
+/// Writes the given quad in N-Quads form, followed by a new line.
+///
+/// Identifiers and literals are resolved using the given `vocabulary`.
+/// Fails if one of them is unknown to the vocabulary, or if the predicate is
+/// a blank node.
+pub fn write_quad_with<V: Vocabulary>(
+	out: &mut impl Write,
+	vocabulary: &V,
+	quad: &Quad<V::Iri, V::BlankId, V::Literal>,
+) -> fmt::Result {
+	if quad.1.is_blank() {
+		return Err(fmt::Error);
+	}
+
+	write_id_with(out, vocabulary, &quad.0)?;
+	out.write_char(' ')?;
+	write_id_with(out, vocabulary, &quad.1)?;
+	out.write_char(' ')?;
+	write_value_with(out, vocabulary, &quad.2)?;
+
+	if let Some(g) = &quad.3 {
+		out.write_char(' ')?;
+		write_id_with(out, vocabulary, g)?
+	}
+
+	out.write_str(" .\n")
+}
+
+/// Writes the given quads in N-Quads form, one per line.
+///
+/// Quads with a blank node predicate are skipped. See [`write_quad_with`].
+pub fn write_quads_with<V: Vocabulary>(
+	out: &mut impl Write,
+	vocabulary: &V,
+	quads: impl IntoIterator<Item = Quad<V::Iri, V::BlankId, V::Literal>>,
+) -> fmt::Result {
+	for quad in quads.into_iter().filter(|quad| quad.1.is_iri()) {
+		write_quad_with(out, vocabulary, &quad)?
+	}
+
+	Ok(())
+}
+
+/// Writes the given quad in N-Quads form, followed by a new line.
+///
+/// Fails if the predicate is a blank node.
+pub fn write_quad(out: &mut impl Write, quad: &Quad<IriBuf, BlankIdBuf, Literal>) -> fmt::Result {
+	write_quad_with(out, &(), quad)
+}
+
+/// Writes the quads produced by the given iterator in N-Quads form, one per
+/// line.
+///
+/// This is the output of [`RdfQuads::rdf_quads`]. Quads with a blank node
+/// predicate are skipped.
+pub fn write_quads<G: Generator>(
+	out: &mut impl Write,
+	quads: Quads<'_, '_, '_, (), G>,
+) -> fmt::Result {
+	for quad in quads.cloned().filter(|quad| quad.1.is_iri()) {
+		write_quad(out, &quad)?
+	}
+
+	Ok(())
+}
+
+/// Writes the given lexical quad in N-Quads form, followed by a new line.
+pub fn write_lexical_quad(out: &mut impl Write, quad: &LexicalQuad) -> fmt::Result {
+	write_labeled_quad(out, quad, |b| b.suffix())
+}
+
+/// Writes the given lexical quad in N-Quads form, followed by a new line.
+///
+/// The `label` function provides the label of each blank node.
+pub(super) fn write_labeled_quad<'q>(
+	out: &mut impl Write,
+	quad: &'q LexicalQuad,
+	label: impl Fn(&'q BlankId) -> &'q str,
+) -> fmt::Result {
+	write_lexical_id(out, &quad.0, &label)?;
+	out.write_char(' ')?;
+	write_iri(out, &quad.1)?;
+	out.write_char(' ')?;
+	match &quad.2 {
+		Term::Id(id) => write_lexical_id(out, id, &label)?,
+		Term::Literal(l) => {
+			write_string(out, l.as_str())?;
+			match &l.type_ {
+				LiteralType::Any(ty) => write_datatype(out, ty)?,
+				LiteralType::LangString(tag) => write_language(out, tag)?,
+			}
+		}
+	}
+
+	if let Some(g) = &quad.3 {
+		out.write_char(' ')?;
+		write_lexical_id(out, g, &label)?
+	}
+
+	out.write_str(" .\n")
+}
+
+fn write_lexical_id<'q>(
+	out: &mut impl Write,
+	id: &'q Id,
+	label: &impl Fn(&'q BlankId) -> &'q str,
+) -> fmt::Result {
+	match id {
+		Id::Iri(iri) => write_iri(out, iri.as_iri()),
+		Id::Blank(b) => {
+			out.write_str("_:")?;
+			out.write_str(label(b))
+		}
+	}
+}
+
+fn write_id_with<V: Vocabulary>(
+	out: &mut impl Write,
+	vocabulary: &V,
+	id: &ValidId<V::Iri, V::BlankId>,
+) -> fmt::Result {
+	match id {
+		Id::Iri(i) => write_iri(out, vocabulary.iri(i).ok_or(fmt::Error)?),
+		Id::Blank(b) => out.write_str(vocabulary.blank_id(b).ok_or(fmt::Error)?.as_str()),
+	}
+}
+
+fn write_value_with<V: Vocabulary>(
+	out: &mut impl Write,
+	vocabulary: &V,
+	value: &Value<V::Iri, V::BlankId, V::Literal>,
+) -> fmt::Result {
+	match value {
+		Term::Id(id) => write_id_with(out, vocabulary, id),
+		Term::Literal(l) => {
+			let l = vocabulary.literal(l).ok_or(fmt::Error)?;
+			write_string(out, l.value)?;
+			match l.type_ {
+				LiteralTypeRef::Any(ty) => {
+					write_datatype(out, vocabulary.iri(ty).ok_or(fmt::Error)?)
+				}
+				LiteralTypeRef::LangString(tag) => write_language(out, tag),
+			}
+		}
+	}
+}
+
+fn write_iri(out: &mut impl Write, iri: &Iri) -> fmt::Result {
+	out.write_char('<')?;
+	out.write_str(iri.as_str())?;
+	out.write_char('>')
+}
+
+/// Writes the given string as an N-Quads literal value, with its quotes.
+fn write_string(out: &mut impl Write, value: &str) -> fmt::Result {
+	out.write_char('"')?;
+	for c in value.chars() {
+		match c {
+			'\u{8}' => out.write_str("\\b")?,
+			'\t' => out.write_str("\\t")?,
+			'\n' => out.write_str("\\n")?,
+			'\u{c}' => out.write_str("\\f")?,
+			'\r' => out.write_str("\\r")?,
+			'"' => out.write_str("\\\"")?,
+			'\\' => out.write_str("\\\\")?,
+			'\u{0}'..='\u{1f}' | '\u{7f}' => write!(out, "\\u{:04X}", c as u32)?,
+			c => out.write_char(c)?,
+		}
+	}
+	out.write_char('"')
+}
+
+/// Writes the datatype of a literal, unless it is `xsd:string`.
+fn write_datatype(out: &mut impl Write, ty: &Iri) -> fmt::Result {
+	if ty != XSD_STRING {
+		out.write_str("^^")?;
+		write_iri(out, ty)?
+	}
+
+	Ok(())
+}
+
+fn write_language(out: &mut impl Write, tag: &LangTag) -> fmt::Result {
+	out.write_char('@')?;
+	out.write_str(tag.as_str())
+}
+
+impl<T, B> ExpandedDocument<T, B> {
+	/// Writes the RDF dataset defined by this document in N-Quads form, using
+	/// the given `vocabulary` and `generator`.
+	///
+	/// The `generator` is used to label the anonymous nodes and the blank
+	/// nodes introduced by the RDF serialization (e.g. for lists). Quads are
+	/// written in document order, one per line. Quads with a blank node
+	/// predicate (generalized RDF) are skipped.
+	pub fn write_nquads_with<V, G>(
+		&self,
+		out: &mut impl Write,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> fmt::Result
+	where
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let quads: Vec<_> = self
+			.rdf_quads_with(vocabulary, generator, rdf_direction)
+			.cloned()
+			.collect();
+
+		write_quads_with(out, vocabulary, quads)
+	}
+
+	/// Writes the RDF dataset defined by this document in N-Quads form.
+	///
+	/// See [`Self::write_nquads_with`] for more details.
+	pub fn write_nquads<G>(
+		&self,
+		out: &mut impl Write,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> fmt::Result
+	where
+		(): Vocabulary<Iri = T, BlankId = B>,
+		T: Clone,
+		B: Clone,
+		G: Generator,
+	{
+		self.write_nquads_with(
+			out,
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			rdf_direction,
+		)
+	}
+
+	/// Returns the RDF dataset defined by this document in N-Quads form, using
+	/// the given `vocabulary` and `generator`.
+	///
+	/// See [`Self::write_nquads_with`] for more details.
+	pub fn to_nquads_with<V, G>(
+		&self,
+		vocabulary: &mut V,
+		generator: &mut G,
+		rdf_direction: Option<RdfDirection>,
+	) -> String
+	where
+		V: Vocabulary<Iri = T, BlankId = B> + IriVocabularyMut + LiteralVocabularyMut,
+		V::Literal: Clone,
+		T: Clone,
+		B: Clone,
+		G: Generator<V>,
+	{
+		let mut result = String::new();
+		self.write_nquads_with(&mut result, vocabulary, generator, rdf_direction)
+			.unwrap();
+		result
+	}
+
+	/// Returns the RDF dataset defined by this document in N-Quads form.
+	///
+	/// ```
+	/// use json_ld_core::{ExpandedDocument, Id, Node};
+	/// use iref::IriBuf;
+	/// use rdf_types::generator;
+	///
+	/// let iri = |s: &str| Id::iri(IriBuf::new(s.to_owned()).unwrap());
+	///
+	/// let mut document: ExpandedDocument = ExpandedDocument::new();
+	/// document.insert(
+	///   Node::builder()
+	///     .id(iri("https://example.org/alice"))
+	///     .type_(iri("https://schema.org/Person"))
+	///     .build_object(),
+	/// );
+	///
+	/// let nquads = document.to_nquads(&mut generator::Blank::new(), None);
+	/// assert_eq!(
+	///   nquads,
+	///   "<https://example.org/alice> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://schema.org/Person> .\n"
+	/// );
+	/// ```
+	pub fn to_nquads<G>(&self, generator: &mut G, rdf_direction: Option<RdfDirection>) -> String
+	where
+		(): Vocabulary<Iri = T, BlankId = B>,
+		T: Clone,
+		B: Clone,
+		G: Generator,
+	{
+		self.to_nquads_with(
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			rdf_direction,
+		)
+	}
+}
+
+/// N-Quads parse error.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("line {line}: {kind}")]
+pub struct ParseError {
+	/// Line of the error, starting at 1.
+	pub line: usize,
+
+	/// Error kind.
+	pub kind: ParseErrorKind,
+}
+
+/// Kind of [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseErrorKind {
+	#[error("unexpected end of line")]
+	UnexpectedEnd,
+
+	#[error("unexpected character `{0}`")]
+	Unexpected(char),
+
+	#[error("invalid escape sequence")]
+	InvalidEscape,
+
+	#[error("invalid IRI `{0}`")]
+	InvalidIri(String),
+
+	#[error("invalid blank node identifier `{0}`")]
+	InvalidBlankId(String),
+
+	#[error("invalid language tag `{0}`")]
+	InvalidLangTag(String),
+}
+
+/// Streaming N-Quads parser.
+///
+/// Iterates over the quads of an N-Quads document, one line at a time,
+/// inserting IRIs, blank node identifiers and literals into the given
+/// vocabulary. Since N-Triples is a subset of N-Quads, N-Triples documents
+/// are parsed the same way, producing quads without graph.
+///
+/// The parser reads its input from any iterator of lines, so the document
+/// never has to be entirely in memory. Lines must not contain their line
+/// terminator. To read from a fallible source, such as a
+/// [`BufRead`](https://doc.rust-lang.org/std/io/trait.BufRead.html), each
+/// line can also be parsed with [`parse_quad_with`].
+///
+/// Empty lines and comments are skipped. Literals without datatype nor
+/// language tag are given the `xsd:string` datatype.
+///
+/// ```
+/// use json_ld_core::rdf::nquads;
+///
+/// let input = "_:b0 <https://schema.org/name> \"Alice\" .\n\
+///   <https://example.org/alice> <https://schema.org/knows> _:b0 <https://example.org/graph> .\n";
+///
+/// let quads: Vec<_> = nquads::parse_quads(input).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(quads.len(), 2);
+///
+/// let mut output = String::new();
+/// nquads::write_quads_with(&mut output, &(), quads).unwrap();
+/// assert_eq!(output, input);
+/// ```
+pub struct Parser<'v, V, L> {
+	vocabulary: &'v mut V,
+	lines: L,
+	line: usize,
+}
+
+impl<'v, V, L: Iterator> Parser<'v, V, L>
+where
+	L::Item: AsRef<str>,
+{
+	/// Creates a parser reading the given `lines`.
+	///
+	/// ```
+	/// use json_ld_core::rdf::nquads::Parser;
+	///
+	/// let lines = [
+	///   "# comment",
+	///   "<https://example.org/s> <https://example.org/p> \"o\" .",
+	/// ];
+	///
+	/// let mut parser = Parser::new(rdf_types::vocabulary::no_vocabulary_mut(), lines);
+	/// assert!(parser.next().unwrap().is_ok());
+	/// assert!(parser.next().is_none());
+	/// ```
+	pub fn new(vocabulary: &'v mut V, lines: impl IntoIterator<IntoIter = L>) -> Self {
+		Self {
+			vocabulary,
+			lines: lines.into_iter(),
+			line: 0,
+		}
+	}
+}
+
+impl<'v, V: VocabularyMut, L: Iterator> Iterator for Parser<'v, V, L>
+where
+	L::Item: AsRef<str>,
+{
+	type Item = Result<Quad<V::Iri, V::BlankId, V::Literal>, ParseError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		for line in self.lines.by_ref() {
+			self.line += 1;
+			match parse_quad_with(self.vocabulary, line.as_ref()) {
+				Ok(Some(quad)) => return Some(Ok(quad)),
+				Ok(None) => (),
+				Err(kind) => {
+					return Some(Err(ParseError {
+						line: self.line,
+						kind,
+					}))
+				}
+			}
+		}
+
+		None
+	}
+}
+
+/// Parses the given N-Quads (or N-Triples) document, using the given
+/// `vocabulary`.
+///
+/// See [`Parser`].
+pub fn parse_quads_with<'a, 'v, V: VocabularyMut>(
+	vocabulary: &'v mut V,
+	input: &'a str,
+) -> Parser<'v, V, core::str::Lines<'a>> {
+	Parser::new(vocabulary, input.lines())
+}
+
+/// Parses the given N-Quads (or N-Triples) document.
+///
+/// See [`Parser`].
+pub fn parse_quads(input: &str) -> Parser<'static, (), core::str::Lines<'_>> {
+	Parser::new(rdf_types::vocabulary::no_vocabulary_mut(), input.lines())
+}
+
+/// Parses a single N-Quads line.
+///
+/// Returns `None` if the line is empty or only contains a comment.
+pub fn parse_quad_with<V: VocabularyMut>(
+	vocabulary: &mut V,
+	line: &str,
+) -> Result<Option<Quad<V::Iri, V::BlankId, V::Literal>>, ParseErrorKind> {
+	let mut input = LineInput(line);
+
+	if input.skip_whitespaces_and_comment() {
+		return Ok(None);
+	}
+
+	let subject = input.parse_id(vocabulary)?;
+	input.skip_whitespaces();
+	let predicate = Id::Iri(input.parse_iri(vocabulary)?);
+	input.skip_whitespaces();
+	let object = input.parse_term(vocabulary)?;
+	input.skip_whitespaces();
+	let graph = match input.peek()? {
+		'.' => None,
+		_ => {
+			let g = input.parse_id(vocabulary)?;
+			input.skip_whitespaces();
+			Some(g)
+		}
+	};
+
+	input.expect('.')?;
+	if !input.skip_whitespaces_and_comment() {
+		return Err(ParseErrorKind::Unexpected(input.peek()?));
+	}
+
+	Ok(Some(rdf_types::Quad(subject, predicate, object, graph)))
+}
+
+/// Remaining input of the line being parsed.
+struct LineInput<'a>(&'a str);
+
+impl<'a> LineInput<'a> {
+	fn peek(&self) -> Result<char, ParseErrorKind> {
+		self.0.chars().next().ok_or(ParseErrorKind::UnexpectedEnd)
+	}
+
+	fn next_char(&mut self) -> Result<char, ParseErrorKind> {
+		let c = self.peek()?;
+		self.0 = &self.0[c.len_utf8()..];
+		Ok(c)
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), ParseErrorKind> {
+		match self.next_char()? {
+			c if c == expected => Ok(()),
+			c => Err(ParseErrorKind::Unexpected(c)),
+		}
+	}
+
+	fn skip_whitespaces(&mut self) {
+		self.0 = self.0.trim_start_matches([' ', '\t'])
+	}
+
+	/// Skips whitespaces and any comment, and returns `true` if the end of
+	/// the line is reached.
+	fn skip_whitespaces_and_comment(&mut self) -> bool {
+		self.skip_whitespaces();
+		if self.0.starts_with('#') {
+			self.0 = ""
+		}
+
+		self.0.is_empty()
+	}
+
+	fn parse_id<V: VocabularyMut>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> Result<ValidId<V::Iri, V::BlankId>, ParseErrorKind> {
+		match self.peek()? {
+			'<' => Ok(Id::Iri(self.parse_iri(vocabulary)?)),
+			'_' => Ok(Id::Blank(self.parse_blank_id(vocabulary)?)),
+			c => Err(ParseErrorKind::Unexpected(c)),
+		}
+	}
+
+	fn parse_term<V: VocabularyMut>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> Result<Value<V::Iri, V::BlankId, V::Literal>, ParseErrorKind> {
+		match self.peek()? {
+			'"' => Ok(Term::Literal(self.parse_literal(vocabulary)?)),
+			_ => Ok(Term::Id(self.parse_id(vocabulary)?)),
+		}
+	}
+
+	fn parse_iri<V: VocabularyMut>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> Result<V::Iri, ParseErrorKind> {
+		self.expect('<')?;
+		let mut iri = String::new();
+		loop {
+			match self.next_char()? {
+				'>' => break,
+				'\\' => iri.push(self.parse_uchar()?),
+				c => iri.push(c),
+			}
+		}
+
+		match IriBuf::new(iri) {
+			Ok(iri) => Ok(vocabulary.insert_owned(iri)),
+			Err(e) => Err(ParseErrorKind::InvalidIri(e.0)),
+		}
+	}
+
+	fn parse_blank_id<V: VocabularyMut>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> Result<V::BlankId, ParseErrorKind> {
+		let end = self
+			.0
+			.find([' ', '\t', '<', '"', '#'])
+			.unwrap_or(self.0.len());
+		// A label may contain `.`, but not end with it.
+		let id = self.0[..end].trim_end_matches('.');
+		self.0 = &self.0[id.len()..];
+
+		match BlankIdBuf::new(id.to_owned()) {
+			Ok(id) => Ok(vocabulary.insert_owned_blank_id(id)),
+			Err(e) => Err(ParseErrorKind::InvalidBlankId(e.0)),
+		}
+	}
+
+	fn parse_literal<V: VocabularyMut>(
+		&mut self,
+		vocabulary: &mut V,
+	) -> Result<V::Literal, ParseErrorKind> {
+		self.expect('"')?;
+		let mut value = String::new();
+		loop {
+			match self.next_char()? {
+				'"' => break,
+				'\r' => return Err(ParseErrorKind::Unexpected('\r')),
+				'\\' => value.push(match self.peek()? {
+					'u' | 'U' => self.parse_uchar()?,
+					_ => match self.next_char()? {
+						't' => '\t',
+						'b' => '\u{8}',
+						'n' => '\n',
+						'r' => '\r',
+						'f' => '\u{c}',
+						'"' => '"',
+						'\'' => '\'',
+						'\\' => '\\',
+						_ => return Err(ParseErrorKind::InvalidEscape),
+					},
+				}),
+				c => value.push(c),
+			}
+		}
+
+		let type_ = if self.0.starts_with("^^") {
+			self.0 = &self.0[2..];
+			LiteralType::Any(self.parse_iri(vocabulary)?)
+		} else if self.0.starts_with('@') {
+			self.0 = &self.0[1..];
+			let end = self
+				.0
+				.find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+				.unwrap_or(self.0.len());
+			let tag = &self.0[..end];
+			self.0 = &self.0[end..];
+
+			match LangTagBuf::new(tag.to_owned()) {
+				Ok(tag) => LiteralType::LangString(tag),
+				Err(e) => return Err(ParseErrorKind::InvalidLangTag(e.0)),
+			}
+		} else {
+			LiteralType::Any(vocabulary.insert(XSD_STRING))
+		};
+
+		Ok(vocabulary.insert_owned_literal(Literal::new(value, type_)))
+	}
+
+	/// Parses a `\uXXXX` or `\UXXXXXXXX` escape sequence, after the `\`.
+	fn parse_uchar(&mut self) -> Result<char, ParseErrorKind> {
+		let len = match self.next_char()? {
+			'u' => 4,
+			'U' => 8,
+			_ => return Err(ParseErrorKind::InvalidEscape),
+		};
+
+		let digits = self
+			.0
+			.get(..len)
+			.filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+			.ok_or(ParseErrorKind::InvalidEscape)?;
+		self.0 = &self.0[len..];
+
+		// `from_str_radix` alone would accept a leading `+`.
+		u32::from_str_radix(digits, 16)
+			.ok()
+			.and_then(char::from_u32)
+			.ok_or(ParseErrorKind::InvalidEscape)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rdf_types::Object;
+
+	type TestQuad = Quad<IriBuf, BlankIdBuf, Literal>;
+
+	fn iri(s: &str) -> ValidId<IriBuf, BlankIdBuf> {
+		Id::Iri(IriBuf::new(s.to_owned()).unwrap())
+	}
+
+	fn blank(s: &str) -> ValidId<IriBuf, BlankIdBuf> {
+		Id::Blank(BlankIdBuf::new(s.to_owned()).unwrap())
+	}
+
+	fn literal(value: &str, type_: LiteralType) -> Value<IriBuf, BlankIdBuf, Literal> {
+		Object::Literal(Literal::new(value.to_owned(), type_))
+	}
+
+	fn typed(value: &str, ty: &str) -> Value<IriBuf, BlankIdBuf, Literal> {
+		literal(value, LiteralType::Any(IriBuf::new(ty.to_owned()).unwrap()))
+	}
+
+	fn quad(object: Value<IriBuf, BlankIdBuf, Literal>) -> TestQuad {
+		rdf_types::Quad(
+			iri("https://example.org/s"),
+			iri("https://example.org/p"),
+			object,
+			None,
+		)
+	}
+
+	fn write(quad: &TestQuad) -> String {
+		let mut output = String::new();
+		write_quad(&mut output, quad).unwrap();
+		output
+	}
+
+	#[test]
+	fn escape_literals() {
+		assert_eq!(
+			write(&quad(typed("a\"b\\c", XSD_STRING.as_str()))),
+			"<https://example.org/s> <https://example.org/p> \"a\\\"b\\\\c\" .\n"
+		);
+		assert_eq!(
+			write(&quad(typed("\u{0}\u{7f}\u{1f}", XSD_STRING.as_str()))),
+			"<https://example.org/s> <https://example.org/p> \"\\u0000\\u007F\\u001F\" .\n"
+		);
+		assert_eq!(
+			write(&quad(typed("\u{8}\t\n\u{c}\r", XSD_STRING.as_str()))),
+			"<https://example.org/s> <https://example.org/p> \"\\b\\t\\n\\f\\r\" .\n"
+		);
+		assert_eq!(
+			write(&quad(typed("é\u{80}\u{1F600}", XSD_STRING.as_str()))),
+			"<https://example.org/s> <https://example.org/p> \"é\u{80}\u{1F600}\" .\n"
+		);
+	}
+
+	#[test]
+	fn literal_types() {
+		assert_eq!(
+			write(&quad(typed(
+				"1",
+				"http://www.w3.org/2001/XMLSchema#integer"
+			))),
+			"<https://example.org/s> <https://example.org/p> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n"
+		);
+		assert_eq!(
+			write(&quad(literal(
+				"chat",
+				LiteralType::LangString(LangTagBuf::new("fr-FR".to_owned()).unwrap())
+			))),
+			"<https://example.org/s> <https://example.org/p> \"chat\"@fr-FR .\n"
+		);
+	}
+
+	#[test]
+	fn blank_nodes() {
+		let quad = rdf_types::Quad(
+			blank("_:b0"),
+			iri("https://example.org/p"),
+			Object::Id(blank("_:b1")),
+			Some(blank("_:g")),
+		);
+		assert_eq!(write(&quad), "_:b0 <https://example.org/p> _:b1 _:g .\n")
+	}
+
+	#[test]
+	fn blank_predicate() {
+		let generalized = rdf_types::Quad(
+			iri("https://example.org/s"),
+			blank("_:p"),
+			Object::Id(iri("https://example.org/o")),
+			None,
+		);
+
+		let mut output = String::new();
+		assert!(write_quad(&mut output, &generalized).is_err());
+
+		let mut output = String::new();
+		write_quads_with(
+			&mut output,
+			&(),
+			[generalized, quad(typed("a", XSD_STRING.as_str()))],
+		)
+		.unwrap();
+		assert_eq!(
+			output,
+			"<https://example.org/s> <https://example.org/p> \"a\" .\n"
+		)
+	}
+
+	fn parse(line: &str) -> Result<Option<TestQuad>, ParseErrorKind> {
+		parse_quad_with(rdf_types::vocabulary::no_vocabulary_mut(), line)
+	}
+
+	fn parse_object(line: &str) -> Value<IriBuf, BlankIdBuf, Literal> {
+		parse(line).unwrap().unwrap().2
+	}
+
+	#[test]
+	fn parse_escapes() {
+		assert_eq!(
+			parse_object(r#"<http://example/s> <http://example/p> "\t\b\n\r\f\"\'\\" ."#),
+			typed("\t\u{8}\n\r\u{c}\"'\\", XSD_STRING.as_str())
+		);
+		assert_eq!(
+			parse_object(r#"<http://example/s> <http://example/p> "é\U0001F600" ."#),
+			typed("é\u{1F600}", XSD_STRING.as_str())
+		);
+		assert_eq!(
+			parse(r#"<http://example/é> <http://example/p> <http://example/o> ."#)
+				.unwrap()
+				.unwrap()
+				.0,
+			iri("http://example/é")
+		);
+	}
+
+	#[test]
+	fn parse_malformed_escapes() {
+		for line in [
+			r#"<http://example/s> <http://example/p> "a\zb" ."#,
+			r#"<http://example/s> <http://example/p> "\u+041" ."#,
+			r#"<http://example/s> <http://example/p> "\u00ZZ" ."#,
+			r#"<http://example/s> <http://example/p> "\U+0000041" ."#,
+			r#"<http://example/s> <http://example/p> "\u004" ."#,
+			r#"<http://example/s> <http://example/p> "\UFFFFFFFF" ."#,
+			r#"<http://example/s> <http://example/p> "\uD800" ."#,
+			r#"<http://example/\u+041> <http://example/p> <http://example/o> ."#,
+			r#"<http://example/\n> <http://example/p> <http://example/o> ."#,
+		] {
+			assert_eq!(parse(line), Err(ParseErrorKind::InvalidEscape), "{line}")
+		}
+	}
+
+	#[test]
+	fn parse_lang_tags() {
+		assert_eq!(
+			parse_object(r#"<http://example/s> <http://example/p> "chat"@fr-FR ."#),
+			literal(
+				"chat",
+				LiteralType::LangString(LangTagBuf::new("fr-FR".to_owned()).unwrap())
+			)
+		);
+		assert_eq!(
+			parse_object(r#"<http://example/s> <http://example/p> "chat"@en."#),
+			literal(
+				"chat",
+				LiteralType::LangString(LangTagBuf::new("en".to_owned()).unwrap())
+			)
+		);
+
+		for line in [
+			r#"<http://example/s> <http://example/p> "string"@1 ."#,
+			r#"<http://example/s> <http://example/p> "string"@ ."#,
+			r#"<http://example/s> <http://example/p> "string"@en- ."#,
+		] {
+			assert!(
+				matches!(parse(line), Err(ParseErrorKind::InvalidLangTag(_))),
+				"{line}"
+			)
+		}
+	}
+
+	#[test]
+	fn parse_datatypes() {
+		assert_eq!(
+			parse_object(
+				r#"<http://example/s> <http://example/p> "1"^^<http://www.w3.org/2001/XMLSchema#integer> ."#
+			),
+			typed("1", "http://www.w3.org/2001/XMLSchema#integer")
+		);
+		assert!(parse(r#"<http://example/s> <http://example/p> "1"^^<integer> ."#).is_err());
+		assert!(parse(r#"<http://example/s> <http://example/p> "1"^^xsd:integer ."#).is_err());
+	}
+
+	#[test]
+	fn parse_comments() {
+		assert_eq!(parse(""), Ok(None));
+		assert_eq!(parse("  \t"), Ok(None));
+		assert_eq!(
+			parse("# <http://example/s> <http://example/p> <http://example/o> ."),
+			Ok(None)
+		);
+
+		for line in [
+			"<http://example/s> <http://example/p> <http://example/o> . # comment",
+			"<http://example/s> <http://example/p> <http://example/o> .# comment",
+			"<http://example/s> <http://example/p> <http://example/o> <http://example/g> .#",
+		] {
+			assert!(parse(line).unwrap().is_some(), "{line}")
+		}
+
+		assert_eq!(
+			parse("<http://example/s> <http://example/p> <http://example/o> . x"),
+			Err(ParseErrorKind::Unexpected('x'))
+		)
+	}
+
+	#[test]
+	fn parse_blank_labels() {
+		let quad = parse("_:s <http://example/p> _:o.").unwrap().unwrap();
+		assert_eq!(quad.0, blank("_:s"));
+		assert_eq!(quad.2, Object::Id(blank("_:o")));
+		assert_eq!(quad.3, None);
+
+		let quad = parse("_:s.1 <http://example/p> _:o.2 _:g.3.")
+			.unwrap()
+			.unwrap();
+		assert_eq!(quad.0, blank("_:s.1"));
+		assert_eq!(quad.2, Object::Id(blank("_:o.2")));
+		assert_eq!(quad.3, Some(blank("_:g.3")));
+
+		assert!(parse("_: <http://example/p> <http://example/o> .").is_err());
+		assert!(parse("_:a <http://example/p> _:b.. .").is_err());
+		assert!(parse("<http://example/s> _:p <http://example/o> .").is_err());
+	}
+
+	#[test]
+	fn parse_errors() {
+		for line in [
+			// Relative IRIs.
+			"<s> <http://example/p> <http://example/o> .",
+			// Literal subject or graph.
+			r#""s" <http://example/p> <http://example/o> ."#,
+			r#"<http://example/s> <http://example/p> <http://example/o> "g" ."#,
+			// Missing or extra terms.
+			"<http://example/s> <http://example/p> .",
+			"<http://example/s> <http://example/p> <http://example/o> <http://example/g> <http://example/x> .",
+			// Missing final dot.
+			"<http://example/s> <http://example/p> <http://example/o>",
+			// Unterminated literal.
+			r#"<http://example/s> <http://example/p> "abc ."#,
+			// Raw carriage return in a literal.
+			"<http://example/s> <http://example/p> \"a\rb\" .",
+			// Non N-Quads terms.
+			"<http://example/s> <http://example/p> 1 .",
+			"<http://example/s> <http://example/p> <http://example/o>, <http://example/o2> .",
+			// Spaces in IRIs.
+			"<http://example/s s> <http://example/p> <http://example/o> .",
+		] {
+			assert!(parse(line).is_err(), "{line}")
+		}
+	}
+
+	#[test]
+	fn parser_lines() {
+		let input = "<http://example/s> <http://example/p> \"a\" .\r\n\
+			# comment\n\
+			\n\
+			<http://example/s> <http://example/p> \"b\" <http://example/g> .\n\
+			<http://example/s> <http://example/p> .\n";
+
+		let results: Vec<_> = parse_quads(input).collect();
+		assert_eq!(results.len(), 3);
+		assert!(results[0].is_ok());
+		assert!(results[1].is_ok());
+		assert_eq!(
+			results[2],
+			Err(ParseError {
+				line: 5,
+				kind: ParseErrorKind::Unexpected('.')
+			})
+		);
+	}
+
+	/// Runs the W3C [N-Quads test
+	/// suite](https://w3c.github.io/rdf-tests/rdf/rdf11/rdf-n-quads/).
+	///
+	/// The suite is read from a local copy of the
+	/// [`rdf-tests`](https://github.com/w3c/rdf-tests) repository
+	/// `rdf/rdf11/rdf-n-quads` directory, given by the `NQUADS_TESTS`
+	/// environment variable. The test is skipped if the variable is not set.
+	/// Negative syntax tests are the `*-bad-*.nq` files, every other `.nq`
+	/// file is a positive syntax test.
+	#[cfg(feature = "std")]
+	#[test]
+	fn w3c_test_suite() {
+		use std::path::PathBuf;
+
+		let Some(root) = std::env::var_os("NQUADS_TESTS").map(PathBuf::from) else {
+			return;
+		};
+
+		let mut failures = Vec::new();
+		for entry in std::fs::read_dir(&root).unwrap() {
+			let path = entry.unwrap().path();
+			let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+				continue;
+			};
+
+			if !name.ends_with(".nq") {
+				continue;
+			}
+
+			let input = std::fs::read_to_string(&path).unwrap();
+			let valid = parse_quads(&input).all(|quad| quad.is_ok());
+			if valid == name.contains("-bad-") {
+				failures.push(name.to_owned())
+			}
+		}
+
+		failures.sort();
+		assert!(failures.is_empty(), "failed tests: {failures:?}")
+	}
+}
@@ -0,0 +1,64 @@
+use super::{Quad, RdfDirection, RdfQuads, ValidId};
+use crate::{ExpandedDocument, IndexedObject, Relabel};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use hashbrown::HashMap;
+use rdf_types::{Generator, VocabularyMut};
+
+/// Incremental RDF serializer.
+///
+/// Converts expanded objects into RDF quads one object at a time, so that a
+/// document can be serialized without first materializing the whole
+/// [`ExpandedDocument`]. Blank node identifiers are relabeled consistently
+/// across all the objects pushed into the same stream.
+///
+/// Unlike [`ExpandedDocument`], the stream does not deduplicate top-level
+/// objects, hence the same quad may be emitted more than once.
+pub struct QuadsStream<T, B> {
+	relabeling: HashMap<B, ValidId<T, B>>,
+	buffer: ryu_js::Buffer,
+	rdf_direction: Option<RdfDirection>,
+	produce_generalized_rdf: bool,
+}
+
+impl<T, B> QuadsStream<T, B> {
+	pub fn new(rdf_direction: Option<RdfDirection>, produce_generalized_rdf: bool) -> Self {
+		Self {
+			relabeling: HashMap::new(),
+			buffer: ryu_js::Buffer::new(),
+			rdf_direction,
+			produce_generalized_rdf,
+		}
+	}
+
+	/// Relabels and canonicalizes the given top-level `object`, and returns
+	/// the quads it defines.
+	pub fn push_with<N, G>(
+		&mut self,
+		vocabulary: &mut N,
+		generator: &mut G,
+		mut object: IndexedObject<T, B>,
+	) -> Vec<Quad<T, B, N::Literal>>
+	where
+		N: VocabularyMut<Iri = T, BlankId = B>,
+		N::Literal: Clone,
+		G: Generator<N>,
+		T: Clone + Eq + Hash,
+		B: Clone + Eq + Hash,
+	{
+		object.relabel_with(vocabulary, generator, &mut self.relabeling);
+		object.canonicalize_with(&mut self.buffer);
+
+		let mut doc = ExpandedDocument::new();
+		doc.insert(object);
+
+		doc.rdf_quads_full(
+			vocabulary,
+			generator,
+			self.rdf_direction,
+			self.produce_generalized_rdf,
+		)
+		.cloned()
+		.collect()
+	}
+}
@@ -0,0 +1,112 @@
+//! `serde` support for expanded documents.
+//!
+//! Expanded documents and objects are (de)serialized in the standard
+//! expanded JSON-LD form, through their [`json_syntax::Value`]
+//! representation. Only the types using [`IriBuf`] and [`BlankIdBuf`] as
+//! identifiers are supported, since other identifier types require a
+//! vocabulary.
+use crate::object::{TryFromJson, TryFromJsonObject};
+use crate::{ExpandedDocument, Indexed, Node, Object};
+use iref::IriBuf;
+use json_ld_syntax::IntoJsonWithContext;
+use rdf_types::BlankIdBuf;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+fn serialize_json<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: Clone + IntoJsonWithContext<()>,
+	S: Serializer,
+{
+	value.clone().into_json_with(&()).serialize(serializer)
+}
+
+fn deserialize_json<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: TryFromJson<IriBuf, BlankIdBuf>,
+	D: Deserializer<'de>,
+{
+	let value = json_syntax::Value::deserialize(deserializer)?;
+	T::try_from_json_in(&mut (), value).map_err(D::Error::custom)
+}
+
+impl Serialize for ExpandedDocument {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serialize_json(self, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for ExpandedDocument {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserialize_json(deserializer)
+	}
+}
+
+impl Serialize for Object {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serialize_json(self, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Object {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserialize_json(deserializer)
+	}
+}
+
+impl Serialize for Node {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serialize_json(self, serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Node {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		match json_syntax::Value::deserialize(deserializer)? {
+			json_syntax::Value::Object(object) => {
+				Node::try_from_json_object_in(&mut (), object).map_err(D::Error::custom)
+			}
+			other => Err(D::Error::custom(
+				crate::object::InvalidExpandedJson::Unexpected(
+					other.kind(),
+					json_syntax::Kind::Object,
+				),
+			)),
+		}
+	}
+}
+
+impl<T: Clone + IntoJsonWithContext<()>> Serialize for Indexed<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serialize_json(self, serializer)
+	}
+}
+
+impl<'de, T: TryFromJsonObject<IriBuf, BlankIdBuf>> Deserialize<'de> for Indexed<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		deserialize_json(deserializer)
+	}
+}
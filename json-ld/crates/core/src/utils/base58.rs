@@ -0,0 +1,79 @@
+//! Base58 encoding using the Bitcoin alphabet (base58btc).
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Decodes a base58btc string.
+///
+/// Returns `None` if the input contains a character outside of the alphabet.
+pub fn decode(value: &str) -> Option<Vec<u8>> {
+	// Little-endian base 256 digits.
+	let mut bytes: Vec<u8> = Vec::new();
+	for c in value.bytes() {
+		let mut carry = ALPHABET.iter().position(|&a| a == c)? as u32;
+		for b in &mut bytes {
+			carry += (*b as u32) * 58;
+			*b = carry as u8;
+			carry >>= 8
+		}
+
+		while carry > 0 {
+			bytes.push(carry as u8);
+			carry >>= 8
+		}
+	}
+
+	let zeros = value.bytes().take_while(|&c| c == b'1').count();
+	bytes.extend(core::iter::repeat(0).take(zeros));
+	bytes.reverse();
+	Some(bytes)
+}
+
+/// Encodes the given bytes as a base58btc string.
+pub fn encode(bytes: &[u8]) -> String {
+	// Little-endian base 58 digits.
+	let mut digits: Vec<u8> = Vec::new();
+	for &b in bytes {
+		let mut carry = b as u32;
+		for d in &mut digits {
+			carry += (*d as u32) << 8;
+			*d = (carry % 58) as u8;
+			carry /= 58
+		}
+
+		while carry > 0 {
+			digits.push((carry % 58) as u8);
+			carry /= 58
+		}
+	}
+
+	let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+	let mut result = String::with_capacity(zeros + digits.len());
+	result.extend(core::iter::repeat('1').take(zeros));
+	result.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+	result
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn decode_leading_zeros() {
+		assert_eq!(decode("11"), Some(alloc::vec![0, 0]));
+		assert_eq!(decode("1112"), Some(alloc::vec![0, 0, 0, 1]));
+		assert_eq!(decode("5R"), Some(alloc::vec![1, 0]));
+		assert_eq!(decode("5Q"), Some(alloc::vec![255]));
+		assert_eq!(decode("0"), None);
+	}
+
+	#[test]
+	fn round_trip() {
+		for bytes in [&[][..], &[0, 0, 1], &[255, 254, 0, 3], b"hello world"] {
+			assert_eq!(decode(&encode(bytes)).as_deref(), Some(bytes))
+		}
+
+		assert_eq!(encode(b"hello world"), "StV1DL6CwTryKyV");
+	}
+}
@@ -1,5 +1,6 @@
 //! Utility functions.
 
+pub mod base58;
 mod comparison;
 mod hash;
 
@@ -1,12 +1,13 @@
-use contextual::DisplayWithContext;
-#[cfg(feature = "std")]
-use contextual::WithContext;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use contextual::{DisplayWithContext, WithContext};
+use iref::IriBuf;
 
 /// Warning handler.
 ///
 /// This trait is implemented by the unit type `()` which ignores warnings.
 /// You can use [`Print`] or [`PrintWith`] to print warnings on the standard
-/// output or implement your own handler.
+/// output, [`Collect`] to store them, or implement your own handler.
 pub trait Handler<N, W> {
 	/// Handle a warning with the given `vocabulary`.
 	fn handle(&mut self, vocabulary: &N, warning: W);
@@ -51,3 +52,134 @@ impl<N, W: DisplayWithContext<N>> Handler<N, W> for PrintWith {
 		}
 	}
 }
+
+/// Machine-readable warning code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Code {
+	/// A term has the form of a keyword (`@` followed by letters).
+	KeywordLikeTerm,
+
+	/// An IRI mapping has the form of a keyword.
+	KeywordLikeValue,
+
+	/// A key has the form of a keyword.
+	KeywordLikeKey,
+
+	/// Malformed IRI.
+	MalformedIri,
+
+	/// Language tag not well-formed according to BCP47.
+	MalformedLanguageTag,
+
+	/// Empty term.
+	EmptyTerm,
+
+	/// Blank node identifier used as property.
+	BlankNodeIdProperty,
+
+	/// Relative IRI reference that cannot be resolved against a base IRI.
+	UnresolvedRelativeIri,
+
+	/// Several entries of an object expand to the same key.
+	DuplicateKey,
+}
+
+impl Code {
+	/// Returns the string representation of the code.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::KeywordLikeTerm => "keyword-like-term",
+			Self::KeywordLikeValue => "keyword-like-value",
+			Self::KeywordLikeKey => "keyword-like-key",
+			Self::MalformedIri => "malformed-iri",
+			Self::MalformedLanguageTag => "malformed-language-tag",
+			Self::EmptyTerm => "empty-term",
+			Self::BlankNodeIdProperty => "blank-node-id-property",
+			Self::UnresolvedRelativeIri => "unresolved-relative-iri",
+			Self::DuplicateKey => "duplicate-key",
+		}
+	}
+}
+
+impl core::fmt::Display for Code {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+/// Warning that can be described as a structured value.
+pub trait Structured<N> {
+	/// Returns the warning code.
+	fn warning_code(&self) -> Code;
+
+	/// Returns the offending term, if any.
+	fn term_with(&self, vocabulary: &N) -> Option<String>;
+}
+
+/// Warning stored by the [`Collect`] handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Collected {
+	/// Warning code.
+	pub code: Code,
+
+	/// Offending term.
+	pub term: Option<String>,
+
+	/// IRI of the document being processed when the warning was emitted.
+	pub source: Option<IriBuf>,
+
+	/// Human readable message.
+	pub message: String,
+}
+
+/// Collects warnings as structured values.
+///
+/// Contrarily to [`Print`] and [`PrintWith`], warnings are not lost and can
+/// be inspected or reported through an API once processing is done.
+#[derive(Debug, Default, Clone)]
+pub struct Collect {
+	source: Option<IriBuf>,
+	warnings: Vec<Collected>,
+}
+
+impl Collect {
+	/// Creates a new empty collector.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Sets the IRI of the document being processed, attached to the
+	/// warnings collected from now on.
+	pub fn set_source(&mut self, source: Option<IriBuf>) {
+		self.source = source
+	}
+
+	/// Returns the collected warnings.
+	pub fn warnings(&self) -> &[Collected] {
+		&self.warnings
+	}
+
+	/// Checks that no warning has been collected.
+	pub fn is_empty(&self) -> bool {
+		self.warnings.is_empty()
+	}
+
+	/// Returns the collected warnings.
+	pub fn into_warnings(self) -> Vec<Collected> {
+		self.warnings
+	}
+}
+
+impl<N, W: Structured<N> + DisplayWithContext<N>> Handler<N, W> for Collect {
+	fn handle(&mut self, vocabulary: &N, warning: W) {
+		self.warnings.push(Collected {
+			code: warning.warning_code(),
+			term: warning.term_with(vocabulary),
+			source: self.source.clone(),
+			message: warning.with(vocabulary).to_string(),
+		})
+	}
+}
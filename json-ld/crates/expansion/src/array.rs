@@ -1,15 +1,14 @@
-use crate::{expand_element, ActiveProperty, Error, Expanded, Loader, Options};
+use crate::{expand_element, ActiveProperty, Error, Expanded, Loader, Options, Report};
 use alloc::boxed::Box;
 use alloc::vec::Vec;
-use async_recursion::async_recursion;
 use core::hash::Hash;
 use json_ld_core::{context::TermDefinitionRef, object, Context, Environment, Object};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_ld_syntax::ContainerKind;
 use json_syntax::Array;
 use rdf_types::VocabularyMut;
 
 #[allow(clippy::too_many_arguments)]
-#[async_recursion(?Send)]
 pub(crate) async fn expand_array<'a, N, L>(
 	env: Environment<'a, N, L>,
 	active_context: &Context<N::Iri, N::BlankId>,
@@ -17,13 +16,14 @@ pub(crate) async fn expand_array<'a, N, L>(
 	active_property_definition: Option<TermDefinitionRef<'a, N::Iri, N::BlankId>>,
 	element: &'a Array,
 	base_url: Option<N::Iri>,
-	options: Options,
+	options: &Options,
 	from_map: bool,
+	mut report: Report<'_>,
 ) -> Result<Expanded<N::Iri, N::BlankId>, Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + Eq + Hash,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// Initialize an empty array, result.
@@ -53,6 +53,7 @@ where
 			base_url.clone(),
 			options,
 			from_map,
+			report.reborrow(),
 		))
 		.await?;
 
@@ -1,12 +1,15 @@
 use super::expand_element;
-use crate::{ActiveProperty, Error, Loader, Options};
-use alloc::boxed::Box;
+use crate::{ActiveProperty, Error, Expanded, Loader, Options, OutputOrder, Report};
+use alloc::vec::Vec;
+use contextual::WithContext;
+use core::cmp::Ordering;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
-use json_ld_core::{Context, Environment, ExpandedDocument, IndexedObject, Object};
+use json_ld_core::object::FragmentRef;
+use json_ld_core::{Context, Environment, ExpandedDocument, Id, IndexedObject, Object};
+use json_ld_core::{MaybeSend, MaybeSync};
 use json_syntax::Value;
-use rdf_types::VocabularyMut;
+use rdf_types::{Vocabulary, VocabularyMut};
 
 /// Expand the given JSON-LD document.
 ///
@@ -19,40 +22,519 @@ pub(crate) fn expand<'a, N, L>(
 	active_context: Context<N::Iri, N::BlankId>,
 	base_url: Option<N::Iri>,
 	options: Options,
-) -> Pin<Box<dyn Future<Output = Result<ExpandedDocument<N::Iri, N::BlankId>, Error>> + 'a>>
+	mut report: Report<'a>,
+) -> impl Future<Output = Result<ExpandedDocument<N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + Eq + Hash,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	L: Loader,
 {
-	Box::pin(async move {
+	async move {
+		let options = &options;
 		let expanded = expand_element(
-			env,
+			Environment {
+				vocabulary: &mut *env.vocabulary,
+				loader: env.loader,
+			},
 			&active_context,
 			ActiveProperty::None,
 			document,
 			base_url,
 			options,
 			false,
+			report.reborrow(),
 		)
-		.await?;
-		if expanded.len() == 1 {
-			let obj = expanded.into_iter().next().unwrap();
-			match obj.into_unnamed_graph() {
-				Ok(graph) => Ok(ExpandedDocument::from(graph)),
-				Err(obj) => {
-					let mut result = ExpandedDocument::new();
-					if filter_top_level_item(&obj) {
-						result.insert(obj);
+		.await
+		.map_err(|e| e.resolve(document))?;
+		report.resolve(document);
+		let mut result = into_document(expanded, options)?;
+		sort_document(env.vocabulary, &mut result, options);
+		Ok(result)
+	}
+}
+
+/// Turns the result of the expansion algorithm on the whole input document
+/// into an expanded document.
+pub(crate) fn into_document<T, B>(
+	expanded: Expanded<T, B>,
+	options: &Options,
+) -> Result<ExpandedDocument<T, B>, Error>
+where
+	T: Eq + Hash,
+	B: Eq + Hash,
+{
+	NodeLimit::new(options).consume(&expanded)?;
+	Ok(collect_document(expanded))
+}
+
+fn collect_document<T, B>(expanded: Expanded<T, B>) -> ExpandedDocument<T, B>
+where
+	T: Eq + Hash,
+	B: Eq + Hash,
+{
+	if expanded.len() == 1 {
+		let obj = expanded.into_iter().next().unwrap();
+		match obj.into_unnamed_graph() {
+			Ok(graph) => ExpandedDocument::from(graph),
+			Err(obj) => {
+				let mut result = ExpandedDocument::new();
+				if filter_top_level_item(&obj) {
+					result.insert(obj);
+				}
+				result
+			}
+		}
+	} else {
+		expanded.into_iter().filter(filter_top_level_item).collect()
+	}
+}
+
+/// Expand the given JSON-LD document, passing each top-level expanded object
+/// to `f` as soon as it is available.
+///
+/// If the document is an array, or an object with only a `@context` and a
+/// `@graph` array, its items are expanded one at a time so that the whole
+/// expanded document is never held in memory. Otherwise the document is
+/// expanded at once before its objects are passed to `f`.
+pub(crate) fn expand_each<'a, N, L, F>(
+	env: Environment<'a, N, L>,
+	document: &'a Value,
+	active_context: Context<N::Iri, N::BlankId>,
+	base_url: Option<N::Iri>,
+	options: Options,
+	f: &'a mut F,
+) -> impl Future<Output = Result<(), Error>> + MaybeSend + 'a
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+	F: FnMut(&mut N, IndexedObject<N::Iri, N::BlankId>) + MaybeSend,
+{
+	async move {
+		let options = &options;
+		let f = &mut |vocabulary: &mut N, mut obj: IndexedObject<N::Iri, N::BlankId>| {
+			sort_object(vocabulary, &mut obj, options);
+			f(vocabulary, obj)
+		};
+
+		let mut node_limit = NodeLimit::new(options);
+		match (document, graph_document(document)) {
+			(Value::Array(items), _) => {
+				// A single top-level object consisting only of a `@graph` is
+				// replaced by its content, so the first object is held back
+				// until we know it is not alone.
+				let mut first = None;
+				let mut count = 0usize;
+
+				for item in items {
+					let expanded = expand_element(
+						Environment {
+							vocabulary: &mut *env.vocabulary,
+							loader: env.loader,
+						},
+						&active_context,
+						ActiveProperty::None,
+						item,
+						base_url.clone(),
+						options,
+						false,
+						Report::disabled(),
+					)
+					.await
+					.map_err(|e| e.resolve(document))?;
+					node_limit.consume(&expanded)?;
+
+					for obj in expanded {
+						count += 1;
+						if count == 1 {
+							first = Some(obj)
+						} else {
+							if let Some(first) = first.take() {
+								if filter_top_level_item(&first) {
+									f(env.vocabulary, first)
+								}
+							}
+
+							if filter_top_level_item(&obj) {
+								f(env.vocabulary, obj)
+							}
+						}
 					}
-					Ok(result)
 				}
+
+				if let Some(first) = first {
+					emit_single(env.vocabulary, first, f)
+				}
+
+				Ok(())
+			}
+			(_, Some((context_entry, items))) => {
+				let active_context = process_graph_context(
+					Environment {
+						vocabulary: &mut *env.vocabulary,
+						loader: env.loader,
+					},
+					document,
+					context_entry,
+					&active_context,
+					base_url.clone(),
+					options,
+				)
+				.await?;
+
+				for item in items {
+					let expanded = expand_element(
+						Environment {
+							vocabulary: &mut *env.vocabulary,
+							loader: env.loader,
+						},
+						&active_context,
+						ActiveProperty::Some("@graph"),
+						item,
+						base_url.clone(),
+						options,
+						false,
+						Report::disabled(),
+					)
+					.await
+					.map_err(|e| e.resolve(document))?;
+					node_limit.consume(&expanded)?;
+
+					for obj in expanded {
+						if filter_top_level_item(&obj) {
+							f(env.vocabulary, obj)
+						}
+					}
+				}
+
+				Ok(())
 			}
+			_ => {
+				let expanded = expand_element(
+					Environment {
+						vocabulary: &mut *env.vocabulary,
+						loader: env.loader,
+					},
+					&active_context,
+					ActiveProperty::None,
+					document,
+					base_url,
+					options,
+					false,
+					Report::disabled(),
+				)
+				.await
+				.map_err(|e| e.resolve(document))?;
+				node_limit.consume(&expanded)?;
+
+				if expanded.len() == 1 {
+					let obj = expanded.into_iter().next().unwrap();
+					emit_single(env.vocabulary, obj, f)
+				} else {
+					for obj in expanded {
+						if filter_top_level_item(&obj) {
+							f(env.vocabulary, obj)
+						}
+					}
+				}
+
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Expand the given owned JSON-LD document, releasing the input as it goes.
+///
+/// If the document is an array, or an object with only a `@context` and a
+/// `@graph` array, each item is replaced with `null` as soon as it is
+/// expanded, so that its memory is released before the next item is
+/// expanded. Otherwise the document is expanded at once before being
+/// dropped. No memory of the input document is reused for the expanded
+/// document.
+pub(crate) fn expand_owned<'a, N, L>(
+	env: Environment<'a, N, L>,
+	mut document: Value,
+	active_context: Context<N::Iri, N::BlankId>,
+	base_url: Option<N::Iri>,
+	options: Options,
+) -> impl Future<Output = Result<ExpandedDocument<N::Iri, N::BlankId>, Error>> + MaybeSend + 'a
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	async move {
+		let options = &options;
+		let document = &mut document;
+		let graph_context = match graph_document(document) {
+			Some((context_entry, _)) => Some(
+				process_graph_context(
+					Environment {
+						vocabulary: &mut *env.vocabulary,
+						loader: env.loader,
+					},
+					document,
+					context_entry,
+					&active_context,
+					base_url.clone(),
+					options,
+				)
+				.await?,
+			),
+			None => None,
+		};
+
+		if let Some(local_context) = graph_context {
+			let items = document
+				.as_object_mut()
+				.unwrap()
+				.get_unique_mut("@graph")
+				.ok()
+				.flatten()
+				.and_then(Value::as_array_mut)
+				.unwrap();
+
+			let objects = expand_items(
+				Environment {
+					vocabulary: &mut *env.vocabulary,
+					loader: env.loader,
+				},
+				items,
+				&local_context,
+				ActiveProperty::Some("@graph"),
+				base_url,
+				options,
+			)
+			.await
+			.map_err(|e| e.resolve(document))?;
+
+			let mut result = objects.into_iter().filter(filter_top_level_item).collect();
+			sort_document(env.vocabulary, &mut result, options);
+			Ok(result)
+		} else if let Value::Array(items) = &mut *document {
+			let objects = expand_items(
+				Environment {
+					vocabulary: &mut *env.vocabulary,
+					loader: env.loader,
+				},
+				items,
+				&active_context,
+				ActiveProperty::None,
+				base_url,
+				options,
+			)
+			.await
+			.map_err(|e| e.resolve(document))?;
+
+			let mut result = collect_document(Expanded::Array(objects));
+			sort_document(env.vocabulary, &mut result, options);
+			Ok(result)
 		} else {
-			Ok(expanded.into_iter().filter(filter_top_level_item).collect())
+			expand(
+				env,
+				document,
+				active_context,
+				base_url,
+				options.clone(),
+				Report::disabled(),
+			)
+			.await
 		}
-	})
+	}
+}
+
+/// Returns the `@context` entry and the `@graph` items of the given document
+/// if it is an object with only these two entries, `@graph` being an array.
+///
+/// The items of such a document are expanded one at a time, like the items
+/// of a top-level array.
+fn graph_document(document: &Value) -> Option<(&Value, &[Value])> {
+	let object = document.as_object()?;
+	if object.len() != 2 {
+		return None;
+	}
+
+	let context_entry = object.get_unique("@context").ok().flatten()?;
+	match object.get_unique("@graph").ok().flatten()? {
+		Value::Array(items) => Some((context_entry, items)),
+		_ => None,
+	}
+}
+
+/// Processes the `@context` entry of a document returned by
+/// [`graph_document`], giving the active context used to expand its
+/// `@graph` items.
+///
+/// The returned error is resolved against `document`.
+async fn process_graph_context<N, L>(
+	env: Environment<'_, N, L>,
+	document: &Value,
+	context_entry: &Value,
+	active_context: &Context<N::Iri, N::BlankId>,
+	base_url: Option<N::Iri>,
+	options: &Options,
+) -> Result<Context<N::Iri, N::BlankId>, Error>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	use json_ld_context_processing::Process;
+	use json_ld_syntax::TryFromJson;
+
+	let located = |e: Error| e.at(context_entry).resolve(document);
+
+	let local_context = json_ld_syntax::context::Context::try_from_json(context_entry.clone())
+		.map_err(|e| located(e.into()))?;
+
+	Ok(local_context
+		.process_with(
+			env.vocabulary,
+			active_context,
+			env.loader,
+			base_url,
+			options.into(),
+		)
+		.await
+		.map_err(|e| located(e.into()))?
+		.into_processed())
+}
+
+/// Expands the given top-level `items`, replacing each of them with `null`
+/// once expanded, and counting their node objects against the
+/// [node limit](Options::max_nodes).
+///
+/// The returned error is not resolved.
+async fn expand_items<N, L>(
+	env: Environment<'_, N, L>,
+	items: &mut [Value],
+	active_context: &Context<N::Iri, N::BlankId>,
+	active_property: ActiveProperty<'_>,
+	base_url: Option<N::Iri>,
+	options: &Options,
+) -> Result<Vec<IndexedObject<N::Iri, N::BlankId>>, Error>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	let mut node_limit = NodeLimit::new(options);
+	let mut objects = Vec::new();
+
+	for item in items {
+		let expanded = expand_element(
+			Environment {
+				vocabulary: &mut *env.vocabulary,
+				loader: env.loader,
+			},
+			active_context,
+			active_property,
+			item,
+			base_url.clone(),
+			options,
+			false,
+			Report::disabled(),
+		)
+		.await?;
+		node_limit.consume(&expanded)?;
+
+		objects.extend(expanded);
+		*item = Value::Null
+	}
+
+	Ok(objects)
+}
+
+/// Sorts the properties of the given expanded `document` according to
+/// [`Options::ordered_output`].
+pub(crate) fn sort_document<N: Vocabulary>(
+	vocabulary: &N,
+	document: &mut ExpandedDocument<N::Iri, N::BlankId>,
+	options: &Options,
+) where
+	N::Iri: Eq + Hash,
+	N::BlankId: Eq + Hash,
+{
+	if options.ordered_output == OutputOrder::Lexicographic {
+		document.sort_properties_by(&mut compare_properties(vocabulary))
+	}
+}
+
+/// Sorts the properties of the given top-level `object` according to
+/// [`Options::ordered_output`].
+fn sort_object<N: Vocabulary>(
+	vocabulary: &N,
+	object: &mut IndexedObject<N::Iri, N::BlankId>,
+	options: &Options,
+) where
+	N::Iri: Eq + Hash,
+	N::BlankId: Eq + Hash,
+{
+	if options.ordered_output == OutputOrder::Lexicographic {
+		object.sort_properties_by(&mut compare_properties(vocabulary))
+	}
+}
+
+fn compare_properties<N: Vocabulary>(
+	vocabulary: &N,
+) -> impl '_ + FnMut(&Id<N::Iri, N::BlankId>, &Id<N::Iri, N::BlankId>) -> Ordering {
+	move |a, b| a.with(vocabulary).as_str().cmp(b.with(vocabulary).as_str())
+}
+
+fn emit_single<N, F>(vocabulary: &mut N, obj: IndexedObject<N::Iri, N::BlankId>, f: &mut F)
+where
+	N: VocabularyMut,
+	F: FnMut(&mut N, IndexedObject<N::Iri, N::BlankId>),
+{
+	match obj.into_unnamed_graph() {
+		Ok(graph) => {
+			for obj in graph {
+				f(vocabulary, obj)
+			}
+		}
+		Err(obj) => {
+			if filter_top_level_item(&obj) {
+				f(vocabulary, obj)
+			}
+		}
+	}
+}
+
+/// Remaining number of node objects allowed by
+/// [`Options::max_nodes`].
+struct NodeLimit(Option<usize>);
+
+impl NodeLimit {
+	fn new(options: &Options) -> Self {
+		Self(options.max_nodes)
+	}
+
+	/// Counts the node objects of the given expanded element against the limit.
+	fn consume<T, B>(&mut self, expanded: &Expanded<T, B>) -> Result<(), Error> {
+		if let Some(remaining) = &mut self.0 {
+			for obj in expanded {
+				let count = obj.traverse().filter(is_node_fragment).count();
+				*remaining = remaining.checked_sub(count).ok_or(Error::TooManyNodes)?
+			}
+		}
+
+		Ok(())
+	}
+}
+
+fn is_node_fragment<T, B>(fragment: &FragmentRef<T, B>) -> bool {
+	match fragment {
+		FragmentRef::Node(_) | FragmentRef::IndexedNode(_) => true,
+		FragmentRef::Object(o) => o.is_node(),
+		FragmentRef::IndexedObject(o) => o.inner().is_node(),
+		_ => false,
+	}
 }
 
 pub(crate) fn filter_top_level_item<T, B>(item: &IndexedObject<T, B>) -> bool {
@@ -0,0 +1,17 @@
+use crate::Location;
+use alloc::string::String;
+
+/// Entry of the input document dropped during expansion.
+///
+/// An entry is dropped when its key does not expand to an IRI, a blank node
+/// identifier or a keyword, because no term definition matches it (or
+/// because it is explicitly mapped to `null`). The whole value of the entry
+/// is then lost. See [`Expand::expand_full_with_report`](crate::Expand::expand_full_with_report).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DroppedEntry {
+	/// Key of the entry.
+	pub key: String,
+
+	/// Location of the entry value in the input document.
+	pub location: Location,
+}
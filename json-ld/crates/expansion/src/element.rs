@@ -1,16 +1,21 @@
 use crate::{
-	expand_array, expand_iri, expand_literal, expand_node, expand_value, Error, Expanded,
-	GivenLiteralValue, LiteralValue, Loader, Options,
+	expand_array, expand_iri, expand_literal, expand_node, expand_value, is_framing_keyword,
+	process_scoped_context, scoped_context_key, DuplicateKeys, Error, Expanded, GivenLiteralValue,
+	KeywordAction, LiteralValue, Loader, Options, Report, Warning,
 };
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::vec::Vec;
-use async_recursion::async_recursion;
 use core::hash::Hash;
-use json_ld_context_processing::{Options as ProcessingOptions, Process};
-use json_ld_core::{object, Context, Environment, Indexed, Object, Term};
-use json_ld_syntax::{Keyword, Nullable};
+use hashbrown::{HashMap, HashSet};
+use json_ld_context_processing::Process;
+use json_ld_core::{
+	context::{ContextCacheKey, Scope},
+	object, Context, Environment, Id, Indexed, Object, Term,
+};
+use json_ld_core::{MaybeSend, MaybeSync};
+use json_ld_syntax::{is_keyword_like, Keyword, Nullable};
 use json_syntax::{object::Entry, Value};
 use mown::Mown;
 use rdf_types::VocabularyMut;
@@ -23,12 +28,12 @@ pub(crate) enum ActiveProperty<'a> {
 }
 
 impl<'a> ActiveProperty<'a> {
-	// pub fn as_str(&self) -> Option<&'a str> {
-	// 	match self {
-	// 		Self::Some(Meta(s, _)) => Some(s),
-	// 		Self::None => None
-	// 	}
-	// }
+	pub fn as_str(&self) -> Option<&'a str> {
+		match self {
+			Self::Some(s) => Some(s),
+			Self::None => None,
+		}
+	}
 
 	pub fn is_some(&self) -> bool {
 		matches!(self, Self::Some(_))
@@ -73,21 +78,54 @@ pub(crate) type ElementExpansionResult<T, B> = Result<Expanded<T, B>, Error>;
 ///
 /// See <https://www.w3.org/TR/json-ld11-api/#expansion-algorithm>.
 /// The default specified value for `ordered` and `from_map` is `false`.
+///
+/// Errors are located at the innermost element whose expansion failed.
 #[allow(clippy::too_many_arguments)]
-#[async_recursion(?Send)]
 pub(crate) async fn expand_element<'a, N, L>(
+	env: Environment<'a, N, L>,
+	active_context: &'a Context<N::Iri, N::BlankId>,
+	active_property: ActiveProperty<'a>,
+	element: &'a Value,
+	base_url: Option<N::Iri>,
+	options: &Options,
+	from_map: bool,
+	report: Report<'_>,
+) -> ElementExpansionResult<N::Iri, N::BlankId>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	expand_unlocated_element(
+		env,
+		active_context,
+		active_property,
+		element,
+		base_url,
+		options,
+		from_map,
+		report,
+	)
+	.await
+	.map_err(|e| e.at(element))
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn expand_unlocated_element<'a, N, L>(
 	mut env: Environment<'a, N, L>,
 	active_context: &'a Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'a>,
 	element: &'a Value,
 	base_url: Option<N::Iri>,
-	options: Options,
+	options: &Options,
 	from_map: bool,
+	mut report: Report<'_>,
 ) -> ElementExpansionResult<N::Iri, N::BlankId>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + Eq + Hash,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// If `element` is null, return null.
@@ -95,6 +133,15 @@ where
 		return Ok(Expanded::Null);
 	}
 
+	// Arrays and maps consume one level of the remaining nesting depth.
+	let nested;
+	let options = if element.is_array() || element.is_object() {
+		nested = options.nested()?;
+		&nested
+	} else {
+		options
+	};
+
 	let active_property_definition = active_property.get_from(active_context);
 
 	// If `active_property` has a term definition in `active_context` with a local context,
@@ -122,6 +169,7 @@ where
 				base_url,
 				options,
 				from_map,
+				report,
 			)
 			.await
 		}
@@ -143,6 +191,8 @@ where
 					Nullable::Some(key.as_str().into()),
 					false,
 					Some(options.policy.vocab),
+					// Keys are recorded once expanded in the final active context.
+					&mut Report::disabled(),
 				)? {
 					Some(Term::Keyword(Keyword::Value)) => {
 						preliminary_value_entry = Some(value.clone())
@@ -176,19 +226,17 @@ where
 			// definition for `active_property`, in `active_context` and `true` for
 			// `override_protected`.
 			if let Some(property_scoped_context) = property_scoped_context {
-				let options: ProcessingOptions = options.into();
 				active_context = Mown::Owned(
-					property_scoped_context
-						.process_with(
-							env.vocabulary,
-							active_context.as_ref(),
-							env.loader,
-							property_scoped_base_url,
-							options.with_override(),
-						)
-						.await?
-						.into_processed(), // .err_at(|| active_property.as_ref().map(Meta::metadata).cloned().unwrap_or_default())?
-					                    // .into_inner(),
+					process_scoped_context(
+						env.vocabulary,
+						env.loader,
+						active_context.as_ref(),
+						Scope::Property,
+						property_scoped_context,
+						property_scoped_base_url,
+						options,
+					)
+					.await?,
 				);
 			}
 
@@ -200,21 +248,46 @@ where
 				.map_err(Error::duplicate_key_ref)?
 			{
 				use json_ld_syntax::TryFromJson;
-				let local_context =
-					json_ld_syntax::context::Context::try_from_json(local_context.clone())?;
 
-				active_context = Mown::Owned(
-					local_context
-						.process_with(
-							env.vocabulary,
-							active_context.as_ref(),
-							env.loader,
-							base_url.clone(),
-							options.into(),
-						)
-						.await?
-						.into_processed(),
-				);
+				// Contexts processed on top of an empty active context are
+				// cached, if a cache is attached to the active context.
+				let cache = active_context.context_cache().and_then(|cache| {
+					ContextCacheKey::new(
+						active_context.as_ref(),
+						local_context.clone(),
+						base_url.clone(),
+						options.processing_mode,
+						options.strict,
+						options.override_protected,
+						cache.namespace(),
+					)
+					.map(|key| (cache.clone(), key))
+				});
+
+				match cache.as_ref().and_then(|(cache, key)| cache.get(key)) {
+					Some(context) => active_context = Mown::Owned(context),
+					None => {
+						let local_context =
+							json_ld_syntax::context::Context::try_from_json(local_context.clone())?;
+
+						let context = local_context
+							.process_with(
+								env.vocabulary,
+								active_context.as_ref(),
+								env.loader,
+								base_url.clone(),
+								options.into(),
+							)
+							.await?
+							.into_processed();
+
+						if let Some((cache, key)) = cache {
+							cache.insert(key, context.clone())
+						}
+
+						active_context = Mown::Owned(context);
+					}
+				}
 			}
 
 			let entries: Cow<[Entry]> = if options.ordered {
@@ -231,6 +304,7 @@ where
 					Nullable::Some(key.as_str().into()),
 					false,
 					Some(options.policy.vocab),
+					&mut report,
 				)?;
 
 				if let Some(Term::Keyword(Keyword::Type)) = expanded_key {
@@ -272,19 +346,34 @@ where
 							// `term`'s local context as `local_context`, `base_url` from the term
 							// definition for value in `active_context`, and `false` for `propagate`.
 							let base_url = term_definition.base_url().cloned();
-							let options: ProcessingOptions = options.into();
-							active_context = Mown::Owned(
-								local_context
-									.process_with(
+
+							// The first type-scoped context may have been
+							// pre-compiled on top of `type_scoped_context`.
+							let compiled = match active_context {
+								Mown::Borrowed(_) => {
+									type_scoped_context.type_scoped_contexts().get(
+										term,
+										&scoped_context_key(Scope::Type, base_url.clone(), options),
+									)
+								}
+								Mown::Owned(_) => None,
+							};
+
+							active_context = Mown::Owned(match compiled {
+								Some(context) => context,
+								None => {
+									process_scoped_context(
 										env.vocabulary,
-										active_context.as_ref(),
 										env.loader,
+										active_context.as_ref(),
+										Scope::Type,
+										local_context,
 										base_url,
-										options.without_propagation(),
+										options,
 									)
 									.await?
-									.into_processed(),
-							);
+								}
+							});
 						}
 					}
 				}
@@ -306,6 +395,7 @@ where
 								Nullable::Some(input_type_str.into()),
 								false,
 								Some(options.policy.vocab),
+								&mut report,
 							)
 						})
 						.transpose()?
@@ -329,24 +419,63 @@ where
 					Nullable::Some(key.as_str().into()),
 					false,
 					Some(options.policy.vocab),
+					&mut report,
 				)?;
 
+				let expanded_key = match expanded_key {
+					// Framing keywords are expanded with the node.
+					Some(Term::Null)
+						if options.frame_expansion && is_framing_keyword(key.as_str()) =>
+					{
+						Some(Term::Null)
+					}
+					Some(Term::Null) if is_keyword_like(key.as_str()) => {
+						match options
+							.keyword_handler
+							.as_ref()
+							.map(|h| h.handle(key.as_str(), value))
+						{
+							None | Some(KeywordAction::Drop) => {
+								let warning = Warning::KeywordLikeKey(key.to_string());
+								if options.strict {
+									return Err(Error::Strict(warning));
+								}
+
+								report.warn(warning, value);
+								Some(Term::Null)
+							}
+							Some(KeywordAction::Ignore) => Some(Term::Null),
+							Some(KeywordAction::Property(iri)) => {
+								Some(Term::Id(Id::iri(env.vocabulary.insert(iri.as_iri()))))
+							}
+							Some(KeywordAction::Reject) => {
+								return Err(Error::UnknownKeyword(key.to_string()))
+							}
+						}
+					}
+					expanded_key => expanded_key,
+				};
+
 				if let Some(expanded_key) = expanded_key {
 					match &expanded_key {
 						Term::Keyword(Keyword::Value) => value_entry = Some(value.clone()),
 						Term::Keyword(Keyword::List) => {
 							if active_property.is_some() && active_property != Keyword::Graph {
-								list_entry = Some(value.clone())
+								list_entry = Some(value)
 							}
 						}
-						Term::Keyword(Keyword::Set) => set_entry = Some(value.clone()),
+						Term::Keyword(Keyword::Set) => set_entry = Some(value),
 						_ => (),
 					}
 
 					expanded_entries.push(ExpandedEntry(key, expanded_key, value))
+				} else {
+					report.record(key, value)
 				}
 			}
 
+			let expanded_entries = check_duplicate_keys(expanded_entries, options, &mut report)?;
+
 			if let Some(list_entry) = list_entry {
 				// List objects.
 				let mut index = None;
@@ -366,7 +495,7 @@ where
 				// base URL, and the ordered flags, ensuring that the
 				// result is an array..
 				let mut result = Vec::new();
-				let list_entry = Value::force_as_array(&list_entry);
+				let list_entry = Value::force_as_array(list_entry);
 				for item in list_entry {
 					let e = Box::pin(expand_element(
 						Environment {
@@ -379,6 +508,7 @@ where
 						base_url.clone(),
 						options,
 						false,
+						report.reborrow(),
 					))
 					.await?;
 					result.extend(e)
@@ -408,10 +538,11 @@ where
 					env,
 					active_context.as_ref(),
 					active_property,
-					&set_entry,
+					set_entry,
 					base_url,
 					options,
 					false,
+					report,
 				))
 				.await
 			} else if let Some(value_entry) = value_entry {
@@ -419,10 +550,15 @@ where
 				let expanded_value = expand_value(
 					&mut env,
 					options.policy.vocab,
+					options.language_tags,
+					options.exact_numbers,
+					options.strict,
+					options.frame_expansion,
 					input_type,
 					type_scoped_context,
 					expanded_entries,
 					&value_entry,
+					&mut report,
 				)?;
 
 				if let Some(value) = expanded_value {
@@ -440,6 +576,7 @@ where
 					expanded_entries,
 					base_url,
 					options,
+					report,
 				)
 				.await?;
 				if let Some(result) = e {
@@ -491,11 +628,67 @@ where
 			// `active_property`, and `element` as value.
 			Ok(Expanded::Object(expand_literal(
 				env,
-				options.policy.vocab,
+				options,
 				active_context.as_ref(),
 				active_property,
 				LiteralValue::Given(GivenLiteralValue::new(element)),
+				element,
+				&mut report,
 			)?))
 		}
 	}
 }
+
+/// Applies the [duplicate keys policy](Options::duplicate_keys) to the
+/// expanded entries of an object.
+fn check_duplicate_keys<'a, T, B>(
+	entries: Vec<ExpandedEntry<'a, T, B>>,
+	options: &Options,
+	report: &mut Report<'_>,
+) -> Result<Vec<ExpandedEntry<'a, T, B>>, Error>
+where
+	T: Eq + Hash,
+	B: Eq + Hash,
+{
+	match options.duplicate_keys {
+		DuplicateKeys::Merge => Ok(entries),
+		DuplicateKeys::KeepLast => {
+			let mut last = HashMap::with_capacity(entries.len());
+			for (i, ExpandedEntry(_, term, _)) in entries.iter().enumerate() {
+				last.insert(term, i);
+			}
+
+			let keep: Vec<bool> = entries
+				.iter()
+				.enumerate()
+				.map(|(i, ExpandedEntry(_, term, _))| last[term] == i)
+				.collect();
+
+			Ok(entries
+				.into_iter()
+				.zip(keep)
+				.filter_map(|(entry, keep)| keep.then_some(entry))
+				.collect())
+		}
+		policy => {
+			let mut seen = HashSet::with_capacity(entries.len());
+			for ExpandedEntry(key, term, value) in &entries {
+				// Entries expanding to `null` are dropped anyway.
+				if !matches!(term, Term::Null) && !seen.insert(term) {
+					if policy == DuplicateKeys::Reject {
+						return Err(Error::DuplicateKey((*key).into()));
+					}
+
+					let warning = Warning::DuplicateKey(key.to_string());
+					if options.strict {
+						return Err(Error::Strict(warning));
+					}
+
+					report.warn(warning, value)
+				}
+			}
+
+			Ok(entries)
+		}
+	}
+}
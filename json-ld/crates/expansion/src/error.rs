@@ -1,6 +1,8 @@
+use alloc::boxed::Box;
 use alloc::string::String;
 use json_ld_context_processing::algorithm::RejectVocab;
 use json_ld_syntax::ErrorCode;
+use json_syntax::Value;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -57,6 +59,24 @@ pub enum Error {
 
 	#[error("Forbidden use of `@vocab`")]
 	ForbiddenVocab,
+
+	#[error("Unknown keyword `{0}`")]
+	UnknownKeyword(String),
+
+	#[error("Maximum nesting depth exceeded")]
+	MaxDepthExceeded,
+
+	#[error("Maximum number of nodes exceeded")]
+	TooManyNodes,
+
+	#[error("{0}")]
+	Strict(crate::Warning<String>),
+
+	#[error("Relative IRI reference `{1}` in `{0}` cannot be resolved")]
+	UnresolvedRelativeIri(String, String),
+
+	#[error("{0} (at `{1}`)")]
+	Located(Box<Error>, crate::Location),
 }
 
 impl From<RejectVocab> for Error {
@@ -86,8 +106,51 @@ impl Error {
 			Self::Literal(e) => e.code(),
 			Self::Value(e) => e.code(),
 			Self::ForbiddenVocab => ErrorCode::InvalidVocabMapping,
+			Self::UnknownKeyword(_) => ErrorCode::UnknownKeyword,
+			Self::MaxDepthExceeded => ErrorCode::MaxDepthExceeded,
+			Self::TooManyNodes => ErrorCode::TooManyNodes,
+			Self::Strict(w) => w.code(),
+			Self::UnresolvedRelativeIri(_, _) => ErrorCode::InvalidBaseIri,
+			Self::Located(e, _) => e.code(),
+		}
+	}
+
+	/// Returns the location of the error in the input document, if known.
+	pub fn location(&self) -> Option<&crate::Location> {
+		match self {
+			Self::Located(_, location) => Some(location),
+			_ => None,
+		}
+	}
+
+	/// Returns the error without its location.
+	pub fn unlocated(&self) -> &Self {
+		match self {
+			Self::Located(e, _) => e,
+			e => e,
 		}
 	}
+
+	/// Locates the error at the given element, unless it is already located
+	/// at an inner element.
+	pub(crate) fn at(self, element: &Value) -> Self {
+		match self {
+			Self::Located(e, mut location) => {
+				location.push(element);
+				Self::Located(e, location)
+			}
+			e => Self::Located(Box::new(e), crate::Location::new(element)),
+		}
+	}
+
+	/// Resolves the error location against the input `document`.
+	pub(crate) fn resolve(mut self, document: &Value) -> Self {
+		if let Self::Located(_, location) = &mut self {
+			location.resolve(document)
+		}
+
+		self
+	}
 }
 
 impl Error {
@@ -0,0 +1,343 @@
+//! Frame expansion patterns.
+//!
+//! When the [`frame_expansion`](crate::Options::frame_expansion) flag is set,
+//! the expanded frame may contain patterns that an
+//! [`ExpandedDocument`] cannot represent: wildcards (`{}`), match-none
+//! arrays (`[]`), `@type` default objects, arrays of `@id`, `@value`,
+//! `@language` or `@direction` values, and the framing keywords (`@default`,
+//! `@embed`, `@explicit`, `@omitDefault` and `@requireAll`).
+//!
+//! Those patterns are kept, already expanded, in the [`FRAME_PATTERNS`]
+//! property of the node they belong to. Value object patterns are kept in a
+//! node whose only property is [`FRAME_PATTERNS`]. [`frame_into_json`]
+//! serializes the expanded frame with the patterns put back in place.
+use crate::{expand_iri, Action, ExpandedEntry, InvalidValue, Report};
+use alloc::borrow::ToOwned;
+use alloc::vec;
+use alloc::vec::Vec;
+use contextual::WithContext;
+use json_ld_core::{
+	Context, Environment, ExpandedDocument, Id, Indexed, IndexedObject, Node, Object, Term, Value,
+};
+use json_ld_syntax::{IntoJsonWithContext, Keyword, Nullable};
+use rdf_types::{Vocabulary, VocabularyMut};
+
+/// Property holding the frame patterns of an expanded node.
+///
+/// Its value is a single JSON literal, the map of the node patterns. Input
+/// documents never produce this property since it does not contain a colon.
+pub const FRAME_PATTERNS: &str = "@frame";
+
+/// Keywords only allowed in frames.
+const FRAMING_KEYWORDS: [&str; 5] = [
+	"@default",
+	"@embed",
+	"@explicit",
+	"@omitDefault",
+	"@requireAll",
+];
+
+/// Checks if the given key is a framing keyword.
+pub(crate) fn is_framing_keyword(key: &str) -> bool {
+	FRAMING_KEYWORDS.contains(&key)
+}
+
+/// Checks if the given `@id`, `@language` or `@direction` value is a frame
+/// pattern: an empty map or an array of strings.
+pub(crate) fn is_frame_pattern(value: &json_syntax::Value) -> bool {
+	match value {
+		json_syntax::Value::Object(o) => o.is_empty(),
+		json_syntax::Value::Array(items) => items.iter().all(json_syntax::Value::is_string),
+		_ => false,
+	}
+}
+
+/// Ensures that the given pattern is an array.
+pub(crate) fn pattern_array(value: &json_syntax::Value) -> json_syntax::Value {
+	match value {
+		json_syntax::Value::Array(_) => value.clone(),
+		value => json_syntax::Value::Array(vec![value.clone()]),
+	}
+}
+
+/// Returns the JSON representation of an expanded IRI, or `None` if it is
+/// `null`.
+pub(crate) fn term_into_json<N: Vocabulary>(
+	vocabulary: &N,
+	term: Term<N::Iri, N::BlankId>,
+) -> Option<json_syntax::Value> {
+	match term {
+		Term::Null => None,
+		Term::Id(id) => Some(id.with(vocabulary).as_str().into()),
+		Term::Keyword(k) => Some(k.into_str().into()),
+	}
+}
+
+/// Creates the [`FRAME_PATTERNS`] property entry holding the given patterns.
+pub(crate) fn patterns_entry<T, B>(
+	patterns: json_syntax::Object,
+) -> (Id<T, B>, IndexedObject<T, B>) {
+	(
+		Id::Invalid(FRAME_PATTERNS.to_owned()),
+		Indexed::new(
+			Object::Value(Value::Json(json_syntax::Value::Object(patterns))),
+			None,
+		),
+	)
+}
+
+/// Checks if the given value object entries contain a frame pattern.
+pub(crate) fn is_value_pattern<T, B>(
+	expanded_entries: &[ExpandedEntry<T, B>],
+	value_entry: &json_syntax::Value,
+) -> bool {
+	value_entry.is_array()
+		|| value_entry.is_object()
+		|| expanded_entries.iter().any(|ExpandedEntry(_, key, value)| {
+			matches!(
+				key,
+				Term::Keyword(Keyword::Type | Keyword::Language | Keyword::Direction)
+			) && (value.is_array() || value.is_object())
+		})
+}
+
+/// Expands a value object pattern.
+///
+/// Every pattern entry is expanded into an array, `@type` values being IRI
+/// expanded. The result is a node holding the pattern in its
+/// [`FRAME_PATTERNS`] property.
+pub(crate) fn expand_value_pattern<N, L>(
+	env: &mut Environment<N, L>,
+	vocab_policy: Action,
+	type_scoped_context: &Context<N::Iri, N::BlankId>,
+	expanded_entries: Vec<ExpandedEntry<N::Iri, N::BlankId>>,
+	report: &mut Report<'_>,
+) -> Result<IndexedObject<N::Iri, N::BlankId>, InvalidValue>
+where
+	N: VocabularyMut,
+	N::Iri: Clone,
+	N::BlankId: Clone,
+{
+	let mut pattern = json_syntax::Object::new();
+
+	for ExpandedEntry(key, expanded_key, value) in expanded_entries {
+		let expanded_value = match expanded_key {
+			Term::Keyword(Keyword::Value) => pattern_array(value),
+			Term::Keyword(Keyword::Language) => {
+				if !value.is_string() && !is_frame_pattern(value) {
+					return Err(InvalidValue::LanguageTaggedString);
+				}
+
+				pattern_array(value)
+			}
+			Term::Keyword(Keyword::Direction) => {
+				if !value.is_string() && !is_frame_pattern(value) {
+					return Err(InvalidValue::BaseDirection);
+				}
+
+				pattern_array(value)
+			}
+			Term::Keyword(Keyword::Index) => match value.as_str() {
+				Some(_) => value.clone(),
+				None => return Err(InvalidValue::IndexValue),
+			},
+			Term::Keyword(Keyword::Type) => match value {
+				json_syntax::Value::Object(o) if o.is_empty() => pattern_array(value),
+				value => {
+					let mut types = Vec::new();
+					for ty in json_syntax::Value::force_as_array(value) {
+						let ty = ty.as_str().ok_or(InvalidValue::TypedValue)?;
+						let ty = expand_iri(
+							env,
+							type_scoped_context,
+							Nullable::Some(ty.into()),
+							true,
+							Some(vocab_policy),
+							report,
+						)?;
+
+						match ty {
+							Some(ty @ (Term::Id(_) | Term::Keyword(Keyword::Json))) => {
+								types.extend(term_into_json(&*env.vocabulary, ty))
+							}
+							_ => return Err(InvalidValue::TypedValue),
+						}
+					}
+
+					json_syntax::Value::Array(types)
+				}
+			},
+			_ => return Err(InvalidValue::ValueObject),
+		};
+
+		pattern.insert(key.into(), expanded_value);
+	}
+
+	let mut node = Node::new();
+	let (prop, object) = patterns_entry(pattern);
+	node.insert(prop, object);
+	Ok(Indexed::new(Object::node(node), None))
+}
+
+/// Serializes an expanded frame into JSON, putting back the frame patterns
+/// kept by the [`frame_expansion`](crate::Options::frame_expansion) flag.
+///
+/// The result is the expanded frame used by the
+/// [framing algorithm](https://www.w3.org/TR/json-ld11-framing/).
+pub fn frame_into_json<N: Vocabulary>(
+	frame: ExpandedDocument<N::Iri, N::BlankId>,
+	vocabulary: &N,
+) -> json_syntax::Value {
+	let mut json = frame.into_json_with(vocabulary);
+	restore_patterns(&mut json);
+	json
+}
+
+/// Moves the content of the [`FRAME_PATTERNS`] entries found in `value`
+/// back into their parent map.
+fn restore_patterns(value: &mut json_syntax::Value) {
+	match value {
+		json_syntax::Value::Array(items) => items.iter_mut().for_each(restore_patterns),
+		json_syntax::Value::Object(object) => {
+			let entries: Vec<_> = object.remove(FRAME_PATTERNS).collect();
+			for entry in entries {
+				if let Some(patterns) = patterns_of(entry.value) {
+					for json_syntax::object::Entry { key, value } in patterns {
+						object.insert(key, value);
+					}
+				}
+			}
+
+			for (_, value) in object.iter_mut() {
+				restore_patterns(value)
+			}
+		}
+		_ => (),
+	}
+}
+
+/// Extracts the patterns map from the serialized [`FRAME_PATTERNS`] value,
+/// an array containing a single JSON literal.
+fn patterns_of(value: json_syntax::Value) -> Option<json_syntax::Object> {
+	match value {
+		json_syntax::Value::Array(items) => items.into_iter().find_map(|item| match item {
+			json_syntax::Value::Object(mut literal) => {
+				match literal.remove_unique("@value").ok().flatten()?.value {
+					json_syntax::Value::Object(patterns) => Some(patterns),
+					_ => None,
+				}
+			}
+			_ => None,
+		}),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Error, Expand, Options};
+	use iref::IriBuf;
+	use json_ld_core::{future::block_on, NoLoader};
+	use json_syntax::{BorrowUnordered, Parse};
+	use rdf_types::{vocabulary::no_vocabulary_mut, BlankIdBuf};
+
+	fn expand(input: &str) -> Result<ExpandedDocument<IriBuf, BlankIdBuf>, Error> {
+		let options = Options {
+			frame_expansion: true,
+			..Options::default()
+		};
+
+		block_on(json(input).expand_full(
+			no_vocabulary_mut(),
+			Context::new(None),
+			None,
+			&NoLoader,
+			options,
+		))
+	}
+
+	fn assert_frame(input: &str, expected: &str) {
+		let expanded = frame_into_json(expand(input).unwrap(), &());
+		assert_eq!(expanded.as_unordered(), json(expected).as_unordered())
+	}
+
+	fn json(input: &str) -> json_syntax::Value {
+		json_syntax::Value::parse_str(input).unwrap().0
+	}
+
+	#[test]
+	fn wildcards() {
+		assert_frame(
+			r#"{
+				"@id": {},
+				"@type": {},
+				"http://example.org/p": {}
+			}"#,
+			r#"[{
+				"@id": [{}],
+				"@type": [{}],
+				"http://example.org/p": [{}]
+			}]"#,
+		)
+	}
+
+	#[test]
+	fn match_none() {
+		assert_frame(r#"{ "@type": [] }"#, r#"[{ "@type": [] }]"#)
+	}
+
+	#[test]
+	fn ids_and_default_type() {
+		assert_frame(
+			r#"{
+				"@context": { "@base": "http://example.org/", "@vocab": "http://example.org/" },
+				"@id": ["a", "b"],
+				"@type": { "@default": "T" }
+			}"#,
+			r#"[{
+				"@id": ["http://example.org/a", "http://example.org/b"],
+				"@type": [{ "@default": "http://example.org/T" }]
+			}]"#,
+		)
+	}
+
+	#[test]
+	fn framing_keywords() {
+		assert_frame(
+			r#"{
+				"@context": { "@vocab": "http://example.org/" },
+				"@explicit": true,
+				"p": { "@default": "d", "@embed": "@never" }
+			}"#,
+			r#"[{
+				"@explicit": [{ "@value": true }],
+				"http://example.org/p": [{
+					"@default": [{ "@value": "d" }],
+					"@embed": [{ "@value": "@never" }]
+				}]
+			}]"#,
+		)
+	}
+
+	#[test]
+	fn value_patterns() {
+		assert_frame(
+			r#"{
+				"@context": { "@vocab": "http://example.org/" },
+				"p": { "@value": {}, "@language": ["en", "fr"] },
+				"q": { "@value": ["a", "b"], "@type": "T" }
+			}"#,
+			r#"[{
+				"http://example.org/p": [{ "@value": [{}], "@language": ["en", "fr"] }],
+				"http://example.org/q": [{ "@value": ["a", "b"], "@type": ["http://example.org/T"] }]
+			}]"#,
+		)
+	}
+
+	#[test]
+	fn invalid_type_pattern() {
+		let error = expand(r#"{ "@type": { "@id": "http://example.org/T" } }"#).unwrap_err();
+		assert!(matches!(error.unlocated(), Error::InvalidTypeValue))
+	}
+}
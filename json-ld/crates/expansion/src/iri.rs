@@ -0,0 +1,48 @@
+use crate::{Action, Report};
+use json_ld_context_processing::algorithm::{expand_iri_simple, IriExpansionResult};
+use json_ld_core::{Context, Environment};
+use json_ld_syntax::{ExpandableRef, Nullable};
+use rdf_types::VocabularyMut;
+
+/// Expands the given IRI `value` using the IRI cache of `active_context`.
+///
+/// Only the [`Action::Keep`] vocabulary policy is cached, since the result
+/// of the other policies depends on how the IRI has been expanded.
+///
+/// The expansion is recorded in the term usage `report`.
+pub(crate) fn expand_iri<N, L>(
+	env: &mut Environment<N, L>,
+	active_context: &Context<N::Iri, N::BlankId>,
+	value: Nullable<ExpandableRef>,
+	document_relative: bool,
+	vocab: Option<Action>,
+	report: &mut Report<'_>,
+) -> IriExpansionResult<N>
+where
+	N: VocabularyMut,
+	N::Iri: Clone,
+	N::BlankId: Clone,
+{
+	report.use_iri(&*env.vocabulary, active_context, &value, vocab);
+
+	match value {
+		Nullable::Some(ExpandableRef::String(s)) if matches!(vocab, None | Some(Action::Keep)) => {
+			let cache = active_context.iri_cache();
+			if let Some(term) = cache.get(s, document_relative, vocab.is_some()) {
+				return Ok(term);
+			}
+
+			let term = expand_iri_simple(
+				env,
+				active_context,
+				Nullable::Some(ExpandableRef::String(s)),
+				document_relative,
+				vocab,
+			)?;
+
+			cache.insert(s, document_relative, vocab.is_some(), term.clone());
+			Ok(term)
+		}
+		value => expand_iri_simple(env, active_context, value, document_relative, vocab),
+	}
+}
@@ -0,0 +1,126 @@
+use iref::IriBuf;
+use json_syntax::Value;
+
+/// Expansion of an unknown keyword entry, decided by a [`KeywordHandler`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeywordAction {
+	/// The entry is dropped as if there was no keyword handler: a
+	/// [`Warning::KeywordLikeKey`](crate::Warning::KeywordLikeKey) is emitted.
+	Drop,
+
+	/// The entry is silently dropped.
+	Ignore,
+
+	/// The entry is expanded as a property with the given IRI.
+	Property(IriBuf),
+
+	/// Expansion fails with
+	/// [`Error::UnknownKeyword`](crate::Error::UnknownKeyword).
+	Reject,
+}
+
+/// Hook invoked by the expansion algorithm on unknown keyword entries.
+///
+/// It is called for every entry of a map whose key has the form of a
+/// keyword (`@` followed by letters) but is not a JSON-LD 1.1 keyword, such
+/// as vendor or experimental keywords, or the JSON-LD-star `@annotation`.
+/// This allows integrators to handle such keywords without changing the
+/// expansion algorithm.
+///
+/// The handler is set with
+/// [`Options::keyword_handler`](crate::Options::keyword_handler).
+pub trait KeywordHandler: Sync {
+	/// Decides how to expand the entry `key` with the given `value`.
+	fn handle(&self, key: &str, value: &Value) -> KeywordAction;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Expand, Options};
+	use alloc::string::{String, ToString};
+	use alloc::sync::Arc;
+	use alloc::vec;
+	use alloc::vec::Vec;
+	use json_ld_core::future::block_on;
+	use json_ld_core::{Context, Id, NoLoader};
+	use json_syntax::Parse;
+	use rdf_types::vocabulary::no_vocabulary_mut;
+	use static_iref::iri;
+
+	/// Handler mapping keywords to IRIs known at run time.
+	struct Registry(Vec<(String, IriBuf)>);
+
+	impl KeywordHandler for Registry {
+		fn handle(&self, key: &str, _value: &Value) -> KeywordAction {
+			match self.0.iter().find(|(keyword, _)| keyword == key) {
+				Some((_, iri)) => KeywordAction::Property(iri.clone()),
+				None => KeywordAction::Reject,
+			}
+		}
+	}
+
+	#[test]
+	fn stateful_handler() {
+		let registry = Registry(vec![(
+			"@custom".to_string(),
+			iri!("http://example.org/custom").to_owned(),
+		)]);
+
+		let options = Options {
+			keyword_handler: Some(Arc::new(registry)),
+			..Options::default()
+		};
+
+		let input = Value::parse_str(r#"{ "@id": "http://example.org/a", "@custom": "v" }"#)
+			.unwrap()
+			.0;
+		let expanded = block_on(input.expand_full(
+			no_vocabulary_mut(),
+			Context::new(None),
+			None,
+			&NoLoader,
+			options.clone(),
+		))
+		.unwrap();
+
+		let node = expanded.into_iter().next().unwrap().into_inner();
+		let node = node.into_node().unwrap();
+		let property = Id::iri(iri!("http://example.org/custom").to_owned());
+		assert_eq!(node.get(&property).count(), 1);
+
+		let input = Value::parse_str(r#"{ "@unknown": "v" }"#).unwrap().0;
+		let result = block_on(input.expand_full(
+			no_vocabulary_mut(),
+			Context::new(None),
+			None,
+			&NoLoader,
+			options,
+		));
+		assert!(result.is_err())
+	}
+
+	#[test]
+	fn annotation_is_not_a_keyword() {
+		let input = Value::parse_str(
+			r#"{ "@id": "http://example.org/a", "@annotation": { "http://example.org/p": "v" } }"#,
+		)
+		.unwrap()
+		.0;
+
+		let mut warnings = Vec::new();
+		block_on(input.expand_full_with_warnings(
+			no_vocabulary_mut(),
+			Context::new(None),
+			None,
+			&NoLoader,
+			Options::default(),
+			&mut warnings,
+		))
+		.unwrap();
+
+		assert!(warnings.iter().any(
+			|w| matches!(&w.warning, crate::Warning::KeywordLikeKey(key) if key == "@annotation")
+		))
+	}
+}
@@ -4,6 +4,48 @@
 //! # Usage
 //!
 //! The expansion algorithm is provided by the [`Expand`] trait.
+//! With the `rayon` feature, the `expand_parallel` function expands the
+//! items of large top-level arrays in parallel.
+//!
+//! # Allocations
+//!
+//! The expanded document owns its data and never borrows from the input
+//! JSON value. A borrowed (zero-copy) expanded document is not supported:
+//! it would add a lifetime parameter to the whole object model
+//! ([`Object`](json_ld_core::Object), [`Node`](json_ld_core::Node),
+//! [`Value`](json_ld_core::Value), [`ExpandedDocument`]) and to
+//! every algorithm built on top of it (flattening, compaction, RDF
+//! serialization), none of which could then outlive the input.
+//!
+//! Allocations can still be limited. IRIs and blank node identifiers are
+//! created through the vocabulary: using an interning vocabulary such as
+//! [`IndexVocabulary`](rdf_types::vocabulary::IndexVocabulary) stores each
+//! distinct IRI only once, whatever the number of occurrences. String
+//! literals are copied, but strings of up to
+//! [`SMALL_STRING_CAPACITY`](json_syntax::SMALL_STRING_CAPACITY) bytes are
+//! stored inline without heap allocation.
+//!
+//! Arena allocation of the expanded document is not supported either.
+//! Every allocation goes through the global allocator: the object model is
+//! not parameterized by an allocator, since the allocator API of the
+//! standard collections (and of the `hashbrown` and `smallvec` containers
+//! used by the object model) is not stable yet. Applications needing to
+//! reclaim the memory of an expansion all at once (high-throughput servers,
+//! `no_std` targets prone to fragmentation) can install a region-based
+//! global allocator and drop the expanded document before resetting the
+//! region.
+//!
+//! # Recursion
+//!
+//! The [`Expand`] trait and the document loaders return `impl Future` and
+//! do not box their futures. The expansion algorithm itself is recursive
+//! though, and a recursive `async fn` has no finite future type: each
+//! recursive call to expand a nested element or the entries of a nested
+//! node object is still boxed with `Box::pin`, so expansion allocates one
+//! future per nested element of the input. Removing this
+//! allocation would require rewriting the algorithm as an explicit stack
+//! machine able to suspend at any depth to load a remote context, which is
+//! not planned.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -11,38 +53,64 @@ extern crate alloc;
 
 extern crate thiserror_nostd_notrait as thiserror;
 
-use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
+use json_ld_core::{MaybeSend, MaybeSync};
 
 use json_ld_context_processing::Context;
-use json_ld_core::{Environment, ExpandedDocument, Loader, RemoteDocument};
+use json_ld_core::{Environment, ExpandedDocument, IndexedObject, Loader, RemoteDocument};
 use json_syntax::Value;
 use rdf_types::{vocabulary, vocabulary::BlankIdVocabulary, BlankIdBuf, VocabularyMut};
 
 mod array;
 mod document;
+mod dropped;
 mod element;
 mod error;
 mod expanded;
+mod frame;
+mod iri;
+mod keyword;
 mod literal;
+mod location;
 mod node;
+mod number;
 mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod report;
+mod scoped;
+mod usage;
 mod value;
 mod warning;
 
+pub use dropped::DroppedEntry;
 pub use error::*;
 pub use expanded::*;
+pub use frame::{frame_into_json, FRAME_PATTERNS};
+pub use keyword::*;
+pub use location::*;
 pub use options::*;
+#[cfg(feature = "rayon")]
+pub use parallel::{
+	expand_parallel, expand_parallel_with_report, expand_parallel_with_usage,
+	expand_parallel_with_warnings,
+};
+pub use scoped::compile_type_scoped_contexts;
+pub use usage::TermUsage;
 pub use warning::*;
 
 pub(crate) use array::*;
 pub(crate) use document::filter_top_level_item;
 pub(crate) use element::*;
-pub(crate) use json_ld_context_processing::algorithm::expand_iri_simple as expand_iri;
+pub(crate) use frame::*;
+pub(crate) use iri::expand_iri;
 pub(crate) use literal::*;
 pub(crate) use node::*;
+pub(crate) use number::exact_literal;
+pub(crate) use report::Report;
+pub(crate) use scoped::*;
 pub(crate) use value::*;
 
 /// Result of the document expansion.
@@ -131,13 +199,131 @@ pub trait Expand<Iri> {
 		base_url: Option<N::Iri>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = ExpansionResult<N::Iri, N::BlankId>> + 'a>>
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader;
+
+	/// Expand the document with full options, pushing every entry of the
+	/// input document dropped by the expansion algorithm into `dropped`.
+	///
+	/// An entry is dropped when its key does not expand to an IRI, a blank
+	/// node identifier or a keyword (no term definition matches it and it is
+	/// not a compact or absolute IRI). This can be used to see exactly what
+	/// data is lost when migrating to a new vocabulary.
+	fn expand_full_with_report<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		dropped: &'a mut Vec<DroppedEntry>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader;
+
+	/// Expand the document with full options, pushing every warning emitted
+	/// by the expansion algorithm into `warnings`.
+	///
+	/// Each warning is located in the input document. If the document was
+	/// parsed along with a [`CodeMap`](json_syntax::CodeMap), the span of the
+	/// offending fragment is given by [`Location::span`]. Warnings are not
+	/// collected in [strict](Options::strict) mode, where the first one is
+	/// returned as an error instead.
+	fn expand_full_with_warnings<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		warnings: &'a mut Vec<LocatedWarning>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 		L: Loader;
 
+	/// Expand the document with full options, recording in `usage` the
+	/// terms, keywords and vocabularies used by the expansion algorithm.
+	///
+	/// See [`TermUsage`] for how to find the unused terms of a context.
+	fn expand_full_with_usage<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		usage: &'a mut TermUsage,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader;
+
+	/// Expand the document with full options, passing each top-level
+	/// expanded object to `f` instead of collecting them into an
+	/// [`ExpandedDocument`].
+	///
+	/// When possible (if the document is an array, or an object with only a
+	/// `@context` and a `@graph` array), items are expanded and passed to `f`
+	/// one at a time, so that the whole expanded document is never held in
+	/// memory. Contrarily to [`ExpandedDocument`], objects are not
+	/// deduplicated.
+	fn expand_each_full<'a, N, L, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), Error>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+		F: FnMut(&mut N, IndexedObject<Iri, N::BlankId>) + MaybeSend;
+
+	/// Expand the document with full options, consuming the input document
+	/// while it is expanded.
+	///
+	/// This is meant for pipelines where the input document is not needed
+	/// after expansion, to lower the peak memory usage. If the document is an
+	/// array, or an object with only a `@context` and a `@graph` array, each
+	/// item is dropped as soon as it is expanded, releasing its memory before
+	/// the next item is expanded. Otherwise the document is dropped once
+	/// expanded. The input document is dropped whether expansion succeeds or
+	/// fails.
+	///
+	/// The memory of the input document is released, not reused: the
+	/// expanded objects are allocated separately, just like with
+	/// [`Self::expand_full`].
+	fn expand_owned<'a, N, L>(
+		self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+		Self: Sized;
+
 	/// Expand the input JSON-LD document with the given `vocabulary`
 	/// to interpret identifiers.
 	///
@@ -149,11 +335,11 @@ pub trait Expand<Iri> {
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a L,
-	) -> Pin<Box<dyn Future<Output = ExpansionResult<Iri, N::BlankId>> + 'a>>
+	) -> impl Future<Output = ExpansionResult<Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.expand_full(
@@ -174,10 +360,10 @@ pub trait Expand<Iri> {
 	fn expand<'a, L>(
 		&'a self,
 		loader: &'a L,
-	) -> Pin<Box<dyn Future<Output = ExpansionResult<Iri, BlankIdBuf>> + 'a>>
+	) -> impl Future<Output = ExpansionResult<Iri, BlankIdBuf>> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.expand_with(vocabulary::no_vocabulary_mut(), loader)
@@ -197,11 +383,61 @@ impl<Iri> Expand<Iri> for Value {
 		base_url: Option<N::Iri>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = ExpansionResult<N::Iri, N::BlankId>> + 'a>>
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		document::expand(
+			Environment { vocabulary, loader },
+			self,
+			context,
+			base_url,
+			options,
+			Report::disabled(),
+		)
+	}
+
+	fn expand_full_with_report<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		dropped: &'a mut Vec<DroppedEntry>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		document::expand(
+			Environment { vocabulary, loader },
+			self,
+			context,
+			base_url,
+			options,
+			Report::new(Some(dropped), None, None),
+		)
+	}
+
+	fn expand_full_with_warnings<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		warnings: &'a mut Vec<LocatedWarning>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		document::expand(
@@ -210,6 +446,81 @@ impl<Iri> Expand<Iri> for Value {
 			context,
 			base_url,
 			options,
+			Report::new(None, Some(warnings), None),
+		)
+	}
+
+	fn expand_full_with_usage<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		usage: &'a mut TermUsage,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		document::expand(
+			Environment { vocabulary, loader },
+			self,
+			context,
+			base_url,
+			options,
+			Report::new(None, None, Some(usage)),
+		)
+	}
+
+	fn expand_each_full<'a, N, L, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), Error>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+		F: FnMut(&mut N, IndexedObject<Iri, N::BlankId>) + MaybeSend,
+	{
+		document::expand_each(
+			Environment { vocabulary, loader },
+			self,
+			context,
+			base_url,
+			options,
+			f,
+		)
+	}
+
+	fn expand_owned<'a, N, L>(
+		self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		document::expand_owned(
+			Environment { vocabulary, loader },
+			self,
+			context,
+			base_url,
+			options,
 		)
 	}
 }
@@ -230,14 +541,109 @@ impl<Iri: Clone> Expand<Iri> for RemoteDocument<Iri> {
 		base_url: Option<N::Iri>,
 		loader: &'a L,
 		options: Options,
-	) -> Pin<Box<dyn Future<Output = ExpansionResult<N::Iri, N::BlankId>> + 'a>>
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 		L: Loader,
 	{
 		self.document()
 			.expand_full(vocabulary, context, base_url, loader, options)
 	}
+
+	fn expand_full_with_report<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		dropped: &'a mut Vec<DroppedEntry>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		self.document()
+			.expand_full_with_report(vocabulary, context, base_url, loader, options, dropped)
+	}
+
+	fn expand_full_with_warnings<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		warnings: &'a mut Vec<LocatedWarning>,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		self.document()
+			.expand_full_with_warnings(vocabulary, context, base_url, loader, options, warnings)
+	}
+
+	fn expand_full_with_usage<'a, N, L>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		usage: &'a mut TermUsage,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		self.document()
+			.expand_full_with_usage(vocabulary, context, base_url, loader, options, usage)
+	}
+
+	fn expand_each_full<'a, N, L, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), Error>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+		F: FnMut(&mut N, IndexedObject<Iri, N::BlankId>) + MaybeSend,
+	{
+		self.document()
+			.expand_each_full(vocabulary, context, base_url, loader, options, f)
+	}
+
+	fn expand_owned<'a, N, L>(
+		self,
+		vocabulary: &'a mut N,
+		context: Context<Iri, N::BlankId>,
+		base_url: Option<N::Iri>,
+		loader: &'a L,
+		options: Options,
+	) -> impl Future<Output = ExpansionResult<N::Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		L: Loader,
+	{
+		self.into_document()
+			.expand_owned(vocabulary, context, base_url, loader, options)
+	}
 }
@@ -1,6 +1,9 @@
-use crate::{expand_iri, node_id_of_term, ActiveProperty};
+use crate::{
+	check_relative_iri, exact_literal, expand_iri, node_id_of_term, ActiveProperty, Error, Options,
+	Report,
+};
 use alloc::borrow::ToOwned;
-use json_ld_context_processing::algorithm::{Action, RejectVocab};
+use json_ld_context_processing::algorithm::RejectVocab;
 use json_ld_core::{
 	object::value::Literal, Context, Environment, IndexedObject, LangString, Node, Object, Type,
 	Value,
@@ -84,16 +87,21 @@ impl From<RejectVocab> for LiteralExpansionError {
 	}
 }
 
-pub(crate) type LiteralExpansionResult<T, B> = Result<ExpandedLiteral<T, B>, LiteralExpansionError>;
+pub(crate) type LiteralExpansionResult<T, B> = Result<ExpandedLiteral<T, B>, Error>;
 
 /// Expand a literal value.
 /// See <https://www.w3.org/TR/json-ld11-api/#value-expansion>.
+///
+/// The `element` is the fragment of the input document `value` is taken
+/// from, used to locate warnings.
 pub(crate) fn expand_literal<N, L>(
 	mut env: Environment<N, L>,
-	vocab_policy: Action,
+	options: &Options,
 	active_context: &Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'_>,
 	value: LiteralValue,
+	element: &json_syntax::Value,
+	report: &mut Report<'_>,
 ) -> LiteralExpansionResult<N::Iri, N::BlankId>
 where
 	N: VocabularyMut,
@@ -115,17 +123,24 @@ where
 		// `false` for vocab.
 		Some(Type::Id) if value.is_string() => {
 			let mut node = Node::new();
-			let id = node_id_of_term(
+			let value = value.as_str().unwrap();
+			let id = check_relative_iri(
 				expand_iri(
 					&mut env,
 					active_context,
-					Nullable::Some(value.as_str().unwrap().into()),
+					Nullable::Some(value.into()),
 					true,
 					None,
+					report,
 				)
-				.unwrap()
 				.unwrap(),
-			);
+				active_property.as_str().unwrap_or_default(),
+				value,
+				element,
+				options,
+				report,
+			)?
+			.and_then(node_id_of_term);
 
 			node.id = id;
 			Ok(Object::node(node).into())
@@ -138,12 +153,23 @@ where
 		Some(Type::Vocab) if value.is_string() => {
 			let mut node = Node::new();
 
+			let value = value.as_str().unwrap();
 			let ty = expand_iri(
 				&mut env,
 				active_context,
-				Nullable::Some(value.as_str().unwrap().into()),
+				Nullable::Some(value.into()),
 				true,
-				Some(vocab_policy),
+				Some(options.policy.vocab),
+				report,
+			)
+			.map_err(LiteralExpansionError::from)?;
+			let ty = check_relative_iri(
+				ty,
+				active_property.as_str().unwrap_or_default(),
+				value,
+				element,
+				options,
+				report,
 			)?;
 
 			if let Some(ty) = ty {
@@ -225,11 +251,17 @@ where
 					if let Ok(t) = t.into_iri() {
 						ty = Some(t)
 					} else {
-						return Err(LiteralExpansionError::InvalidTypeValue);
+						return Err(LiteralExpansionError::InvalidTypeValue.into());
 					}
 				}
 			}
 
+			let (result, ty) = if options.exact_numbers {
+				exact_literal(env.vocabulary, result, ty)
+			} else {
+				(result, ty)
+			};
+
 			Ok(Object::Value(Value::Literal(result, ty)).into())
 		}
 	}
@@ -0,0 +1,137 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+use json_syntax::{CodeMap, Value};
+use locspan::Span;
+
+/// Location of an expansion error in the input document.
+///
+/// The location is given as a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901)
+/// to the innermost element whose expansion failed. If the input document
+/// was parsed along with a [`CodeMap`], the byte span of this element in the
+/// source can be retrieved using [`Self::span`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+	/// Addresses of the enclosing elements, from the innermost to the
+	/// outermost, until the location is resolved.
+	addresses: Vec<usize>,
+
+	/// JSON pointer to the element.
+	pointer: String,
+
+	/// Index of the element in the code map.
+	fragment: Option<usize>,
+}
+
+impl Location {
+	pub(crate) fn new(element: &Value) -> Self {
+		Self {
+			addresses: alloc::vec![address_of(element)],
+			pointer: String::new(),
+			fragment: None,
+		}
+	}
+
+	/// Records an enclosing element, used as fallback location if the
+	/// innermost element is not part of the input document (e.g. a value
+	/// built during expansion).
+	pub(crate) fn push(&mut self, element: &Value) {
+		if self.fragment.is_none() {
+			self.addresses.push(address_of(element))
+		}
+	}
+
+	/// Resolves the location against the input `document`.
+	pub(crate) fn resolve(&mut self, document: &Value) {
+		if self.fragment.is_none() {
+			for &address in &self.addresses {
+				let mut pointer = String::new();
+				let mut fragment = 0;
+				if find(document, address, &mut pointer, &mut fragment) {
+					self.pointer = pointer;
+					self.fragment = Some(fragment);
+					break;
+				}
+			}
+
+			self.addresses.clear()
+		}
+	}
+
+	/// Returns the JSON pointer to the faulty element.
+	pub fn pointer(&self) -> &str {
+		&self.pointer
+	}
+
+	/// Returns the index of the faulty element in the code map of the input
+	/// document (which is also its index in the [`Value::traverse`] order).
+	pub fn fragment(&self) -> Option<usize> {
+		self.fragment
+	}
+
+	/// Returns the byte span of the faulty element, using the code map
+	/// produced when parsing the input document.
+	pub fn span(&self, code_map: &CodeMap) -> Option<Span> {
+		self.fragment
+			.and_then(|i| code_map.get(i))
+			.map(|entry| entry.span)
+	}
+}
+
+impl fmt::Display for Location {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.pointer)
+	}
+}
+
+fn address_of(value: &Value) -> usize {
+	value as *const Value as usize
+}
+
+/// Searches the value at the given `address` in `value`, following the code
+/// map traversal order.
+fn find(value: &Value, address: usize, pointer: &mut String, fragment: &mut usize) -> bool {
+	if address_of(value) == address {
+		return true;
+	}
+
+	*fragment += 1;
+	match value {
+		Value::Array(items) => {
+			for (i, item) in items.iter().enumerate() {
+				let len = pointer.len();
+				write!(pointer, "/{i}").unwrap();
+				if find(item, address, pointer, fragment) {
+					return true;
+				}
+
+				pointer.truncate(len)
+			}
+		}
+		Value::Object(object) => {
+			for entry in object {
+				// Entry and key fragments.
+				*fragment += 2;
+
+				let len = pointer.len();
+				pointer.push('/');
+				for c in entry.key.as_str().chars() {
+					match c {
+						'~' => pointer.push_str("~0"),
+						'/' => pointer.push_str("~1"),
+						c => pointer.push(c),
+					}
+				}
+
+				if find(&entry.value, address, pointer, fragment) {
+					return true;
+				}
+
+				pointer.truncate(len)
+			}
+		}
+		_ => (),
+	}
+
+	false
+}
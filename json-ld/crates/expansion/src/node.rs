@@ -1,21 +1,24 @@
 use crate::{
-	expand_element, expand_iri, expand_literal, filter_top_level_item, Action, ActiveProperty,
-	Error, Expanded, ExpandedEntry, LiteralValue, Options,
+	expand_element, expand_iri, expand_language_tag, expand_literal, filter_top_level_item,
+	is_frame_pattern, is_framing_keyword, patterns_entry, term_into_json, Action, ActiveProperty,
+	Error, Expanded, ExpandedEntry, LiteralValue, Options, Report, Warning,
 };
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
 use alloc::string::ToString;
+use alloc::vec;
 use alloc::vec::Vec;
-use async_recursion::async_recursion;
 use contextual::WithContext;
 use core::hash::Hash;
 use indexmap::IndexSet;
-use json_ld_context_processing::{Options as ProcessingOptions, Process};
+use iref::IriRef;
+use json_ld_context_processing::Process;
 use json_ld_core::{
 	object, object::value::Literal, Container, Context, Environment, Id, Indexed, IndexedObject,
 	LangString, Loader, Node, Object, ProcessingMode, Term, Type, Value,
 };
-use json_ld_syntax::{ContainerKind, Keyword, LenientLangTagBuf, Nullable};
+use json_ld_core::{MaybeSend, MaybeSync};
+use json_ld_syntax::{ContainerKind, IntoJsonWithContext, Keyword, Nullable};
 use json_syntax::object::Entry;
 use mown::Mown;
 use rdf_types::VocabularyMut;
@@ -30,6 +33,44 @@ pub(crate) fn node_id_of_term<T, B>(term: Term<T, B>) -> Option<Id<T, B>> {
 	}
 }
 
+/// Applies the [relative IRI policy](crate::Policy::relative) to the
+/// `term` resulting from the document relative IRI expansion of `value`,
+/// found in the `key` entry at `element`.
+pub(crate) fn check_relative_iri<T, B>(
+	term: Option<Term<T, B>>,
+	key: &str,
+	value: &str,
+	element: &json_syntax::Value,
+	options: &Options,
+	report: &mut Report<'_>,
+) -> Result<Option<Term<T, B>>, Error> {
+	if !matches!(term, Some(Term::Id(Id::Invalid(_)))) || !is_relative_iri_ref(value) {
+		return Ok(term);
+	}
+
+	match options.policy.relative {
+		Action::Keep => {
+			let warning = Warning::UnresolvedRelativeIri(key.to_owned(), value.to_owned());
+			if options.strict {
+				Err(Error::Strict(warning))
+			} else {
+				report.warn(warning, element);
+				Ok(term)
+			}
+		}
+		Action::Drop => Ok(None),
+		Action::Reject => Err(Error::UnresolvedRelativeIri(
+			key.to_owned(),
+			value.to_owned(),
+		)),
+	}
+}
+
+/// Checks if the given value is a relative IRI reference.
+fn is_relative_iri_ref(value: &str) -> bool {
+	IriRef::new(value).is_ok_and(|iri_ref| iri_ref.scheme().is_none())
+}
+
 /// Expand a node object.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn expand_node<'a, N, L>(
@@ -39,31 +80,41 @@ pub(crate) async fn expand_node<'a, N, L>(
 	active_property: ActiveProperty<'a>,
 	expanded_entries: Vec<ExpandedEntry<'a, N::Iri, N::BlankId>>,
 	base_url: Option<N::Iri>,
-	options: Options,
+	options: &Options,
+	report: Report<'_>,
 ) -> Result<Option<Indexed<Node<N::Iri, N::BlankId>>>, Error>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + Eq + Hash,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// Initialize two empty maps, `result` and `nests`.
 	// let mut result = Indexed::new(Node::new(), None);
 	// let mut has_value_object_entries = false;
 
-	let (result, has_value_object_entries) = expand_node_entries(
+	let mut patterns = json_syntax::Object::new();
+	let (mut result, has_value_object_entries) = expand_node_entries(
 		env,
 		Indexed::new(Node::new(), None),
 		false,
+		&mut patterns,
 		active_context,
 		type_scoped_context,
 		active_property,
 		expanded_entries,
 		base_url,
 		options,
+		report,
 	)
 	.await?;
 
+	// Frame patterns are kept aside the node entries.
+	if !patterns.is_empty() {
+		let (prop, object) = patterns_entry(patterns);
+		result.insert(prop, object);
+	}
+
 	// If result contains the entry @value:
 	// The result must not contain any entries other than @direction, @index,
 	// @language, @type, and @value.
@@ -82,7 +133,9 @@ where
 
 	// If active property is null or @graph, drop free-floating
 	// values as follows:
-	if active_property.is_none() || active_property == Keyword::Graph {
+	// Free-floating nodes are kept when the frameExpansion flag is set.
+	if (active_property.is_none() || active_property == Keyword::Graph) && !options.frame_expansion
+	{
 		// If `result` is a map which is empty,
 		// [or contains only the entries `@value` or `@list` (does not apply here)]
 		// set `result` to null.
@@ -107,29 +160,65 @@ type ExpandedNode<T, B> = (Indexed<Node<T, B>>, bool);
 type NodeEntriesExpensionResult<T, B> = Result<ExpandedNode<T, B>, Error>;
 
 #[allow(clippy::too_many_arguments)]
-#[async_recursion(?Send)]
 async fn expand_node_entries<'a, N, L>(
 	mut env: Environment<'a, N, L>,
 	mut result: Indexed<Node<N::Iri, N::BlankId>>,
 	mut has_value_object_entries: bool,
+	patterns: &mut json_syntax::Object,
 	active_context: &'a Context<N::Iri, N::BlankId>,
 	type_scoped_context: &'a Context<N::Iri, N::BlankId>,
 	active_property: ActiveProperty<'a>,
 	expanded_entries: Vec<ExpandedEntry<'a, N::Iri, N::BlankId>>,
 	base_url: Option<N::Iri>,
-	options: Options,
+	options: &Options,
+	mut report: Report<'_>,
 ) -> NodeEntriesExpensionResult<N::Iri, N::BlankId>
 where
-	N: VocabularyMut,
-	N::Iri: Clone + Eq + Hash,
-	N::BlankId: Clone + Eq + Hash,
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	L: Loader,
 {
 	// For each `key` and `value` in `element`, ordered lexicographically by key
 	// if `ordered` is `true`:
 	for ExpandedEntry(key, expanded_key, value) in expanded_entries {
 		match expanded_key {
-			Term::Null => (),
+			// When the frameExpansion flag is set, framing keywords are
+			// expanded recursively, using the active property for `@default`
+			// and the keyword itself otherwise.
+			Term::Null if options.frame_expansion && is_framing_keyword(key) => {
+				let property = if key == "@default" {
+					active_property
+				} else {
+					ActiveProperty::Some(key)
+				};
+
+				let expanded_value = Box::pin(expand_element(
+					Environment {
+						vocabulary: env.vocabulary,
+						loader: env.loader,
+					},
+					active_context,
+					property,
+					value,
+					base_url.clone(),
+					options,
+					false,
+					report.reborrow(),
+				))
+				.await?;
+
+				patterns.insert(
+					key.into(),
+					json_syntax::Value::Array(
+						expanded_value
+							.into_iter()
+							.map(|object| object.into_json_with(&*env.vocabulary))
+							.collect(),
+					),
+				);
+			}
+			Term::Null => report.record(key, value),
 
 			// If key is @context, continue to the next key.
 			Term::Keyword(Keyword::Context) => (),
@@ -168,14 +257,58 @@ where
 							// Otherwise, set `expanded_value` to the result of IRI
 							// expanding value using true for document relative and
 							// false for vocab.
-							result.id = expand_iri(
-								&mut env,
-								active_context,
-								Nullable::Some(str_value.into()),
-								true,
-								None,
+							result.id = check_relative_iri(
+								expand_iri(
+									&mut env,
+									active_context,
+									Nullable::Some(str_value.into()),
+									true,
+									None,
+									&mut report,
+								)?,
+								key,
+								str_value,
+								value,
+								options,
+								&mut report,
 							)?
 							.and_then(node_id_of_term);
+						} else if options.frame_expansion && is_frame_pattern(value) {
+							// When the frameExpansion flag is set, value MAY be an
+							// empty map, or an array of one or more strings. The
+							// strings are IRI expanded.
+							let pattern = match value {
+								json_syntax::Value::Array(items) => {
+									let mut ids = Vec::with_capacity(items.len());
+									for item in items {
+										let str_item = item.as_str().unwrap();
+										let id = check_relative_iri(
+											expand_iri(
+												&mut env,
+												active_context,
+												Nullable::Some(str_item.into()),
+												true,
+												None,
+												&mut report,
+											)?,
+											key,
+											str_item,
+											item,
+											options,
+											&mut report,
+										)?;
+
+										ids.extend(
+											id.and_then(|id| term_into_json(&*env.vocabulary, id)),
+										)
+									}
+
+									json_syntax::Value::Array(ids)
+								}
+								value => json_syntax::Value::Array(vec![value.clone()]),
+							};
+
+							patterns.insert("@id".into(), pattern);
 						} else {
 							return Err(Error::InvalidIdValue);
 						}
@@ -184,22 +317,79 @@ where
 					Keyword::Type => {
 						// If value is neither a string nor an array of strings, an
 						// invalid type value error has been detected and processing
-						// is aborted.
+						// is aborted. When the frameExpansion flag is set, value
+						// MAY be an empty map, or a default object. An empty array
+						// is kept as well, as it matches nodes without type.
+						if options.frame_expansion {
+							if let Some(pattern) = value.as_object() {
+								let pattern = match pattern.get_unique("@default") {
+									Ok(None) if pattern.is_empty() => json_syntax::Object::new(),
+									Ok(Some(json_syntax::Value::String(default)))
+										if pattern.len() == 1 =>
+									{
+										let default = expand_iri(
+											&mut env,
+											type_scoped_context,
+											Nullable::Some(default.as_str().into()),
+											true,
+											Some(options.policy.vocab),
+											&mut report,
+										)?
+										.and_then(|ty| term_into_json(&*env.vocabulary, ty))
+										.ok_or(Error::InvalidTypeValue)?;
+
+										let mut pattern = json_syntax::Object::new();
+										pattern.insert("@default".into(), default);
+										pattern
+									}
+									_ => return Err(Error::InvalidTypeValue),
+								};
+
+								patterns.insert(
+									"@type".into(),
+									json_syntax::Value::Array(vec![pattern.into()]),
+								);
+								continue;
+							}
+
+							if value.as_array().is_some_and(|items| items.is_empty()) {
+								patterns
+									.insert("@type".into(), json_syntax::Value::Array(Vec::new()));
+								continue;
+							}
+						}
+
 						let value = json_syntax::Value::force_as_array(value);
 						// Set `expanded_value` to the result of IRI expanding each
 						// of its values using `type_scoped_context` for active
 						// context, and true for document relative.
-						for ty in value {
-							if let Some(str_ty) = ty.as_str() {
-								if let Some(ty) = expand_iri(
+						for ty_value in value {
+							if let Some(str_ty) = ty_value.as_str() {
+								let ty = expand_iri(
 									&mut env,
 									type_scoped_context,
 									Nullable::Some(str_ty.into()),
 									true,
 									Some(options.policy.vocab),
-								)? {
+									&mut report,
+								)?;
+
+								// Unresolved relative IRI references are
+								// handled by the relative IRI policy only.
+								let relative = matches!(ty, Some(Term::Id(Id::Invalid(_))))
+									&& is_relative_iri_ref(str_ty);
+								let ty = check_relative_iri(
+									ty,
+									key,
+									str_ty,
+									ty_value,
+									options,
+									&mut report,
+								)?;
+
+								if let Some(ty) = ty {
 									if let Ok(ty) = ty.try_into() {
-										if let Id::Invalid(_) = &ty {
+										if matches!(ty, Id::Invalid(_)) && !relative {
 											match options.policy.invalid {
 												Action::Keep => (),
 												Action::Drop => continue,
@@ -237,6 +427,7 @@ where
 							base_url.clone(),
 							options,
 							false,
+							report.reborrow(),
 						))
 						.await?;
 
@@ -270,6 +461,7 @@ where
 							base_url.clone(),
 							options,
 							false,
+							report.reborrow(),
 						))
 						.await?;
 						let mut expanded_nodes = Vec::new();
@@ -324,6 +516,7 @@ where
 									Nullable::Some(reverse_key.as_str().into()),
 									false,
 									Some(options.policy.vocab),
+									&mut report,
 								)? {
 									Some(Term::Keyword(_)) => {
 										return Err(Error::InvalidReversePropertyMap)
@@ -337,7 +530,11 @@ where
 										if !reverse_prop.is_valid() {
 											match options.policy.invalid {
 												Action::Keep => (),
-												Action::Drop => continue,
+												Action::Drop => {
+													report
+														.drop(reverse_key.as_str(), reverse_value);
+													continue;
+												}
 												Action::Reject => {
 													return Err(Error::KeyExpansionFailed(
 														reverse_key.to_string(),
@@ -357,6 +554,7 @@ where
 											base_url.clone(),
 											options,
 											false,
+											report.reborrow(),
 										))
 										.await?;
 
@@ -408,6 +606,7 @@ where
 										}
 
 										// otherwise the key is just dropped.
+										report.record(reverse_key.as_str(), reverse_value)
 									}
 								}
 							}
@@ -437,21 +636,18 @@ where
 
 							// Step 8 again.
 							let active_context = match property_scoped_context {
-								Some(property_scoped_context) => {
-									let options: ProcessingOptions = options.into();
-									Mown::Owned(
-										property_scoped_context
-											.process_with(
-												env.vocabulary,
-												active_context,
-												env.loader,
-												property_scoped_base_url,
-												options.with_override(),
-											)
-											.await?
-											.into_processed(),
+								Some(property_scoped_context) => Mown::Owned(
+									process_scoped_context(
+										env.vocabulary,
+										env.loader,
+										active_context,
+										Scope::Property,
+										property_scoped_context,
+										property_scoped_base_url,
+										options,
 									)
-								}
+									.await?,
+								),
 								None => Mown::Borrowed(active_context),
 							};
 
@@ -476,11 +672,16 @@ where
 											Nullable::Some(key.as_str().into()),
 											false,
 											Some(options.policy.vocab),
+											&mut report,
 										)
-										.map(|e| {
-											e.map(|expanded_key| {
-												ExpandedEntry(key, expanded_key, value)
-											})
+										.map(|e| match e {
+											Some(expanded_key) => {
+												Some(ExpandedEntry(key, expanded_key, value))
+											}
+											None => {
+												report.record(key.as_str(), value);
+												None
+											}
 										})
 										.transpose()
 									})
@@ -494,12 +695,14 @@ where
 										},
 										result,
 										has_value_object_entries,
+										patterns,
 										active_context.as_ref(),
 										type_scoped_context,
 										active_property,
 										nested_expanded_entries,
 										base_url.clone(),
-										options,
+										&options.nested()?,
+										report.reborrow(),
 									))
 									.await?;
 
@@ -519,7 +722,10 @@ where
 				if let Id::Invalid(name) = &prop {
 					match options.policy.invalid {
 						Action::Keep => (),
-						Action::Drop => continue,
+						Action::Drop => {
+							report.record(key, value);
+							continue;
+						}
 						Action::Reject => return Err(Error::KeyExpansionFailed(name.to_owned())),
 					}
 				}
@@ -592,7 +798,7 @@ where
 										// If item is null, continue to the next entry in
 										// language value.
 										json_syntax::Value::Null => (),
-										json_syntax::Value::String(item) => {
+										element @ json_syntax::Value::String(item) => {
 											// If language is @none, or expands to
 											// @none, remove @language from v.
 											let language = if expand_iri(
@@ -601,15 +807,23 @@ where
 												Nullable::Some(language.as_str().into()),
 												false,
 												Some(options.policy.vocab),
+												&mut report,
 											)? == Some(Term::Keyword(
 												Keyword::None,
 											)) {
 												None
 											} else {
-												let (language, _error) =
-													LenientLangTagBuf::new(language.to_string());
-
-												Some(language)
+												// If item is neither @none nor well-formed
+												// according to section 2.2.9 of [BCP47],
+												// processors SHOULD issue a warning.
+												expand_language_tag(
+													language.to_string(),
+													options.language_tags,
+													options.strict,
+													element,
+													&mut report,
+												)
+												.map_err(Error::Value)?
 											};
 
 											// initialize a new map v consisting of two
@@ -618,11 +832,6 @@ where
 											if let Ok(v) =
 												LangString::new(item.clone(), language, direction)
 											{
-												// If item is neither @none nor well-formed
-												// according to section 2.2.9 of [BCP47],
-												// processors SHOULD issue a warning.
-												// TODO warning
-
 												// Append v to expanded value.
 												expanded_value.push(
 													Object::Value(Value::LangString(v)).into(),
@@ -743,6 +952,7 @@ where
 									Nullable::Some(index.as_str().into()),
 									false,
 									Some(options.policy.vocab),
+									&mut report,
 								)? {
 									Some(Term::Null) | Some(Term::Keyword(Keyword::None)) => None,
 									key => key,
@@ -769,6 +979,7 @@ where
 									base_url.clone(),
 									options,
 									true,
+									report.reborrow(),
 								))
 								.await?;
 								// For each item in index value:
@@ -805,10 +1016,12 @@ where
 													vocabulary: env.vocabulary,
 													loader: env.loader,
 												},
-												options.policy.vocab,
+												options,
 												active_context,
 												ActiveProperty::Some(index_key),
 												LiteralValue::Inferred(index.as_str().into()),
+												index_value,
+												&mut report,
 											)?;
 
 											// Initialize expanded index key to the result
@@ -819,6 +1032,7 @@ where
 												Nullable::Some(index_key.into()),
 												false,
 												Some(options.policy.vocab),
+												&mut report,
 											)? {
 												Some(Term::Id(prop)) => prop,
 												_ => continue,
@@ -856,12 +1070,20 @@ where
 											// result of IRI expanding index using true for
 											// document relative and false for vocab.
 											if let Object::Node(ref mut node) = *item {
-												node.id = expand_iri(
-													&mut env,
-													active_context,
-													Nullable::Some(index.as_str().into()),
-													true,
-													None,
+												node.id = check_relative_iri(
+													expand_iri(
+														&mut env,
+														active_context,
+														Nullable::Some(index.as_str().into()),
+														true,
+														None,
+														&mut report,
+													)?,
+													key,
+													index.as_str(),
+													index_value,
+													options,
+													&mut report,
 												)?
 												.and_then(node_id_of_term);
 											}
@@ -906,6 +1128,7 @@ where
 								base_url.clone(),
 								options,
 								false,
+								report.reborrow(),
 							))
 							.await?
 						}
@@ -986,6 +1209,8 @@ where
 						prop.with(env.vocabulary).to_string(),
 					));
 				}
+
+				report.record(key, value)
 			}
 		}
 	}
@@ -0,0 +1,157 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use iref::Iri;
+use json_ld_core::object::value::Literal;
+use json_ld_core::rdf::{XSD_DECIMAL, XSD_INTEGER};
+use json_syntax::Number;
+use rdf_types::vocabulary::IriVocabularyMut;
+
+/// Maximum absolute value of the exponent of the numbers converted into
+/// exact literals.
+///
+/// Numbers with a larger exponent are left untouched, to bound the size of
+/// their decimal representation.
+const MAX_EXPONENT: i64 = 1024;
+
+/// Exact value of a JSON number.
+enum ExactNumber {
+	Integer(xsd_types::Integer),
+	Decimal(xsd_types::Decimal),
+}
+
+impl ExactNumber {
+	fn into_lexical(self) -> (String, &'static Iri) {
+		match self {
+			Self::Integer(i) => (i.to_string(), XSD_INTEGER),
+			Self::Decimal(d) => (d.to_string(), XSD_DECIMAL),
+		}
+	}
+}
+
+/// Turns the given untyped numeric literal into an `xsd:integer` or
+/// `xsd:decimal` typed string holding its exact value.
+///
+/// Integers fitting in 64 bits are left untouched since they are already
+/// represented exactly. See [`Options::exact_numbers`](crate::Options::exact_numbers).
+pub(crate) fn exact_literal<V: IriVocabularyMut>(
+	vocabulary: &mut V,
+	literal: Literal,
+	ty: Option<V::Iri>,
+) -> (Literal, Option<V::Iri>) {
+	if ty.is_none() {
+		if let Literal::Number(n) = &literal {
+			if let Some(exact) = exact_number(n) {
+				let (lexical, ty) = exact.into_lexical();
+				return (
+					Literal::String(lexical.as_str().into()),
+					Some(vocabulary.insert(ty)),
+				);
+			}
+		}
+	}
+
+	(literal, ty)
+}
+
+/// Computes the exact value of the given JSON number, unless it is an
+/// integer fitting in 64 bits.
+///
+/// Numbers written without fractional part nor exponent are integers,
+/// others are decimals.
+fn exact_number(n: &Number) -> Option<ExactNumber> {
+	if n.is_i64() {
+		return None;
+	}
+
+	let s = n.as_str();
+	let is_integer = !s.contains(['.', 'e', 'E']);
+
+	let (negative, s) = match s.strip_prefix('-') {
+		Some(s) => (true, s),
+		None => (false, s),
+	};
+
+	let (mantissa, exponent) = match s.find(['e', 'E']) {
+		Some(i) => (&s[..i], s[(i + 1)..].parse::<i64>().ok()?),
+		None => (s, 0),
+	};
+
+	if exponent.abs() > MAX_EXPONENT {
+		return None;
+	}
+
+	let (integer_part, fractional_part) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	let digits = format!("{integer_part}{fractional_part}");
+
+	// Position of the decimal point in `digits`.
+	let point = integer_part.len() as i64 + exponent;
+	let (integer_part, fractional_part) = if point <= 0 {
+		(
+			String::new(),
+			"0".repeat(point.unsigned_abs() as usize) + &digits,
+		)
+	} else if point as usize >= digits.len() {
+		(
+			digits.clone() + &"0".repeat(point as usize - digits.len()),
+			String::new(),
+		)
+	} else {
+		let (i, f) = digits.split_at(point as usize);
+		(i.to_string(), f.to_string())
+	};
+
+	let integer_part = match integer_part.trim_start_matches('0') {
+		"" => "0",
+		i => i,
+	};
+	let fractional_part = fractional_part.trim_end_matches('0');
+
+	let sign = if negative && (integer_part != "0" || !fractional_part.is_empty()) {
+		"-"
+	} else {
+		""
+	};
+
+	if is_integer {
+		format!("{sign}{integer_part}")
+			.parse()
+			.ok()
+			.map(ExactNumber::Integer)
+	} else if fractional_part.is_empty() {
+		format!("{sign}{integer_part}")
+			.parse()
+			.ok()
+			.map(ExactNumber::Decimal)
+	} else {
+		format!("{sign}{integer_part}.{fractional_part}")
+			.parse()
+			.ok()
+			.map(ExactNumber::Decimal)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn exact(s: &str) -> Option<String> {
+		exact_number(Number::new(s).unwrap()).map(|n| n.into_lexical().0)
+	}
+
+	#[test]
+	fn exact_numbers() {
+		assert_eq!(exact("42"), None);
+		assert_eq!(
+			exact("18446744073709551616").as_deref(),
+			Some("18446744073709551616")
+		);
+		assert_eq!(exact("-0.10").as_deref(), Some("-0.1"));
+		assert_eq!(exact("1.5e3").as_deref(), Some("1500"));
+		assert_eq!(exact("12.5E-3").as_deref(), Some("0.0125"));
+		assert_eq!(
+			exact("0.1234567890123456789").as_deref(),
+			Some("0.1234567890123456789")
+		);
+		assert_eq!(exact("1e100000"), None);
+	}
+}
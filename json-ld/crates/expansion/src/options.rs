@@ -1,9 +1,11 @@
+use crate::KeywordHandler;
+use alloc::sync::Arc;
 use json_ld_core::ProcessingMode;
 
 pub use json_ld_context_processing::algorithm::Action;
 
 /// Expansion options.
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
 	/// Sets the processing mode.
 	pub processing_mode: ProcessingMode,
@@ -16,6 +18,158 @@ pub struct Options {
 	/// If set to true, input document entries are processed lexicographically.
 	/// If false, order is not considered in processing.
 	pub ordered: bool,
+
+	/// Maximum nesting depth of the input document.
+	///
+	/// Every nested array or object (e.g. through `@graph` or `@list`)
+	/// counts as one level. Expansion fails with
+	/// [`Error::MaxDepthExceeded`](crate::Error::MaxDepthExceeded) when the
+	/// limit is reached, instead of recursing indefinitely.
+	///
+	/// Default is `None`, meaning no limit.
+	pub max_depth: Option<usize>,
+
+	/// Maximum number of node objects in the expanded document.
+	///
+	/// Expansion fails with
+	/// [`Error::TooManyNodes`](crate::Error::TooManyNodes) when the limit is
+	/// exceeded, which in turn bounds the size of the node map built by
+	/// flattening and RDF serialization.
+	///
+	/// Default is `None`, meaning no limit.
+	pub max_nodes: Option<usize>,
+
+	/// Promote warnings to errors.
+	///
+	/// When set, any [`Warning`](crate::Warning) (emitted by expansion or
+	/// context processing) fails the expansion with
+	/// [`Error::Strict`](crate::Error::Strict) instead of being ignored.
+	pub strict: bool,
+
+	/// Enables frame expansion.
+	///
+	/// This is the `frameExpansion` flag used by the
+	/// [framing algorithm](https://www.w3.org/TR/json-ld11-framing/) to expand
+	/// frames. Free-floating node objects (empty maps and maps whose only
+	/// entry is `@id`) are then kept in the output.
+	///
+	/// Frame patterns (such as an empty map used as wildcard `@id`, `@type`
+	/// or `@value`, a `@type` default object, arrays of `@id`, `@value` or
+	/// `@language` values, and the framing keywords) are accepted. Since an
+	/// [`ExpandedDocument`](json_ld_core::ExpandedDocument) cannot represent
+	/// them, they are kept in the
+	/// [`FRAME_PATTERNS`](crate::FRAME_PATTERNS) property of their node. Use
+	/// [`frame_into_json`](crate::frame_into_json) to get the expanded frame.
+	pub frame_expansion: bool,
+
+	/// How to handle the entries of an object expanding to the same key
+	/// (e.g. a term and the IRI it is mapped to, duplicate JSON keys, or
+	/// aliases of `@id` or `@type`).
+	///
+	/// Default is [`DuplicateKeys::Merge`].
+	pub duplicate_keys: DuplicateKeys,
+
+	/// Order of the properties of the expanded node objects.
+	///
+	/// Default is [`OutputOrder::Input`].
+	pub ordered_output: OutputOrder,
+
+	/// Hook invoked on unknown keyword entries.
+	///
+	/// The handler is shared, so it may hold run-time state such as a
+	/// registry of known keywords.
+	///
+	/// Default is `None`, meaning that such entries are dropped with a
+	/// [`Warning::KeywordLikeKey`](crate::Warning::KeywordLikeKey).
+	pub keyword_handler: Option<Arc<dyn KeywordHandler + Send + Sync>>,
+
+	/// How to handle malformed language tags, in value objects and language
+	/// maps.
+	///
+	/// Default is [`LanguageTagPolicy::Lenient`].
+	pub language_tags: LanguageTagPolicy,
+
+	/// Preserve the exact value of numbers.
+	///
+	/// JSON numbers are converted to RDF through 64-bit floating point
+	/// numbers, unless they are 64-bit integers. If this is set, untyped
+	/// numbers that are not 64-bit integers are expanded into `xsd:integer`
+	/// (if written without fractional part nor exponent) or `xsd:decimal`
+	/// typed strings holding their exact value in canonical form, so that
+	/// large identifiers and high-precision decimals round-trip exactly.
+	pub exact_numbers: bool,
+
+	/// Allow embedded and type-scoped contexts to redefine protected terms.
+	///
+	/// By default, redefining a protected term fails with a
+	/// `protected term redefinition` error, and a `null` context cannot
+	/// clear an active context containing protected terms. If this is set,
+	/// protected terms are replaced as any other term. This is meant for
+	/// controlled environments only, since protected terms ensure that a
+	/// document is interpreted as intended by the author of the context.
+	pub override_protected: bool,
+}
+
+/// Handling of language tags that are not well-formed according to
+/// [BCP47](https://www.rfc-editor.org/info/bcp47).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageTagPolicy {
+	/// The language tag is kept as is, and a
+	/// [`Warning::MalformedLanguageTag`](crate::Warning::MalformedLanguageTag)
+	/// is emitted.
+	#[default]
+	Lenient,
+
+	/// Surrounding whitespace is removed and underscores are replaced by
+	/// hyphens (e.g. `en_US` becomes `en-US`). If the result is well-formed
+	/// it is used silently, otherwise the original tag is kept as with
+	/// [`Lenient`](Self::Lenient).
+	Normalize,
+
+	/// The language tag is dropped, turning the value into a plain string,
+	/// and a [`Warning::MalformedLanguageTag`](crate::Warning::MalformedLanguageTag)
+	/// is emitted.
+	Drop,
+
+	/// Expansion fails with an `invalid language-tagged string` error.
+	Reject,
+}
+
+/// Order of the properties of the expanded node objects.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputOrder {
+	/// Properties appear in the order their entries are processed: the order
+	/// of the input document, or the lexicographic order of the input keys
+	/// if [`Options::ordered`] is set. Properties of `@nest` entries come
+	/// after the other properties of the node.
+	#[default]
+	Input,
+
+	/// Properties and reverse properties are sorted lexicographically by
+	/// expanded IRI, so that the output does not depend on the order of the
+	/// input entries nor on the terms used to write them.
+	Lexicographic,
+}
+
+/// Handling of the entries of an object expanding to the same key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeys {
+	/// The values of the entries are merged, as specified by the expansion
+	/// algorithm. Duplicate keywords other than `@type` and `@included` are
+	/// rejected with [`Error::CollidingKeywords`](crate::Error::CollidingKeywords).
+	#[default]
+	Merge,
+
+	/// The values of the entries are merged, and a
+	/// [`Warning::DuplicateKey`](crate::Warning::DuplicateKey) is emitted for
+	/// each duplicate entry.
+	Warn,
+
+	/// Only the last entry is kept.
+	KeepLast,
+
+	/// Expansion fails with [`Error::DuplicateKey`](crate::Error::DuplicateKey).
+	Reject,
 }
 
 impl Options {
@@ -27,10 +181,33 @@ impl Options {
 	}
 }
 
+impl Options {
+	/// Returns the options used to expand a nested element, with one less
+	/// level of remaining nesting depth.
+	pub(crate) fn nested(&self) -> Result<Self, crate::Error> {
+		match self.max_depth {
+			Some(0) => Err(crate::Error::MaxDepthExceeded),
+			Some(depth) => Ok(Self {
+				max_depth: Some(depth - 1),
+				..self.clone()
+			}),
+			None => Ok(self.clone()),
+		}
+	}
+}
+
 impl From<Options> for json_ld_context_processing::Options {
 	fn from(options: Options) -> json_ld_context_processing::Options {
+		(&options).into()
+	}
+}
+
+impl From<&Options> for json_ld_context_processing::Options {
+	fn from(options: &Options) -> json_ld_context_processing::Options {
 		json_ld_context_processing::Options {
 			processing_mode: options.processing_mode,
+			strict: options.strict,
+			override_protected: options.override_protected,
 			..Default::default()
 		}
 	}
@@ -60,6 +237,17 @@ pub struct Policy {
 
 	/// How to expand valid terms when there is no vocabulary mapping.
 	pub allow_undefined: bool,
+
+	/// How to expand relative IRI references that cannot be resolved
+	/// because there is no base IRI (in `@id` and `@type` values, or values
+	/// coerced to IRIs).
+	///
+	/// They are kept as is by default, which is reported as a
+	/// [`Warning::UnresolvedRelativeIri`](crate::Warning::UnresolvedRelativeIri)
+	/// in [strict](Options::strict) mode. If set to [`Action::Reject`],
+	/// expansion fails with
+	/// [`Error::UnresolvedRelativeIri`](crate::Error::UnresolvedRelativeIri).
+	pub relative: Action,
 }
 
 impl Default for Policy {
@@ -68,6 +256,7 @@ impl Default for Policy {
 			invalid: Action::Keep,
 			vocab: Action::Keep,
 			allow_undefined: true,
+			relative: Action::Keep,
 		}
 	}
 }
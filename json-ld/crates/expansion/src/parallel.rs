@@ -0,0 +1,254 @@
+use crate::{
+	document, expand_element, ActiveProperty, DroppedEntry, Error, Expanded, ExpansionResult,
+	LocatedWarning, Options, Report, TermUsage,
+};
+use alloc::vec::Vec;
+use core::hash::Hash;
+use json_ld_core::future::block_on;
+use json_ld_core::{Context, Environment, SyncLoader, SyncLoaderAdapter};
+use json_syntax::Value;
+use rayon::prelude::*;
+use rdf_types::{vocabulary, BlankIdBuf, VocabularyMut};
+
+/// Expands the given JSON-LD `document` using the [`rayon`] thread pool.
+///
+/// When the top level of the document is an array, its items are
+/// independent from one another, and each of them is expanded on its own
+/// worker thread. This is meant for bulk ingestion of large arrays of node
+/// objects. Any other document is expanded on the current thread.
+///
+/// The result is the same as sequential expansion: expanded objects are
+/// merged in document order and, if several items fail to expand, the error
+/// of the first one in document order is returned.
+///
+/// Since every worker runs its own instance of the expansion algorithm, no
+/// vocabulary is used to interpret identifiers, and the `loader` must be
+/// synchronous. The active `context` (with its caches) is shared between
+/// workers.
+pub fn expand_parallel<Iri, L>(
+	document: &Value,
+	context: Context<Iri, BlankIdBuf>,
+	base_url: Option<Iri>,
+	loader: &L,
+	options: Options,
+) -> ExpansionResult<Iri, BlankIdBuf>
+where
+	(): VocabularyMut<Iri = Iri>,
+	Iri: Clone + Eq + Hash + Send + Sync,
+	L: SyncLoader + Sync,
+{
+	expand_parallel_with(
+		document,
+		context,
+		base_url,
+		loader,
+		options,
+		Report::disabled(),
+	)
+}
+
+/// Expands the given JSON-LD `document` using the [`rayon`] thread pool,
+/// recording in `dropped` the entries dropped during expansion.
+///
+/// See [`expand_parallel`]. Entries are recorded in document order, as with
+/// sequential expansion.
+pub fn expand_parallel_with_report<Iri, L>(
+	document: &Value,
+	context: Context<Iri, BlankIdBuf>,
+	base_url: Option<Iri>,
+	loader: &L,
+	options: Options,
+	dropped: &mut Vec<DroppedEntry>,
+) -> ExpansionResult<Iri, BlankIdBuf>
+where
+	(): VocabularyMut<Iri = Iri>,
+	Iri: Clone + Eq + Hash + Send + Sync,
+	L: SyncLoader + Sync,
+{
+	expand_parallel_with(
+		document,
+		context,
+		base_url,
+		loader,
+		options,
+		Report::new(Some(dropped), None, None),
+	)
+}
+
+/// Expands the given JSON-LD `document` using the [`rayon`] thread pool,
+/// recording in `warnings` the warnings emitted during expansion.
+///
+/// See [`expand_parallel`]. Warnings are recorded in document order, as with
+/// sequential expansion.
+pub fn expand_parallel_with_warnings<Iri, L>(
+	document: &Value,
+	context: Context<Iri, BlankIdBuf>,
+	base_url: Option<Iri>,
+	loader: &L,
+	options: Options,
+	warnings: &mut Vec<LocatedWarning>,
+) -> ExpansionResult<Iri, BlankIdBuf>
+where
+	(): VocabularyMut<Iri = Iri>,
+	Iri: Clone + Eq + Hash + Send + Sync,
+	L: SyncLoader + Sync,
+{
+	expand_parallel_with(
+		document,
+		context,
+		base_url,
+		loader,
+		options,
+		Report::new(None, Some(warnings), None),
+	)
+}
+
+/// Expands the given JSON-LD `document` using the [`rayon`] thread pool,
+/// recording in `usage` the terms used during expansion.
+///
+/// See [`expand_parallel`] and [`TermUsage`].
+pub fn expand_parallel_with_usage<Iri, L>(
+	document: &Value,
+	context: Context<Iri, BlankIdBuf>,
+	base_url: Option<Iri>,
+	loader: &L,
+	options: Options,
+	usage: &mut TermUsage,
+) -> ExpansionResult<Iri, BlankIdBuf>
+where
+	(): VocabularyMut<Iri = Iri>,
+	Iri: Clone + Eq + Hash + Send + Sync,
+	L: SyncLoader + Sync,
+{
+	expand_parallel_with(
+		document,
+		context,
+		base_url,
+		loader,
+		options,
+		Report::new(None, None, Some(usage)),
+	)
+}
+
+/// Parallel expansion, recording dropped entries, warnings and term usage
+/// in the given `report`.
+///
+/// Each item is expanded with its own forked report. The item reports are
+/// then merged in document order, up to the first item that failed to
+/// expand, so that the report is the same as with sequential expansion.
+fn expand_parallel_with<Iri, L>(
+	document: &Value,
+	context: Context<Iri, BlankIdBuf>,
+	base_url: Option<Iri>,
+	loader: &L,
+	options: Options,
+	mut report: Report<'_>,
+) -> ExpansionResult<Iri, BlankIdBuf>
+where
+	(): VocabularyMut<Iri = Iri>,
+	Iri: Clone + Eq + Hash + Send + Sync,
+	L: SyncLoader + Sync,
+{
+	let loader = SyncLoaderAdapter(loader);
+
+	let items = match document {
+		Value::Array(items) if items.len() > 1 => items,
+		_ => {
+			return block_on(document::expand(
+				Environment {
+					vocabulary: vocabulary::no_vocabulary_mut(),
+					loader: &loader,
+				},
+				document,
+				context,
+				base_url,
+				options,
+				report,
+			))
+		}
+	};
+
+	let options = &options;
+	let fork = report.fork();
+	let results: Vec<(Result<Expanded<Iri, BlankIdBuf>, Error>, _)> = items
+		.par_iter()
+		.map(|item| {
+			let mut item_report = fork.clone();
+			let result = block_on(expand_element(
+				Environment {
+					vocabulary: vocabulary::no_vocabulary_mut(),
+					loader: &loader,
+				},
+				&context,
+				ActiveProperty::None,
+				item,
+				base_url.clone(),
+				options,
+				false,
+				item_report.as_report(),
+			));
+
+			(result, item_report)
+		})
+		.collect();
+
+	let mut expanded = Vec::new();
+	for (result, item_report) in results {
+		report.merge(item_report);
+		expanded.extend(result.map_err(|e| e.resolve(document))?)
+	}
+
+	report.resolve(document);
+	let mut result = document::into_document(Expanded::Array(expanded), options)?;
+	document::sort_document(&(), &mut result, options);
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::Warning;
+	use iref::IriBuf;
+	use json_ld_core::NoLoader;
+	use json_syntax::Parse;
+
+	#[test]
+	fn warnings_in_document_order() {
+		let (document, _) = Value::parse_str(
+			r#"[
+				{ "@first": 1, "http://example.org/p": 1 },
+				{ "@second": 2, "http://example.org/p": 2 },
+				{ "@third": 3, "http://example.org/p": 3 }
+			]"#,
+		)
+		.unwrap();
+
+		let mut warnings = Vec::new();
+		let expanded = expand_parallel_with_warnings::<IriBuf, _>(
+			&document,
+			Context::new(None),
+			None,
+			&NoLoader,
+			Options::default(),
+			&mut warnings,
+		)
+		.unwrap();
+		assert_eq!(expanded.len(), 3);
+
+		let warnings: Vec<_> = warnings
+			.iter()
+			.map(|w| match &w.warning {
+				Warning::KeywordLikeKey(key) => (key.as_str(), w.location.pointer()),
+				other => panic!("unexpected warning {other}"),
+			})
+			.collect();
+		assert_eq!(
+			warnings,
+			[
+				("@first", "/0/@first"),
+				("@second", "/1/@second"),
+				("@third", "/2/@third")
+			]
+		);
+	}
+}
@@ -0,0 +1,137 @@
+use crate::{Action, DroppedEntry, LocatedWarning, Location, TermUsage, Warning};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use json_ld_core::Context;
+use json_ld_syntax::{ExpandableRef, Nullable};
+use json_syntax::Value;
+use rdf_types::Vocabulary;
+
+/// Expansion report, filled with the dropped entries, the warnings emitted
+/// and the terms used during expansion if enabled.
+pub(crate) struct Report<'a> {
+	dropped: Option<&'a mut Vec<DroppedEntry>>,
+	warnings: Option<&'a mut Vec<LocatedWarning>>,
+	usage: Option<&'a mut TermUsage>,
+}
+
+impl<'a> Report<'a> {
+	pub fn new(
+		dropped: Option<&'a mut Vec<DroppedEntry>>,
+		warnings: Option<&'a mut Vec<LocatedWarning>>,
+		usage: Option<&'a mut TermUsage>,
+	) -> Self {
+		Self {
+			dropped,
+			warnings,
+			usage,
+		}
+	}
+
+	pub fn disabled() -> Self {
+		Self::new(None, None, None)
+	}
+
+	/// Creates an empty owned report recording the same information as this
+	/// one, to be filled by a worker thread and [merged](Self::merge) back.
+	#[cfg(feature = "rayon")]
+	pub fn fork(&self) -> OwnedReport {
+		OwnedReport {
+			dropped: self.dropped.as_ref().map(|_| Vec::new()),
+			warnings: self.warnings.as_ref().map(|_| Vec::new()),
+			usage: self.usage.as_ref().map(|_| TermUsage::new()),
+		}
+	}
+
+	/// Appends the content of the given forked report to this one.
+	#[cfg(feature = "rayon")]
+	pub fn merge(&mut self, other: OwnedReport) {
+		if let (Some(entries), Some(other)) = (&mut self.dropped, other.dropped) {
+			entries.extend(other)
+		}
+
+		if let (Some(warnings), Some(other)) = (&mut self.warnings, other.warnings) {
+			warnings.extend(other)
+		}
+
+		if let (Some(usage), Some(other)) = (&mut self.usage, other.usage) {
+			usage.append(other)
+		}
+	}
+
+	pub fn reborrow(&mut self) -> Report<'_> {
+		Report {
+			dropped: self.dropped.as_deref_mut(),
+			warnings: self.warnings.as_deref_mut(),
+			usage: self.usage.as_deref_mut(),
+		}
+	}
+
+	/// Records that the entry `key` with the given `value` has been dropped.
+	pub fn record(&mut self, key: &str, value: &Value) {
+		if let Some(entries) = &mut self.dropped {
+			entries.push(DroppedEntry {
+				key: key.to_string(),
+				location: Location::new(value),
+			})
+		}
+	}
+
+	/// Records the given `warning`, emitted while expanding `value`.
+	pub fn warn(&mut self, warning: Warning<String>, value: &Value) {
+		if let Some(warnings) = &mut self.warnings {
+			warnings.push(LocatedWarning {
+				warning,
+				location: Location::new(value),
+			})
+		}
+	}
+
+	/// Records the IRI expansion of `value` in the term usage report.
+	pub fn use_iri<N: Vocabulary>(
+		&mut self,
+		vocabulary: &N,
+		active_context: &Context<N::Iri, N::BlankId>,
+		value: &Nullable<ExpandableRef>,
+		vocab: Option<Action>,
+	) {
+		if let Some(usage) = &mut self.usage {
+			usage.record(vocabulary, active_context, value, vocab)
+		}
+	}
+
+	/// Resolves the location of the recorded entries and warnings against
+	/// the input `document`.
+	pub fn resolve(&mut self, document: &Value) {
+		if let Some(entries) = &mut self.dropped {
+			for entry in entries.iter_mut() {
+				entry.location.resolve(document)
+			}
+		}
+
+		if let Some(warnings) = &mut self.warnings {
+			for warning in warnings.iter_mut() {
+				warning.location.resolve(document)
+			}
+		}
+	}
+}
+
+/// Owned expansion report, see [`Report::fork`].
+#[cfg(feature = "rayon")]
+#[derive(Clone)]
+pub(crate) struct OwnedReport {
+	dropped: Option<Vec<DroppedEntry>>,
+	warnings: Option<Vec<LocatedWarning>>,
+	usage: Option<TermUsage>,
+}
+
+#[cfg(feature = "rayon")]
+impl OwnedReport {
+	pub fn as_report(&mut self) -> Report<'_> {
+		Report::new(
+			self.dropped.as_mut(),
+			self.warnings.as_mut(),
+			self.usage.as_mut(),
+		)
+	}
+}
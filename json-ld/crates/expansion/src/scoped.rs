@@ -0,0 +1,133 @@
+use crate::{Error, Loader, Options};
+use core::hash::Hash;
+use json_ld_context_processing::{Options as ProcessingOptions, Process};
+use json_ld_core::context::{BindingRef, Scope, ScopedContextKey};
+use json_ld_core::{Context, MaybeSend, MaybeSync};
+use rdf_types::VocabularyMut;
+
+/// Returns the key identifying the processing of a scoped context with the
+/// given parameters.
+pub(crate) fn scoped_context_key<T>(
+	scope: Scope,
+	base_url: Option<T>,
+	options: &Options,
+) -> ScopedContextKey<T> {
+	ScopedContextKey {
+		scope,
+		base_url,
+		processing_mode: options.processing_mode,
+		strict: options.strict,
+		override_protected: options.override_protected,
+	}
+}
+
+/// Processes the given type-scoped or property-scoped `local_context` on top
+/// of `active_context`.
+///
+/// The result is cached on `active_context`, so that the same scoped context
+/// is processed only once per active context (and not once per node of the
+/// given type, or value of the given property).
+pub(crate) async fn process_scoped_context<N, L>(
+	vocabulary: &mut N,
+	loader: &L,
+	active_context: &Context<N::Iri, N::BlankId>,
+	scope: Scope,
+	local_context: &json_ld_syntax::context::Context,
+	base_url: Option<N::Iri>,
+	options: &Options,
+) -> Result<Context<N::Iri, N::BlankId>, Error>
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	let key = scoped_context_key(scope, base_url.clone(), options);
+
+	let cache = active_context.scoped_contexts();
+	if let Some(context) = cache.get(&key, local_context) {
+		return Ok(context);
+	}
+
+	let processing_options: ProcessingOptions = options.into();
+	let processing_options = match scope {
+		Scope::Property => processing_options.with_override(),
+		Scope::Type => processing_options.without_propagation(),
+	};
+
+	let context = local_context
+		.process_with(
+			vocabulary,
+			active_context,
+			loader,
+			base_url,
+			processing_options,
+		)
+		.await?
+		.into_processed();
+
+	cache.insert(key, local_context.clone(), context.clone());
+	Ok(context)
+}
+
+/// Pre-compiles the type-scoped contexts of `active_context`.
+///
+/// The scoped context of every term defined in `active_context` is
+/// processed as a type-scoped context, and stored in the
+/// [type-scoped contexts table](Context::type_scoped_contexts) of
+/// `active_context`, shared with its clones. Expanding a document with
+/// this context (or one of its clones) and the same `options` then uses the
+/// table for the nodes whose types have a single type-scoped context,
+/// instead of processing it again. This is useful for large datasets of
+/// nodes of the same types.
+///
+/// Scoped contexts that cannot be processed are skipped, so that the error
+/// is reported by the expansion if the term is actually used as a type.
+/// Returns the number of compiled contexts.
+///
+/// The table is only enabled with the `std` or `spin` feature.
+pub async fn compile_type_scoped_contexts<N, L>(
+	vocabulary: &mut N,
+	loader: &L,
+	active_context: &Context<N::Iri, N::BlankId>,
+	options: Options,
+) -> usize
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	L: Loader,
+{
+	let table = active_context.type_scoped_contexts();
+	let options = &options;
+	let processing_options = ProcessingOptions::from(options).without_propagation();
+
+	for binding in active_context.definitions() {
+		let BindingRef::Normal(term, definition) = binding else {
+			continue;
+		};
+
+		if let Some(local_context) = &definition.context {
+			let base_url = definition.base_url().cloned();
+			let result = local_context
+				.process_with(
+					&mut *vocabulary,
+					active_context,
+					loader,
+					base_url.clone(),
+					processing_options,
+				)
+				.await;
+
+			if let Ok(processed) = result {
+				table.insert(
+					term.clone(),
+					scoped_context_key(Scope::Type, base_url, options),
+					processed.into_processed(),
+				)
+			}
+		}
+	}
+
+	table.len()
+}
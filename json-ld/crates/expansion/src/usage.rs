@@ -0,0 +1,137 @@
+use crate::Action;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use contextual::WithContext;
+use iref::Iri;
+use json_ld_core::context::BindingRef;
+use json_ld_core::{Context, Term};
+use json_ld_syntax::{is_keyword_like, CompactIri, ExpandableRef, Keyword, Nullable};
+use rdf_types::{BlankId, Vocabulary};
+
+/// Terms, keywords and vocabularies used to expand a document.
+///
+/// This can be used to prune the unused definitions of oversized contexts,
+/// or to detect misspelled terms (that are then either dropped, or expanded
+/// using the vocabulary mapping).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TermUsage {
+	/// Terms of the active contexts used to expand a key or a value,
+	/// including keyword aliases and the prefixes of compact IRIs.
+	pub terms: BTreeSet<String>,
+
+	/// Keywords appearing in the document, directly or through an alias.
+	pub keywords: BTreeSet<Keyword>,
+
+	/// IRIs of the vocabulary mappings (`@vocab`) and prefixes used to
+	/// produce expanded IRIs.
+	pub vocabularies: BTreeSet<String>,
+
+	/// Undefined terms expanded using the vocabulary mapping.
+	pub undefined_terms: BTreeSet<String>,
+}
+
+impl TermUsage {
+	/// Creates an empty usage report.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds the terms, keywords and vocabularies recorded by `other` to this
+	/// report.
+	#[cfg(feature = "rayon")]
+	pub(crate) fn append(&mut self, other: Self) {
+		self.terms.extend(other.terms);
+		self.keywords.extend(other.keywords);
+		self.vocabularies.extend(other.vocabularies);
+		self.undefined_terms.extend(other.undefined_terms);
+	}
+
+	/// Returns an iterator over the terms defined in `context` that have not
+	/// been used.
+	///
+	/// Terms defined by scoped contexts are not listed, unless they are also
+	/// defined in `context`.
+	pub fn unused_terms<'c, T, B>(
+		&'c self,
+		context: &'c Context<T, B>,
+	) -> impl Iterator<Item = &'c str> {
+		context
+			.definitions()
+			.iter()
+			.filter_map(|binding| match binding {
+				BindingRef::Normal(key, _) => Some(key.as_str()),
+				BindingRef::Type(_) => None,
+			})
+			.filter(|term| !self.terms.contains(*term))
+	}
+
+	/// Records the IRI expansion of `value` using `active_context`, following
+	/// the steps of the IRI expansion algorithm.
+	pub(crate) fn record<N: Vocabulary>(
+		&mut self,
+		vocabulary: &N,
+		active_context: &Context<N::Iri, N::BlankId>,
+		value: &Nullable<ExpandableRef>,
+		vocab: Option<Action>,
+	) {
+		let value = match value {
+			Nullable::Some(ExpandableRef::Keyword(k)) => {
+				self.keywords.insert(*k);
+				return;
+			}
+			Nullable::Some(ExpandableRef::String(value)) => *value,
+			Nullable::Null => return,
+		};
+
+		if is_keyword_like(value) {
+			return;
+		}
+
+		if let Some(term_definition) = active_context.get(value) {
+			match term_definition.value() {
+				Some(Term::Keyword(k)) => {
+					self.terms.insert(value.to_string());
+					self.keywords.insert(*k);
+					return;
+				}
+				_ if vocab.is_some() => {
+					self.terms.insert(value.to_string());
+					return;
+				}
+				_ => (),
+			}
+		}
+
+		if value.find(':').map(|i| i > 0).unwrap_or(false) {
+			if BlankId::new(value).is_ok() || value == "_:" {
+				return;
+			}
+
+			if let Ok(compact_iri) = CompactIri::new(value) {
+				let prefix = compact_iri.prefix();
+				if let Some(term_definition) = active_context.get_normal(prefix) {
+					if term_definition.prefix {
+						if let Some(mapping) = &term_definition.value {
+							self.terms.insert(prefix.to_string());
+							self.vocabularies
+								.insert(mapping.with(vocabulary).as_str().to_string());
+							return;
+						}
+					}
+				}
+			}
+
+			if Iri::new(value).is_ok() {
+				return;
+			}
+		}
+
+		if matches!(vocab, Some(Action::Keep)) {
+			if let Some(Term::Id(mapping)) = active_context.vocabulary() {
+				self.vocabularies
+					.insert(mapping.with(vocabulary).as_str().to_string());
+				self.undefined_terms.insert(value.to_string());
+			}
+		}
+	}
+}
@@ -1,6 +1,9 @@
-use crate::{expand_iri, Action, ExpandedEntry};
+use crate::{
+	exact_literal, expand_iri, expand_value_pattern, is_value_pattern, Action, ExpandedEntry,
+	LanguageTagPolicy, Report, Warning,
+};
 use alloc::borrow::ToOwned;
-use alloc::string::ToString;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use json_ld_context_processing::algorithm::RejectVocab;
 use json_ld_core::{
@@ -35,6 +38,9 @@ pub enum InvalidValue {
 
 	#[error("Forbidden use of `@vocab`")]
 	ForbiddenVocab,
+
+	#[error("{0}")]
+	Strict(Warning<String>),
 }
 
 impl InvalidValue {
@@ -48,6 +54,7 @@ impl InvalidValue {
 			Self::ValueObjectValue => ErrorCode::InvalidValueObjectValue,
 			Self::LanguageTaggedValue => ErrorCode::InvalidLanguageTaggedValue,
 			Self::ForbiddenVocab => ErrorCode::InvalidTypeValue,
+			Self::Strict(w) => w.code(),
 		}
 	}
 }
@@ -64,16 +71,34 @@ pub type ValueExpansionResult<T, B> = Result<Option<IndexedObject<T, B>>, Invali
 pub(crate) fn expand_value<N, L>(
 	env: &mut Environment<N, L>,
 	vocab_policy: Action,
+	language_tags: LanguageTagPolicy,
+	exact_numbers: bool,
+	strict: bool,
+	frame_expansion: bool,
 	input_type: Option<Term<N::Iri, N::BlankId>>,
 	type_scoped_context: &Context<N::Iri, N::BlankId>,
 	expanded_entries: Vec<ExpandedEntry<N::Iri, N::BlankId>>,
 	value_entry: &json_syntax::Value,
+	report: &mut Report<'_>,
 ) -> ValueExpansionResult<N::Iri, N::BlankId>
 where
 	N: VocabularyMut,
 	N::Iri: Clone + PartialEq,
 	N::BlankId: Clone + PartialEq,
 {
+	// When the frameExpansion flag is set, `@value`, `@type`, `@language`
+	// and `@direction` MAY be frame patterns.
+	if frame_expansion && is_value_pattern(&expanded_entries, value_entry) {
+		return expand_value_pattern(
+			env,
+			vocab_policy,
+			type_scoped_context,
+			expanded_entries,
+			report,
+		)
+		.map(Some);
+	}
+
 	let mut is_json = input_type
 		.as_ref()
 		.map(|t| *t == Term::Keyword(Keyword::Json))
@@ -89,14 +114,18 @@ where
 			Term::Keyword(Keyword::Language) => {
 				// If value is not a string, an invalid language-tagged string
 				// error has been detected and processing is aborted.
-				if let Some(value) = value.as_str() {
+				if let Some(tag) = value.as_str() {
 					// Otherwise, set expanded value to value. If value is not
 					// well-formed according to section 2.2.9 of [BCP47],
 					// processors SHOULD issue a warning.
-					// TODO warning.
-
-					if value != "@none" {
-						language = Some(value.to_owned());
+					if tag != "@none" {
+						language = expand_language_tag(
+							tag.to_owned(),
+							language_tags,
+							strict,
+							value,
+							report,
+						)?;
 					}
 				} else {
 					return Err(InvalidValue::LanguageTaggedString);
@@ -139,6 +168,7 @@ where
 						Nullable::Some(ty_value.into()),
 						true,
 						Some(vocab_policy),
+						report,
 					)?;
 
 					match expanded_ty {
@@ -207,16 +237,7 @@ where
 		}
 
 		if let Literal::String(s) = result {
-			let lang = match language {
-				Some(language) => {
-					let (language, _error) = LenientLangTagBuf::new(language);
-
-					Some(language)
-				}
-				None => None,
-			};
-
-			return match LangString::new(s, lang, direction) {
+			return match LangString::new(s, language, direction) {
 				Ok(result) => Ok(Some(Indexed::new(
 					Object::Value(Value::LangString(result)),
 					index,
@@ -233,8 +254,56 @@ where
 	// @list, set result to null.
 	// TODO
 
+	let (result, ty) = if exact_numbers {
+		exact_literal(env.vocabulary, result, ty)
+	} else {
+		(result, ty)
+	};
+
 	Ok(Some(Indexed::new(
 		Object::Value(Value::Literal(result, ty)),
 		index,
 	)))
 }
+
+/// Applies the given language tag `policy` to `tag`, the `@language` of
+/// `element`.
+///
+/// Returns `None` if the language tag must be dropped.
+pub(crate) fn expand_language_tag(
+	tag: String,
+	policy: LanguageTagPolicy,
+	strict: bool,
+	element: &json_syntax::Value,
+	report: &mut Report<'_>,
+) -> Result<Option<LenientLangTagBuf>, InvalidValue> {
+	let (language, error) = LenientLangTagBuf::new(tag);
+	let error = match error {
+		Some(error) => error,
+		None => return Ok(Some(language)),
+	};
+
+	match policy {
+		LanguageTagPolicy::Reject => return Err(InvalidValue::LanguageTaggedString),
+		LanguageTagPolicy::Normalize => {
+			let normalized = language.as_str().trim().replace('_', "-");
+			if let (normalized, None) = LenientLangTagBuf::new(normalized) {
+				return Ok(Some(normalized));
+			}
+		}
+		LanguageTagPolicy::Lenient | LanguageTagPolicy::Drop => (),
+	}
+
+	let warning = Warning::MalformedLanguageTag(language.to_string(), error);
+	if strict {
+		return Err(InvalidValue::Strict(warning));
+	}
+
+	report.warn(warning, element);
+
+	if policy == LanguageTagPolicy::Drop {
+		Ok(None)
+	} else {
+		Ok(Some(language))
+	}
+}
@@ -1,7 +1,9 @@
-use alloc::string::String;
+use alloc::string::{String, ToString};
 use contextual::DisplayWithContext;
 use core::fmt;
 use json_ld_context_processing::algorithm::MalformedIri;
+use json_ld_core::warning::{Code, Structured};
+use json_ld_syntax::ErrorCode;
 use langtag::InvalidLangTag;
 use rdf_types::vocabulary::BlankIdVocabulary;
 
@@ -11,6 +13,31 @@ pub enum Warning<B> {
 	EmptyTerm,
 	BlankNodeIdProperty(B),
 	MalformedLanguageTag(String, InvalidLangTag<String>),
+	KeywordLikeKey(String),
+
+	/// Relative IRI reference that cannot be resolved against a base IRI,
+	/// with the key of the entry it was found in.
+	UnresolvedRelativeIri(String, String),
+
+	/// Key of an entry expanding to the same key as a previous entry of the
+	/// same object (see [`Options::duplicate_keys`](crate::Options::duplicate_keys)).
+	DuplicateKey(String),
+}
+
+impl<B> Warning<B> {
+	/// Returns the error code reported when this warning is promoted to an
+	/// error (see [`Options::strict`](crate::Options::strict)).
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Self::MalformedIri(_) => ErrorCode::InvalidIriMapping,
+			Self::EmptyTerm | Self::BlankNodeIdProperty(_) | Self::KeywordLikeKey(_) => {
+				ErrorCode::KeyExpansionFailed
+			}
+			Self::MalformedLanguageTag(_, _) => ErrorCode::InvalidLanguageTaggedString,
+			Self::UnresolvedRelativeIri(_, _) => ErrorCode::InvalidBaseIri,
+			Self::DuplicateKey(_) => ErrorCode::DuplicateKey,
+		}
+	}
 }
 
 impl<B> From<MalformedIri> for Warning<B> {
@@ -28,6 +55,37 @@ impl<B: fmt::Display> fmt::Display for Warning<B> {
 				write!(f, "blank node identifier `{b}` used as property")
 			}
 			Self::MalformedLanguageTag(t, e) => write!(f, "invalid language tag `{t}`: {e}"),
+			Self::KeywordLikeKey(k) => write!(f, "keyword-like key `{k}`"),
+			Self::UnresolvedRelativeIri(k, iri) => {
+				write!(f, "unresolved relative IRI reference `{iri}` in `{k}`")
+			}
+			Self::DuplicateKey(k) => write!(f, "duplicate key `{k}`"),
+		}
+	}
+}
+
+impl<B, N: BlankIdVocabulary<BlankId = B>> Structured<N> for Warning<B> {
+	fn warning_code(&self) -> Code {
+		match self {
+			Self::MalformedIri(_) => Code::MalformedIri,
+			Self::EmptyTerm => Code::EmptyTerm,
+			Self::BlankNodeIdProperty(_) => Code::BlankNodeIdProperty,
+			Self::MalformedLanguageTag(_, _) => Code::MalformedLanguageTag,
+			Self::KeywordLikeKey(_) => Code::KeywordLikeKey,
+			Self::UnresolvedRelativeIri(_, _) => Code::UnresolvedRelativeIri,
+			Self::DuplicateKey(_) => Code::DuplicateKey,
+		}
+	}
+
+	fn term_with(&self, vocabulary: &N) -> Option<String> {
+		match self {
+			Self::MalformedIri(s)
+			| Self::MalformedLanguageTag(s, _)
+			| Self::KeywordLikeKey(s)
+			| Self::UnresolvedRelativeIri(s, _)
+			| Self::DuplicateKey(s) => Some(s.clone()),
+			Self::EmptyTerm => None,
+			Self::BlankNodeIdProperty(b) => Some(vocabulary.blank_id(b).unwrap().to_string()),
 		}
 	}
 }
@@ -45,6 +103,33 @@ impl<B, N: BlankIdVocabulary<BlankId = B>> DisplayWithContext<N> for Warning<B>
 				)
 			}
 			Self::MalformedLanguageTag(t, e) => write!(f, "invalid language tag `{t}`: {e}"),
+			Self::KeywordLikeKey(k) => write!(f, "keyword-like key `{k}`"),
+			Self::UnresolvedRelativeIri(k, iri) => {
+				write!(f, "unresolved relative IRI reference `{iri}` in `{k}`")
+			}
+			Self::DuplicateKey(k) => write!(f, "duplicate key `{k}`"),
 		}
 	}
 }
+
+/// Warning emitted during expansion, with its location in the input
+/// document.
+///
+/// If the input document was parsed along with a
+/// [`CodeMap`](json_syntax::CodeMap), the span of the offending fragment is
+/// given by [`Location::span`](crate::Location::span). See
+/// [`Expand::expand_full_with_warnings`](crate::Expand::expand_full_with_warnings).
+#[derive(Debug)]
+pub struct LocatedWarning {
+	/// Warning.
+	pub warning: Warning<String>,
+
+	/// Location of the offending fragment in the input document.
+	pub location: crate::Location,
+}
+
+impl fmt::Display for LocatedWarning {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (at `{}`)", self.warning, self.location)
+	}
+}
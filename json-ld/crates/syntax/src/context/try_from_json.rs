@@ -29,6 +29,12 @@ pub enum InvalidContext {
 
 	#[error("Invalid `@nest` value `{0}`")]
 	InvalidNestValue(String),
+
+	#[error("Invalid `@import` value")]
+	InvalidImportValue,
+
+	#[error("Invalid `@propagate` value")]
+	InvalidPropagateValue,
 }
 
 impl InvalidContext {
@@ -40,6 +46,8 @@ impl InvalidContext {
 			Self::DuplicateKey => ErrorCode::DuplicateKey,
 			Self::InvalidTermDefinition => ErrorCode::InvalidTermDefinition,
 			Self::InvalidNestValue(_) => ErrorCode::InvalidNestValue,
+			Self::InvalidImportValue => ErrorCode::InvalidImportValue,
+			Self::InvalidPropagateValue => ErrorCode::InvalidPropagateValue,
 		}
 	}
 }
@@ -311,14 +319,24 @@ impl TryFromJson for ContextEntry {
 				for json_syntax::object::Entry { key, value } in o {
 					match Keyword::try_from(key.as_str()) {
 						Ok(Keyword::Base) => def.base = Some(Nullable::try_from_json(value)?),
-						Ok(Keyword::Import) => def.import = Some(IriRefBuf::try_from_json(value)?),
+						Ok(Keyword::Import) => {
+							def.import = Some(
+								IriRefBuf::try_from_json(value)
+									.map_err(|_| InvalidContext::InvalidImportValue)?,
+							)
+						}
 						Ok(Keyword::Language) => {
 							def.language = Some(Nullable::try_from_json(value)?)
 						}
 						Ok(Keyword::Direction) => {
 							def.direction = Some(Nullable::try_from_json(value)?)
 						}
-						Ok(Keyword::Propagate) => def.propagate = Some(bool::try_from_json(value)?),
+						Ok(Keyword::Propagate) => {
+							def.propagate = Some(
+								bool::try_from_json(value)
+									.map_err(|_| InvalidContext::InvalidPropagateValue)?,
+							)
+						}
 						Ok(Keyword::Protected) => def.protected = Some(bool::try_from_json(value)?),
 						Ok(Keyword::Type) => {
 							def.type_ = Some(definition::Type::try_from_json(value)?)
@@ -353,3 +371,53 @@ impl TryFromJson for ContextEntry {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use crate::{context::Context, ErrorCode, TryFromJson};
+
+	fn error_code(value: json_syntax::Value) -> Option<ErrorCode> {
+		Context::try_from_json(value).err().map(|e| e.code())
+	}
+
+	#[test]
+	fn invalid_import_value() {
+		assert_eq!(
+			error_code(json_syntax::json!({ "@import": true })),
+			Some(ErrorCode::InvalidImportValue)
+		);
+		assert_eq!(
+			error_code(json_syntax::json!({ "@import": { "@vocab": "http://example.org/" } })),
+			Some(ErrorCode::InvalidImportValue)
+		);
+	}
+
+	#[test]
+	fn invalid_propagate_value() {
+		assert_eq!(
+			error_code(json_syntax::json!({ "@propagate": "false" })),
+			Some(ErrorCode::InvalidPropagateValue)
+		);
+		assert_eq!(
+			error_code(json_syntax::json!([{ "@propagate": null }])),
+			Some(ErrorCode::InvalidPropagateValue)
+		);
+	}
+
+	#[test]
+	fn valid_import_and_propagate() {
+		let context = Context::try_from_json(json_syntax::json!({
+			"@import": "context.jsonld",
+			"@propagate": false
+		}))
+		.unwrap();
+
+		match context {
+			Context::One(crate::ContextEntry::Definition(def)) => {
+				assert_eq!(def.import.unwrap().as_str(), "context.jsonld");
+				assert_eq!(def.propagate, Some(false))
+			}
+			_ => panic!("expected a context definition"),
+		}
+	}
+}
@@ -171,6 +171,22 @@ pub enum ErrorCode {
 
 	/// Duplicate key in JSON object.
 	DuplicateKey,
+
+	/// The maximum nesting depth has been exceeded.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	MaxDepthExceeded,
+
+	/// The maximum number of nodes has been exceeded.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	TooManyNodes,
+
+	/// The requested document form (profile) is not supported.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	UnsupportedProfile,
+
+	/// Unknown keyword rejected by a keyword handler.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	UnknownKeyword,
 }
 
 impl ErrorCode {
@@ -230,6 +246,10 @@ impl ErrorCode {
 			ProcessingModeConflict => "processing mode conflict",
 			ProtectedTermRedefinition => "protected term redefinition",
 			DuplicateKey => "duplicate key",
+			MaxDepthExceeded => "max depth exceeded",
+			TooManyNodes => "too many nodes",
+			UnsupportedProfile => "unsupported profile",
+			UnknownKeyword => "unknown keyword",
 		}
 	}
 }
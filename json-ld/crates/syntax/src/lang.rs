@@ -2,6 +2,7 @@ use alloc::borrow::Borrow;
 use alloc::borrow::ToOwned;
 use alloc::string::String;
 use core::{fmt, hash::Hash, ops::Deref};
+use langtag::GrandfatheredLangTag;
 pub use langtag::{InvalidLangTag, LangTag, LangTagBuf};
 
 use crate::utils::{case_insensitive_cmp, case_insensitive_eq, case_insensitive_hash};
@@ -33,6 +34,116 @@ impl LenientLangTag {
 	pub fn as_well_formed(&self) -> Option<&LangTag> {
 		LangTag::new(self.as_str()).ok()
 	}
+
+	/// Returns the canonical form of this tag, or `None` if it is not
+	/// well-formed.
+	///
+	/// Following [RFC 5646 Section 4.5](https://www.rfc-editor.org/rfc/rfc5646#section-4.5),
+	/// grandfathered tags and deprecated language and region subtags are
+	/// replaced with their preferred value, and the case of each subtag is
+	/// normalized: script subtags are title-cased, region subtags
+	/// upper-cased and all the others lower-cased.
+	///
+	/// The replacement tables are not generated from the IANA Language
+	/// Subtag Registry. They cover every grandfathered tag, and the
+	/// two-letter language and region subtags with a `Preferred-Value` in
+	/// the registry. Deprecated three-letter language subtags, extended
+	/// language subtags, numeric region subtags and variant subtags are
+	/// only case-normalized. Deprecated subtags without a `Preferred-Value`,
+	/// such as the `DD` region (split between `DE` and other regions), are
+	/// left unchanged.
+	pub fn canonicalize(&self) -> Option<LangTagBuf> {
+		let tag = self.as_well_formed()?;
+
+		if let Some(grandfathered) = tag.as_grandfathered() {
+			let canonical =
+				preferred_grandfathered(grandfathered).unwrap_or(grandfathered.as_str());
+			return LangTagBuf::new(canonical.to_owned()).ok();
+		}
+
+		let mut result = String::with_capacity(tag.as_str().len());
+		let mut singleton = false;
+
+		for (i, subtag) in tag.as_str().split('-').enumerate() {
+			if i > 0 {
+				result.push('-')
+			}
+
+			singleton |= subtag.len() == 1;
+
+			if singleton {
+				result.push_str(&subtag.to_ascii_lowercase())
+			} else if i == 0 {
+				let subtag = subtag.to_ascii_lowercase();
+				result.push_str(preferred_language(&subtag).unwrap_or(&subtag))
+			} else if subtag.len() == 4 && subtag.bytes().all(|c| c.is_ascii_alphabetic()) {
+				result.push_str(&subtag[..1].to_ascii_uppercase());
+				result.push_str(&subtag[1..].to_ascii_lowercase())
+			} else if subtag.len() == 2 {
+				let subtag = subtag.to_ascii_uppercase();
+				result.push_str(preferred_region(&subtag).unwrap_or(&subtag))
+			} else {
+				result.push_str(&subtag.to_ascii_lowercase())
+			}
+		}
+
+		LangTagBuf::new(result).ok()
+	}
+}
+
+/// Preferred value of the given grandfathered tag, if any.
+fn preferred_grandfathered(tag: GrandfatheredLangTag) -> Option<&'static str> {
+	use GrandfatheredLangTag::*;
+	match tag {
+		EnGbOed => Some("en-GB-oxendict"),
+		IAmi => Some("ami"),
+		IBnn => Some("bnn"),
+		IHak => Some("hak"),
+		IKlingon => Some("tlh"),
+		ILux => Some("lb"),
+		INavajo => Some("nv"),
+		IPwn => Some("pwn"),
+		ITao => Some("tao"),
+		ITay => Some("tay"),
+		ITsu => Some("tsu"),
+		SgnBeFr => Some("sfb"),
+		SgnBeNl => Some("vgt"),
+		SgnChDe => Some("sgg"),
+		ArtLojban => Some("jbo"),
+		NoBok => Some("nb"),
+		NoNyn => Some("nn"),
+		ZhGuoyu => Some("cmn"),
+		ZhHakka => Some("hak"),
+		ZhMinNan => Some("nan"),
+		ZhXiang => Some("hsn"),
+		IDefault | IEnochian | IMingo | CelGaulish | ZhMin => None,
+	}
+}
+
+/// Preferred value of the given deprecated (lower-case) two-letter primary
+/// language subtag, if any.
+fn preferred_language(subtag: &str) -> Option<&'static str> {
+	match subtag {
+		"in" => Some("id"),
+		"iw" => Some("he"),
+		"ji" => Some("yi"),
+		"jw" => Some("jv"),
+		"mo" => Some("ro"),
+		_ => None,
+	}
+}
+
+/// Preferred value of the given deprecated (upper-case) two-letter region
+/// subtag, if any.
+fn preferred_region(subtag: &str) -> Option<&'static str> {
+	match subtag {
+		"BU" => Some("MM"),
+		"FX" => Some("FR"),
+		"TP" => Some("TL"),
+		"YD" => Some("YE"),
+		"ZR" => Some("CD"),
+		_ => None,
+	}
 }
 
 impl PartialEq for LenientLangTag {
@@ -197,3 +308,31 @@ impl<'de> serde::Deserialize<'de> for LenientLangTagBuf {
 		Ok(Self(String::deserialize(deserializer)?))
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::LenientLangTag;
+
+	#[test]
+	fn canonicalize() {
+		let canonical = |s: &str| {
+			LenientLangTag::new(s)
+				.0
+				.canonicalize()
+				.map(|t| t.into_string())
+		};
+
+		assert_eq!(canonical("EN-us").as_deref(), Some("en-US"));
+		assert_eq!(canonical("zh-hant-tw").as_deref(), Some("zh-Hant-TW"));
+		assert_eq!(canonical("iw-IL").as_deref(), Some("he-IL"));
+		assert_eq!(canonical("my-bu").as_deref(), Some("my-MM"));
+		assert_eq!(canonical("de-dd").as_deref(), Some("de-DD"));
+		assert_eq!(canonical("sr-CS").as_deref(), Some("sr-CS"));
+		assert_eq!(
+			canonical("en-a-BBB-x-Private").as_deref(),
+			Some("en-a-bbb-x-private")
+		);
+		assert_eq!(canonical("i-klingon").as_deref(), Some("tlh"));
+		assert_eq!(canonical("en--us"), None);
+	}
+}
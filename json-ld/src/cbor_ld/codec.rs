@@ -0,0 +1,285 @@
+//! Value codecs.
+use super::context::{TermTable, ValueType};
+use alloc::{format, string::String, vec, vec::Vec};
+use ciborium::Value as CborValue;
+use json_ld_core::utils::base58;
+
+pub(crate) const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+
+pub(crate) const XSD_DATE: &str = "http://www.w3.org/2001/XMLSchema#date";
+
+pub(crate) const MULTIBASE: &str = "https://w3id.org/security#multibase";
+
+/// IRI scheme codecs.
+const HTTP: u64 = 1;
+const HTTPS: u64 = 2;
+const URN_UUID: u64 = 3;
+const DID_V1_NYM: u64 = 1024;
+const DID_KEY: u64 = 1025;
+
+/// Encodes a string value of the given type.
+///
+/// Falls back to the uncompressed string whenever the value does not have
+/// the canonical form expected by the codec.
+pub(crate) fn encode_string(table: &TermTable, ty: ValueType, value: &str) -> CborValue {
+	let encoded = match ty {
+		ValueType::Plain => None,
+		ValueType::Id => encode_iri(value),
+		ValueType::Vocab => match table.id(value) {
+			Some(id) => Some(CborValue::Integer(id.into())),
+			None => encode_iri(value),
+		},
+		ValueType::DateTime => encode_date_time(value).map(|t| CborValue::Integer(t.into())),
+		ValueType::Date => encode_date(value).map(|t| CborValue::Integer(t.into())),
+		ValueType::Multibase => encode_multibase(value).map(CborValue::Bytes),
+	};
+
+	encoded.unwrap_or_else(|| CborValue::Text(value.into()))
+}
+
+/// Decodes a compressed value of the given type.
+pub(crate) fn decode_string(table: &TermTable, ty: ValueType, value: &CborValue) -> Option<String> {
+	match (ty, value) {
+		(ValueType::Id | ValueType::Vocab, CborValue::Array(items)) => decode_iri(items),
+		(ValueType::Vocab, CborValue::Integer(id)) => {
+			let id = u64::try_from(*id).ok()?;
+			table.term(id).map(Into::into)
+		}
+		(ValueType::DateTime, CborValue::Integer(t)) => decode_date_time(i64::try_from(*t).ok()?),
+		(ValueType::Date, CborValue::Integer(t)) => decode_date(i64::try_from(*t).ok()?),
+		(ValueType::Multibase, CborValue::Bytes(bytes)) => decode_multibase(bytes),
+		_ => None,
+	}
+}
+
+fn encode_iri(iri: &str) -> Option<CborValue> {
+	let integer = |i: u64| CborValue::Integer(i.into());
+
+	if let Some(rest) = iri.strip_prefix("https://") {
+		return Some(CborValue::Array(vec![
+			integer(HTTPS),
+			CborValue::Text(rest.into()),
+		]));
+	}
+
+	if let Some(rest) = iri.strip_prefix("http://") {
+		return Some(CborValue::Array(vec![
+			integer(HTTP),
+			CborValue::Text(rest.into()),
+		]));
+	}
+
+	if let Some(rest) = iri.strip_prefix("urn:uuid:") {
+		let uuid = encode_uuid(rest)?;
+		return Some(CborValue::Array(vec![
+			integer(URN_UUID),
+			CborValue::Bytes(uuid),
+		]));
+	}
+
+	if let Some(rest) = iri.strip_prefix("did:v1:nym:") {
+		let nym = encode_multibase(rest)?;
+		return Some(CborValue::Array(vec![
+			integer(DID_V1_NYM),
+			CborValue::Bytes(nym),
+		]));
+	}
+
+	if let Some(rest) = iri.strip_prefix("did:key:") {
+		let mut items = vec![integer(DID_KEY)];
+		let (key, fragment) = match rest.split_once('#') {
+			Some((key, fragment)) => (key, Some(fragment)),
+			None => (rest, None),
+		};
+
+		items.push(CborValue::Bytes(encode_multibase(key)?));
+		if let Some(fragment) = fragment {
+			items.push(CborValue::Bytes(encode_multibase(fragment)?))
+		}
+
+		return Some(CborValue::Array(items));
+	}
+
+	None
+}
+
+fn decode_iri(items: &[CborValue]) -> Option<String> {
+	let (scheme, rest) = items.split_first()?;
+	match (u64::try_from(scheme.as_integer()?).ok()?, rest) {
+		(HTTPS, [CborValue::Text(rest)]) => Some(format!("https://{rest}")),
+		(HTTP, [CborValue::Text(rest)]) => Some(format!("http://{rest}")),
+		(URN_UUID, [CborValue::Bytes(uuid)]) => Some(format!("urn:uuid:{}", decode_uuid(uuid)?)),
+		(DID_V1_NYM, [CborValue::Bytes(nym)]) => {
+			Some(format!("did:v1:nym:{}", decode_multibase(nym)?))
+		}
+		(DID_KEY, [CborValue::Bytes(key)]) => Some(format!("did:key:{}", decode_multibase(key)?)),
+		(DID_KEY, [CborValue::Bytes(key), CborValue::Bytes(fragment)]) => Some(format!(
+			"did:key:{}#{}",
+			decode_multibase(key)?,
+			decode_multibase(fragment)?
+		)),
+		_ => None,
+	}
+}
+
+/// Encodes a lowercase hyphenated UUID.
+fn encode_uuid(uuid: &str) -> Option<Vec<u8>> {
+	let hex: Vec<u8> = uuid.bytes().filter(|&c| c != b'-').collect();
+	if hex.len() != 32 {
+		return None;
+	}
+
+	let bytes: Vec<u8> = hex
+		.chunks(2)
+		.map(|pair| Some((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+		.collect::<Option<_>>()?;
+
+	(decode_uuid(&bytes)? == uuid).then_some(bytes)
+}
+
+fn decode_uuid(bytes: &[u8]) -> Option<String> {
+	if bytes.len() != 16 {
+		return None;
+	}
+
+	let mut result = String::with_capacity(36);
+	for (i, b) in bytes.iter().enumerate() {
+		if matches!(i, 4 | 6 | 8 | 10) {
+			result.push('-')
+		}
+
+		result.push_str(&format!("{b:02x}"))
+	}
+
+	Some(result)
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+	match c {
+		b'0'..=b'9' => Some(c - b'0'),
+		b'a'..=b'f' => Some(c - b'a' + 10),
+		_ => None,
+	}
+}
+
+/// Encodes a base58-btc multibase string, keeping the multibase prefix.
+fn encode_multibase(value: &str) -> Option<Vec<u8>> {
+	let data = value.strip_prefix('z')?;
+	let mut bytes = vec![b'z'];
+	bytes.extend(base58::decode(data)?);
+	(base58::encode(&bytes[1..]) == data).then_some(bytes)
+}
+
+fn decode_multibase(bytes: &[u8]) -> Option<String> {
+	match bytes.split_first()? {
+		(b'z', data) => Some(format!("z{}", base58::encode(data))),
+		_ => None,
+	}
+}
+
+/// Encodes a `YYYY-MM-DDThh:mm:ssZ` date-time as seconds since the Unix epoch.
+fn encode_date_time(value: &str) -> Option<i64> {
+	let (date, time) = value.strip_suffix('Z')?.split_once('T')?;
+	let mut time = time.split(':').map(|n| n.parse::<i64>().ok());
+	let (h, m, s) = (time.next()??, time.next()??, time.next()??);
+	if !(0..24).contains(&h) || !(0..60).contains(&m) || !(0..60).contains(&s) {
+		return None;
+	}
+
+	let t = encode_date(date)? + h * 3600 + m * 60 + s;
+	(decode_date_time(t)? == value).then_some(t)
+}
+
+fn decode_date_time(t: i64) -> Option<String> {
+	let date = decode_date(t.div_euclid(86400) * 86400)?;
+	let s = t.rem_euclid(86400);
+	Some(format!(
+		"{date}T{:02}:{:02}:{:02}Z",
+		s / 3600,
+		s / 60 % 60,
+		s % 60
+	))
+}
+
+/// Encodes a `YYYY-MM-DD` date as the seconds since the Unix epoch at
+/// midnight UTC.
+fn encode_date(value: &str) -> Option<i64> {
+	let mut parts = value.split('-').map(|n| n.parse::<u32>().ok());
+	let (y, m, d) = (parts.next()??, parts.next()??, parts.next()??);
+	if parts.next().is_some() || !(1..=12).contains(&m) || !(1..=31).contains(&d) {
+		return None;
+	}
+
+	let t = days_from_civil(y as i64, m, d) * 86400;
+	(decode_date(t)? == value).then_some(t)
+}
+
+fn decode_date(t: i64) -> Option<String> {
+	let (y, m, d) = civil_from_days(t.div_euclid(86400));
+	(0..=9999)
+		.contains(&y)
+		.then(|| format!("{y:04}-{m:02}-{d:02}"))
+}
+
+/// Number of days since the Unix epoch of the given proleptic Gregorian
+/// calendar date.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+	let y = if m <= 2 { y - 1 } else { y };
+	let era = y.div_euclid(400);
+	let yoe = y - era * 400;
+	let doy = (153 * ((m as i64 + 9) % 12) + 2) / 5 + d as i64 - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	era * 146097 + doe - 719468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+	let z = days + 719468;
+	let era = z.div_euclid(146097);
+	let doe = z - era * 146097;
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+	let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+	let y = yoe + era * 400;
+	(if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn date_time() {
+		assert_eq!(encode_date_time("1970-01-01T00:00:00Z"), Some(0));
+		assert_eq!(encode_date_time("2020-01-01T00:00:00Z"), Some(1577836800));
+		assert_eq!(encode_date("2020-02-29"), Some(1582934400));
+		assert_eq!(
+			decode_date_time(1577836800).unwrap(),
+			"2020-01-01T00:00:00Z"
+		);
+
+		// Values without canonical form are not compressed.
+		assert_eq!(encode_date_time("2020-01-01T00:00:00.5Z"), None);
+		assert_eq!(encode_date("2020-02-30"), None)
+	}
+
+	#[test]
+	fn iri() {
+		for iri in [
+			"https://example.org/a",
+			"urn:uuid:188e8450-269e-11eb-b545-d3692cf35398",
+			"did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+		] {
+			let Some(CborValue::Array(items)) = encode_iri(iri) else {
+				panic!("`{iri}` is not compressed")
+			};
+
+			assert_eq!(decode_iri(&items).unwrap(), iri)
+		}
+
+		assert!(encode_iri("urn:uuid:188E8450-269E-11EB-B545-D3692CF35398").is_none());
+		assert!(encode_iri("mailto:jane@example.org").is_none())
+	}
+}
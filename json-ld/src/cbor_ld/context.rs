@@ -0,0 +1,391 @@
+use super::codec::{MULTIBASE, XSD_DATE, XSD_DATE_TIME};
+use alloc::{
+	borrow::ToOwned,
+	boxed::Box,
+	collections::{BTreeMap, BTreeSet},
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use iref::Iri;
+use json_ld_core::{LoadError, Loader};
+use json_syntax::Value;
+
+/// Keywords, in the order defining their codec identifier.
+const KEYWORDS: [&str; 27] = [
+	"@context",
+	"@type",
+	"@id",
+	"@value",
+	"@direction",
+	"@graph",
+	"@included",
+	"@index",
+	"@json",
+	"@language",
+	"@list",
+	"@nest",
+	"@reverse",
+	"@base",
+	"@container",
+	"@default",
+	"@embed",
+	"@explicit",
+	"@none",
+	"@omitDefault",
+	"@prefix",
+	"@preserve",
+	"@protected",
+	"@requireAll",
+	"@set",
+	"@version",
+	"@vocab",
+];
+
+/// Identifier of the first non-keyword term.
+const FIRST_TERM_ID: u64 = 100;
+
+/// Context error.
+#[derive(Debug, thiserror::Error)]
+pub enum ContextError {
+	/// Remote context loading failed.
+	#[error(transparent)]
+	Loading(#[from] LoadError),
+
+	/// Context URL is not an absolute IRI.
+	#[error("invalid context URL `{0}`")]
+	InvalidUrl(String),
+
+	/// Context is invalid.
+	#[error("invalid context")]
+	Invalid,
+}
+
+/// Term codec table.
+///
+/// Maps every keyword and every term defined by the contexts encountered so
+/// far to an even integer identifier. The odd identifier following a term
+/// identifier denotes the same term, applied to an array of values.
+///
+/// Keywords have fixed identifiers, from `0` to `52`. Other terms are
+/// numbered from [`FIRST_TERM_ID`] as their context is registered, see
+/// [`ContextProcessor::register`].
+pub(crate) struct TermTable {
+	ids: BTreeMap<String, u64>,
+	terms: BTreeMap<u64, String>,
+	next_id: u64,
+}
+
+impl TermTable {
+	pub fn new() -> Self {
+		let mut result = Self {
+			ids: BTreeMap::new(),
+			terms: BTreeMap::new(),
+			next_id: 0,
+		};
+
+		for keyword in KEYWORDS {
+			result.insert(keyword)
+		}
+
+		result.next_id = FIRST_TERM_ID;
+		result
+	}
+
+	fn insert(&mut self, term: &str) {
+		if !self.ids.contains_key(term) {
+			let id = self.next_id;
+			self.ids.insert(term.to_owned(), id);
+			self.terms.insert(id, term.to_owned());
+			self.next_id += 2
+		}
+	}
+
+	/// Returns the identifier of the given term.
+	pub fn id(&self, term: &str) -> Option<u64> {
+		self.ids.get(term).copied()
+	}
+
+	/// Returns the term with the given (even) identifier.
+	pub fn term(&self, id: u64) -> Option<&str> {
+		self.terms.get(&id).map(String::as_str)
+	}
+}
+
+/// Term definition, restricted to what is relevant to CBOR-LD.
+#[derive(Clone, Default)]
+pub(crate) struct Definition {
+	id: Option<String>,
+	type_: Option<String>,
+	context: Option<Value>,
+}
+
+impl Definition {
+	fn from_json(value: &Value) -> Result<Option<Self>, ContextError> {
+		match value {
+			Value::Null => Ok(None),
+			Value::String(id) => Ok(Some(Self {
+				id: Some(id.to_string()),
+				..Default::default()
+			})),
+			Value::Object(o) => {
+				let get = |key: &str| {
+					o.get_unique(key)
+						.map_err(|_| ContextError::Invalid)
+						.map(|v| v.filter(|v| !v.is_null()))
+				};
+
+				Ok(Some(Self {
+					id: get("@id")?.and_then(Value::as_str).map(ToOwned::to_owned),
+					type_: get("@type")?.and_then(Value::as_str).map(ToOwned::to_owned),
+					context: get("@context")?.cloned(),
+				}))
+			}
+			_ => Err(ContextError::Invalid),
+		}
+	}
+}
+
+/// How the values of an entry are compressed.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ValueType {
+	/// No compression.
+	Plain,
+
+	/// IRI.
+	Id,
+
+	/// Term or IRI.
+	Vocab,
+
+	/// `xsd:dateTime` literal.
+	DateTime,
+
+	/// `xsd:date` literal.
+	Date,
+
+	/// Multibase-encoded literal.
+	Multibase,
+}
+
+/// Active context, restricted to what is relevant to CBOR-LD.
+#[derive(Clone, Default)]
+pub(crate) struct ActiveContext {
+	definitions: BTreeMap<String, Definition>,
+}
+
+impl ActiveContext {
+	/// Returns the local context scoped to the given term, if any.
+	pub fn scoped_context(&self, term: &str) -> Option<&Value> {
+		self.definitions.get(term)?.context.as_ref()
+	}
+
+	/// Returns the keyword the given key is, or is an alias of.
+	pub fn keyword<'a>(&'a self, key: &'a str) -> Option<&'a str> {
+		let key = match self.definitions.get(key) {
+			Some(definition) => definition.id.as_deref()?,
+			None => key,
+		};
+
+		KEYWORDS.contains(&key).then_some(key)
+	}
+
+	/// Expands the given term or compact IRI.
+	fn expand_iri(&self, value: &str) -> String {
+		if let Some(id) = self.definitions.get(value).and_then(|d| d.id.as_deref()) {
+			return id.to_owned();
+		}
+
+		if let Some((prefix, suffix)) = value.split_once(':') {
+			if !suffix.starts_with("//") {
+				if let Some(id) = self.definitions.get(prefix).and_then(|d| d.id.as_deref()) {
+					return format!("{id}{suffix}");
+				}
+			}
+		}
+
+		value.to_owned()
+	}
+
+	/// Returns how the values of the given entry key are compressed.
+	pub fn value_type(&self, key: &str) -> ValueType {
+		match self.keyword(key) {
+			Some("@id") => ValueType::Id,
+			Some("@type") => ValueType::Vocab,
+			Some(_) => ValueType::Plain,
+			None => match self.definitions.get(key).and_then(|d| d.type_.as_deref()) {
+				Some("@id") => ValueType::Id,
+				Some("@vocab") => ValueType::Vocab,
+				Some(ty) => match self.expand_iri(ty).as_str() {
+					XSD_DATE_TIME => ValueType::DateTime,
+					XSD_DATE => ValueType::Date,
+					MULTIBASE => ValueType::Multibase,
+					_ => ValueType::Plain,
+				},
+				None => ValueType::Plain,
+			},
+		}
+	}
+}
+
+/// Context processor.
+///
+/// Applies local contexts and registers the terms they define into the term
+/// table.
+pub(crate) struct ContextProcessor<'l, L> {
+	loader: &'l L,
+	pub table: TermTable,
+	loaded: BTreeMap<String, Value>,
+	registered: BTreeSet<String>,
+}
+
+impl<'l, L: Loader> ContextProcessor<'l, L> {
+	pub fn new(loader: &'l L) -> Self {
+		Self {
+			loader,
+			table: TermTable::new(),
+			loaded: BTreeMap::new(),
+			registered: BTreeSet::new(),
+		}
+	}
+
+	/// Applies the given local context to the active context, registering
+	/// its terms first.
+	pub async fn apply(
+		&mut self,
+		active_context: &ActiveContext,
+		local_context: &Value,
+	) -> Result<ActiveContext, ContextError> {
+		self.register(local_context).await?;
+		let mut result = active_context.clone();
+		self.apply_in(&mut result, local_context).await?;
+		Ok(result)
+	}
+
+	/// Applies the contexts scoped to the given types, in lexicographic
+	/// order.
+	///
+	/// Their terms are already registered, along with the context defining
+	/// the types.
+	pub async fn apply_type_scoped(
+		&mut self,
+		active_context: &ActiveContext,
+		mut types: Vec<String>,
+	) -> Result<ActiveContext, ContextError> {
+		types.sort_unstable();
+		types.dedup();
+
+		let mut result = active_context.clone();
+		for ty in types {
+			if let Some(context) = active_context.scoped_context(&ty) {
+				self.apply_in(&mut result, context).await?
+			}
+		}
+
+		Ok(result)
+	}
+
+	async fn apply_in(
+		&mut self,
+		result: &mut ActiveContext,
+		local_context: &Value,
+	) -> Result<(), ContextError> {
+		match local_context {
+			Value::Null => *result = ActiveContext::default(),
+			Value::Array(items) => {
+				for item in items {
+					Box::pin(self.apply_in(result, item)).await?
+				}
+			}
+			Value::String(url) => {
+				let context = self.load(url).await?;
+				Box::pin(self.apply_in(result, &context)).await?
+			}
+			Value::Object(o) => {
+				for entry in o {
+					let term = entry.key.as_str();
+					if term.starts_with('@') {
+						continue;
+					}
+
+					match Definition::from_json(&entry.value)? {
+						Some(definition) => {
+							result.definitions.insert(term.to_owned(), definition);
+						}
+						None => {
+							result.definitions.remove(term);
+						}
+					}
+				}
+			}
+			_ => return Err(ContextError::Invalid),
+		}
+
+		Ok(())
+	}
+
+	/// Registers the terms defined by the given local context into the term
+	/// table.
+	///
+	/// The terms of each context are registered in lexicographic order, then
+	/// the contexts scoped to these terms are registered, in the same order.
+	/// Term identifiers hence only depend on the contexts, and not on where
+	/// the document uses their terms. Remote contexts are registered once.
+	pub async fn register(&mut self, local_context: &Value) -> Result<(), ContextError> {
+		match local_context {
+			Value::Array(items) => {
+				for item in items {
+					Box::pin(self.register(item)).await?
+				}
+			}
+			Value::String(url) => {
+				if self.registered.insert(url.to_string()) {
+					let context = self.load(url).await?;
+					Box::pin(self.register(&context)).await?
+				}
+			}
+			Value::Object(o) => {
+				let mut terms: Vec<_> = o
+					.iter()
+					.filter(|entry| !entry.key.starts_with('@') && !entry.value.is_null())
+					.collect();
+				terms.sort_unstable_by(|a, b| a.key.as_str().cmp(b.key.as_str()));
+
+				for entry in &terms {
+					self.table.insert(&entry.key)
+				}
+
+				for entry in terms {
+					let scoped_context = entry
+						.value
+						.as_object()
+						.and_then(|definition| definition.get_unique("@context").ok().flatten());
+
+					if let Some(scoped_context) = scoped_context {
+						Box::pin(self.register(scoped_context)).await?
+					}
+				}
+			}
+			_ => (),
+		}
+
+		Ok(())
+	}
+
+	async fn load(&mut self, url: &str) -> Result<Value, ContextError> {
+		if let Some(context) = self.loaded.get(url) {
+			return Ok(context.clone());
+		}
+
+		let iri = Iri::new(url).map_err(|_| ContextError::InvalidUrl(url.to_owned()))?;
+		let document = self.loader.load(iri).await?.into_document();
+		let context = document
+			.as_object()
+			.and_then(|o| o.get_unique("@context").ok().flatten())
+			.cloned()
+			.ok_or(ContextError::Invalid)?;
+
+		self.loaded.insert(url.to_owned(), context.clone());
+		Ok(context)
+	}
+}
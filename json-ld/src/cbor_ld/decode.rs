@@ -0,0 +1,218 @@
+use super::{
+	codec,
+	context::{ActiveContext, ContextProcessor, ValueType},
+	DecodeError,
+};
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use ciborium::Value as CborValue;
+use json_ld_core::Loader;
+use json_syntax::{object::Key, Object, Value};
+
+/// Converts a CBOR value into JSON, without decompression.
+pub(crate) fn cbor_to_json(value: &CborValue) -> Result<Value, DecodeError> {
+	match value {
+		CborValue::Null => Ok(Value::Null),
+		CborValue::Bool(b) => Ok(Value::Boolean(*b)),
+		CborValue::Integer(i) => match (i64::try_from(*i), u64::try_from(*i)) {
+			(Ok(i), _) => Ok(i.into()),
+			(Err(_), Ok(u)) => Ok(u.into()),
+			_ => Err(DecodeError::InvalidValue),
+		},
+		CborValue::Float(f) => Value::try_from(*f).map_err(|_| DecodeError::InvalidValue),
+		CborValue::Text(s) => Ok(s.as_str().into()),
+		CborValue::Array(items) => Ok(Value::Array(
+			items.iter().map(cbor_to_json).collect::<Result<_, _>>()?,
+		)),
+		CborValue::Map(entries) => {
+			let mut result = Object::new();
+			for (key, value) in entries {
+				let key = key.as_text().ok_or(DecodeError::InvalidValue)?;
+				result.push(key.into(), cbor_to_json(value)?);
+			}
+
+			Ok(Value::Object(result))
+		}
+		_ => Err(DecodeError::InvalidValue),
+	}
+}
+
+/// Decoded entry key.
+enum DecodedKey<'a> {
+	/// Uncompressed key.
+	Text(&'a str),
+
+	/// Term, with a flag indicating if it is applied to an array of values.
+	Term(u64, bool),
+}
+
+impl<'a> DecodedKey<'a> {
+	fn new(key: &'a CborValue) -> Result<Self, DecodeError> {
+		match key {
+			CborValue::Text(key) => Ok(Self::Text(key)),
+			CborValue::Integer(id) => {
+				let id = u64::try_from(*id).map_err(|_| DecodeError::InvalidValue)?;
+				Ok(Self::Term(id & !1, id & 1 == 1))
+			}
+			_ => Err(DecodeError::InvalidValue),
+		}
+	}
+}
+
+/// CBOR-LD decoder.
+pub(crate) struct Decoder<'l, L> {
+	contexts: ContextProcessor<'l, L>,
+}
+
+impl<'l, L: Loader> Decoder<'l, L> {
+	pub fn new(loader: &'l L) -> Self {
+		Self {
+			contexts: ContextProcessor::new(loader),
+		}
+	}
+
+	pub async fn decode(
+		&mut self,
+		active_context: &ActiveContext,
+		value: &CborValue,
+	) -> Result<Value, DecodeError> {
+		match value {
+			CborValue::Array(items) => {
+				let mut result = Vec::with_capacity(items.len());
+				for item in items {
+					result.push(Box::pin(self.decode(active_context, item)).await?)
+				}
+
+				Ok(Value::Array(result))
+			}
+			CborValue::Map(entries) => Box::pin(self.decode_object(active_context, entries)).await,
+			value => cbor_to_json(value),
+		}
+	}
+
+	fn term(&self, id: u64) -> Result<&str, DecodeError> {
+		self.contexts
+			.table
+			.term(id)
+			.ok_or(DecodeError::UnknownTerm(id))
+	}
+
+	async fn decode_object(
+		&mut self,
+		active_context: &ActiveContext,
+		entries: &[(CborValue, CborValue)],
+	) -> Result<Value, DecodeError> {
+		let mut active_context = Cow::Borrowed(active_context);
+		for (key, value) in entries {
+			if let DecodedKey::Term(id, _) = DecodedKey::new(key)? {
+				if self.contexts.table.term(id) == Some("@context") {
+					let local_context = cbor_to_json(value)?;
+					active_context =
+						Cow::Owned(self.contexts.apply(&active_context, &local_context).await?)
+				}
+			}
+		}
+
+		// Type-scoped contexts only apply to the entries of this node.
+		let mut types = Vec::new();
+		for (key, value) in entries {
+			if let DecodedKey::Term(id, is_array) = DecodedKey::new(key)? {
+				let Some(term) = self.contexts.table.term(id) else {
+					continue;
+				};
+
+				if active_context.keyword(term) == Some("@type") {
+					let items = if is_array {
+						value
+							.as_array()
+							.ok_or(DecodeError::InvalidValue)?
+							.as_slice()
+					} else {
+						core::slice::from_ref(value)
+					};
+
+					for item in items {
+						// Only strings are considered by the encoder.
+						if !matches!(
+							item,
+							CborValue::Map(_) | CborValue::Null | CborValue::Bool(_)
+						) {
+							types.push(self.decode_string(ValueType::Vocab, item)?)
+						}
+					}
+				}
+			}
+		}
+
+		let type_scoped_context = self
+			.contexts
+			.apply_type_scoped(&active_context, types)
+			.await?;
+
+		let mut result = Object::new();
+		for (key, value) in entries {
+			let (id, is_array) = match DecodedKey::new(key)? {
+				DecodedKey::Text(key) => {
+					result.push(
+						key.into(),
+						Box::pin(self.decode(&active_context, value)).await?,
+					);
+					continue;
+				}
+				DecodedKey::Term(id, is_array) => (id, is_array),
+			};
+
+			let key: Key = self.term(id)?.into();
+			let value = if key.as_str() == "@context" {
+				cbor_to_json(value)?
+			} else {
+				let ty = type_scoped_context.value_type(&key);
+				let active_context = match type_scoped_context.scoped_context(&key) {
+					Some(scoped_context) => {
+						Cow::Owned(self.contexts.apply(&active_context, scoped_context).await?)
+					}
+					None => Cow::Borrowed(active_context.as_ref()),
+				};
+
+				if is_array {
+					let items = value.as_array().ok_or(DecodeError::InvalidValue)?;
+					let mut result = Vec::with_capacity(items.len());
+					for item in items {
+						result.push(self.decode_typed(&active_context, ty, item).await?)
+					}
+
+					Value::Array(result)
+				} else {
+					self.decode_typed(&active_context, ty, value).await?
+				}
+			};
+
+			result.push(key, value);
+		}
+
+		Ok(Value::Object(result))
+	}
+
+	async fn decode_typed(
+		&mut self,
+		active_context: &ActiveContext,
+		ty: ValueType,
+		value: &CborValue,
+	) -> Result<Value, DecodeError> {
+		match (ty, value) {
+			(_, CborValue::Map(entries)) => {
+				Box::pin(self.decode_object(active_context, entries)).await
+			}
+			(ValueType::Plain, value) => Box::pin(self.decode(active_context, value)).await,
+			(_, CborValue::Null | CborValue::Bool(_)) => cbor_to_json(value),
+			(ty, value) => Ok(self.decode_string(ty, value)?.into()),
+		}
+	}
+
+	fn decode_string(&self, ty: ValueType, value: &CborValue) -> Result<String, DecodeError> {
+		match value {
+			CborValue::Text(s) => Ok(s.clone()),
+			value => codec::decode_string(&self.contexts.table, ty, value)
+				.ok_or(DecodeError::InvalidValue),
+		}
+	}
+}
@@ -0,0 +1,165 @@
+use super::{
+	codec,
+	context::{ActiveContext, ContextProcessor, ValueType},
+	EncodeError,
+};
+use alloc::{
+	borrow::{Cow, ToOwned},
+	boxed::Box,
+	string::ToString,
+	vec::Vec,
+};
+use ciborium::Value as CborValue;
+use json_ld_core::Loader;
+use json_syntax::{Object, Value};
+
+/// Converts a JSON value into CBOR, without compression.
+pub(crate) fn json_to_cbor(value: &Value) -> CborValue {
+	match value {
+		Value::Null => CborValue::Null,
+		Value::Boolean(b) => CborValue::Bool(*b),
+		Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+			(Some(i), _) => CborValue::Integer(i.into()),
+			(None, Some(u)) => CborValue::Integer(u.into()),
+			(None, None) => CborValue::Float(n.as_f64_lossy()),
+		},
+		Value::String(s) => CborValue::Text(s.to_string()),
+		Value::Array(items) => CborValue::Array(items.iter().map(json_to_cbor).collect()),
+		Value::Object(o) => CborValue::Map(
+			o.iter()
+				.map(|entry| {
+					(
+						CborValue::Text(entry.key.to_string()),
+						json_to_cbor(&entry.value),
+					)
+				})
+				.collect(),
+		),
+	}
+}
+
+/// CBOR-LD encoder.
+pub(crate) struct Encoder<'l, L> {
+	contexts: ContextProcessor<'l, L>,
+}
+
+impl<'l, L: Loader> Encoder<'l, L> {
+	pub fn new(loader: &'l L) -> Self {
+		Self {
+			contexts: ContextProcessor::new(loader),
+		}
+	}
+
+	pub async fn encode(
+		&mut self,
+		active_context: &ActiveContext,
+		value: &Value,
+	) -> Result<CborValue, EncodeError> {
+		match value {
+			Value::Array(items) => {
+				let mut result = Vec::with_capacity(items.len());
+				for item in items {
+					result.push(Box::pin(self.encode(active_context, item)).await?)
+				}
+
+				Ok(CborValue::Array(result))
+			}
+			Value::Object(o) => Box::pin(self.encode_object(active_context, o)).await,
+			value => Ok(json_to_cbor(value)),
+		}
+	}
+
+	async fn encode_object(
+		&mut self,
+		active_context: &ActiveContext,
+		object: &Object,
+	) -> Result<CborValue, EncodeError> {
+		let mut active_context = Cow::Borrowed(active_context);
+		if let Some(local_context) = object
+			.get_unique("@context")
+			.map_err(|_| EncodeError::DuplicateKey("@context".to_owned()))?
+		{
+			active_context = Cow::Owned(self.contexts.apply(&active_context, local_context).await?)
+		}
+
+		// Type-scoped contexts only apply to the entries of this node.
+		let mut types = Vec::new();
+		for entry in object {
+			if active_context.keyword(&entry.key) == Some("@type") {
+				match &entry.value {
+					Value::String(ty) => types.push(ty.to_string()),
+					Value::Array(items) => types.extend(
+						items
+							.iter()
+							.filter_map(Value::as_str)
+							.map(ToOwned::to_owned),
+					),
+					_ => (),
+				}
+			}
+		}
+
+		let type_scoped_context = self
+			.contexts
+			.apply_type_scoped(&active_context, types)
+			.await?;
+
+		let mut result = Vec::with_capacity(object.len());
+		for entry in object {
+			let key = entry.key.as_str();
+			let Some(id) = self.contexts.table.id(key) else {
+				// Unknown keys are not compressed.
+				result.push((
+					CborValue::Text(key.to_owned()),
+					Box::pin(self.encode(&active_context, &entry.value)).await?,
+				));
+				continue;
+			};
+
+			let value = if key == "@context" {
+				json_to_cbor(&entry.value)
+			} else {
+				let ty = type_scoped_context.value_type(key);
+				let active_context = match type_scoped_context.scoped_context(key) {
+					Some(scoped_context) => {
+						Cow::Owned(self.contexts.apply(&active_context, scoped_context).await?)
+					}
+					None => Cow::Borrowed(active_context.as_ref()),
+				};
+
+				match &entry.value {
+					Value::Array(items) => {
+						let mut result = Vec::with_capacity(items.len());
+						for item in items {
+							result.push(self.encode_typed(&active_context, ty, key, item).await?)
+						}
+
+						CborValue::Array(result)
+					}
+					value => self.encode_typed(&active_context, ty, key, value).await?,
+				}
+			};
+
+			let id = if entry.value.is_array() { id + 1 } else { id };
+			result.push((CborValue::Integer(id.into()), value))
+		}
+
+		Ok(CborValue::Map(result))
+	}
+
+	async fn encode_typed(
+		&mut self,
+		active_context: &ActiveContext,
+		ty: ValueType,
+		key: &str,
+		value: &Value,
+	) -> Result<CborValue, EncodeError> {
+		match (ty, value) {
+			(_, Value::Object(o)) => Box::pin(self.encode_object(active_context, o)).await,
+			(ValueType::Plain, value) => Box::pin(self.encode(active_context, value)).await,
+			(ty, Value::String(s)) => Ok(codec::encode_string(&self.contexts.table, ty, s)),
+			(_, Value::Null | Value::Boolean(_)) => Ok(json_to_cbor(value)),
+			_ => Err(EncodeError::AmbiguousValue(key.to_owned())),
+		}
+	}
+}
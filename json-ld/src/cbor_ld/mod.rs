@@ -0,0 +1,316 @@
+//! CBOR-LD encoding and decoding.
+//!
+//! [CBOR-LD](https://json-ld.github.io/cbor-ld-spec/) is a compact binary
+//! serialization of JSON-LD documents, well suited for constrained devices
+//! and barcodes (e.g. Verifiable Credentials).
+//!
+//! Compression is semantic: keywords and the terms defined by the contexts
+//! used in the document are replaced by integers, while some values are
+//! replaced by a more compact binary representation according to their type:
+//!   - IRIs using the `http`, `https`, `urn:uuid`, `did:key` and `did:v1:nym`
+//!     schemes;
+//!   - terms and IRIs used as types or vocabulary values;
+//!   - `xsd:dateTime` and `xsd:date` literals;
+//!   - base58-btc multibase values.
+//!
+//! Both compacted and expanded documents can be encoded. Expanded documents
+//! do not reference any context, so only keywords and values are compressed.
+//!
+//! Keywords have fixed identifiers (`@context` is `0`, `@type` is `2`, etc.).
+//! Other terms are numbered from `100`, in lexicographic order for each
+//! context as it is first encountered, followed by the terms of the contexts
+//! scoped to them. Each identifier is even, the next odd integer denoting the
+//! same term applied to an array of values. Since the term table is built
+//! from the contexts used by the document, the decoder must be able to load
+//! the same contexts as the encoder.
+//!
+//! Only the registry entries `0` (uncompressed) and `1` are supported. The
+//! type tables of other registry entries (such as compressed context URLs)
+//! are not.
+//!
+//! ```ignore
+//! let bytes = json_ld::cbor_ld::encode(&document, &loader).await?;
+//! let decoded = json_ld::cbor_ld::decode(&bytes, &loader).await?;
+//! assert_eq!(document, decoded)
+//! ```
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use ciborium::Value as CborValue;
+use json_ld_core::Loader;
+
+mod codec;
+mod context;
+mod decode;
+mod encode;
+
+use context::ActiveContext;
+pub use context::ContextError;
+use decode::{cbor_to_json, Decoder};
+use encode::{json_to_cbor, Encoder};
+
+/// CBOR tag identifying a CBOR-LD payload.
+pub const CBOR_LD_TAG: u64 = 0xcb1d;
+
+/// Registry entry identifier of uncompressed payloads.
+pub const UNCOMPRESSED: u64 = 0;
+
+/// Registry entry identifier of payloads compressed using the default type
+/// table.
+pub const COMPRESSED: u64 = 1;
+
+/// CBOR-LD encoding error.
+#[derive(Debug, thiserror::Error)]
+pub enum EncodeError {
+	/// Context processing failed.
+	#[error(transparent)]
+	Context(#[from] ContextError),
+
+	/// A node object has duplicate entries.
+	#[error("duplicate key `{0}`")]
+	DuplicateKey(String),
+
+	/// A value of the given entry cannot be compressed unambiguously.
+	#[error("unsupported value for `{0}`")]
+	AmbiguousValue(String),
+
+	/// CBOR serialization failed.
+	#[error("CBOR serialization failed")]
+	Serialization,
+}
+
+/// CBOR-LD decoding error.
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+	/// Context processing failed.
+	#[error(transparent)]
+	Context(#[from] ContextError),
+
+	/// Input is not valid CBOR.
+	#[error("invalid CBOR")]
+	Cbor,
+
+	/// Input is not a CBOR-LD payload.
+	#[error("not a CBOR-LD payload")]
+	NotCborLd,
+
+	/// Unknown registry entry.
+	#[error("unknown registry entry `{0}`")]
+	UnknownRegistryEntry(u64),
+
+	/// Unknown term identifier.
+	#[error("unknown term `{0}`")]
+	UnknownTerm(u64),
+
+	/// Invalid compressed value.
+	#[error("invalid value")]
+	InvalidValue,
+}
+
+/// Encodes the given JSON-LD document into CBOR-LD.
+///
+/// The contexts referenced by the document are loaded using the given
+/// `loader`.
+pub async fn encode<L: Loader>(
+	document: &json_syntax::Value,
+	loader: &L,
+) -> Result<Vec<u8>, EncodeError> {
+	let payload = Encoder::new(loader)
+		.encode(&ActiveContext::default(), document)
+		.await?;
+
+	serialize(COMPRESSED, payload)
+}
+
+/// Encodes the given JSON-LD document into CBOR-LD without compression.
+pub fn encode_uncompressed(document: &json_syntax::Value) -> Result<Vec<u8>, EncodeError> {
+	serialize(UNCOMPRESSED, json_to_cbor(document))
+}
+
+fn serialize(registry_entry: u64, payload: CborValue) -> Result<Vec<u8>, EncodeError> {
+	let value = CborValue::Tag(
+		CBOR_LD_TAG,
+		Box::new(CborValue::Array(vec![
+			CborValue::Integer(registry_entry.into()),
+			payload,
+		])),
+	);
+
+	let mut bytes = Vec::new();
+	ciborium::ser::into_writer(&value, &mut bytes).map_err(|_| EncodeError::Serialization)?;
+	Ok(bytes)
+}
+
+/// Decodes the given CBOR-LD payload into a JSON-LD document.
+///
+/// The contexts referenced by the document are loaded using the given
+/// `loader`.
+pub async fn decode<L: Loader>(
+	bytes: &[u8],
+	loader: &L,
+) -> Result<json_syntax::Value, DecodeError> {
+	let value: CborValue = ciborium::de::from_reader(bytes).map_err(|_| DecodeError::Cbor)?;
+	let (registry_entry, payload) = match value {
+		CborValue::Tag(CBOR_LD_TAG, content) => match *content {
+			CborValue::Array(items) => match <[CborValue; 2]>::try_from(items) {
+				Ok([CborValue::Integer(id), payload]) => (
+					u64::try_from(id).map_err(|_| DecodeError::NotCborLd)?,
+					payload,
+				),
+				_ => return Err(DecodeError::NotCborLd),
+			},
+			_ => return Err(DecodeError::NotCborLd),
+		},
+		_ => return Err(DecodeError::NotCborLd),
+	};
+
+	match registry_entry {
+		UNCOMPRESSED => cbor_to_json(&payload),
+		COMPRESSED => {
+			Decoder::new(loader)
+				.decode(&ActiveContext::default(), &payload)
+				.await
+		}
+		id => Err(DecodeError::UnknownRegistryEntry(id)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::collections::BTreeMap;
+	use alloc::string::ToString;
+	use iref::IriBuf;
+	use json_ld_core::future::block_on;
+	use json_ld_core::{NoLoader, RemoteDocument};
+	use json_syntax::Parse;
+
+	const CONTEXT: &str = r#"{
+		"@context": {
+			"name": "https://schema.org/name",
+			"homepage": { "@id": "https://schema.org/url", "@type": "@id" },
+			"issued": {
+				"@id": "https://example.org/issued",
+				"@type": "http://www.w3.org/2001/XMLSchema#dateTime"
+			},
+			"key": { "@id": "https://example.org/key", "@type": "@id" },
+			"Person": {
+				"@id": "https://schema.org/Person",
+				"@context": { "nickname": "https://example.org/nickname" }
+			}
+		}
+	}"#;
+
+	fn json(input: &str) -> json_syntax::Value {
+		json_syntax::Value::parse_str(input).unwrap().0
+	}
+
+	fn loader() -> BTreeMap<IriBuf, RemoteDocument> {
+		let url = IriBuf::new("https://example.org/context".to_string()).unwrap();
+		let mut loader = BTreeMap::new();
+		loader.insert(
+			url.clone(),
+			RemoteDocument::new(Some(url), None, json(CONTEXT)),
+		);
+		loader
+	}
+
+	/// Returns the compressed payload of the given CBOR-LD document.
+	fn payload(bytes: &[u8]) -> Vec<(CborValue, CborValue)> {
+		let value: CborValue = ciborium::de::from_reader(bytes).unwrap();
+		let CborValue::Tag(CBOR_LD_TAG, content) = value else {
+			panic!("not a CBOR-LD payload")
+		};
+
+		match *content {
+			CborValue::Array(mut items) => items.pop().unwrap().into_map().unwrap(),
+			_ => panic!("not a CBOR-LD payload"),
+		}
+	}
+
+	fn integer(i: u64) -> CborValue {
+		CborValue::Integer(i.into())
+	}
+
+	#[test]
+	fn round_trip() {
+		let loader = loader();
+		let document = json(
+			r#"{
+				"@context": "https://example.org/context",
+				"@id": "urn:uuid:188e8450-269e-11eb-b545-d3692cf35398",
+				"@type": "Person",
+				"name": "Jane Doe",
+				"nickname": "JD",
+				"homepage": "https://example.org/~jane",
+				"issued": "2020-01-01T00:00:00Z",
+				"key": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+			}"#,
+		);
+
+		let bytes = block_on(encode(&document, &loader)).unwrap();
+		assert_eq!(block_on(decode(&bytes, &loader)).unwrap(), document);
+
+		let bytes = encode_uncompressed(&document).unwrap();
+		assert_eq!(block_on(decode(&bytes, &NoLoader)).unwrap(), document)
+	}
+
+	#[test]
+	fn term_codes() {
+		// Terms are numbered in lexicographic order from 100, followed by the
+		// terms of their scoped contexts, whether or not the document uses
+		// them.
+		let loader = loader();
+		let document = json(
+			r#"{
+				"@context": "https://example.org/context",
+				"@type": "Person",
+				"nickname": "JD",
+				"homepage": ["https://example.org/~jane"]
+			}"#,
+		);
+
+		let bytes = block_on(encode(&document, &loader)).unwrap();
+		let keys: Vec<_> = payload(&bytes).into_iter().map(|(key, _)| key).collect();
+		assert_eq!(keys, [integer(0), integer(2), integer(110), integer(103)]);
+
+		let entries = payload(&bytes);
+		assert_eq!(entries[1].1, integer(100));
+		assert_eq!(
+			entries[3].1,
+			CborValue::Array(vec![CborValue::Array(vec![
+				integer(2),
+				CborValue::Text("example.org/~jane".into())
+			])])
+		)
+	}
+
+	#[test]
+	fn compressed_vector() {
+		let document = json(r#"{ "@context": { "a": "http://example.org/a" }, "a": "v" }"#);
+		let mut expected = vec![
+			0xd9, 0xcb, 0x1d, 0x82, 0x01, 0xa2, 0x00, 0xa1, 0x61, b'a', 0x74,
+		];
+		expected.extend_from_slice(b"http://example.org/a");
+		expected.extend_from_slice(&[0x18, 0x64, 0x61, b'v']);
+
+		let bytes = block_on(encode(&document, &NoLoader)).unwrap();
+		assert_eq!(bytes, expected);
+		assert_eq!(block_on(decode(&expected, &NoLoader)).unwrap(), document)
+	}
+
+	#[test]
+	fn uncompressed_vector() {
+		let document = json(r#"{ "a": 1 }"#);
+		let expected = [0xd9, 0xcb, 0x1d, 0x82, 0x00, 0xa1, 0x61, b'a', 0x01];
+		assert_eq!(encode_uncompressed(&document).unwrap(), expected);
+		assert_eq!(block_on(decode(&expected, &NoLoader)).unwrap(), document)
+	}
+
+	#[test]
+	fn unknown_registry_entry() {
+		let bytes = [0xd9, 0xcb, 0x1d, 0x82, 0x02, 0xa0];
+		assert!(matches!(
+			block_on(decode(&bytes, &NoLoader)),
+			Err(DecodeError::UnknownRegistryEntry(2))
+		))
+	}
+}
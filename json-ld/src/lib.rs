@@ -322,6 +322,18 @@ pub use expansion::Expand;
 mod processor;
 pub use processor::*;
 
+#[cfg(feature = "cbor-ld")]
+pub mod cbor_ld;
+
+#[cfg(feature = "sophia")]
+pub mod sophia;
+
+#[cfg(feature = "oxrdf")]
+pub mod oxrdf;
+
+#[cfg(feature = "test-suite")]
+pub mod test_suite;
+
 #[doc(hidden)]
 pub use iref;
 pub use iref::{InvalidIri, Iri, IriBuf, IriRef, IriRefBuf};
@@ -0,0 +1,197 @@
+//! [Oxrdf](https://docs.rs/oxrdf) interoperability.
+//!
+//! This module converts the RDF quads produced by this crate into `oxrdf`
+//! quads, so that JSON-LD documents can be loaded into an
+//! [Oxigraph](https://docs.rs/oxigraph) store, and back.
+//!
+//! Neither `rdf_types::Quad` nor `oxrdf::Quad` are defined by this crate, so
+//! the conversions are provided as functions instead of `From`/`TryFrom`
+//! implementations.
+use crate::{ExpandedDocument, RdfQuads, ToRdf};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use iref::IriBuf;
+use json_ld_core::rdf::RdfDirection;
+use langtag::LangTagBuf;
+use rdf_types::{BlankIdBuf, Generator, Id, LexicalQuad, Literal, LiteralType, Term};
+
+/// Error raised when converting a quad into an `oxrdf` quad.
+#[derive(Debug, thiserror::Error)]
+pub enum IntoOxrdfError {
+	/// The subject or graph name is a literal.
+	#[error("literal used as node identifier")]
+	LiteralId,
+
+	/// The predicate is not an IRI (generalized RDF is not supported by
+	/// `oxrdf`).
+	#[error("invalid predicate")]
+	InvalidPredicate,
+}
+
+/// Error raised when converting an `oxrdf` quad into a [`LexicalQuad`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromOxrdfError {
+	/// Quoted triples are not supported.
+	#[error("unsupported quoted triple")]
+	QuotedTriple,
+
+	/// Invalid IRI.
+	#[error("invalid IRI `{0}`")]
+	InvalidIri(String),
+
+	/// Invalid blank node identifier.
+	#[error("invalid blank node identifier `{0}`")]
+	InvalidBlankId(String),
+
+	/// Invalid language tag.
+	#[error("invalid language tag `{0}`")]
+	InvalidLangTag(String),
+}
+
+/// Converts an IRI into an `oxrdf` named node.
+pub fn iri_to_oxrdf(iri: IriBuf) -> oxrdf::NamedNode {
+	oxrdf::NamedNode::new_unchecked(iri.as_str().to_string())
+}
+
+/// Converts a node identifier into an `oxrdf` subject.
+pub fn id_to_oxrdf(id: Id) -> oxrdf::Subject {
+	match id {
+		Id::Iri(iri) => iri_to_oxrdf(iri).into(),
+		Id::Blank(b) => oxrdf::BlankNode::new_unchecked(b.suffix().to_string()).into(),
+	}
+}
+
+/// Converts a graph label into an `oxrdf` graph name.
+pub fn graph_to_oxrdf(id: Id) -> oxrdf::GraphName {
+	match id {
+		Id::Iri(iri) => iri_to_oxrdf(iri).into(),
+		Id::Blank(b) => oxrdf::BlankNode::new_unchecked(b.suffix().to_string()).into(),
+	}
+}
+
+/// Converts a literal into an `oxrdf` literal.
+pub fn literal_to_oxrdf(literal: Literal) -> oxrdf::Literal {
+	let (value, type_) = literal.into_parts();
+	match type_ {
+		LiteralType::Any(iri) => oxrdf::Literal::new_typed_literal(value, iri_to_oxrdf(iri)),
+		LiteralType::LangString(tag) => oxrdf::Literal::new_language_tagged_literal_unchecked(
+			value,
+			tag.as_str().to_lowercase(),
+		),
+	}
+}
+
+/// Converts an RDF term into an `oxrdf` term.
+pub fn term_to_oxrdf(term: Term) -> oxrdf::Term {
+	match term {
+		Term::Id(id) => id_to_oxrdf(id).into(),
+		Term::Literal(literal) => literal_to_oxrdf(literal).into(),
+	}
+}
+
+/// Converts an RDF quad into an `oxrdf` quad.
+pub fn quad_to_oxrdf(
+	rdf_types::Quad(s, p, o, g): rdf_types::Quad<Term>,
+) -> Result<oxrdf::Quad, IntoOxrdfError> {
+	let s = s.into_id().ok_or(IntoOxrdfError::LiteralId)?;
+	let p = match p {
+		Term::Id(Id::Iri(iri)) => iri_to_oxrdf(iri),
+		_ => return Err(IntoOxrdfError::InvalidPredicate),
+	};
+	let g = match g {
+		Some(g) => graph_to_oxrdf(g.into_id().ok_or(IntoOxrdfError::LiteralId)?),
+		None => oxrdf::GraphName::DefaultGraph,
+	};
+
+	Ok(oxrdf::Quad::new(id_to_oxrdf(s), p, term_to_oxrdf(o), g))
+}
+
+impl<'v, 'g, G: Generator> ToRdf<'v, 'g, (), G> {
+	/// Collects the RDF quads into an `oxrdf` dataset.
+	///
+	/// Fails if generalized RDF is enabled and a quad has a blank node
+	/// predicate.
+	pub fn into_oxrdf_dataset(self) -> Result<oxrdf::Dataset, IntoOxrdfError> {
+		let mut dataset = oxrdf::Dataset::new();
+		for quad in self.into_dataset::<Vec<_>>() {
+			dataset.insert(&quad_to_oxrdf(quad)?);
+		}
+
+		Ok(dataset)
+	}
+}
+
+/// Serializes the given expanded `document` into an `oxrdf` dataset, using
+/// `generator` to label the blank nodes.
+///
+/// Contrarily to [`ToRdf`], blank nodes are not relabeled beforehand.
+pub fn expanded_document_to_oxrdf<G: Generator>(
+	document: &ExpandedDocument,
+	generator: &mut G,
+	rdf_direction: Option<RdfDirection>,
+) -> oxrdf::Dataset {
+	let mut dataset = oxrdf::Dataset::new();
+	for rdf_types::Quad(s, p, o, g) in document.rdf_quads(generator, rdf_direction).cloned() {
+		if let Id::Iri(p) = p {
+			dataset.insert(&oxrdf::Quad::new(
+				id_to_oxrdf(s),
+				iri_to_oxrdf(p),
+				term_to_oxrdf(o),
+				g.map(graph_to_oxrdf)
+					.unwrap_or(oxrdf::GraphName::DefaultGraph),
+			));
+		}
+	}
+
+	dataset
+}
+
+/// Converts an `oxrdf` quad into a [`LexicalQuad`].
+pub fn quad_from_oxrdf(quad: oxrdf::QuadRef) -> Result<LexicalQuad, FromOxrdfError> {
+	let s = match quad.subject {
+		oxrdf::SubjectRef::NamedNode(n) => Id::Iri(iri_from_oxrdf(n)?),
+		oxrdf::SubjectRef::BlankNode(b) => Id::Blank(blank_id_from_oxrdf(b)?),
+		#[allow(unreachable_patterns)]
+		_ => return Err(FromOxrdfError::QuotedTriple),
+	};
+
+	let o = match quad.object {
+		oxrdf::TermRef::NamedNode(n) => Term::Id(Id::Iri(iri_from_oxrdf(n)?)),
+		oxrdf::TermRef::BlankNode(b) => Term::Id(Id::Blank(blank_id_from_oxrdf(b)?)),
+		oxrdf::TermRef::Literal(l) => {
+			let type_ = match l.language() {
+				Some(tag) => LiteralType::LangString(
+					LangTagBuf::new(tag.to_string())
+						.map_err(|e| FromOxrdfError::InvalidLangTag(e.0))?,
+				),
+				None => LiteralType::Any(iri_from_oxrdf(l.datatype())?),
+			};
+
+			Term::Literal(Literal::new(l.value().to_string(), type_))
+		}
+		#[allow(unreachable_patterns)]
+		_ => return Err(FromOxrdfError::QuotedTriple),
+	};
+
+	let g = match quad.graph_name {
+		oxrdf::GraphNameRef::NamedNode(n) => Some(Id::Iri(iri_from_oxrdf(n)?)),
+		oxrdf::GraphNameRef::BlankNode(b) => Some(Id::Blank(blank_id_from_oxrdf(b)?)),
+		oxrdf::GraphNameRef::DefaultGraph => None,
+	};
+
+	Ok(rdf_types::Quad(s, iri_from_oxrdf(quad.predicate)?, o, g))
+}
+
+/// Converts the quads of an `oxrdf` dataset into [`LexicalQuad`]s.
+pub fn dataset_from_oxrdf(dataset: &oxrdf::Dataset) -> Result<Vec<LexicalQuad>, FromOxrdfError> {
+	dataset.iter().map(quad_from_oxrdf).collect()
+}
+
+fn iri_from_oxrdf(node: oxrdf::NamedNodeRef) -> Result<IriBuf, FromOxrdfError> {
+	IriBuf::new(node.as_str().to_string()).map_err(|e| FromOxrdfError::InvalidIri(e.0))
+}
+
+fn blank_id_from_oxrdf(node: oxrdf::BlankNodeRef) -> Result<BlankIdBuf, FromOxrdfError> {
+	BlankIdBuf::from_suffix(node.as_str())
+		.map_err(|_| FromOxrdfError::InvalidBlankId(node.as_str().to_string()))
+}
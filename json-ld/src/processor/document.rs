@@ -0,0 +1,137 @@
+use super::{compact_expanded_full, CompactError, ExpandError, JsonLdProcessor, Options};
+use crate::{Document, ExpandedDocument, Loader, RemoteContextReference};
+use crate::{MaybeSend, MaybeSync};
+use core::future::Future;
+use core::hash::Hash;
+use rdf_types::{vocabulary, VocabularyMut};
+
+/// Lazy refresh of the views of a [`Document`].
+///
+/// Each view is recomputed from the other only if it is stale, that is
+/// if the other view has been mutated since the last refresh. Otherwise
+/// the cached view is returned as is.
+pub trait RefreshDocument<I, B>: Sized {
+	/// Returns the expanded view of the document, expanding the compact
+	/// view with the given `vocabulary`, `loader` and `options` if needed.
+	fn refresh_expanded_full<'a, N>(
+		&'a mut self,
+		vocabulary: &'a mut N,
+		loader: &'a impl Loader,
+		options: Options<I, B>,
+	) -> impl Future<Output = Result<&'a ExpandedDocument<I, B>, ExpandError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: Clone + Eq + Hash + MaybeSend + MaybeSync;
+
+	/// Returns the expanded view of the document, expanding the compact
+	/// view with the given `loader` if needed.
+	fn refresh_expanded<'a>(
+		&'a mut self,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = Result<&'a ExpandedDocument<I, B>, ExpandError>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.refresh_expanded_full(vocabulary::no_vocabulary_mut(), loader, Options::default())
+	}
+
+	/// Returns the compact view of the document, compacting the expanded
+	/// view with the given `vocabulary`, `context`, `loader` and `options` if
+	/// needed.
+	fn refresh_compact_full<'a, N>(
+		&'a mut self,
+		vocabulary: &'a mut N,
+		context: RemoteContextReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I, B>,
+	) -> impl Future<Output = Result<&'a json_syntax::Value, CompactError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync;
+
+	/// Returns the compact view of the document, compacting the expanded
+	/// view with the given `context` and `loader` if needed.
+	fn refresh_compact<'a>(
+		&'a mut self,
+		context: RemoteContextReference<I>,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = Result<&'a json_syntax::Value, CompactError>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = I, BlankId = B>,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.refresh_compact_full(
+			vocabulary::no_vocabulary_mut(),
+			context,
+			loader,
+			Options::default(),
+		)
+	}
+}
+
+impl<I: MaybeSend + MaybeSync, B: MaybeSend + MaybeSync> RefreshDocument<I, B> for Document<I, B> {
+	fn refresh_expanded_full<'a, N>(
+		&'a mut self,
+		vocabulary: &'a mut N,
+		loader: &'a impl Loader,
+		options: Options<I, B>,
+	) -> impl Future<Output = Result<&'a ExpandedDocument<I, B>, ExpandError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			if !self.is_expanded_up_to_date() {
+				// The compact view is up to date when the expanded view is
+				// not.
+				let expanded = self
+					.as_remote()
+					.expand_full(vocabulary, loader, options)
+					.await?;
+				self.update_expanded(expanded)
+			}
+
+			let this: &'a Self = self;
+			Ok(this.as_expanded())
+		}
+	}
+
+	fn refresh_compact_full<'a, N>(
+		&'a mut self,
+		vocabulary: &'a mut N,
+		context: RemoteContextReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I, B>,
+	) -> impl Future<Output = Result<&'a json_syntax::Value, CompactError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I, BlankId = B> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		B: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			if !self.is_compact_up_to_date() {
+				// The expanded view is up to date when the compact view is
+				// not.
+				let compact = compact_expanded_full(
+					self.as_expanded(),
+					self.url(),
+					vocabulary,
+					context,
+					loader,
+					options,
+				)
+				.await?;
+				self.update_compact(compact)
+			}
+
+			let this: &'a Self = self;
+			Ok(this.as_compact())
+		}
+	}
+}
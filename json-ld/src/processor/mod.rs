@@ -1,25 +1,39 @@
+use crate::compaction::{self, Compact};
 use crate::context_processing;
+use crate::context_processing::Process;
 use crate::expansion;
 use crate::syntax::ErrorCode;
-use crate::{flattening::ConflictingIndexes, ExpandedDocument, Loader, ProcessingMode};
-use alloc::boxed::Box;
+use crate::{
+	flattening::ConflictingIndexes, Context, ExpandedDocument, Loader, ProcessingMode,
+	RemoteDocument, StandardProfile, ValidId,
+};
+use crate::{MaybeSend, MaybeSync};
+use alloc::sync::Arc;
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
 use iref::IriBuf;
-use json_ld_core::rdf::RdfDirection;
+use json_ld_core::context::ContextCache;
+use json_ld_core::future::block_on;
+use json_ld_core::rdf::{self, RdfDirection};
 use json_ld_core::RdfQuads;
-use json_ld_core::{ContextLoadError, LoadError};
+use json_ld_core::{ContextLoadError, LoadError, SyncLoader, SyncLoaderAdapter};
 use json_ld_core::{Document, RemoteContextReference};
+use json_ld_syntax::IntoJsonWithContext;
+use rdf_types::vocabulary::{
+	BlankIdVocabulary, IriVocabulary, IriVocabularyMut, LiteralVocabulary, LiteralVocabularyMut,
+};
 use rdf_types::Generator;
 use rdf_types::Vocabulary;
 use rdf_types::{vocabulary, BlankIdBuf, VocabularyMut};
 
+mod document;
 mod remote_document;
 
+pub use document::RefreshDocument;
+
 /// JSON-LD Processor options.
 #[derive(Clone)]
-pub struct Options<I = IriBuf> {
+pub struct Options<I = IriBuf, B = BlankIdBuf> {
 	/// The base IRI to use when expanding or compacting the document.
 	///
 	/// If set, this overrides the input document's IRI.
@@ -81,9 +95,107 @@ pub struct Options<I = IriBuf> {
 
 	/// Term expansion policy, passed to the document expansion algorithm.
 	pub expansion_policy: expansion::Policy,
+
+	/// Maximum nesting depth of the input document, passed to the document
+	/// expansion algorithm.
+	///
+	/// Default is `None`, meaning no limit.
+	pub max_depth: Option<usize>,
+
+	/// Maximum number of node objects in the expanded document, passed to the
+	/// document expansion algorithm.
+	///
+	/// Default is `None`, meaning no limit.
+	pub max_nodes: Option<usize>,
+
+	/// Maximum number of remote documents (contexts included) loaded while
+	/// expanding a document.
+	///
+	/// The loader is wrapped into a [`LimitedLoader`](crate::LimitedLoader)
+	/// enforcing this limit during expansion and context processing. The
+	/// size of each loaded document is controlled by the loader itself (see
+	/// for instance the `max_document_bytes` option of the HTTP loaders, or
+	/// [`FsLoader::set_max_document_bytes`](crate::FsLoader::set_max_document_bytes)).
+	///
+	/// Default is `None`, meaning no limit.
+	pub max_loaded_contexts: Option<usize>,
+
+	/// Promote warnings to errors, passed to the context processing and
+	/// document expansion algorithms.
+	///
+	/// Useful to validate incoming documents: keyword-like terms, malformed
+	/// language tags, etc. are rejected with their error code instead of
+	/// being silently ignored.
+	pub strict: bool,
+
+	/// Allow contexts to redefine protected terms, passed to the context
+	/// processing and document expansion algorithms.
+	///
+	/// By default, redefining a protected term fails with a
+	/// `protected term redefinition` error. This is meant for controlled
+	/// environments only.
+	pub override_protected: bool,
+
+	/// Enables frame expansion, passed to the document expansion algorithm.
+	///
+	/// See [`expansion::Options::frame_expansion`].
+	pub frame_expansion: bool,
+
+	/// How to handle the entries of an object expanding to the same key,
+	/// passed to the document expansion algorithm.
+	///
+	/// See [`expansion::Options::duplicate_keys`].
+	pub duplicate_keys: expansion::DuplicateKeys,
+
+	/// Order of the properties of the expanded node objects, passed to the
+	/// document expansion algorithm.
+	///
+	/// See [`expansion::Options::ordered_output`].
+	pub ordered_output: expansion::OutputOrder,
+
+	/// Hook invoked on unknown keyword entries, passed to the document
+	/// expansion algorithm.
+	///
+	/// See [`expansion::Options::keyword_handler`].
+	pub keyword_handler: Option<Arc<dyn expansion::KeywordHandler + Send + Sync>>,
+
+	/// How to handle malformed language tags, passed to the document
+	/// expansion algorithm.
+	///
+	/// See [`expansion::Options::language_tags`].
+	pub language_tags: expansion::LanguageTagPolicy,
+
+	/// Preserve the exact value of numbers, passed to the document expansion
+	/// algorithm.
+	///
+	/// See [`expansion::Options::exact_numbers`].
+	pub exact_numbers: bool,
+
+	/// Trust remote documents served with the `expanded` profile to already
+	/// be in expanded form.
+	///
+	/// When set, such documents are converted directly into an
+	/// [`ExpandedDocument`] without running the expansion algorithm. This
+	/// skips the `max_depth`, `max_nodes` and `strict` checks, so it must
+	/// only be enabled for documents coming from trusted sources.
+	///
+	/// Default is `false`.
+	pub trust_expanded_profile: bool,
+
+	/// Cache of the processed contexts, attached to the initial active
+	/// context of the expansion algorithm.
+	///
+	/// Passing the same cache to the processing of every document allows
+	/// their common contexts to be processed only once. Cached contexts are
+	/// keyed by the namespace of the cache (see
+	/// [`ContextCache::with_namespace`]), which must identify the loader
+	/// passed along with these options.
+	///
+	/// Default is `None`.
+	pub context_cache: Option<ContextCache<I, B>>,
 }
 
-impl<I> Options<I> {
+impl<I, B> Options<I, B> {
 	/// Returns these options with the `ordered` flag set to `false`.
 	///
 	/// This means entries will not be ordered by keys before being processed.
@@ -107,6 +219,8 @@ impl<I> Options<I> {
 	pub fn context_processing_options(&self) -> context_processing::Options {
 		context_processing::Options {
 			processing_mode: self.processing_mode,
+			strict: self.strict,
+			override_protected: self.override_protected,
 			..Default::default()
 		}
 	}
@@ -117,11 +231,31 @@ impl<I> Options<I> {
 			processing_mode: self.processing_mode,
 			ordered: self.ordered,
 			policy: self.expansion_policy,
+			max_depth: self.max_depth,
+			max_nodes: self.max_nodes,
+			strict: self.strict,
+			frame_expansion: self.frame_expansion,
+			duplicate_keys: self.duplicate_keys,
+			ordered_output: self.ordered_output,
+			keyword_handler: self.keyword_handler.clone(),
+			language_tags: self.language_tags,
+			exact_numbers: self.exact_numbers,
+			override_protected: self.override_protected,
+		}
+	}
+
+	/// Builds options for the compaction algorithm from these options.
+	pub fn compaction_options(&self) -> compaction::Options {
+		compaction::Options {
+			processing_mode: self.processing_mode,
+			compact_to_relative: self.compact_to_relative,
+			compact_arrays: self.compact_arrays,
+			ordered: self.ordered,
 		}
 	}
 }
 
-impl<I> Default for Options<I> {
+impl<I, B> Default for Options<I, B> {
 	fn default() -> Self {
 		Self {
 			base: None,
@@ -133,6 +267,19 @@ impl<I> Default for Options<I> {
 			rdf_direction: None,
 			produce_generalized_rdf: false,
 			expansion_policy: expansion::Policy::default(),
+			max_depth: None,
+			max_nodes: None,
+			max_loaded_contexts: None,
+			strict: false,
+			override_protected: false,
+			frame_expansion: false,
+			duplicate_keys: expansion::DuplicateKeys::default(),
+			ordered_output: expansion::OutputOrder::default(),
+			keyword_handler: None,
+			language_tags: expansion::LanguageTagPolicy::default(),
+			exact_numbers: false,
+			trust_expanded_profile: false,
+			context_cache: None,
 		}
 	}
 }
@@ -185,6 +332,10 @@ pub enum CompactError {
 	#[error("Context processing failed: {0}")]
 	ContextProcessing(context_processing::Error),
 
+	/// Compaction algorithm failed.
+	#[error("Compaction failed: {0}")]
+	Compaction(compaction::Error),
+
 	/// Remote document loading failed.
 	#[error(transparent)]
 	Loading(#[from] LoadError),
@@ -193,12 +344,31 @@ pub enum CompactError {
 	ContextLoading(ContextLoadError),
 }
 
+impl CompactError {
+	/// Returns the code of this error.
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Self::Expand(e) => e.code(),
+			Self::ContextProcessing(e) => e.code(),
+			Self::Compaction(e) => e.code(),
+			Self::Loading(_) => ErrorCode::LoadingDocumentFailed,
+			Self::ContextLoading(_) => ErrorCode::LoadingRemoteContextFailed,
+		}
+	}
+}
+
+/// Result returned by the [`JsonLdProcessor::compact`] function.
+pub type CompactResult = Result<json_syntax::Value, CompactError>;
+
 /// Error that can be raised by the [`JsonLdProcessor::flatten`] function.
 #[derive(Debug, thiserror::Error)]
 pub enum FlattenError<I, B> {
 	#[error("Expansion failed: {0}")]
 	Expand(ExpandError),
 
+	#[error("Compaction failed: {0}")]
+	Compact(CompactError),
+
 	#[error("Conflicting indexes: {0}")]
 	ConflictingIndexes(ConflictingIndexes<I, B>),
 
@@ -214,6 +384,7 @@ impl<I, B> FlattenError<I, B> {
 	pub fn code(&self) -> ErrorCode {
 		match self {
 			Self::Expand(e) => e.code(),
+			Self::Compact(e) => e.code(),
 			Self::ConflictingIndexes(_) => ErrorCode::ConflictingIndexes,
 			Self::Loading(_) => ErrorCode::LoadingDocumentFailed,
 			Self::ContextLoading(_) => ErrorCode::LoadingRemoteContextFailed,
@@ -221,6 +392,44 @@ impl<I, B> FlattenError<I, B> {
 	}
 }
 
+/// Result returned by the [`JsonLdProcessor::flatten`] function.
+pub type FlattenResult<I, B> = Result<json_syntax::Value, FlattenError<I, B>>;
+
+/// Error that can be raised by the [`JsonLdProcessor::transform`] function.
+#[derive(Debug, thiserror::Error)]
+pub enum TransformError<I, B> {
+	/// Document expansion failed.
+	#[error("Expansion failed: {0}")]
+	Expand(ExpandError),
+
+	/// Document compaction failed.
+	#[error("Compaction failed: {0}")]
+	Compact(CompactError),
+
+	/// Document flattening failed.
+	#[error("Flattening failed: {0}")]
+	Flatten(FlattenError<I, B>),
+
+	/// The requested profile is not supported.
+	#[error("Unsupported profile `{}`", .0.iri())]
+	UnsupportedProfile(StandardProfile),
+}
+
+impl<I, B> TransformError<I, B> {
+	/// Returns the code of this error.
+	pub fn code(&self) -> ErrorCode {
+		match self {
+			Self::Expand(e) => e.code(),
+			Self::Compact(e) => e.code(),
+			Self::Flatten(e) => e.code(),
+			Self::UnsupportedProfile(_) => ErrorCode::UnsupportedProfile,
+		}
+	}
+}
+
+/// Result returned by the [`JsonLdProcessor::transform`] function.
+pub type TransformResult<I, B> = Result<json_syntax::Value, TransformError<I, B>>;
+
 /// Error that can be raised by the [`JsonLdProcessor::to_rdf`] function.
 #[derive(Debug, thiserror::Error)]
 pub enum ToRdfError {
@@ -287,7 +496,14 @@ pub type ToRdfResult<'a, V, G> = Result<ToRdf<'a, 'a, V, G>, ToRdfError>;
 ///   .expect("expansion failed");
 /// # }
 /// ```
-pub trait JsonLdProcessor<Iri>: Sized {
+///
+/// # Thread safety
+///
+/// When the `send` feature is enabled, every future returned by the processor
+/// is `Send`, provided that the vocabulary, loader, generator and identifier
+/// types are `Send`/`Sync`. This is required to run the processor inside a
+/// multi-threaded executor (for instance with `tokio::spawn`).
+pub trait JsonLdProcessor<Iri>: Sized + MaybeSync {
 	/// Expand the document with the given `vocabulary` and `loader`, using
 	/// the given `options` and warning handler.
 	///
@@ -328,12 +544,12 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a impl Loader,
-		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<Iri, N::BlankId>> + 'a>>
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = ExpandResult<Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash;
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync;
 
 	/// Expand the document with the given `vocabulary` and `loader`, using
 	/// the given `options`.
@@ -375,12 +591,12 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a impl Loader,
-		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<Iri, N::BlankId>> + 'a>>
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = ExpandResult<Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
 		self.expand_full(vocabulary, loader, options)
 	}
@@ -424,11 +640,11 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a impl Loader,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<Iri, N::BlankId>> + 'a>>
+	) -> impl Future<Output = ExpandResult<Iri, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
 		self.expand_with_using(vocabulary, loader, Options::default())
 	}
@@ -467,10 +683,10 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		&'a self,
 		loader: &'a impl Loader,
 		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<Iri, BlankIdBuf>> + 'a>>
+	) -> impl Future<Output = ExpandResult<Iri, BlankIdBuf>> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
 		self.expand_with_using(vocabulary::no_vocabulary_mut(), loader, options)
 	}
@@ -506,38 +722,27 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	fn expand<'a>(
 		&'a self,
 		loader: &'a impl Loader,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<Iri, BlankIdBuf>> + 'a>>
+	) -> impl Future<Output = ExpandResult<Iri, BlankIdBuf>> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = Iri>,
-		Iri: Clone + Eq + Hash,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
 		self.expand_with(vocabulary::no_vocabulary_mut(), loader)
 	}
 
-	/// Serializes the document into an RDF dataset with a custom vocabulary
-	/// using the given `options` and warnings handler.
-	///
-	/// Expands the document and returns a [`ToRdf`] instance from which an
-	/// iterator over the RDF quads defined by the document can be accessed
-	/// using the [`ToRdf::quads`] method.
-	///
-	/// The quads will have type [`rdf::Quads`] which borrows the subject,
-	/// predicate and graph values from the documents if possible using [`Cow`].
-	/// If you prefer to have quads owning the values directly you can use the
-	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
-	/// method method form the value returned by [`ToRdf::quads`].
+	/// Compacts the document with the given `vocabulary` and `loader`, using
+	/// the given `options`.
 	///
-	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
-	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
-	/// [`Cow`]: std::borrow::Cow
+	/// The document is first expanded, then compacted using the given
+	/// `context`. On success, the result is the compacted JSON document
+	/// with the context embedded in its `@context` entry.
 	///
 	/// # Example
 	///
 	/// ```
 	/// use static_iref::iri;
-	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
-	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
-	///
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, RemoteContextReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
 	/// # #[async_std::main]
 	/// # async fn main() {
 	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
@@ -547,83 +752,48 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
 	/// let input = RemoteDocumentReference::iri(iri_index);
 	///
+	/// let context_iri_index = vocabulary.insert(iri!("https://example.com/context.jsonld"));
+	/// let context = RemoteContextReference::iri(context_iri_index);
+	///
 	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
 	/// // the local `example` directory. No HTTP query.
 	/// let mut loader = json_ld::FsLoader::default();
 	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
 	///
-	/// let mut generator = rdf_types::generator::Blank::new();
-	///
-	/// let mut rdf = input
-	///   .to_rdf_full(
+	/// let compact = input
+	///   .compact_full(
 	///     &mut vocabulary,
-	///     &mut generator,
+	///     context,
 	///     &loader,
-	///     Options::default(),
-	///     warning::PrintWith
+	///     Options::default()
 	///   )
 	///   .await
-	///   .expect("flattening failed");
-	///
-	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
-	///   // ...
-	/// }
+	///   .expect("compaction failed");
 	/// # }
 	/// ```
-	fn to_rdf_full<'a, N, G>(
+	fn compact_full<'a, N>(
 		&'a self,
 		vocabulary: &'a mut N,
-		generator: &'a mut G,
+		context: RemoteContextReference<Iri>,
 		loader: &'a impl Loader,
-		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ToRdfResult<'a, N, G>> + 'a>>
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
-		G: Generator<N>,
-	{
-		Box::pin(async move {
-			let rdf_direction = options.rdf_direction;
-			let produce_generalized_rdf = options.produce_generalized_rdf;
-			let expanded_input = self
-				.expand_full(&mut *vocabulary, loader, options.unordered())
-				.await
-				.map_err(ToRdfError::Expand)?;
-			Ok(ToRdf::new(
-				vocabulary,
-				generator,
-				expanded_input,
-				rdf_direction,
-				produce_generalized_rdf,
-			))
-		})
-	}
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync;
 
-	/// Serializes the document into an RDF dataset with a custom vocabulary
-	/// using the given `options`.
-	///
-	/// Expands the document and returns a [`ToRdf`] instance from which an
-	/// iterator over the RDF quads defined by the document can be accessed
-	/// using the [`ToRdf::quads`] method.
-	///
-	/// The quads will have type [`rdf::Quads`] which borrows the subject,
-	/// predicate and graph values from the documents if possible using [`Cow`].
-	/// If you prefer to have quads owning the values directly you can use the
-	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
-	/// method method form the value returned by [`ToRdf::quads`].
+	/// Compacts the document with the given `vocabulary` and `loader`, using
+	/// the given `options`.
 	///
-	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
-	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
-	/// [`Cow`]: std::borrow::Cow
+	/// On success, the result is the compacted JSON document.
 	///
 	/// # Example
 	///
 	/// ```
 	/// use static_iref::iri;
-	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
-	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
-	///
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, RemoteContextReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
 	/// # #[async_std::main]
 	/// # async fn main() {
 	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
@@ -633,69 +803,51 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
 	/// let input = RemoteDocumentReference::iri(iri_index);
 	///
+	/// let context_iri_index = vocabulary.insert(iri!("https://example.com/context.jsonld"));
+	/// let context = RemoteContextReference::iri(context_iri_index);
+	///
 	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
 	/// // the local `example` directory. No HTTP query.
 	/// let mut loader = json_ld::FsLoader::default();
 	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
 	///
-	/// let mut generator = rdf_types::generator::Blank::new();
-	///
-	/// let mut rdf = input
-	///   .to_rdf_with_using(
+	/// let compact = input
+	///   .compact_with_using(
 	///     &mut vocabulary,
-	///     &mut generator,
+	///     context,
 	///     &loader,
 	///     Options::default()
 	///   )
 	///   .await
-	///   .expect("flattening failed");
-	///
-	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
-	///   // ...
-	/// }
+	///   .expect("compaction failed");
 	/// # }
 	/// ```
-	fn to_rdf_with_using<'a, N, G>(
+	fn compact_with_using<'a, N>(
 		&'a self,
 		vocabulary: &'a mut N,
-		generator: &'a mut G,
+		context: RemoteContextReference<Iri>,
 		loader: &'a impl Loader,
-		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ToRdfResult<'a, N, G>> + 'a>>
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
-		G: Generator<N>,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
-		self.to_rdf_full(vocabulary, generator, loader, options)
+		self.compact_full(vocabulary, context, loader, options)
 	}
 
-	/// Serializes the document into an RDF dataset with a custom vocabulary.
+	/// Compacts the document with the given `vocabulary` and `loader`.
 	///
 	/// Default options are used.
-	///
-	/// Expands the document and returns a [`ToRdf`] instance from which an
-	/// iterator over the RDF quads defined by the document can be accessed
-	/// using the [`ToRdf::quads`] method.
-	///
-	/// The quads will have type [`rdf::Quads`] which borrows the subject,
-	/// predicate and graph values from the documents if possible using [`Cow`].
-	/// If you prefer to have quads owning the values directly you can use the
-	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
-	/// method method form the value returned by [`ToRdf::quads`].
-	///
-	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
-	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
-	/// [`Cow`]: std::borrow::Cow
+	/// On success, the result is the compacted JSON document.
 	///
 	/// # Example
 	///
 	/// ```
 	/// use static_iref::iri;
-	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
-	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
-	///
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, RemoteContextReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
 	/// # #[async_std::main]
 	/// # async fn main() {
 	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
@@ -705,134 +857,783 @@ pub trait JsonLdProcessor<Iri>: Sized {
 	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
 	/// let input = RemoteDocumentReference::iri(iri_index);
 	///
+	/// let context_iri_index = vocabulary.insert(iri!("https://example.com/context.jsonld"));
+	/// let context = RemoteContextReference::iri(context_iri_index);
+	///
 	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
 	/// // the local `example` directory. No HTTP query.
 	/// let mut loader = json_ld::FsLoader::default();
 	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
 	///
-	/// let mut generator = rdf_types::generator::Blank::new();
-	///
-	/// let mut rdf = input
-	///   .to_rdf_with(
+	/// let compact = input
+	///   .compact_with(
 	///     &mut vocabulary,
-	///     &mut generator,
+	///     context,
 	///     &loader
 	///   )
 	///   .await
-	///   .expect("flattening failed");
-	///
-	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
-	///   // ...
-	/// }
+	///   .expect("compaction failed");
 	/// # }
 	/// ```
-	fn to_rdf_with<'a, N, G>(
+	fn compact_with<'a, N>(
 		&'a self,
 		vocabulary: &'a mut N,
-		generator: &'a mut G,
+		context: RemoteContextReference<Iri>,
 		loader: &'a impl Loader,
-	) -> Pin<Box<dyn Future<Output = ToRdfResult<'a, N, G>> + 'a>>
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		N::BlankId: 'a + Clone + Eq + Hash,
-		G: Generator<N>,
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
-		self.to_rdf_full(vocabulary, generator, loader, Options::default())
+		self.compact_with_using(vocabulary, context, loader, Options::default())
 	}
 
-	/// Serializes the document into an RDF dataset using the given `options`.
-	///
-	/// Expands the document and returns a [`ToRdf`] instance from which an
-	/// iterator over the RDF quads defined by the document can be accessed
-	/// using the [`ToRdf::quads`] method.
-	///
-	/// The quads will have type [`rdf::Quads`] which borrows the subject,
-	/// predicate and graph values from the documents if possible using [`Cow`].
-	/// If you prefer to have quads owning the values directly you can use the
-	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
-	/// method method form the value returned by [`ToRdf::quads`].
+	/// Compacts the document with the given `loader` using the given
+	/// `options`.
 	///
-	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
-	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
-	/// [`Cow`]: std::borrow::Cow
+	/// On success, the result is the compacted JSON document.
 	///
 	/// # Example
 	///
 	/// ```
 	/// use static_iref::iri;
-	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
-	/// use rdf_types::Quad;
-	/// use locspan::{Location, Span};
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, RemoteContextReference};
 	///
 	/// # #[async_std::main]
 	/// # async fn main() {
-	/// let iri_index = iri!("https://example.com/sample.jsonld").to_owned();
-	/// let input = RemoteDocumentReference::iri(iri_index);
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// let context = RemoteContextReference::iri(iri!("https://example.com/context.jsonld").to_owned());
 	///
 	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
 	/// // the local `example` directory. No HTTP query.
 	/// let mut loader = json_ld::FsLoader::default();
 	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
 	///
-	/// let mut generator = rdf_types::generator::Blank::new();
-	///
-	/// let mut rdf = input
-	///   .to_rdf_using(
-	///     &mut generator,
+	/// let compact = input
+	///   .compact_using(
+	///     context,
 	///     &loader,
 	///     Options::default()
 	///   )
 	///   .await
-	///   .expect("flattening failed");
-	///
-	/// for Quad(s, p, o, g) in rdf.quads() {
-	///   println!("subject: {}", s);
-	///   println!("predicate: {}", p);
-	///   println!("object: {}", o);
-	///
-	///   if let Some(g) = g {
-	///     println!("graph: {}", g);
-	///   }
-	/// }
+	///   .expect("compaction failed");
 	/// # }
 	/// ```
-	fn to_rdf_using<'a, G>(
+	fn compact_using<'a>(
 		&'a self,
-		generator: &'a mut G,
+		context: RemoteContextReference<Iri>,
 		loader: &'a impl Loader,
 		options: Options<Iri>,
-	) -> Pin<Box<dyn Future<Output = ToRdfResult<'a, (), G>> + 'a>>
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		G: Generator,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
-		self.to_rdf_with_using(
-			rdf_types::vocabulary::no_vocabulary_mut(),
-			generator,
-			loader,
-			options,
-		)
+		self.compact_with_using(vocabulary::no_vocabulary_mut(), context, loader, options)
 	}
 
-	/// Serializes the document into an RDF dataset.
+	/// Compacts the document with the given `loader`.
 	///
 	/// Default options are used.
-	///
-	/// Expands the document and returns a [`ToRdf`] instance from which an
-	/// iterator over the RDF quads defined by the document can be accessed
-	/// using the [`ToRdf::quads`] method.
-	///
-	/// The quads will have type [`rdf::Quads`] which borrows the subject,
-	/// predicate and graph values from the documents if possible using [`Cow`].
-	/// If you prefer to have quads owning the values directly you can use the
-	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
-	/// method method form the value returned by [`ToRdf::quads`].
-	///
-	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
-	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
-	/// [`Cow`]: std::borrow::Cow
+	/// On success, the result is the compacted JSON document.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, RemoteContextReference};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// let context = RemoteContextReference::iri(iri!("https://example.com/context.jsonld").to_owned());
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let compact = input
+	///   .compact(
+	///     context,
+	///     &loader
+	///   )
+	///   .await
+	///   .expect("compaction failed");
+	/// # }
+	/// ```
+	fn compact<'a>(
+		&'a self,
+		context: RemoteContextReference<Iri>,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.compact_with(vocabulary::no_vocabulary_mut(), context, loader)
+	}
+
+	/// Flattens the document with the given `vocabulary`, `generator` and
+	/// `loader`, using the given `options`.
+	///
+	/// Generators are used to generate fresh identifiers for unidentified
+	/// nodes. If a `context` is given, the flattened document is compacted
+	/// with it. Otherwise the result is the flattened document as a JSON
+	/// array of node objects.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let nodes = input
+	///   .flatten_full(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     None,
+	///     &loader,
+	///     Options::default()
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	/// # }
+	/// ```
+	fn flatten_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		context: Option<RemoteContextReference<Iri>>,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = FlattenResult<Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync;
+
+	/// Flattens the document with the given `vocabulary`, `generator` and
+	/// `loader`, using the given `options`.
+	///
+	/// The result is not compacted.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let nodes = input
+	///   .flatten_with_using(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader,
+	///     Options::default()
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	/// # }
+	/// ```
+	fn flatten_with_using<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = FlattenResult<Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.flatten_full(vocabulary, generator, None, loader, options)
+	}
+
+	/// Flattens the document with the given `vocabulary`, `generator` and
+	/// `loader`.
+	///
+	/// Default options are used. The result is not compacted.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let nodes = input
+	///   .flatten_with(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	/// # }
+	/// ```
+	fn flatten_with<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = FlattenResult<Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.flatten_with_using(vocabulary, generator, loader, Options::default())
+	}
+
+	/// Flattens the document with the given `generator` and `loader`, using
+	/// the given `options`.
+	///
+	/// The result is not compacted.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let nodes = input
+	///   .flatten_using(
+	///     &mut generator,
+	///     &loader,
+	///     Options::default()
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	/// # }
+	/// ```
+	fn flatten_using<'a>(
+		&'a self,
+		generator: &'a mut impl Generator,
+		loader: &'a impl Loader,
+		options: Options<Iri>,
+	) -> impl Future<Output = FlattenResult<Iri, BlankIdBuf>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.flatten_with_using(vocabulary::no_vocabulary_mut(), generator, loader, options)
+	}
+
+	/// Flattens the document with the given `generator` and `loader`.
+	///
+	/// Default options are used. The result is not compacted.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let nodes = input
+	///   .flatten(
+	///     &mut generator,
+	///     &loader
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	/// # }
+	/// ```
+	fn flatten<'a>(
+		&'a self,
+		generator: &'a mut impl Generator,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = FlattenResult<Iri, BlankIdBuf>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.flatten_using(generator, loader, Options::default())
+	}
+
+	/// Transforms the document into the form identified by the given
+	/// standard `profile`, with the given `vocabulary`, `generator` and
+	/// `loader`, using the given `options`.
+	///
+	/// This can be used to honor the `profile` parameter of the `Accept`
+	/// header of an HTTP request, as described in the
+	/// [IANA considerations](https://www.w3.org/TR/json-ld11/#iana-considerations)
+	/// section of the JSON-LD specification:
+	///   - [`StandardProfile::Expanded`] expands the document;
+	///   - [`StandardProfile::Compacted`] compacts the document using the
+	///     given `context`, or an empty context;
+	///   - [`StandardProfile::Flattened`] flattens the document, compacting
+	///     the result if a `context` is given.
+	///
+	/// Other profiles are not supported and fail with
+	/// [`TransformError::UnsupportedProfile`].
+	fn transform_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		profile: StandardProfile,
+		context: Option<RemoteContextReference<Iri>>,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = TransformResult<Iri, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			match profile {
+				StandardProfile::Expanded => {
+					let expanded = self
+						.expand_full(vocabulary, loader, options)
+						.await
+						.map_err(TransformError::Expand)?;
+					Ok(json_syntax::Value::Array(
+						expanded
+							.into_iter()
+							.map(|o| o.into_json_with(vocabulary))
+							.collect(),
+					))
+				}
+				StandardProfile::Compacted => {
+					let context = context.unwrap_or_else(|| {
+						RemoteContextReference::Loaded(RemoteDocument::new(
+							None,
+							None,
+							json_ld_syntax::context::Context::default(),
+						))
+					});
+
+					self.compact_full(vocabulary, context, loader, options)
+						.await
+						.map_err(TransformError::Compact)
+				}
+				StandardProfile::Flattened => self
+					.flatten_full(vocabulary, generator, context, loader, options)
+					.await
+					.map_err(TransformError::Flatten),
+				profile => Err(TransformError::UnsupportedProfile(profile)),
+			}
+		}
+	}
+
+	/// Transforms the document into the form identified by the given
+	/// standard `profile`, with the given `generator` and `loader`.
+	///
+	/// Default options are used.
+	/// See [`Self::transform_full`] for more details.
+	fn transform<'a>(
+		&'a self,
+		generator: &'a mut impl Generator,
+		profile: StandardProfile,
+		context: Option<RemoteContextReference<Iri>>,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = TransformResult<Iri, BlankIdBuf>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.transform_full(
+			vocabulary::no_vocabulary_mut(),
+			generator,
+			profile,
+			context,
+			loader,
+			Options::default(),
+		)
+	}
+
+	/// Serializes the document into an RDF dataset with a custom vocabulary
+	/// using the given `options` and warnings handler.
+	///
+	/// Expands the document and returns a [`ToRdf`] instance from which an
+	/// iterator over the RDF quads defined by the document can be accessed
+	/// using the [`ToRdf::quads`] method.
+	///
+	/// The quads will have type [`rdf::Quads`] which borrows the subject,
+	/// predicate and graph values from the documents if possible using [`Cow`].
+	/// If you prefer to have quads owning the values directly you can use the
+	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
+	/// method method form the value returned by [`ToRdf::quads`].
+	///
+	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
+	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
+	/// [`Cow`]: std::borrow::Cow
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
+	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_full(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader,
+	///     Options::default(),
+	///     warning::PrintWith
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	///
+	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
+	///   // ...
+	/// }
+	/// # }
+	/// ```
+	fn to_rdf_full<'a, N, G>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = ToRdfResult<'a, N, G>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+	{
+		async move {
+			let rdf_direction = options.rdf_direction;
+			let produce_generalized_rdf = options.produce_generalized_rdf;
+			let expanded_input = self
+				.expand_full(&mut *vocabulary, loader, options.unordered())
+				.await
+				.map_err(ToRdfError::Expand)?;
+			Ok(ToRdf::new(
+				vocabulary,
+				generator,
+				expanded_input,
+				rdf_direction,
+				produce_generalized_rdf,
+			))
+		}
+	}
+
+	/// Serializes the document into an RDF dataset with a custom vocabulary
+	/// using the given `options`.
+	///
+	/// Expands the document and returns a [`ToRdf`] instance from which an
+	/// iterator over the RDF quads defined by the document can be accessed
+	/// using the [`ToRdf::quads`] method.
+	///
+	/// The quads will have type [`rdf::Quads`] which borrows the subject,
+	/// predicate and graph values from the documents if possible using [`Cow`].
+	/// If you prefer to have quads owning the values directly you can use the
+	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
+	/// method method form the value returned by [`ToRdf::quads`].
+	///
+	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
+	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
+	/// [`Cow`]: std::borrow::Cow
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
+	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_with_using(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader,
+	///     Options::default()
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	///
+	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
+	///   // ...
+	/// }
+	/// # }
+	/// ```
+	fn to_rdf_with_using<'a, N, G>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+	) -> impl Future<Output = ToRdfResult<'a, N, G>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+	{
+		self.to_rdf_full(vocabulary, generator, loader, options)
+	}
+
+	/// Serializes the document into an RDF dataset with a custom vocabulary.
+	///
+	/// Default options are used.
+	///
+	/// Expands the document and returns a [`ToRdf`] instance from which an
+	/// iterator over the RDF quads defined by the document can be accessed
+	/// using the [`ToRdf::quads`] method.
+	///
+	/// The quads will have type [`rdf::Quads`] which borrows the subject,
+	/// predicate and graph values from the documents if possible using [`Cow`].
+	/// If you prefer to have quads owning the values directly you can use the
+	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
+	/// method method form the value returned by [`ToRdf::quads`].
+	///
+	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
+	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
+	/// [`Cow`]: std::borrow::Cow
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
+	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_with(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	///
+	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
+	///   // ...
+	/// }
+	/// # }
+	/// ```
+	fn to_rdf_with<'a, N, G>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+	) -> impl Future<Output = ToRdfResult<'a, N, G>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+	{
+		self.to_rdf_full(vocabulary, generator, loader, Options::default())
+	}
+
+	/// Serializes the document into an RDF dataset using the given `options`.
+	///
+	/// Expands the document and returns a [`ToRdf`] instance from which an
+	/// iterator over the RDF quads defined by the document can be accessed
+	/// using the [`ToRdf::quads`] method.
+	///
+	/// The quads will have type [`rdf::Quads`] which borrows the subject,
+	/// predicate and graph values from the documents if possible using [`Cow`].
+	/// If you prefer to have quads owning the values directly you can use the
+	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
+	/// method method form the value returned by [`ToRdf::quads`].
+	///
+	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
+	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
+	/// [`Cow`]: std::borrow::Cow
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference, warning};
+	/// use rdf_types::Quad;
+	/// use locspan::{Location, Span};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let iri_index = iri!("https://example.com/sample.jsonld").to_owned();
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_using(
+	///     &mut generator,
+	///     &loader,
+	///     Options::default()
+	///   )
+	///   .await
+	///   .expect("flattening failed");
+	///
+	/// for Quad(s, p, o, g) in rdf.quads() {
+	///   println!("subject: {}", s);
+	///   println!("predicate: {}", p);
+	///   println!("object: {}", o);
+	///
+	///   if let Some(g) = g {
+	///     println!("graph: {}", g);
+	///   }
+	/// }
+	/// # }
+	/// ```
+	fn to_rdf_using<'a, G>(
+		&'a self,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<Iri>,
+	) -> impl Future<Output = ToRdfResult<'a, (), G>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator + MaybeSend,
+	{
+		self.to_rdf_with_using(
+			rdf_types::vocabulary::no_vocabulary_mut(),
+			generator,
+			loader,
+			options,
+		)
+	}
+
+	/// Serializes the document into an RDF dataset.
+	///
+	/// Default options are used.
+	///
+	/// Expands the document and returns a [`ToRdf`] instance from which an
+	/// iterator over the RDF quads defined by the document can be accessed
+	/// using the [`ToRdf::quads`] method.
+	///
+	/// The quads will have type [`rdf::Quads`] which borrows the subject,
+	/// predicate and graph values from the documents if possible using [`Cow`].
+	/// If you prefer to have quads owning the values directly you can use the
+	/// [`ToRdf::cloned_quads`] method or call the [`rdf::Quads::cloned`]
+	/// method method form the value returned by [`ToRdf::quads`].
+	///
+	/// [`rdf::Quads`]: json_ld_core::rdf::Quads
+	/// [`rdf::Quads::cloned`]: json_ld_core::rdf::Quads::cloned
+	/// [`Cow`]: std::borrow::Cow
 	///
 	/// # Example
 	///
@@ -877,14 +1678,421 @@ pub trait JsonLdProcessor<Iri>: Sized {
 		&'a self,
 		generator: &'a mut G,
 		loader: &'a impl Loader,
-	) -> Pin<Box<dyn Future<Output = ToRdfResult<'a, (), G>> + 'a>>
+	) -> impl Future<Output = ToRdfResult<'a, (), G>> + MaybeSend + 'a
 	where
 		(): VocabularyMut<Iri = Iri>,
-		Iri: 'a + Clone + Eq + Hash,
-		G: Generator,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator + MaybeSend,
 	{
 		self.to_rdf_using(generator, loader, Options::default())
 	}
+
+	/// Serializes the document into RDF quads with a custom vocabulary using
+	/// the given `options`, passing each quad to `f` as soon as it is
+	/// produced.
+	///
+	/// Contrarily to [`JsonLdProcessor::to_rdf_full`], the document is not
+	/// fully expanded before being serialized. If the input is a JSON array
+	/// (or an object with only a `@context` and a `@graph` array), its items
+	/// are expanded and serialized one at a time, keeping memory usage
+	/// bounded by the size of the largest item instead of the size of the
+	/// whole document. Blank node identifiers are relabeled consistently
+	/// across items. Since top-level objects are not deduplicated, the
+	/// same quad may be emitted more than once.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// // Creates the vocabulary that will map each `rdf_types::vocabulary::Index`
+	/// // to an actual `IriBuf`.
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut count = 0;
+	/// input
+	///   .to_rdf_stream_full(
+	///     &mut vocabulary,
+	///     &mut generator,
+	///     &loader,
+	///     Options::default(),
+	///     &mut |_vocabulary, Quad(_s, _p, _o, _g)| {
+	///       count += 1;
+	///     }
+	///   )
+	///   .await
+	///   .expect("serialization failed");
+	/// # }
+	/// ```
+	fn to_rdf_stream_full<'a, N, G, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<Iri, N::BlankId>,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), ToRdfError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		N::Literal: Clone,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+		F: FnMut(&N, rdf::Quad<Iri, N::BlankId, N::Literal>) + MaybeSend;
+
+	/// Serializes the document into RDF quads with a custom vocabulary,
+	/// passing each quad to `f` as soon as it is produced.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::to_rdf_stream_full`] for more details.
+	fn to_rdf_stream_with<'a, N, G, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), ToRdfError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		N::Literal: Clone,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+		F: FnMut(&N, rdf::Quad<Iri, N::BlankId, N::Literal>) + MaybeSend,
+	{
+		self.to_rdf_stream_full(vocabulary, generator, loader, Options::default(), f)
+	}
+
+	/// Serializes the document into RDF quads, passing each quad to `f` as
+	/// soon as it is produced.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::to_rdf_stream_full`] for more details.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::Quad;
+	///
+	/// # #[async_std::main]
+	/// # async fn main() {
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// // Use `FsLoader` to redirect any URL starting with `https://example.com/` to
+	/// // the local `example` directory. No HTTP query.
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// input
+	///   .to_rdf_stream(
+	///     &mut generator,
+	///     &loader,
+	///     &mut |_, Quad(s, p, o, _g)| {
+	///       println!("{s} {p} {o}");
+	///     }
+	///   )
+	///   .await
+	///   .expect("serialization failed");
+	/// # }
+	/// ```
+	fn to_rdf_stream<'a, G, F>(
+		&'a self,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), ToRdfError>> + MaybeSend + 'a
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator + MaybeSend,
+		F: FnMut(&(), rdf::Quad<Iri, BlankIdBuf, rdf_types::Literal>) + MaybeSend,
+	{
+		self.to_rdf_stream_with(vocabulary::no_vocabulary_mut(), generator, loader, f)
+	}
+
+	/// Expand the document with the given `vocabulary` and synchronous
+	/// `loader`, using the given `options`, without requiring an async
+	/// executor.
+	///
+	/// This is the blocking counterpart of [`JsonLdProcessor::expand_full`].
+	/// It runs the same expansion algorithm, driven by
+	/// [`block_on`](json_ld_core::future::block_on).
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::vocabulary::{IriVocabularyMut, IndexVocabulary};
+	///
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let expanded = input
+	///   .expand_sync_full(&mut vocabulary, &loader, Options::default())
+	///   .expect("expansion failed");
+	/// ```
+	fn expand_sync_full<N>(
+		&self,
+		vocabulary: &mut N,
+		loader: &(impl SyncLoader + MaybeSync),
+		options: Options<Iri, N::BlankId>,
+	) -> ExpandResult<Iri, N::BlankId>
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		let loader = SyncLoaderAdapter(loader);
+		block_on(self.expand_full(vocabulary, &loader, options))
+	}
+
+	/// Expand the document with the given `vocabulary` and synchronous
+	/// `loader`, without requiring an async executor.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::expand_sync_full`] for more details.
+	fn expand_sync_with<N>(
+		&self,
+		vocabulary: &mut N,
+		loader: &(impl SyncLoader + MaybeSync),
+	) -> ExpandResult<Iri, N::BlankId>
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.expand_sync_full(vocabulary, loader, Options::default())
+	}
+
+	/// Expand the document with the given synchronous `loader`, without
+	/// requiring an async executor.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::expand_sync_full`] for more details.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, RemoteDocumentReference};
+	///
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let expanded = input
+	///   .expand_sync(&loader)
+	///   .expect("expansion failed");
+	/// ```
+	fn expand_sync(&self, loader: &(impl SyncLoader + MaybeSync)) -> ExpandResult<Iri, BlankIdBuf>
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		self.expand_sync_with(vocabulary::no_vocabulary_mut(), loader)
+	}
+
+	/// Serializes the document into an RDF dataset with a custom vocabulary
+	/// and synchronous `loader`, using the given `options`, without requiring
+	/// an async executor.
+	///
+	/// This is the blocking counterpart of [`JsonLdProcessor::to_rdf_full`].
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, Options, RemoteDocumentReference};
+	/// use rdf_types::{Quad, vocabulary::{IriVocabularyMut, IndexVocabulary}};
+	///
+	/// let mut vocabulary: IndexVocabulary = IndexVocabulary::new();
+	///
+	/// let iri_index = vocabulary.insert(iri!("https://example.com/sample.jsonld"));
+	/// let input = RemoteDocumentReference::iri(iri_index);
+	///
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_sync_full(&mut vocabulary, &mut generator, &loader, Options::default())
+	///   .expect("serialization failed");
+	///
+	/// for Quad(_s, _p, _o, _g) in rdf.quads() {
+	///   // ...
+	/// }
+	/// ```
+	fn to_rdf_sync_full<'a, N, G>(
+		&self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &(impl SyncLoader + MaybeSync),
+		options: Options<Iri, N::BlankId>,
+	) -> ToRdfResult<'a, N, G>
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N>,
+	{
+		let rdf_direction = options.rdf_direction;
+		let produce_generalized_rdf = options.produce_generalized_rdf;
+		let expanded_input = self
+			.expand_sync_full(&mut *vocabulary, loader, options.unordered())
+			.map_err(ToRdfError::Expand)?;
+		Ok(ToRdf::new(
+			vocabulary,
+			generator,
+			expanded_input,
+			rdf_direction,
+			produce_generalized_rdf,
+		))
+	}
+
+	/// Serializes the document into an RDF dataset with a custom vocabulary
+	/// and synchronous `loader`, without requiring an async executor.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::to_rdf_sync_full`] for more details.
+	fn to_rdf_sync_with<'a, N, G>(
+		&self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &(impl SyncLoader + MaybeSync),
+	) -> ToRdfResult<'a, N, G>
+	where
+		N: VocabularyMut<Iri = Iri> + MaybeSend,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N>,
+	{
+		self.to_rdf_sync_full(vocabulary, generator, loader, Options::default())
+	}
+
+	/// Serializes the document into an RDF dataset with the given synchronous
+	/// `loader`, without requiring an async executor.
+	///
+	/// Default options are used.
+	/// See [`JsonLdProcessor::to_rdf_sync_full`] for more details.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use static_iref::iri;
+	/// use json_ld::{JsonLdProcessor, RemoteDocumentReference};
+	/// use rdf_types::Quad;
+	///
+	/// let input = RemoteDocumentReference::iri(iri!("https://example.com/sample.jsonld").to_owned());
+	///
+	/// let mut loader = json_ld::FsLoader::default();
+	/// loader.mount(iri!("https://example.com/").to_owned(), "examples");
+	///
+	/// let mut generator = rdf_types::generator::Blank::new();
+	///
+	/// let mut rdf = input
+	///   .to_rdf_sync(&mut generator, &loader)
+	///   .expect("serialization failed");
+	///
+	/// for Quad(s, p, o, _g) in rdf.quads() {
+	///   println!("{s} {p} {o}");
+	/// }
+	/// ```
+	fn to_rdf_sync<'a, G>(
+		&self,
+		generator: &'a mut G,
+		loader: &(impl SyncLoader + MaybeSync),
+	) -> ToRdfResult<'a, (), G>
+	where
+		(): VocabularyMut<Iri = Iri>,
+		Iri: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator,
+	{
+		self.to_rdf_sync_with(vocabulary::no_vocabulary_mut(), generator, loader)
+	}
+}
+
+/// Compacts an already expanded (or flattened) document.
+///
+/// The `url` is the location of the input document, used as base IRI
+/// when processing the compaction context if no `base` option is set.
+async fn compact_expanded_full<'a, T, N, L>(
+	expanded_input: &'a T,
+	url: Option<&'a N::Iri>,
+	vocabulary: &'a mut N,
+	context: RemoteContextReference<N::Iri>,
+	loader: &'a L,
+	options: Options<N::Iri, N::BlankId>,
+) -> CompactResult
+where
+	N: VocabularyMut + MaybeSend,
+	N::Iri: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	T: Compact<N::Iri, N::BlankId> + MaybeSync,
+	L: Loader,
+{
+	let context_base = url.or(options.base.as_ref());
+
+	let context = context
+		.load_context_with(vocabulary, loader)
+		.await
+		.map_err(CompactError::ContextLoading)?
+		.into_document();
+
+	let mut active_context = context
+		.process_full(
+			vocabulary,
+			&Context::new(options.base.clone()),
+			loader,
+			context_base.cloned(),
+			options.context_processing_options(),
+		)
+		.await
+		.map_err(CompactError::ContextProcessing)?;
+
+	match options.base.as_ref() {
+		Some(base) => active_context.set_base_iri(Some(base.clone())),
+		None => {
+			if options.compact_to_relative && active_context.base_iri().is_none() {
+				active_context.set_base_iri(context_base.cloned());
+			}
+		}
+	}
+
+	expanded_input
+		.compact_full(
+			vocabulary,
+			active_context.as_ref(),
+			loader,
+			options.compaction_options(),
+		)
+		.await
+		.map_err(CompactError::Compaction)
 }
 
 pub struct ToRdf<'v, 'g, V: Vocabulary, G> {
@@ -932,4 +2140,49 @@ impl<'v, 'g, V: Vocabulary, G: rdf_types::Generator<V>> ToRdf<'v, 'g, V, G> {
 	) -> json_ld_core::rdf::ClonedQuads<'a, 'v, 'g, V, G> {
 		self.quads().cloned()
 	}
+
+	/// Collects the RDF quads into the dataset type `D`.
+	///
+	/// Contrarily to [`Self::quads`], this consumes the `ToRdf` instance and
+	/// does not borrow it. Every quad component is converted into an RDF
+	/// term, so the output can be collected into any `rdf_types` dataset,
+	/// such as [`BTreeDataset`](rdf_types::dataset::BTreeDataset), or simply
+	/// into a `Vec`.
+	pub fn into_dataset<D>(self) -> D
+	where
+		V: IriVocabularyMut + LiteralVocabularyMut,
+		V::Iri: Clone,
+		V::BlankId: Clone,
+		V::Literal: Clone,
+		D: FromIterator<DatasetQuad<V>>,
+	{
+		let Self {
+			vocabulary,
+			generator,
+			doc,
+			rdf_direction,
+			produce_generalized_rdf,
+		} = self;
+
+		doc.rdf_quads_full(
+			vocabulary,
+			generator,
+			rdf_direction,
+			produce_generalized_rdf,
+		)
+		.cloned()
+		.map(|rdf_types::Quad(s, p, o, g)| {
+			rdf_types::Quad(s.into_term(), p.into_term(), o, g.map(ValidId::into_term))
+		})
+		.collect()
+	}
 }
+
+/// Quad collected by [`ToRdf::into_dataset`], where every component is an RDF
+/// term.
+pub type DatasetQuad<V> = rdf_types::Quad<
+	rdf_types::Term<
+		ValidId<<V as IriVocabulary>::Iri, <V as BlankIdVocabulary>::BlankId>,
+		<V as LiteralVocabulary>::Literal,
+	>,
+>;
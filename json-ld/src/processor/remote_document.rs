@@ -1,94 +1,433 @@
-use super::{ExpandError, ExpandResult, JsonLdProcessor, Options};
+use super::{
+	compact_expanded_full, CompactError, CompactResult, ExpandError, ExpandResult, FlattenError,
+	FlattenResult, JsonLdProcessor, Options, ToRdfError,
+};
 use crate::context_processing::Process;
 use crate::expansion::Expand;
-use crate::{Context, Loader, RemoteDocument, RemoteDocumentReference};
-use alloc::boxed::Box;
+use crate::Flatten;
+use crate::{
+	Context, ExpandedDocument, LimitedLoader, Loader, RemoteContextReference, RemoteDocument,
+	RemoteDocumentReference, StandardProfile, TryFromJson,
+};
+use crate::{MaybeSend, MaybeSync};
 use core::future::Future;
 use core::hash::Hash;
-use core::pin::Pin;
-use rdf_types::VocabularyMut;
+use json_ld_core::rdf::{Quad, QuadsStream};
+use json_ld_syntax::IntoJsonWithContext;
+use rdf_types::{Generator, VocabularyMut};
 
-impl<I> JsonLdProcessor<I> for RemoteDocument<I> {
+impl<I: MaybeSync> JsonLdProcessor<I> for RemoteDocument<I> {
 	fn expand_full<'a, N>(
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a impl Loader,
-		mut options: Options<I>,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<I, N::BlankId>> + 'a>>
+		mut options: Options<I, N::BlankId>,
+	) -> impl Future<Output = ExpandResult<I, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash,
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
-		Box::pin(async move {
-			let mut active_context =
-				Context::new(options.base.clone().or_else(|| self.url().cloned()));
+		async move {
+			// Documents served with the `expanded` profile are already in
+			// expanded form and, if trusted, are not expanded again.
+			if options.trust_expanded_profile
+				&& options.expand_context.is_none()
+				&& self.has_profile(StandardProfile::Expanded)
+			{
+				if let Ok(expanded) =
+					ExpandedDocument::try_from_json_in(vocabulary, self.document().clone())
+				{
+					return Ok(expanded);
+				}
+			}
 
-			if let Some(expand_context) = options.expand_context.take() {
-				active_context = expand_context
-					.load_context_with(vocabulary, loader)
-					.await
-					.map_err(ExpandError::ContextLoading)?
-					.into_document()
-					.process_full(
-						vocabulary,
-						&active_context,
-						loader,
-						active_context.original_base_url().cloned(),
-						options.context_processing_options(),
-					)
-					.await
-					.map_err(ExpandError::ContextProcessing)?
-					.into_processed()
-			};
+			let loader = LimitedLoader::new(loader, options.max_loaded_contexts);
+			let active_context = initial_context(self, vocabulary, &loader, &mut options).await?;
 
-			if let Some(context_url) = self.context_url() {
-				active_context = RemoteDocumentReference::Iri(context_url.clone())
-					.load_context_with(vocabulary, loader)
+			self.document()
+				.expand_full(
+					vocabulary,
+					active_context,
+					self.url().or(options.base.as_ref()).cloned(),
+					&loader,
+					options.expansion_options(),
+				)
+				.await
+				.map_err(ExpandError::Expansion)
+		}
+	}
+
+	fn compact_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: RemoteContextReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			let expanded_input =
+				JsonLdProcessor::expand_full(self, vocabulary, loader, options.clone().unordered())
 					.await
-					.map_err(ExpandError::ContextLoading)?
-					.into_document()
-					.process_full(
-						vocabulary,
-						&active_context,
-						loader,
-						Some(context_url.clone()),
-						options.context_processing_options(),
-					)
+					.map_err(CompactError::Expand)?;
+
+			compact_expanded_full(
+				&expanded_input,
+				self.url(),
+				vocabulary,
+				context,
+				loader,
+				options,
+			)
+			.await
+		}
+	}
+
+	fn flatten_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		context: Option<RemoteContextReference<I>>,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+	) -> impl Future<Output = FlattenResult<I, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			let expanded_input =
+				JsonLdProcessor::expand_full(self, vocabulary, loader, options.clone().unordered())
 					.await
-					.map_err(ExpandError::ContextProcessing)?
-					.into_processed()
+					.map_err(FlattenError::Expand)?;
+
+			let flattened_output =
+				Flatten::flatten_with(expanded_input, vocabulary, generator, options.ordered)
+					.map_err(FlattenError::ConflictingIndexes)?;
+
+			match context {
+				Some(context) => compact_expanded_full(
+					&flattened_output,
+					self.url(),
+					vocabulary,
+					context,
+					loader,
+					options,
+				)
+				.await
+				.map_err(FlattenError::Compact),
+				None => Ok(json_syntax::Value::Array(
+					flattened_output
+						.into_iter()
+						.map(|n| n.into_json_with(vocabulary))
+						.collect(),
+				)),
 			}
+		}
+	}
+
+	fn to_rdf_stream_full<'a, N, G, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), ToRdfError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		N::Literal: Clone,
+		I: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+		F: FnMut(&N, Quad<I, N::BlankId, N::Literal>) + MaybeSend,
+	{
+		async move {
+			let mut options = options.unordered();
+			let loader = LimitedLoader::new(loader, options.max_loaded_contexts);
+			let active_context = initial_context(self, vocabulary, &loader, &mut options)
+				.await
+				.map_err(ToRdfError::Expand)?;
+
+			let mut stream =
+				QuadsStream::new(options.rdf_direction, options.produce_generalized_rdf);
 
 			self.document()
-				.expand_full(
+				.expand_each_full(
 					vocabulary,
 					active_context,
 					self.url().or(options.base.as_ref()).cloned(),
-					loader,
+					&loader,
 					options.expansion_options(),
+					&mut |vocabulary: &mut N, object| {
+						for quad in stream.push_with(vocabulary, generator, object) {
+							f(vocabulary, quad)
+						}
+					},
 				)
 				.await
-				.map_err(ExpandError::Expansion)
-		})
+				.map_err(|e| ToRdfError::Expand(ExpandError::Expansion(e)))
+		}
 	}
 }
 
-impl<I> JsonLdProcessor<I> for RemoteDocumentReference<I, json_syntax::Value> {
+impl<I: MaybeSync> JsonLdProcessor<I> for RemoteDocumentReference<I, json_syntax::Value> {
 	fn expand_full<'a, N>(
 		&'a self,
 		vocabulary: &'a mut N,
 		loader: &'a impl Loader,
-		options: Options<I>,
-	) -> Pin<Box<dyn Future<Output = ExpandResult<I, N::BlankId>> + 'a>>
+		options: Options<I, N::BlankId>,
+	) -> impl Future<Output = ExpandResult<I, N::BlankId>> + MaybeSend + 'a
 	where
-		N: VocabularyMut<Iri = I>,
-		I: Clone + Eq + Hash,
-		N::BlankId: Clone + Eq + Hash,
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
 	{
-		Box::pin(async move {
+		async move {
 			let doc = self.loaded_with(vocabulary, loader).await?;
 			JsonLdProcessor::expand_full(doc.as_ref(), vocabulary, loader, options).await
-		})
+		}
+	}
+
+	fn compact_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		context: RemoteContextReference<I>,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+	) -> impl Future<Output = CompactResult> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			let doc = self.loaded_with(vocabulary, loader).await?;
+			JsonLdProcessor::compact_full(doc.as_ref(), vocabulary, context, loader, options).await
+		}
+	}
+
+	fn flatten_full<'a, N>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut impl Generator<N>,
+		context: Option<RemoteContextReference<I>>,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+	) -> impl Future<Output = FlattenResult<I, N::BlankId>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+	{
+		async move {
+			let doc = self.loaded_with(vocabulary, loader).await?;
+			JsonLdProcessor::flatten_full(
+				doc.as_ref(),
+				vocabulary,
+				generator,
+				context,
+				loader,
+				options,
+			)
+			.await
+		}
+	}
+
+	fn to_rdf_stream_full<'a, N, G, F>(
+		&'a self,
+		vocabulary: &'a mut N,
+		generator: &'a mut G,
+		loader: &'a impl Loader,
+		options: Options<I, N::BlankId>,
+		f: &'a mut F,
+	) -> impl Future<Output = Result<(), ToRdfError>> + MaybeSend + 'a
+	where
+		N: VocabularyMut<Iri = I> + MaybeSend,
+		N::Literal: Clone,
+		I: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		N::BlankId: 'a + Clone + Eq + Hash + MaybeSend + MaybeSync,
+		G: Generator<N> + MaybeSend,
+		F: FnMut(&N, Quad<I, N::BlankId, N::Literal>) + MaybeSend,
+	{
+		async move {
+			let doc = self
+				.loaded_with(vocabulary, loader)
+				.await
+				.map_err(|e| ToRdfError::Expand(ExpandError::Loading(e)))?;
+			JsonLdProcessor::to_rdf_stream_full(
+				doc.as_ref(),
+				vocabulary,
+				generator,
+				loader,
+				options,
+				f,
+			)
+			.await
+		}
+	}
+}
+
+/// Computes the initial active context used to expand the given document.
+///
+/// This is the empty context (with the appropriate base IRI) updated with
+/// the `expand_context` option and the context linked from the document's
+/// HTTP `Link` header, if any.
+async fn initial_context<I, N>(
+	document: &RemoteDocument<I>,
+	vocabulary: &mut N,
+	loader: &impl Loader,
+	options: &mut Options<I, N::BlankId>,
+) -> Result<Context<I, N::BlankId>, ExpandError>
+where
+	N: VocabularyMut<Iri = I> + MaybeSend,
+	I: Clone + Eq + Hash + MaybeSend + MaybeSync,
+	N::BlankId: Clone + Eq + Hash + MaybeSend + MaybeSync,
+{
+	let mut active_context = Context::new(options.base.clone().or_else(|| document.url().cloned()));
+	if let Some(cache) = &options.context_cache {
+		active_context = active_context.with_context_cache(cache.clone())
+	}
+
+	if let Some(expand_context) = options.expand_context.take() {
+		active_context = expand_context
+			.load_context_with(vocabulary, loader)
+			.await
+			.map_err(ExpandError::ContextLoading)?
+			.into_document()
+			.process_full(
+				vocabulary,
+				&active_context,
+				loader,
+				active_context.original_base_url().cloned(),
+				options.context_processing_options(),
+			)
+			.await
+			.map_err(ExpandError::ContextProcessing)?
+			.into_processed()
+	};
+
+	if let Some(context_url) = document.context_url() {
+		active_context = RemoteDocumentReference::Iri(context_url.clone())
+			.load_context_with(vocabulary, loader)
+			.await
+			.map_err(ExpandError::ContextLoading)?
+			.into_document()
+			.process_full(
+				vocabulary,
+				&active_context,
+				loader,
+				Some(context_url.clone()),
+				options.context_processing_options(),
+			)
+			.await
+			.map_err(ExpandError::ContextProcessing)?
+			.into_processed()
+	}
+
+	Ok(active_context)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::context::ContextCache;
+	use crate::{NoLoader, Profile};
+	use alloc::string::ToString;
+	use iref::IriBuf;
+	use json_ld_core::future::block_on;
+	use json_syntax::Parse;
+
+	fn expanded_document() -> RemoteDocument {
+		let (document, _) = json_syntax::Value::parse_str(
+			r#"[{
+				"http://example.org/p": [{
+					"http://example.org/p": [{
+						"http://example.org/p": [{ "@value": "deep" }]
+					}]
+				}]
+			}]"#,
+		)
+		.unwrap();
+
+		RemoteDocument::new_full(
+			Some(IriBuf::new("https://example.org/document".to_string()).unwrap()),
+			None,
+			None,
+			core::iter::once(Profile::Standard(StandardProfile::Expanded)).collect(),
+			document,
+		)
+	}
+
+	fn options(trust_expanded_profile: bool) -> Options {
+		Options {
+			max_depth: Some(1),
+			trust_expanded_profile,
+			..Options::default()
+		}
+	}
+
+	#[test]
+	fn expanded_profile_is_expanded_by_default() {
+		let document = expanded_document();
+		let result = block_on(JsonLdProcessor::expand_full(
+			&document,
+			&mut (),
+			&NoLoader,
+			options(false),
+		));
+		assert!(matches!(result, Err(ExpandError::Expansion(_))))
+	}
+
+	#[test]
+	fn trusted_expanded_profile() {
+		let document = expanded_document();
+		let result = block_on(JsonLdProcessor::expand_full(
+			&document,
+			&mut (),
+			&NoLoader,
+			options(true),
+		));
+		assert_eq!(result.unwrap().len(), 1)
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn context_cache() {
+		let (document, _) = json_syntax::Value::parse_str(
+			r#"{
+				"@context": { "name": "http://schema.org/name" },
+				"name": "Alice"
+			}"#,
+		)
+		.unwrap();
+		let document = RemoteDocument::new(None, None, document);
+
+		let cache = ContextCache::new().with_namespace("none");
+		let options = Options {
+			context_cache: Some(cache.clone()),
+			..Options::default()
+		};
+
+		for _ in 0..2 {
+			let expanded = block_on(JsonLdProcessor::expand_full(
+				&document,
+				&mut (),
+				&NoLoader,
+				options.clone(),
+			))
+			.unwrap();
+			assert_eq!(expanded.len(), 1)
+		}
+
+		let stats = cache.stats();
+		assert_eq!(stats.misses, 1);
+		assert_eq!(stats.hits, 1);
 	}
 }
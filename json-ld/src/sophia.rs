@@ -0,0 +1,180 @@
+//! [Sophia](https://docs.rs/sophia) interoperability.
+//!
+//! This module converts the RDF quads produced by this crate into Sophia
+//! terms, so that the output of the [`ToRdf`] algorithm can be used with any
+//! Sophia [`Dataset`] consumer (serializers, SPARQL engines, etc.). Because
+//! `Vec<Q>` is itself a Sophia dataset whenever `Q` is a Sophia quad, the
+//! result can be used as is.
+//!
+//! Conversely, [`from_sophia_dataset`] reads a Sophia dataset into
+//! [`LexicalQuad`]s, the input format of the RDF deserialization algorithm.
+use crate::{ExpandedDocument, RdfQuads, ToRdf};
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use iref::IriBuf;
+use json_ld_core::rdf::RdfDirection;
+use langtag::LangTagBuf;
+use rdf_types::{BlankIdBuf, Generator, Id, LexicalQuad, Literal, LiteralType, Term};
+use sophia_api::dataset::Dataset;
+use sophia_api::quad::{Quad as _, Spog};
+use sophia_api::term::{BnodeId, IriRef, LanguageTag, SimpleTerm, Term as _, TermKind};
+
+/// Sophia quad produced by the conversion functions of this module.
+pub type SophiaQuad = Spog<SimpleTerm<'static>>;
+
+/// Converts a node identifier into a Sophia term.
+pub fn id_to_sophia(id: Id) -> SimpleTerm<'static> {
+	match id {
+		Id::Iri(iri) => SimpleTerm::Iri(IriRef::new_unchecked(iri.as_str().to_owned().into())),
+		Id::Blank(b) => SimpleTerm::BlankNode(BnodeId::new_unchecked(b.suffix().to_owned().into())),
+	}
+}
+
+/// Converts an RDF term into a Sophia term.
+pub fn term_to_sophia(term: Term) -> SimpleTerm<'static> {
+	match term {
+		Term::Id(id) => id_to_sophia(id),
+		Term::Literal(literal) => {
+			let (value, type_) = literal.into_parts();
+			match type_ {
+				LiteralType::Any(iri) => SimpleTerm::LiteralDatatype(
+					value.into(),
+					IriRef::new_unchecked(iri.as_str().to_owned().into()),
+				),
+				LiteralType::LangString(tag) => SimpleTerm::LiteralLanguage(
+					value.into(),
+					LanguageTag::new_unchecked(tag.as_str().to_owned().into()),
+				),
+			}
+		}
+	}
+}
+
+/// Converts an RDF quad into a Sophia quad.
+pub fn quad_to_sophia(rdf_types::Quad(s, p, o, g): rdf_types::Quad<Term>) -> SophiaQuad {
+	(
+		[term_to_sophia(s), term_to_sophia(p), term_to_sophia(o)],
+		g.map(term_to_sophia),
+	)
+}
+
+impl<'v, 'g, G: Generator> ToRdf<'v, 'g, (), G> {
+	/// Collects the RDF quads into a Sophia dataset.
+	pub fn into_sophia_dataset(self) -> Vec<SophiaQuad> {
+		self.into_dataset::<Vec<_>>()
+			.into_iter()
+			.map(quad_to_sophia)
+			.collect()
+	}
+}
+
+/// Serializes the given expanded `document` into a Sophia dataset, using
+/// `generator` to label the blank nodes.
+///
+/// Contrarily to [`ToRdf`], blank nodes are not relabeled beforehand.
+pub fn expanded_document_to_sophia<G: Generator>(
+	document: &ExpandedDocument,
+	generator: &mut G,
+	rdf_direction: Option<RdfDirection>,
+) -> Vec<SophiaQuad> {
+	document
+		.rdf_quads(generator, rdf_direction)
+		.cloned()
+		.map(|rdf_types::Quad(s, p, o, g)| {
+			(
+				[id_to_sophia(s), id_to_sophia(p), term_to_sophia(o)],
+				g.map(id_to_sophia),
+			)
+		})
+		.collect()
+}
+
+/// Error raised by [`from_sophia_dataset`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromSophiaError<E> {
+	/// The dataset failed to provide its quads.
+	#[error(transparent)]
+	Dataset(E),
+
+	/// Variables and quoted triples are not supported.
+	#[error("unsupported term")]
+	UnsupportedTerm,
+
+	/// A literal is used as subject, predicate or graph name, or a blank node
+	/// as predicate.
+	#[error("invalid quad")]
+	InvalidQuad,
+
+	/// Invalid IRI.
+	#[error("invalid IRI `{0}`")]
+	InvalidIri(String),
+
+	/// Invalid blank node identifier.
+	#[error("invalid blank node identifier `{0}`")]
+	InvalidBlankId(String),
+
+	/// Invalid language tag.
+	#[error("invalid language tag `{0}`")]
+	InvalidLangTag(String),
+}
+
+/// Reads the quads of the given Sophia dataset.
+pub fn from_sophia_dataset<D: Dataset>(
+	dataset: &D,
+) -> Result<Vec<LexicalQuad>, FromSophiaError<D::Error>> {
+	dataset
+		.quads()
+		.map(|quad| {
+			let ([s, p, o], g) = quad.map_err(FromSophiaError::Dataset)?.spog();
+			let s = term_from_sophia(s)?
+				.into_id()
+				.ok_or(FromSophiaError::InvalidQuad)?;
+			let p = match term_from_sophia(p)? {
+				Term::Id(Id::Iri(iri)) => iri,
+				_ => return Err(FromSophiaError::InvalidQuad),
+			};
+			let o = term_from_sophia(o)?;
+			let g = g
+				.map(|g| {
+					term_from_sophia(g)?
+						.into_id()
+						.ok_or(FromSophiaError::InvalidQuad)
+				})
+				.transpose()?;
+
+			Ok(rdf_types::Quad(s, p, o, g))
+		})
+		.collect()
+}
+
+fn term_from_sophia<T: sophia_api::term::Term, E>(term: T) -> Result<Term, FromSophiaError<E>> {
+	match term.kind() {
+		TermKind::Iri => {
+			iri_from_sophia(term.iri().unwrap().as_str()).map(|iri| Term::Id(Id::Iri(iri)))
+		}
+		TermKind::BlankNode => {
+			let suffix = term.bnode_id().unwrap();
+			BlankIdBuf::from_suffix(suffix.as_str())
+				.map(|b| Term::Id(Id::Blank(b)))
+				.map_err(|_| FromSophiaError::InvalidBlankId(suffix.as_str().to_string()))
+		}
+		TermKind::Literal => {
+			let value = term.lexical_form().unwrap().to_string();
+			let type_ = match term.language_tag() {
+				Some(tag) => LiteralType::LangString(
+					LangTagBuf::new(tag.as_str().to_string())
+						.map_err(|e| FromSophiaError::InvalidLangTag(e.0))?,
+				),
+				None => LiteralType::Any(iri_from_sophia(term.datatype().unwrap().as_str())?),
+			};
+
+			Ok(Term::Literal(Literal::new(value, type_)))
+		}
+		_ => Err(FromSophiaError::UnsupportedTerm),
+	}
+}
+
+fn iri_from_sophia<E>(iri: &str) -> Result<IriBuf, FromSophiaError<E>> {
+	IriBuf::new(iri.to_string()).map_err(|e| FromSophiaError::InvalidIri(e.0))
+}
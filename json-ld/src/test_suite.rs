@@ -0,0 +1,1018 @@
+//! W3C [JSON-LD test suite](https://w3c.github.io/json-ld-api/tests/) runner.
+//!
+//! This module parses the official manifest format and runs the expansion,
+//! compaction, flattening and RDF serialization tests through the
+//! [`JsonLdProcessor`] trait, reporting the outcome of each test. It is meant
+//! to continuously measure the conformance of the processor.
+//!
+//! The test suite is read from a local copy of the
+//! [`json-ld-api`](https://github.com/w3c/json-ld-api) repository `tests`
+//! directory, mounted on the base IRI of the test suite.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use static_iref::iri;
+//! use json_ld::test_suite::TestSuite;
+//!
+//! let suite = TestSuite::new(
+//!   iri!("https://w3c.github.io/json-ld-api/tests/").to_owned(),
+//!   "json-ld-api/tests",
+//! );
+//!
+//! let manifest = suite
+//!   .load_manifest(iri!("https://w3c.github.io/json-ld-api/tests/expand-manifest.jsonld"))
+//!   .expect("unable to load manifest");
+//!
+//! let report = suite.run(&manifest);
+//! println!("{report}");
+//! ```
+use crate::{
+	ExpandedDocument, FsLoader, JsonLdProcessor, LoadError, Options, ProcessingMode,
+	RemoteContextReference, RemoteDocumentReference, TryFromJson,
+};
+use iref::{Iri, IriBuf, IriRef};
+use json_ld_core::future::block_on;
+use json_ld_core::rdf::{nquads, RdfDirection};
+use json_syntax::Value;
+use rdf_types::vocabulary;
+use rdf_types::{BlankIdBuf, Id, Literal, Term};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Error raised when loading a test manifest.
+#[derive(Debug, thiserror::Error)]
+pub enum ManifestError {
+	/// The manifest could not be loaded.
+	#[error(transparent)]
+	Load(LoadError),
+
+	/// The manifest is not valid.
+	#[error("invalid manifest: {0}")]
+	Invalid(&'static str),
+
+	/// Invalid IRI reference in the manifest.
+	#[error("invalid IRI reference `{0}`")]
+	InvalidIri(String),
+}
+
+/// Test manifest.
+#[derive(Debug, Clone)]
+pub struct Manifest {
+	/// Manifest IRI.
+	pub iri: IriBuf,
+
+	/// Manifest name.
+	pub name: Option<String>,
+
+	/// Tests declared by the manifest, in order.
+	pub tests: Vec<Test>,
+}
+
+/// Test case.
+#[derive(Debug, Clone)]
+pub struct Test {
+	/// Test IRI.
+	pub id: IriBuf,
+
+	/// Test name.
+	pub name: Option<String>,
+
+	/// Tested algorithm.
+	pub kind: TestKind,
+
+	/// Input document IRI.
+	pub input: IriBuf,
+
+	/// Context document IRI, used by compaction and flattening tests.
+	pub context: Option<IriBuf>,
+
+	/// Expected result.
+	pub expectation: Expectation,
+
+	/// Test options.
+	pub options: TestOptions,
+}
+
+/// Tested algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestKind {
+	Expand,
+	Compact,
+	Flatten,
+	ToRdf,
+
+	/// Test type not supported by this runner (framing, RDF
+	/// deserialization, HTML, etc.).
+	Unsupported,
+}
+
+/// Expected result of a test.
+#[derive(Debug, Clone)]
+pub enum Expectation {
+	/// The test must succeed and produce the output behind the given IRI.
+	Positive(IriBuf),
+
+	/// The test must fail with the given error code.
+	Negative(String),
+
+	/// The test must succeed, whatever its output.
+	Syntax,
+}
+
+/// Test options, as defined by the `option` entry of a test.
+#[derive(Debug, Clone, Default)]
+pub struct TestOptions {
+	/// Base IRI.
+	pub base: Option<IriBuf>,
+
+	/// Context used to initialize the active context when expanding.
+	pub expand_context: Option<IriBuf>,
+
+	/// Processing mode.
+	pub processing_mode: Option<ProcessingMode>,
+
+	/// Version of the specification the test applies to (`json-ld-1.0` or
+	/// `json-ld-1.1`).
+	pub spec_version: Option<String>,
+
+	/// See [`Options::compact_arrays`].
+	pub compact_arrays: Option<bool>,
+
+	/// See [`Options::compact_to_relative`].
+	pub compact_to_relative: Option<bool>,
+
+	/// See [`Options::produce_generalized_rdf`].
+	pub produce_generalized_rdf: Option<bool>,
+
+	/// See [`Options::rdf_direction`].
+	pub rdf_direction: Option<RdfDirection>,
+
+	/// Whether or not the tested feature is normative.
+	pub normative: Option<bool>,
+}
+
+impl TestOptions {
+	/// Returns the processor options for these test options.
+	pub fn processor_options(&self) -> Options {
+		let mut options = Options {
+			base: self.base.clone(),
+			expand_context: self.expand_context.clone().map(RemoteContextReference::iri),
+			rdf_direction: self.rdf_direction,
+			..Options::default()
+		};
+
+		if let Some(mode) = self.processing_mode {
+			options.processing_mode = mode
+		}
+
+		if let Some(b) = self.compact_arrays {
+			options.compact_arrays = b
+		}
+
+		if let Some(b) = self.compact_to_relative {
+			options.compact_to_relative = b
+		}
+
+		if let Some(b) = self.produce_generalized_rdf {
+			options.produce_generalized_rdf = b
+		}
+
+		options
+	}
+}
+
+/// Outcome of a test.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+	/// The test passed.
+	Passed,
+
+	/// The test failed, with a description of the failure.
+	Failed(String),
+
+	/// The test was not run, for the given reason.
+	Skipped(&'static str),
+}
+
+impl Outcome {
+	pub fn is_passed(&self) -> bool {
+		matches!(self, Self::Passed)
+	}
+
+	pub fn is_failed(&self) -> bool {
+		matches!(self, Self::Failed(_))
+	}
+
+	pub fn is_skipped(&self) -> bool {
+		matches!(self, Self::Skipped(_))
+	}
+}
+
+/// Result of a single test.
+#[derive(Debug, Clone)]
+pub struct TestResult {
+	/// Test IRI.
+	pub id: IriBuf,
+
+	/// Test name.
+	pub name: Option<String>,
+
+	/// Test outcome.
+	pub outcome: Outcome,
+}
+
+/// Results of a manifest run.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+	/// Result of each test, in the manifest order.
+	pub results: Vec<TestResult>,
+}
+
+impl Report {
+	/// Number of passed tests.
+	pub fn passed(&self) -> usize {
+		self.results
+			.iter()
+			.filter(|r| r.outcome.is_passed())
+			.count()
+	}
+
+	/// Number of failed tests.
+	pub fn failed(&self) -> usize {
+		self.results
+			.iter()
+			.filter(|r| r.outcome.is_failed())
+			.count()
+	}
+
+	/// Number of skipped tests.
+	pub fn skipped(&self) -> usize {
+		self.results
+			.iter()
+			.filter(|r| r.outcome.is_skipped())
+			.count()
+	}
+
+	/// Returns an iterator over the failed tests.
+	pub fn failures(&self) -> impl Iterator<Item = &TestResult> {
+		self.results.iter().filter(|r| r.outcome.is_failed())
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for result in &self.results {
+			match &result.outcome {
+				Outcome::Passed => writeln!(f, "PASS {}", result.id)?,
+				Outcome::Failed(reason) => writeln!(f, "FAIL {}: {reason}", result.id)?,
+				Outcome::Skipped(reason) => writeln!(f, "SKIP {}: {reason}", result.id)?,
+			}
+		}
+
+		write!(
+			f,
+			"{} passed, {} failed, {} skipped",
+			self.passed(),
+			self.failed(),
+			self.skipped()
+		)
+	}
+}
+
+/// Test suite runner.
+///
+/// Runs the tests of a local copy of the test suite, mounted on its base
+/// IRI.
+pub struct TestSuite {
+	loader: FsLoader,
+}
+
+impl TestSuite {
+	/// Creates a new runner for the test suite located at the given `path`,
+	/// with the given `base` IRI.
+	pub fn new(base: IriBuf, path: impl Into<PathBuf>) -> Self {
+		let mut loader = FsLoader::new();
+		loader.mount(base, path.into());
+		Self { loader }
+	}
+
+	/// Returns the underlying document loader.
+	pub fn loader(&self) -> &FsLoader {
+		&self.loader
+	}
+
+	/// Loads the test manifest behind the given IRI.
+	pub fn load_manifest(&self, iri: &Iri) -> Result<Manifest, ManifestError> {
+		let document =
+			block_on(json_ld_core::Loader::load(&self.loader, iri)).map_err(ManifestError::Load)?;
+		Manifest::from_json(iri, document.into_document())
+	}
+
+	/// Runs every test of the given manifest.
+	pub fn run(&self, manifest: &Manifest) -> Report {
+		Report {
+			results: manifest.tests.iter().map(|t| self.run_test(t)).collect(),
+		}
+	}
+
+	/// Runs the given test.
+	pub fn run_test(&self, test: &Test) -> TestResult {
+		TestResult {
+			id: test.id.clone(),
+			name: test.name.clone(),
+			outcome: self.test_outcome(test),
+		}
+	}
+
+	fn test_outcome(&self, test: &Test) -> Outcome {
+		if test.kind == TestKind::Unsupported {
+			return Outcome::Skipped("unsupported test type");
+		}
+
+		if test.options.spec_version.as_deref() == Some("json-ld-1.0") {
+			return Outcome::Skipped("JSON-LD 1.0 only");
+		}
+
+		if !is_json_document(&test.input) {
+			return Outcome::Skipped("non-JSON input");
+		}
+
+		let input = RemoteDocumentReference::iri(test.input.clone());
+		let options = test.options.processor_options();
+
+		let output = match test.kind {
+			TestKind::Expand => block_on(input.expand_using(&self.loader, options))
+				.map(Output::Expanded)
+				.map_err(|e| e.code()),
+			TestKind::Compact => {
+				let context = match &test.context {
+					Some(context) => RemoteContextReference::iri(context.clone()),
+					None => return Outcome::Failed("missing context".to_string()),
+				};
+
+				block_on(input.compact_using(context, &self.loader, options))
+					.map(Output::Json)
+					.map_err(|e| e.code())
+			}
+			TestKind::Flatten => {
+				// Same labels as the expected outputs of the test suite.
+				let mut generator = rdf_types::generator::Blank::new_with_prefix("b".to_string());
+				block_on(input.flatten_full(
+					vocabulary::no_vocabulary_mut(),
+					&mut generator,
+					test.context.clone().map(RemoteContextReference::iri),
+					&self.loader,
+					options,
+				))
+				.map(Output::Json)
+				.map_err(|e| e.code())
+			}
+			TestKind::ToRdf => {
+				let mut generator = rdf_types::generator::Blank::new();
+				block_on(input.to_rdf_using(&mut generator, &self.loader, options))
+					.map(|rdf| {
+						Output::NQuads(
+							rdf.into_dataset::<Vec<_>>()
+								.into_iter()
+								.map(|rdf_types::Quad(s, p, o, g)| {
+									// Subjects, predicates and graphs are always
+									// identifiers.
+									rdf_types::Quad(
+										s.into_id().unwrap(),
+										p.into_id().unwrap(),
+										o,
+										g.map(|g| g.into_id().unwrap()),
+									)
+								})
+								.collect(),
+						)
+					})
+					.map_err(|e| e.code())
+			}
+			TestKind::Unsupported => unreachable!(),
+		};
+
+		match (&test.expectation, output) {
+			(Expectation::Positive(expect), Ok(output)) => self.check_output(expect, output),
+			(Expectation::Syntax, Ok(_)) => Outcome::Passed,
+			(Expectation::Negative(expected), Ok(_)) => {
+				Outcome::Failed(format!("expected error `{expected}`"))
+			}
+			(Expectation::Negative(expected), Err(code)) => {
+				if code.as_str() == expected {
+					Outcome::Passed
+				} else {
+					Outcome::Failed(format!("expected error `{expected}`, found `{code}`"))
+				}
+			}
+			(_, Err(code)) => Outcome::Failed(format!("unexpected error `{code}`")),
+		}
+	}
+
+	fn check_output(&self, expect: &Iri, output: Output) -> Outcome {
+		match output {
+			Output::Expanded(expanded) => match self.load_json(expect) {
+				Ok(json) => {
+					match ExpandedDocument::<IriBuf, BlankIdBuf>::try_from_json_in(&mut (), json) {
+						Ok(expected) => {
+							if expanded.isomorphic_to(&expected).is_some() {
+								Outcome::Passed
+							} else {
+								Outcome::Failed("output mismatch".to_string())
+							}
+						}
+						Err(e) => Outcome::Failed(format!("invalid expected output: {e:?}")),
+					}
+				}
+				Err(e) => Outcome::Failed(e),
+			},
+			Output::Json(json) => match self.load_json(expect) {
+				Ok(expected) => {
+					if json_equivalent(&json, &expected, false, &mut Bijection::default()) {
+						Outcome::Passed
+					} else {
+						Outcome::Failed("output mismatch".to_string())
+					}
+				}
+				Err(e) => Outcome::Failed(e),
+			},
+			Output::NQuads(quads) => match self.load_text(expect) {
+				Ok(text) => match nquads::parse_quads(&text).collect::<Result<Vec<_>, _>>() {
+					Ok(expected) => {
+						if isomorphic(&quads, &expected) {
+							Outcome::Passed
+						} else {
+							Outcome::Failed("output mismatch".to_string())
+						}
+					}
+					Err(e) => Outcome::Failed(format!("invalid expected output: {e}")),
+				},
+				Err(e) => Outcome::Failed(e),
+			},
+		}
+	}
+
+	fn load_json(&self, iri: &Iri) -> Result<Value, String> {
+		block_on(json_ld_core::Loader::load(&self.loader, iri))
+			.map(|document| document.into_document())
+			.map_err(|e| format!("unable to load expected output: {e}"))
+	}
+
+	fn load_text(&self, iri: &Iri) -> Result<String, String> {
+		let path = self
+			.loader
+			.filepath(iri)
+			.ok_or_else(|| format!("unable to load expected output: no mount point for `{iri}`"))?;
+		std::fs::read_to_string(path).map_err(|e| format!("unable to load expected output: {e}"))
+	}
+}
+
+/// Output of a tested algorithm.
+enum Output {
+	Expanded(ExpandedDocument),
+
+	/// Compacted or flattened document, compared to the expected document up
+	/// to blank node relabeling.
+	Json(Value),
+
+	/// RDF dataset, compared to the expected N-Quads document up to blank
+	/// node relabeling.
+	NQuads(Vec<RdfQuad>),
+}
+
+impl Manifest {
+	/// Reads a manifest from its JSON representation.
+	///
+	/// The manifest is read as plain JSON, without going through the
+	/// expansion algorithm.
+	pub fn from_json(iri: &Iri, json: Value) -> Result<Self, ManifestError> {
+		let object = json
+			.as_object()
+			.ok_or(ManifestError::Invalid("manifest is not an object"))?;
+
+		let base = match object.get("baseIri").next() {
+			Some(Value::String(base)) => {
+				IriBuf::new(base.to_string()).map_err(|e| ManifestError::InvalidIri(e.0))?
+			}
+			Some(_) => return Err(ManifestError::Invalid("invalid `baseIri`")),
+			None => iri.to_owned(),
+		};
+
+		let tests = match object.get("sequence").next() {
+			Some(Value::Array(items)) => items
+				.iter()
+				.map(|item| Test::from_json(iri, &base, item))
+				.collect::<Result<_, _>>()?,
+			_ => return Err(ManifestError::Invalid("missing `sequence`")),
+		};
+
+		Ok(Self {
+			iri: iri.to_owned(),
+			name: get_string(object, "name").map(ToString::to_string),
+			tests,
+		})
+	}
+}
+
+impl Test {
+	fn from_json(manifest: &Iri, base: &Iri, json: &Value) -> Result<Self, ManifestError> {
+		let object = json
+			.as_object()
+			.ok_or(ManifestError::Invalid("test is not an object"))?;
+
+		let id = resolve(
+			get_string(object, "@id").ok_or(ManifestError::Invalid("missing test `@id`"))?,
+			manifest,
+		)?;
+
+		let types: Vec<&str> = match object.get("@type").next() {
+			Some(Value::String(ty)) => vec![ty.as_str()],
+			Some(Value::Array(items)) => items.iter().filter_map(Value::as_str).collect(),
+			_ => Vec::new(),
+		};
+
+		let kind = if types.contains(&"jld:ExpandTest") {
+			TestKind::Expand
+		} else if types.contains(&"jld:CompactTest") {
+			TestKind::Compact
+		} else if types.contains(&"jld:FlattenTest") {
+			TestKind::Flatten
+		} else if types.contains(&"jld:ToRDFTest") {
+			TestKind::ToRdf
+		} else {
+			TestKind::Unsupported
+		};
+
+		let expectation = if types.contains(&"jld:NegativeEvaluationTest") {
+			Expectation::Negative(
+				get_string(object, "expectErrorCode")
+					.ok_or(ManifestError::Invalid("missing `expectErrorCode`"))?
+					.to_string(),
+			)
+		} else {
+			match get_string(object, "expect") {
+				Some(expect) => Expectation::Positive(resolve(expect, base)?),
+				None => Expectation::Syntax,
+			}
+		};
+
+		Ok(Self {
+			id,
+			name: get_string(object, "name").map(ToString::to_string),
+			kind,
+			input: resolve(
+				get_string(object, "input").ok_or(ManifestError::Invalid("missing `input`"))?,
+				base,
+			)?,
+			context: get_string(object, "context")
+				.map(|c| resolve(c, base))
+				.transpose()?,
+			expectation,
+			options: match object.get("option").next() {
+				Some(Value::Object(option)) => TestOptions::from_json(base, option)?,
+				_ => TestOptions::default(),
+			},
+		})
+	}
+}
+
+impl TestOptions {
+	fn from_json(base: &Iri, object: &json_syntax::Object) -> Result<Self, ManifestError> {
+		Ok(Self {
+			base: get_string(object, "base")
+				.map(|b| resolve(b, base))
+				.transpose()?,
+			expand_context: get_string(object, "expandContext")
+				.map(|c| resolve(c, base))
+				.transpose()?,
+			processing_mode: get_string(object, "processingMode")
+				.map(|m| {
+					ProcessingMode::try_from(m)
+						.map_err(|_| ManifestError::Invalid("invalid `processingMode`"))
+				})
+				.transpose()?,
+			spec_version: get_string(object, "specVersion").map(ToString::to_string),
+			compact_arrays: get_bool(object, "compactArrays"),
+			compact_to_relative: get_bool(object, "compactToRelative"),
+			produce_generalized_rdf: get_bool(object, "produceGeneralizedRdf"),
+			rdf_direction: get_string(object, "rdfDirection")
+				.map(|d| {
+					RdfDirection::try_from(d)
+						.map_err(|_| ManifestError::Invalid("invalid `rdfDirection`"))
+				})
+				.transpose()?,
+			normative: get_bool(object, "normative"),
+		})
+	}
+}
+
+fn get_string<'a>(object: &'a json_syntax::Object, key: &str) -> Option<&'a str> {
+	object.get(key).next().and_then(Value::as_str)
+}
+
+fn get_bool(object: &json_syntax::Object, key: &str) -> Option<bool> {
+	object.get(key).next().and_then(Value::as_boolean)
+}
+
+fn resolve(iri_ref: &str, base: &Iri) -> Result<IriBuf, ManifestError> {
+	IriRef::new(iri_ref)
+		.map(|r| r.resolved(base))
+		.map_err(|_| ManifestError::InvalidIri(iri_ref.to_string()))
+}
+
+fn is_json_document(iri: &Iri) -> bool {
+	let path = iri.path().as_str();
+	path.ends_with(".jsonld") || path.ends_with(".json")
+}
+
+/// Compares two JSON-LD documents up to blank node relabeling.
+///
+/// Objects are compared regardless of the order of their entries, and
+/// arrays regardless of the order of their items, except for the values of
+/// `@list` entries. Strings and keys starting with `_:` are blank node
+/// identifiers, equivalent if they are mapped to each other by the given
+/// `bijection`, which is extended as needed. The bijection is searched by
+/// backtracking over the matched array items and blank node keys.
+fn json_equivalent<'a>(
+	a: &'a Value,
+	b: &'a Value,
+	ordered: bool,
+	bijection: &mut Bijection<'a>,
+) -> bool {
+	match (a, b) {
+		(Value::Null, Value::Null) => true,
+		(Value::Boolean(a), Value::Boolean(b)) => a == b,
+		(Value::Number(a), Value::Number(b)) => a.canonical() == b.canonical(),
+		(Value::String(a), Value::String(b)) => bijection.equivalent(a.as_str(), b.as_str()),
+		(Value::Array(a), Value::Array(b)) => {
+			if a.len() != b.len() {
+				return false;
+			}
+
+			if ordered {
+				a.iter()
+					.zip(b)
+					.all(|(a, b)| json_equivalent(a, b, false, bijection))
+			} else {
+				let a: Vec<_> = a.iter().collect();
+				let b: Vec<_> = b.iter().collect();
+				match_all(&a, &b, bijection, |a, b, bijection| {
+					json_equivalent(a, b, false, bijection)
+				})
+			}
+		}
+		(Value::Object(a), Value::Object(b)) => {
+			if a.len() != b.len() {
+				return false;
+			}
+
+			let (a_blanks, a_entries): (Vec<_>, Vec<_>) =
+				a.iter().partition(|entry| is_blank_id(entry.key.as_str()));
+			let b_blanks: Vec<_> = b
+				.iter()
+				.filter(|entry| is_blank_id(entry.key.as_str()))
+				.collect();
+
+			a_entries.iter().all(|entry| {
+				let key = entry.key.as_str();
+				match b.get(key).next() {
+					Some(value) => json_equivalent(&entry.value, value, key == "@list", bijection),
+					None => false,
+				}
+			}) && match_all(&a_blanks, &b_blanks, bijection, |a, b, bijection| {
+				bijection.equivalent(a.key.as_str(), b.key.as_str())
+					&& json_equivalent(&a.value, &b.value, false, bijection)
+			})
+		}
+		_ => false,
+	}
+}
+
+/// Matches every item of `a` with a distinct item of `b`, backtracking on
+/// the bijection extended by each match.
+fn match_all<'a, T>(
+	a: &[&'a T],
+	b: &[&'a T],
+	bijection: &mut Bijection<'a>,
+	equivalent: impl Copy + Fn(&'a T, &'a T, &mut Bijection<'a>) -> bool,
+) -> bool {
+	fn search<'a, T>(
+		a: &[&'a T],
+		b: &[&'a T],
+		matched: &mut [bool],
+		bijection: &mut Bijection<'a>,
+		equivalent: impl Copy + Fn(&'a T, &'a T, &mut Bijection<'a>) -> bool,
+	) -> bool {
+		let Some((item, rest)) = a.split_first() else {
+			return true;
+		};
+
+		for (i, candidate) in b.iter().enumerate() {
+			if matched[i] {
+				continue;
+			}
+
+			let snapshot = bijection.clone();
+			if equivalent(item, candidate, bijection) {
+				matched[i] = true;
+				if search(rest, b, matched, bijection, equivalent) {
+					return true;
+				}
+				matched[i] = false;
+			}
+
+			*bijection = snapshot;
+		}
+
+		false
+	}
+
+	a.len() == b.len() && search(a, b, &mut vec![false; b.len()], bijection, equivalent)
+}
+
+fn is_blank_id(s: &str) -> bool {
+	s.starts_with("_:")
+}
+
+/// Bijection between the blank node identifiers of two JSON-LD documents.
+#[derive(Default, Clone)]
+struct Bijection<'a> {
+	forward: HashMap<&'a str, &'a str>,
+	backward: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> Bijection<'a> {
+	/// Checks if the given strings are equal, or are blank node identifiers
+	/// mapped to each other.
+	///
+	/// Unmapped blank node identifiers are mapped to each other.
+	fn equivalent(&mut self, a: &'a str, b: &'a str) -> bool {
+		match (is_blank_id(a), is_blank_id(b)) {
+			(true, true) => match (self.forward.get(a), self.backward.get(b)) {
+				(Some(c), _) => *c == b,
+				(None, Some(_)) => false,
+				(None, None) => {
+					self.forward.insert(a, b);
+					self.backward.insert(b, a);
+					true
+				}
+			},
+			(false, false) => a == b,
+			_ => false,
+		}
+	}
+}
+
+/// RDF quad of a toRdf test output.
+type RdfQuad = json_ld_core::rdf::Quad<IriBuf, BlankIdBuf, Literal>;
+
+/// Checks if the given RDF datasets are isomorphic, that is equal up to a
+/// bijection between their blank nodes.
+///
+/// Duplicate quads are ignored. Blank nodes are only matched with blank
+/// nodes of the same signature (see [`signature`]), the bijection being then
+/// searched by backtracking.
+fn isomorphic(a: &[RdfQuad], b: &[RdfQuad]) -> bool {
+	let a: Vec<&RdfQuad> = a.iter().collect::<HashSet<_>>().into_iter().collect();
+	let b: HashSet<&RdfQuad> = b.iter().collect();
+	if a.len() != b.len() {
+		return false;
+	}
+
+	let a_blanks = blank_nodes(a.iter().copied());
+	let b_blanks = blank_nodes(b.iter().copied());
+	if a_blanks.len() != b_blanks.len() {
+		return false;
+	}
+
+	let blanks: Vec<_> = a_blanks
+		.iter()
+		.map(|(id, quads)| (*id, signature(id, quads), quads.as_slice()))
+		.collect();
+	let candidates: Vec<_> = b_blanks
+		.iter()
+		.map(|(id, quads)| (*id, signature(id, quads)))
+		.collect();
+
+	let matcher = Matcher {
+		blanks,
+		candidates,
+		expected: &b,
+	};
+
+	a.iter()
+		.filter(|quad| quad_blank_nodes(quad).next().is_none())
+		.all(|quad| b.contains(quad))
+		&& matcher.search(0, &mut HashMap::new(), &mut HashSet::new())
+}
+
+/// Groups the quads of a dataset by the blank nodes they contain.
+fn blank_nodes<'a>(
+	quads: impl Iterator<Item = &'a RdfQuad>,
+) -> HashMap<&'a BlankIdBuf, Vec<&'a RdfQuad>> {
+	let mut result: HashMap<_, Vec<_>> = HashMap::new();
+	for quad in quads {
+		for id in quad_blank_nodes(quad).collect::<HashSet<_>>() {
+			result.entry(id).or_default().push(quad)
+		}
+	}
+
+	result
+}
+
+fn quad_blank_nodes(quad: &RdfQuad) -> impl Iterator<Item = &BlankIdBuf> {
+	[
+		Some(&quad.0),
+		Some(&quad.1),
+		quad.2.as_id(),
+		quad.3.as_ref(),
+	]
+	.into_iter()
+	.flatten()
+	.filter_map(Id::as_blank)
+}
+
+/// Signature of the given blank node: the sorted list of the quads it
+/// appears in, where it is renamed `_:self` and every other blank node
+/// `_:other`.
+///
+/// Isomorphic blank nodes have the same signature.
+fn signature(id: &BlankIdBuf, quads: &[&RdfQuad]) -> Vec<String> {
+	let this = BlankIdBuf::new("_:self".to_string()).unwrap();
+	let other = BlankIdBuf::new("_:other".to_string()).unwrap();
+	let mut result: Vec<String> = quads
+		.iter()
+		.map(|quad| {
+			let quad = relabel(quad, |b| if b == id { &this } else { &other });
+			format!("{quad}")
+		})
+		.collect();
+	result.sort_unstable();
+	result
+}
+
+/// Relabels the blank nodes of the given quad.
+fn relabel<'a>(quad: &RdfQuad, f: impl Fn(&BlankIdBuf) -> &'a BlankIdBuf) -> RdfQuad {
+	let id = |i: &Id| match i {
+		Id::Blank(b) => Id::Blank(f(b).clone()),
+		i => i.clone(),
+	};
+
+	rdf_types::Quad(
+		id(&quad.0),
+		id(&quad.1),
+		match &quad.2 {
+			Term::Id(i) => Term::Id(id(i)),
+			term => term.clone(),
+		},
+		quad.3.as_ref().map(id),
+	)
+}
+
+/// Blank node bijection search.
+struct Matcher<'a> {
+	/// Blank nodes of the first dataset, with their signature and the quads
+	/// they appear in.
+	blanks: Vec<(&'a BlankIdBuf, Vec<String>, &'a [&'a RdfQuad])>,
+
+	/// Blank nodes of the second dataset, with their signature.
+	candidates: Vec<(&'a BlankIdBuf, Vec<String>)>,
+
+	/// Quads of the second dataset.
+	expected: &'a HashSet<&'a RdfQuad>,
+}
+
+impl<'a> Matcher<'a> {
+	/// Extends the given partial bijection to the blank nodes starting at
+	/// index `i`.
+	///
+	/// Each blank node is mapped to an unused candidate of the same
+	/// signature, as long as the quads whose blank nodes are all mapped are
+	/// found in the second dataset.
+	fn search(
+		&self,
+		i: usize,
+		bijection: &mut HashMap<&'a BlankIdBuf, &'a BlankIdBuf>,
+		used: &mut HashSet<&'a BlankIdBuf>,
+	) -> bool {
+		let Some((id, signature, quads)) = self.blanks.get(i) else {
+			return true;
+		};
+
+		for (candidate, candidate_signature) in &self.candidates {
+			if used.contains(candidate) || candidate_signature != signature {
+				continue;
+			}
+
+			bijection.insert(*id, *candidate);
+			let consistent = quads.iter().all(|quad| {
+				quad_blank_nodes(quad).any(|b| !bijection.contains_key(b))
+					|| self.expected.contains(&relabel(quad, |b| bijection[b]))
+			});
+
+			if consistent {
+				used.insert(*candidate);
+				if self.search(i + 1, bijection, used) {
+					return true;
+				}
+				used.remove(*candidate);
+			}
+
+			bijection.remove(*id);
+		}
+
+		false
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use json_syntax::Parse;
+
+	fn quads(input: &str) -> Vec<RdfQuad> {
+		nquads::parse_quads(input)
+			.collect::<Result<_, _>>()
+			.unwrap()
+	}
+
+	fn json(input: &str) -> Value {
+		Value::parse_str(input).unwrap().0
+	}
+
+	#[test]
+	fn relabeled_json() {
+		let a = json(
+			r#"{ "@graph": [
+				{ "@id": "_:b0", "p": { "@id": "_:b1" } },
+				{ "@id": "_:b1", "p": "x" }
+			] }"#,
+		);
+		let b = json(
+			r#"{ "@graph": [
+				{ "@id": "_:b1", "p": "x" },
+				{ "@id": "_:b0", "p": { "@id": "_:b1" } }
+			] }"#,
+		);
+		let c = json(
+			r#"{ "@graph": [
+				{ "@id": "_:0", "p": "x" },
+				{ "@id": "_:1", "p": { "@id": "_:0" } }
+			] }"#,
+		);
+
+		assert!(json_equivalent(&a, &b, false, &mut Bijection::default()));
+		assert!(json_equivalent(&a, &c, false, &mut Bijection::default()));
+		assert!(json_equivalent(&c, &a, false, &mut Bijection::default()));
+	}
+
+	#[test]
+	fn non_relabeled_json() {
+		let a = json(r#"[{ "@id": "_:a", "p": { "@id": "_:b" } }]"#);
+		let b = json(r#"[{ "@id": "_:a", "p": { "@id": "_:a" } }]"#);
+		let c = json(r#"{ "_:a": "x", "_:b": "y" }"#);
+		let d = json(r#"{ "_:a": "x", "_:b": "x" }"#);
+		let e = json(r#"[{ "@id": "_:a" }]"#);
+		let f = json(r#"[{ "@id": "http://example.org/a" }]"#);
+
+		assert!(!json_equivalent(&a, &b, false, &mut Bijection::default()));
+		assert!(!json_equivalent(&c, &d, false, &mut Bijection::default()));
+		assert!(!json_equivalent(&e, &f, false, &mut Bijection::default()));
+	}
+
+	#[test]
+	fn isomorphic_datasets() {
+		let a = quads(
+			"_:a <http://example.org/p> _:b .\n\
+			_:b <http://example.org/p> \"x\" .\n\
+			<http://example.org/s> <http://example.org/q> _:a _:g .\n",
+		);
+		let b = quads(
+			"_:c14n0 <http://example.org/p> \"x\" .\n\
+			_:c14n1 <http://example.org/p> _:c14n0 .\n\
+			<http://example.org/s> <http://example.org/q> _:c14n1 _:c14n2 .\n",
+		);
+
+		assert!(isomorphic(&a, &b));
+		assert!(isomorphic(&b, &a));
+	}
+
+	#[test]
+	fn non_isomorphic_datasets() {
+		let a = quads(
+			"_:a <http://example.org/p> _:b .\n\
+			_:b <http://example.org/p> _:a .\n",
+		);
+		let b = quads(
+			"_:a <http://example.org/p> _:a .\n\
+			_:b <http://example.org/p> _:b .\n",
+		);
+		let c = quads("_:a <http://example.org/p> \"x\" .\n");
+		let d = quads("_:a <http://example.org/p> \"y\" .\n");
+
+		assert!(!isomorphic(&a, &b));
+		assert!(!isomorphic(&c, &d));
+	}
+}